@@ -0,0 +1,414 @@
+use crate::table;
+use application::inbound_ports::{
+    AccountActivity, AccountActivityUseCase, AccountAdministrationUseCase, AccountSummary,
+    CloseAccountError, GrantAccountMandateCommand, GrantAccountMandateError, OpenAccountCommand,
+    OpenAccountError,
+};
+use domain::{
+    ar::{account::AccountId, account_mandate::MandateKind, activity::ActivityId},
+    vo::{currency::Currency, money::Money},
+};
+use serde::{Deserialize, Serialize};
+
+pub const USAGE: &str =
+    "usage: main account open [--currency <code>] [--overdraft-limit <amount>] [--json]
+       main account close <account id> [--json]
+       main account show <account id> [--json]
+       main account history <account id> [--after <activity id>] [--limit <count>] [--json]
+       main account grant <account id> <customer> [--signatory] [--json]";
+
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+#[derive(PartialEq, Debug)]
+pub enum AccountAction {
+    /// Defaults to an account in EUR without overdraft.
+    Open {
+        currency: Currency,
+        overdraft_limit: Money,
+    },
+    Close {
+        account_id: i64,
+    },
+    Show {
+        account_id: i64,
+    },
+    /// The activities stored after the given one, from the first one by default.
+    History {
+        account_id: i64,
+        after_activity_id: Option<i64>,
+        limit: usize,
+    },
+    /// Grants the customer a mandate as an owner unless stated otherwise.
+    Grant {
+        account_id: i64,
+        customer: String,
+        kind: MandateKind,
+    },
+}
+
+#[derive(PartialEq, Debug)]
+pub struct AccountArgs {
+    pub action: AccountAction,
+    /// Prints JSON instead of a table.
+    pub json: bool,
+}
+
+impl AccountArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let json = args.iter().any(|arg| arg == "--json");
+        let mut args = args.iter().filter(|arg| *arg != "--json");
+        let action = match args.next().map(String::as_str) {
+            Some("open") => {
+                let mut currency = Currency::default();
+                let mut overdraft_limit = Money::of(0);
+                while let Some(arg) = args.next() {
+                    let Some(value) = args.next() else {
+                        return Err(format!("{} needs a value", arg));
+                    };
+                    match arg.as_str() {
+                        "--currency" => {
+                            currency = value
+                                .parse()
+                                .map_err(|_| format!("invalid currency: {}", value))?
+                        }
+                        "--overdraft-limit" => {
+                            overdraft_limit = Money::of(parse_number(value)?.into())
+                        }
+                        _ => return Err(format!("unknown option: {}", arg)),
+                    }
+                }
+                AccountAction::Open {
+                    currency,
+                    overdraft_limit,
+                }
+            }
+            Some(action @ ("close" | "show" | "history")) => {
+                let Some(account_id) = args.next() else {
+                    return Err(format!("{} needs an account id", action));
+                };
+                let account_id = parse_number(account_id)?;
+                let mut after_activity_id = None;
+                let mut limit = DEFAULT_HISTORY_LIMIT;
+                while let Some(arg) = args.next() {
+                    let Some(value) = args.next().filter(|_| action == "history") else {
+                        return Err(format!("unknown option: {}", arg));
+                    };
+                    match arg.as_str() {
+                        "--after" => after_activity_id = Some(parse_number(value)?),
+                        "--limit" => limit = parse_number(value)? as usize,
+                        _ => return Err(format!("unknown option: {}", arg)),
+                    }
+                }
+                match action {
+                    "close" => AccountAction::Close { account_id },
+                    "show" => AccountAction::Show { account_id },
+                    _ => AccountAction::History {
+                        account_id,
+                        after_activity_id,
+                        limit,
+                    },
+                }
+            }
+            Some("grant") => {
+                let (Some(account_id), Some(customer)) = (args.next(), args.next()) else {
+                    return Err("grant needs an account id and a customer".to_string());
+                };
+                let account_id = parse_number(account_id)?;
+                let mut kind = MandateKind::Owner;
+                for arg in args {
+                    match arg.as_str() {
+                        "--signatory" => kind = MandateKind::Signatory,
+                        _ => return Err(format!("unknown option: {}", arg)),
+                    }
+                }
+                AccountAction::Grant {
+                    account_id,
+                    customer: customer.clone(),
+                    kind,
+                }
+            }
+            Some(action) => return Err(format!("unknown action: {}", action)),
+            None => return Err("missing action".to_string()),
+        };
+        Ok(Self { action, json })
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct AccountSummaryDto {
+    pub account_id: i64,
+    pub currency: String,
+    pub balance: i64,
+    pub overdraft_limit: i64,
+    pub closed_at: Option<String>,
+}
+
+impl From<&AccountSummary> for AccountSummaryDto {
+    fn from(summary: &AccountSummary) -> Self {
+        Self {
+            account_id: summary.details.account_id.0,
+            currency: summary.details.currency.to_string(),
+            balance: to_i64(&summary.balance),
+            overdraft_limit: to_i64(&summary.details.overdraft_limit),
+            closed_at: summary
+                .details
+                .closed_at
+                .map(|closed_at| closed_at.to_string()),
+        }
+    }
+}
+
+/// The amount is negative for a withdrawal, the balance is the one right after the activity.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct AccountActivityDto {
+    pub activity_id: i64,
+    pub timestamp: String,
+    pub counterpart_account_id: i64,
+    pub amount: i64,
+    pub balance: i64,
+    pub exchange_rate: Option<String>,
+}
+
+impl From<&AccountActivity> for AccountActivityDto {
+    fn from(account_activity: &AccountActivity) -> Self {
+        let activity = &account_activity.activity;
+        let withdrawal = activity.owner_account_id == activity.source_account_id;
+        Self {
+            activity_id: activity.id.as_ref().unwrap().0,
+            timestamp: activity.timestamp.to_string(),
+            counterpart_account_id: if withdrawal {
+                activity.target_account_id.0
+            } else {
+                activity.source_account_id.0
+            },
+            amount: if withdrawal {
+                -to_i64(&activity.money)
+            } else {
+                to_i64(&activity.money)
+            },
+            balance: to_i64(&account_activity.balance),
+            exchange_rate: activity
+                .exchange_rate
+                .as_ref()
+                .map(|exchange_rate| exchange_rate.to_string()),
+        }
+    }
+}
+
+/**
+ * Runs the action and renders its outcome, the account for all actions but the history.
+ */
+pub async fn run(
+    account_administration_use_case: &dyn AccountAdministrationUseCase,
+    account_activity_use_case: &dyn AccountActivityUseCase,
+    args: &AccountArgs,
+) -> Result<String, String> {
+    let account_id = match &args.action {
+        AccountAction::Open {
+            currency,
+            overdraft_limit,
+        } => account_administration_use_case
+            .open_account(OpenAccountCommand::new(
+                currency.clone(),
+                overdraft_limit.clone(),
+            ))
+            .await
+            .map_err(|error| match error {
+                OpenAccountError::NegativeOverdraftLimit => {
+                    "the overdraft limit is negative".to_string()
+                }
+            })?,
+        AccountAction::Close { account_id } => {
+            account_administration_use_case
+                .close_account(AccountId(*account_id))
+                .await
+                .map_err(|error| match error {
+                    CloseAccountError::AccountNotFound => format!("unknown account {}", account_id),
+                    CloseAccountError::AlreadyClosed => {
+                        format!("account {} is closed already", account_id)
+                    }
+                    CloseAccountError::BalanceNotZero => {
+                        format!("the balance of account {} is not zero", account_id)
+                    }
+                })?;
+            AccountId(*account_id)
+        }
+        AccountAction::Show { account_id } => AccountId(*account_id),
+        AccountAction::Grant {
+            account_id,
+            customer,
+            kind,
+        } => {
+            account_administration_use_case
+                .grant_account_mandate(GrantAccountMandateCommand::new(
+                    AccountId(*account_id),
+                    customer.clone(),
+                    *kind,
+                ))
+                .await
+                .map_err(|error| match error {
+                    GrantAccountMandateError::AccountNotFound => {
+                        format!("unknown account {}", account_id)
+                    }
+                    GrantAccountMandateError::AccountClosed => {
+                        format!("account {} is closed", account_id)
+                    }
+                })?;
+            AccountId(*account_id)
+        }
+        AccountAction::History {
+            account_id,
+            after_activity_id,
+            limit,
+        } => {
+            account_administration_use_case
+                .get_account_summary(AccountId(*account_id))
+                .await
+                .ok_or_else(|| format!("unknown account {}", account_id))?;
+            let activities: Vec<AccountActivityDto> = account_activity_use_case
+                .list_activities_after(
+                    AccountId(*account_id),
+                    after_activity_id.map(ActivityId),
+                    *limit,
+                )
+                .await
+                .iter()
+                .map(AccountActivityDto::from)
+                .collect();
+            return Ok(format_activities(&activities, args.json));
+        }
+    };
+    let summary = account_administration_use_case
+        .get_account_summary(account_id.clone())
+        .await
+        .ok_or_else(|| format!("unknown account {}", account_id.0))?;
+    Ok(format_summary(
+        &AccountSummaryDto::from(&summary),
+        args.json,
+    ))
+}
+
+pub fn format_summary(summary: &AccountSummaryDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(summary).unwrap() + "\n";
+    }
+    table::format_table(
+        &[
+            "ACCOUNT",
+            "CURRENCY",
+            "BALANCE",
+            "OVERDRAFT LIMIT",
+            "CLOSED AT",
+        ],
+        &[vec![
+            summary.account_id.to_string(),
+            summary.currency.clone(),
+            summary.balance.to_string(),
+            summary.overdraft_limit.to_string(),
+            summary.closed_at.clone().unwrap_or_default(),
+        ]],
+    )
+}
+
+pub fn format_activities(activities: &[AccountActivityDto], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(activities).unwrap() + "\n";
+    }
+    table::format_table(
+        &[
+            "ACTIVITY",
+            "TIMESTAMP",
+            "COUNTERPART",
+            "AMOUNT",
+            "BALANCE",
+            "EXCHANGE RATE",
+        ],
+        &activities
+            .iter()
+            .map(|activity| {
+                vec![
+                    activity.activity_id.to_string(),
+                    activity.timestamp.clone(),
+                    activity.counterpart_account_id.to_string(),
+                    activity.amount.to_string(),
+                    activity.balance.to_string(),
+                    activity.exchange_rate.clone().unwrap_or_default(),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn parse_number(value: &str) -> Result<i64, String> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|number| *number >= 0)
+        .ok_or_else(|| format!("invalid number: {}", value))
+}
+
+fn to_i64(money: &Money) -> i64 {
+    money.amount.to_string().parse::<i64>().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(AccountArgs {
+                action: AccountAction::Open {
+                    currency: Currency::of("USD"),
+                    overdraft_limit: Money::of(500),
+                },
+                json: true,
+            }),
+            AccountArgs::parse(&args(&[
+                "open",
+                "--json",
+                "--currency",
+                "USD",
+                "--overdraft-limit",
+                "500",
+            ]))
+        );
+        assert_eq!(
+            Ok(AccountArgs {
+                action: AccountAction::History {
+                    account_id: 1,
+                    after_activity_id: Some(10),
+                    limit: DEFAULT_HISTORY_LIMIT,
+                },
+                json: false,
+            }),
+            AccountArgs::parse(&args(&["history", "1", "--after", "10"]))
+        );
+        assert_eq!(
+            Err("unknown option: --limit".to_string()),
+            AccountArgs::parse(&args(&["show", "1", "--limit", "5"]))
+        );
+        assert_eq!(
+            Err("close needs an account id".to_string()),
+            AccountArgs::parse(&args(&["close"]))
+        );
+        assert_eq!(
+            Ok(AccountArgs {
+                action: AccountAction::Grant {
+                    account_id: 1,
+                    customer: "alice".to_string(),
+                    kind: MandateKind::Signatory,
+                },
+                json: false,
+            }),
+            AccountArgs::parse(&args(&["grant", "1", "alice", "--signatory"]))
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
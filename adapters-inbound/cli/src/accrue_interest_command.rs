@@ -0,0 +1,216 @@
+use application::inbound_ports::{AccrueInterestUseCase, InterestAccrualReport};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+pub const USAGE: &str = "usage: main accrue-interest [<until date, e.g. 2019-08-31>] [--json]";
+
+#[derive(PartialEq, Debug)]
+pub struct AccrueInterestArgs {
+    /// The last day to accrue, the previous day if not given.
+    pub until: Option<NaiveDate>,
+    /// Prints JSON instead of a summary.
+    pub json: bool,
+}
+
+impl AccrueInterestArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let json = args.iter().any(|arg| arg == "--json");
+        let args: Vec<&String> = args.iter().filter(|arg| *arg != "--json").collect();
+        match args[..] {
+            [] => Ok(Self { until: None, json }),
+            [until] => NaiveDate::parse_from_str(until, "%Y-%m-%d")
+                .map(|until| Self {
+                    until: Some(until),
+                    json,
+                })
+                .map_err(|_| format!("invalid date: {}", until)),
+            [_, arg, ..] => Err(format!("unknown argument: {}", arg)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct InterestAccrualReportDto {
+    pub until: String,
+    pub accrued_days: usize,
+    /// The interest the funding account could not pay, the days have been accrued without it.
+    pub unpaid_interest: Vec<UnpaidInterestDto>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct UnpaidInterestDto {
+    pub account_id: i64,
+    pub accrual_date: String,
+    pub interest: i64,
+}
+
+impl InterestAccrualReportDto {
+    // Functions
+
+    pub fn of(until: NaiveDate, report: &InterestAccrualReport) -> Self {
+        Self {
+            until: until.to_string(),
+            accrued_days: report.accrued_days,
+            unpaid_interest: report
+                .unpaid_interest
+                .iter()
+                .map(|unpaid_interest| UnpaidInterestDto {
+                    account_id: unpaid_interest.account_id.0,
+                    accrual_date: unpaid_interest.accrual_date.to_string(),
+                    interest: unpaid_interest
+                        .interest
+                        .amount
+                        .to_string()
+                        .parse::<i64>()
+                        .unwrap(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/**
+ * Accrues the interest of every day missed up to the given one, e.g. after the service has been
+ * down over the end of a month.
+ *
+ * # Arguments
+ *
+ * * `today` - Neither today nor a later day can be accrued, as its balance is not final yet.
+ */
+pub async fn run(
+    accrue_interest_use_case: &dyn AccrueInterestUseCase,
+    args: &AccrueInterestArgs,
+    today: NaiveDate,
+) -> Result<String, String> {
+    let until = args.until.unwrap_or(today.pred_opt().unwrap());
+    if until >= today {
+        return Err(format!("{} has not ended yet", until));
+    }
+    let report = accrue_interest_use_case.accrue_interest(until).await;
+    Ok(format_report(
+        &InterestAccrualReportDto::of(until, &report),
+        args.json,
+    ))
+}
+
+pub fn format_report(report: &InterestAccrualReportDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(report).unwrap() + "\n";
+    }
+    let mut output = format!(
+        "accrued {} days of interest until {}\n",
+        report.accrued_days, report.until
+    );
+    for unpaid_interest in &report.unpaid_interest {
+        output.push_str(&format!(
+            "the interest of {} for account {} on {} could not be paid\n",
+            unpaid_interest.interest, unpaid_interest.account_id, unpaid_interest.accrual_date
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::UnpaidInterest;
+    use async_trait::async_trait;
+    use domain::{ar::account::AccountId, vo::money::Money};
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        AccrueInterestUseCaseImpl {}
+        #[async_trait]
+        impl AccrueInterestUseCase for AccrueInterestUseCaseImpl {
+            async fn accrue_interest(&self, until: NaiveDate) -> InterestAccrualReport;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        // Given
+        let mut accrue_interest_use_case = MockAccrueInterestUseCaseImpl::new();
+        accrue_interest_use_case
+            .expect_accrue_interest()
+            .times(2)
+            .with(eq(date(8, 31)))
+            .return_const(InterestAccrualReport {
+                accrued_days: 3,
+                unpaid_interest: vec![UnpaidInterest::new(
+                    AccountId(41),
+                    date(8, 30),
+                    Money::of(12),
+                )],
+            });
+
+        // When the previous day is accrued
+        let output = run(
+            &accrue_interest_use_case,
+            &AccrueInterestArgs::parse(&[]).unwrap(),
+            date(9, 1),
+        )
+        .await;
+
+        // Then
+        assert_eq!(
+            Ok("accrued 3 days of interest until 2019-08-31\n\
+                the interest of 12 for account 41 on 2019-08-30 could not be paid\n"
+                .to_string()),
+            output
+        );
+        // And as JSON
+        let json: serde_json::Value = serde_json::from_str(
+            &run(
+                &accrue_interest_use_case,
+                &AccrueInterestArgs::parse(&args(&["--json"])).unwrap(),
+                date(9, 1),
+            )
+            .await
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(3, json["accrued_days"]);
+        assert_eq!("2019-08-30", json["unpaid_interest"][0]["accrual_date"]);
+        assert_eq!(12, json["unpaid_interest"][0]["interest"]);
+        // And a day which has not ended is refused
+        assert_eq!(
+            Err("2019-09-01 has not ended yet".to_string()),
+            run(
+                &accrue_interest_use_case,
+                &AccrueInterestArgs::parse(&args(&["2019-09-01"])).unwrap(),
+                date(9, 1),
+            )
+            .await
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(AccrueInterestArgs {
+                until: Some(date(8, 31)),
+                json: true,
+            }),
+            AccrueInterestArgs::parse(&args(&["2019-08-31", "--json"]))
+        );
+        assert_eq!(
+            Err("invalid date: 31.08.2019".to_string()),
+            AccrueInterestArgs::parse(&args(&["31.08.2019"]))
+        );
+        assert_eq!(
+            Err("unknown argument: --force".to_string()),
+            AccrueInterestArgs::parse(&args(&["2019-08-31", "--force"]))
+        );
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2019, month, day).unwrap()
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
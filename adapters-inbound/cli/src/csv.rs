@@ -0,0 +1,58 @@
+/**
+ * Splits a line of comma separated values, where values containing a comma or a quote are
+ * enclosed in quotes and their quotes doubled. Values cannot span several lines.
+ */
+pub fn split_line(line: &str) -> Result<Vec<String>, String> {
+    let mut values = vec![];
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if value.is_empty() => quoted = true,
+            ',' if !quoted => values.push(std::mem::take(&mut value)),
+            _ => value.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    values.push(value);
+    Ok(values)
+}
+
+/**
+ * Encloses the value in quotes if it would otherwise not be read back as a single value.
+ */
+pub fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(
+            vec!["account", "", "say \"hi\", then go", "7"],
+            split_line("account,,\"say \"\"hi\"\", then go\",7").unwrap()
+        );
+        assert_eq!(
+            Err("unterminated quote".to_string()),
+            split_line("account,\"7")
+        );
+        assert_eq!(
+            vec!["say \"hi\", then go"],
+            split_line(&escape("say \"hi\", then go")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use application::inbound_ports::{
+    ExportLedgerCommand, ExportLedgerError, ExportLedgerUseCase, LedgerExport,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "usage: main export-ledger [--format csv|json] [--from yyyy-mm-dd] [--to yyyy-mm-dd] [--incremental] [--output-directory <directory>] [--json]";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ExportLedgerArgs {
+    /// Defaults to CSV.
+    pub format: ExportFormat,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub incremental: bool,
+    /// Defaults to the working directory.
+    pub output_directory: PathBuf,
+    /// Prints JSON instead of a summary, regardless of the format of the ledger file.
+    pub json: bool,
+}
+
+impl ExportLedgerArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut export_ledger_args = Self {
+            format: ExportFormat::Csv,
+            from: None,
+            to: None,
+            incremental: false,
+            output_directory: PathBuf::from("."),
+            json: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--incremental" => {
+                    export_ledger_args.incremental = true;
+                    continue;
+                }
+                "--json" => {
+                    export_ledger_args.json = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(value) = args.next() else {
+                return Err(format!("{} needs a value", arg));
+            };
+            match arg.as_str() {
+                "--format" => {
+                    export_ledger_args.format = match value.as_str() {
+                        "csv" => ExportFormat::Csv,
+                        "json" => ExportFormat::Json,
+                        _ => return Err(format!("unknown format: {}", value)),
+                    }
+                }
+                "--from" => export_ledger_args.from = Some(parse_date(value)?),
+                "--to" => export_ledger_args.to = Some(parse_date(value)?),
+                "--output-directory" => export_ledger_args.output_directory = PathBuf::from(value),
+                _ => return Err(format!("unknown option: {}", arg)),
+            }
+        }
+        Ok(export_ledger_args)
+    }
+}
+
+/**
+ * The result of an export, e.g. `{"exported_at":"2019-08-09 09:00:00","from":"2019-08-01",
+ * "to":null,"first_activity_id":1,"last_activity_id":4,"transfer_count":2,
+ * "incomplete_transfer_count":0,"path":"ledger-1-4.csv","checksum":"9f86d0..."}`.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LedgerExportDto {
+    pub exported_at: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub first_activity_id: Option<i64>,
+    pub last_activity_id: Option<i64>,
+    pub transfer_count: usize,
+    pub incomplete_transfer_count: usize,
+    pub path: String,
+    pub checksum: String,
+}
+
+impl From<&LedgerExport> for LedgerExportDto {
+    fn from(ledger_export: &LedgerExport) -> Self {
+        Self {
+            exported_at: ledger_export.exported_at.to_string(),
+            from: ledger_export.from.map(|from| from.to_string()),
+            to: ledger_export.to.map(|to| to.to_string()),
+            first_activity_id: ledger_export
+                .first_activity_id
+                .as_ref()
+                .map(|activity_id| activity_id.0),
+            last_activity_id: ledger_export
+                .last_activity_id
+                .as_ref()
+                .map(|activity_id| activity_id.0),
+            transfer_count: ledger_export.transfer_count,
+            incomplete_transfer_count: ledger_export.incomplete_transfer_count,
+            path: ledger_export.file.path.clone(),
+            checksum: ledger_export.file.checksum.clone(),
+        }
+    }
+}
+
+pub async fn run(
+    export_ledger_use_case: &dyn ExportLedgerUseCase,
+    args: &ExportLedgerArgs,
+) -> Result<LedgerExport, String> {
+    export_ledger_use_case
+        .export_ledger(ExportLedgerCommand::new(
+            args.from,
+            args.to,
+            args.incremental,
+        ))
+        .await
+        .map_err(|error| match error {
+            ExportLedgerError::InvalidPeriod => "the period ends before it starts".to_string(),
+            ExportLedgerError::WriteFailed(reason) => reason,
+        })
+}
+
+pub fn format_export(ledger_export: &LedgerExportDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(ledger_export).unwrap() + "\n";
+    }
+    match (
+        ledger_export.first_activity_id,
+        ledger_export.last_activity_id,
+    ) {
+        (Some(first_activity_id), Some(last_activity_id)) => format!(
+            "{} transfers ({} incomplete) of activities {} to {} exported to {} (sha256 {})\n",
+            ledger_export.transfer_count,
+            ledger_export.incomplete_transfer_count,
+            first_activity_id,
+            last_activity_id,
+            ledger_export.path,
+            ledger_export.checksum
+        ),
+        _ => format!(
+            "no activities to export, an empty ledger was written to {} (sha256 {})\n",
+            ledger_export.path, ledger_export.checksum
+        ),
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("invalid date: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::LedgerFile;
+    use domain::ar::activity::ActivityId;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(ExportLedgerArgs {
+                format: ExportFormat::Json,
+                from: NaiveDate::from_ymd_opt(2019, 8, 1),
+                to: None,
+                incremental: true,
+                output_directory: PathBuf::from("exports"),
+                json: true,
+            }),
+            ExportLedgerArgs::parse(&args(&[
+                "--incremental",
+                "--json",
+                "--format",
+                "json",
+                "--from",
+                "2019-08-01",
+                "--output-directory",
+                "exports",
+            ]))
+        );
+        assert_eq!(
+            Err("invalid date: 2019-08-32".to_string()),
+            ExportLedgerArgs::parse(&args(&["--to", "2019-08-32"]))
+        );
+        assert_eq!(
+            Err("--format needs a value".to_string()),
+            ExportLedgerArgs::parse(&args(&["--format"]))
+        );
+    }
+
+    #[test]
+    fn test_format_export() {
+        // Given
+        let ledger_export = LedgerExportDto::from(&LedgerExport {
+            exported_at: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            from: NaiveDate::from_ymd_opt(2019, 8, 1),
+            to: None,
+            first_activity_id: Some(ActivityId(1)),
+            last_activity_id: Some(ActivityId(4)),
+            transfer_count: 2,
+            incomplete_transfer_count: 0,
+            file: LedgerFile {
+                path: "ledger-1-4.csv".to_string(),
+                checksum: "9f86d0".to_string(),
+            },
+        });
+
+        // When
+        let json: serde_json::Value =
+            serde_json::from_str(&format_export(&ledger_export, true)).unwrap();
+
+        // Then
+        assert_eq!("2019-08-01", json["from"]);
+        assert_eq!(serde_json::Value::Null, json["to"]);
+        assert_eq!(4, json["last_activity_id"]);
+        assert_eq!("ledger-1-4.csv", json["path"]);
+        assert_eq!(
+            "2 transfers (0 incomplete) of activities 1 to 4 exported to ledger-1-4.csv (sha256 9f86d0)\n",
+            format_export(&ledger_export, false)
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
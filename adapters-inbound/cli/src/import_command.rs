@@ -0,0 +1,465 @@
+use crate::csv;
+use application::inbound_ports::{
+    ImportCommand, ImportLine, ImportRecord, ImportReport, ImportUseCase, ImportedAccount,
+};
+use chrono::NaiveDateTime;
+use domain::{
+    ar::{account::AccountId, activity::Activity},
+    vo::{currency::Currency, money::Money},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+pub const USAGE: &str =
+    "usage: main import <file.csv|file.jsonl> [--dry-run] [--rejected-report <file>] [--json]";
+
+#[derive(PartialEq, Debug)]
+pub struct ImportArgs {
+    pub path: PathBuf,
+    pub dry_run: bool,
+    /// Defaults to `<file>.rejected.csv`.
+    pub rejected_report_path: PathBuf,
+    /// Prints JSON instead of a summary.
+    pub json: bool,
+}
+
+impl ImportArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut path = None;
+        let mut dry_run = false;
+        let mut rejected_report_path = None;
+        let mut json = false;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dry-run" => dry_run = true,
+                "--json" => json = true,
+                "--rejected-report" => match args.next() {
+                    Some(report_path) => rejected_report_path = Some(PathBuf::from(report_path)),
+                    None => return Err("--rejected-report needs a file".to_string()),
+                },
+                _ if arg.starts_with("--") => return Err(format!("unknown option: {}", arg)),
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+        let Some(path) = path else {
+            return Err("the file to import is missing".to_string());
+        };
+        let rejected_report_path = rejected_report_path.unwrap_or_else(|| {
+            let mut report_path = path.clone().into_os_string();
+            report_path.push(".rejected.csv");
+            PathBuf::from(report_path)
+        });
+        Ok(Self {
+            path,
+            dry_run,
+            rejected_report_path,
+            json,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImportFormat {
+    /// Comma separated values with a header naming the columns, see [RecordDto].
+    Csv,
+    /// A JSON object per line with the fields of [RecordDto].
+    JsonLines,
+}
+
+impl ImportFormat {
+    // Functions
+
+    pub fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * A record of an import file, either an account or a historical activity of its owner.
+ * Accounts are held in Euro without overdraft unless stated otherwise.
+ */
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordDto {
+    Account {
+        account_id: i64,
+        currency: Option<String>,
+        overdraft_limit: Option<i64>,
+    },
+    Activity {
+        owner_account_id: i64,
+        source_account_id: i64,
+        target_account_id: i64,
+        timestamp: String,
+        amount: i64,
+    },
+}
+
+/**
+ * Imports the file, which is streamed line by line, and writes the rejected lines as CSV to the
+ * report file. An import interrupted before resumes after the lines it has stored.
+ */
+pub async fn run(
+    import_use_case: &dyn ImportUseCase,
+    args: &ImportArgs,
+) -> io::Result<ImportReport> {
+    let Some(format) = ImportFormat::of(&args.path) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("not a .csv or .jsonl file: {}", args.path.display()),
+        ));
+    };
+    let file = File::open(&args.path)?;
+    // the progress is kept per file
+    let source = args.path.canonicalize()?.to_string_lossy().into_owned();
+
+    let report = import_use_case
+        .import(
+            ImportCommand::new(source, args.dry_run),
+            read_import_lines(BufReader::new(file), format),
+        )
+        .await;
+    write_rejected_lines(&report, &args.rejected_report_path)?;
+    Ok(report)
+}
+
+/**
+ * The result of an import, e.g. `{"dry_run":false,"imported_accounts":1,"imported_activities":2,
+ * "skipped_lines":0,"rejected_line_count":1,"rejected_report":"legacy.csv.rejected.csv"}`.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ImportReportDto {
+    pub dry_run: bool,
+    /// The accounts and activities which are valid in a dry run.
+    pub imported_accounts: usize,
+    pub imported_activities: usize,
+    pub skipped_lines: usize,
+    pub rejected_line_count: usize,
+    /// The file listing the rejected lines.
+    pub rejected_report: String,
+}
+
+impl ImportReportDto {
+    // Functions
+
+    pub fn of(report: &ImportReport, args: &ImportArgs) -> Self {
+        Self {
+            dry_run: args.dry_run,
+            imported_accounts: report.imported_accounts,
+            imported_activities: report.imported_activities,
+            skipped_lines: report.skipped_lines,
+            rejected_line_count: report.rejected_lines.len(),
+            rejected_report: args.rejected_report_path.display().to_string(),
+        }
+    }
+}
+
+pub fn format_report(report: &ImportReportDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(report).unwrap() + "\n";
+    }
+    format!(
+        "{}{} accounts and {} activities {}, {} lines skipped as imported before, {} lines rejected (see {})\n",
+        if report.dry_run { "dry run: " } else { "" },
+        report.imported_accounts,
+        report.imported_activities,
+        if report.dry_run { "valid" } else { "imported" },
+        report.skipped_lines,
+        report.rejected_line_count,
+        report.rejected_report
+    )
+}
+
+/**
+ * Reads the records lazily, the numbers of the lines count from 1 and include the CSV header.
+ * Blank lines are ignored.
+ */
+fn read_import_lines(
+    reader: impl BufRead + Send + 'static,
+    format: ImportFormat,
+) -> Box<dyn Iterator<Item = ImportLine> + Send> {
+    let mut header: Option<Result<Vec<String>, String>> = None;
+    Box::new(reader.lines().enumerate().filter_map(move |(index, line)| {
+        let line_number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                return Some(ImportLine {
+                    line_number,
+                    record: Err(error.to_string()),
+                });
+            }
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+        let record = match format {
+            ImportFormat::JsonLines => {
+                serde_json::from_str::<RecordDto>(&line).map_err(|error| error.to_string())
+            }
+            ImportFormat::Csv => match &header {
+                None => {
+                    header = Some(csv::split_line(&line));
+                    return None;
+                }
+                Some(Err(error)) => Err(format!("invalid header: {}", error)),
+                Some(Ok(header)) => read_csv_record(header, &line),
+            },
+        };
+        Some(ImportLine {
+            line_number,
+            record: record.and_then(map_to_import_record),
+        })
+    }))
+}
+
+fn read_csv_record(header: &[String], line: &str) -> Result<RecordDto, String> {
+    let values = csv::split_line(line)?;
+    let value = |name: &str| {
+        header
+            .iter()
+            .position(|column| column == name)
+            .and_then(|index| values.get(index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let number = |name: &str| match value(name) {
+        None => Ok(None),
+        Some(value) => value
+            .parse::<i64>()
+            .map(Some)
+            .map_err(|_| format!("invalid {}: {}", name, value)),
+    };
+    let required = |name: &str| number(name)?.ok_or(format!("missing {}", name));
+
+    match value("type") {
+        Some("account") => Ok(RecordDto::Account {
+            account_id: required("account_id")?,
+            currency: value("currency").map(str::to_string),
+            overdraft_limit: number("overdraft_limit")?,
+        }),
+        Some("activity") => Ok(RecordDto::Activity {
+            owner_account_id: required("owner_account_id")?,
+            source_account_id: required("source_account_id")?,
+            target_account_id: required("target_account_id")?,
+            timestamp: value("timestamp")
+                .ok_or("missing timestamp".to_string())?
+                .to_string(),
+            amount: required("amount")?,
+        }),
+        Some(record_type) => Err(format!("unknown type: {}", record_type)),
+        None => Err("missing type".to_string()),
+    }
+}
+
+fn map_to_import_record(record_dto: RecordDto) -> Result<ImportRecord, String> {
+    match record_dto {
+        RecordDto::Account {
+            account_id,
+            currency,
+            overdraft_limit,
+        } => Ok(ImportRecord::Account(ImportedAccount {
+            account_id: AccountId(account_id),
+            currency: match currency {
+                Some(currency) => currency.parse()?,
+                None => Currency::default(),
+            },
+            overdraft_limit: Money::of(overdraft_limit.unwrap_or(0) as i128),
+        })),
+        RecordDto::Activity {
+            owner_account_id,
+            source_account_id,
+            target_account_id,
+            timestamp,
+            amount,
+        } => Ok(ImportRecord::Activity(Activity::new(
+            AccountId(owner_account_id),
+            AccountId(source_account_id),
+            AccountId(target_account_id),
+            parse_timestamp(&timestamp)?,
+            Money::of(amount as i128),
+        ))),
+    }
+}
+
+/**
+ * Accepts `2019-08-09 09:00:00` as well as `2019-08-09T09:00:00`, both without time zone.
+ */
+fn parse_timestamp(timestamp: &str) -> Result<NaiveDateTime, String> {
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S"))
+        .map_err(|_| format!("invalid timestamp: {}", timestamp))
+}
+
+fn write_rejected_lines(report: &ImportReport, path: &Path) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    writeln!(file, "line_number,reason")?;
+    for rejected_line in &report.rejected_lines {
+        writeln!(
+            file,
+            "{},{}",
+            rejected_line.line_number,
+            csv::escape(&rejected_line.reason)
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::RejectedLine;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use mockall::{
+        mock,
+        predicate::{always, function},
+    };
+    use std::io::Cursor;
+
+    mock! {
+        #[derive(Debug)]
+        ImportUseCaseImpl {}
+        #[async_trait]
+        impl ImportUseCase for ImportUseCaseImpl {
+            async fn import(
+                &self,
+                command: ImportCommand,
+                lines: Box<dyn Iterator<Item = ImportLine> + Send>,
+            ) -> ImportReport;
+        }
+    }
+
+    #[test]
+    fn test_read_import_lines() {
+        // Given
+        let csv = "type,account_id,currency,overdraft_limit,owner_account_id,source_account_id,target_account_id,timestamp,amount\n\
+                   account,5,USD,200,,,,,\n\
+                   \n\
+                   activity,,,,5,1,5,2019-08-09 09:00:00,500\n\
+                   activity,,,,5,1,5,yesterday,500\n";
+        let json_lines = "{\"type\":\"account\",\"account_id\":5}\n\
+                          {\"type\":\"activity\",\"owner_account_id\":5,\"source_account_id\":1,\"target_account_id\":5,\"timestamp\":\"2019-08-09T09:00:00\",\"amount\":500}\n\
+                          {\"type\":\"transfer\"}\n";
+
+        // When
+        let csv_lines = read_import_lines(Cursor::new(csv), ImportFormat::Csv).collect::<Vec<_>>();
+        let json_lines =
+            read_import_lines(Cursor::new(json_lines), ImportFormat::JsonLines).collect::<Vec<_>>();
+
+        // Then
+        assert_eq!(
+            vec![
+                ImportLine {
+                    line_number: 2,
+                    record: Ok(ImportRecord::Account(ImportedAccount {
+                        account_id: AccountId(5),
+                        currency: Currency::of("USD"),
+                        overdraft_limit: Money::of(200),
+                    })),
+                },
+                ImportLine {
+                    line_number: 4,
+                    record: Ok(ImportRecord::Activity(activity())),
+                },
+                ImportLine {
+                    line_number: 5,
+                    record: Err("invalid timestamp: yesterday".to_string()),
+                },
+            ],
+            csv_lines
+        );
+        assert_eq!(
+            Ok(ImportRecord::Account(ImportedAccount {
+                account_id: AccountId(5),
+                currency: Currency::default(),
+                overdraft_limit: Money::of(0),
+            })),
+            json_lines[0].record
+        );
+        assert_eq!(Ok(ImportRecord::Activity(activity())), json_lines[1].record);
+        assert_eq!(3, json_lines[2].line_number);
+        assert!(json_lines[2].record.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_the_rejected_lines() {
+        // Given
+        let directory = std::env::temp_dir().join(format!("import-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("legacy.jsonl");
+        std::fs::write(&path, "{\"type\":\"account\",\"account_id\":5}\n").unwrap();
+        let args = ImportArgs::parse(&[
+            path.to_string_lossy().into_owned(),
+            "--dry-run".to_string(),
+            "--json".to_string(),
+        ])
+        .unwrap();
+
+        let mut import_use_case = MockImportUseCaseImpl::new();
+        import_use_case
+            .expect_import()
+            .times(1)
+            .with(
+                function(|command: &ImportCommand| {
+                    command.dry_run && command.source.ends_with("legacy.jsonl")
+                }),
+                always(),
+            )
+            .returning(|_, lines| ImportReport {
+                imported_accounts: lines.count(),
+                rejected_lines: vec![RejectedLine {
+                    line_number: 7,
+                    reason: "account 5 exists already, twice".to_string(),
+                }],
+                ..ImportReport::default()
+            });
+
+        // When
+        let report = run(&import_use_case, &args).await.unwrap();
+
+        // Then
+        assert_eq!(1, report.imported_accounts);
+        assert_eq!(
+            directory.join("legacy.jsonl.rejected.csv"),
+            args.rejected_report_path
+        );
+        assert_eq!(
+            "line_number,reason\n7,\"account 5 exists already, twice\"\n",
+            std::fs::read_to_string(&args.rejected_report_path).unwrap()
+        );
+        let json: serde_json::Value = serde_json::from_str(&format_report(
+            &ImportReportDto::of(&report, &args),
+            args.json,
+        ))
+        .unwrap();
+        assert_eq!(true, json["dry_run"]);
+        assert_eq!(1, json["rejected_line_count"]);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn activity() -> Activity {
+        Activity::new(
+            AccountId(5),
+            AccountId(1),
+            AccountId(5),
+            NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            Money::of(500),
+        )
+    }
+}
@@ -0,0 +1,9 @@
+pub mod account_command;
+pub mod accrue_interest_command;
+mod csv;
+pub mod export_ledger_command;
+pub mod import_command;
+pub mod migrate_command;
+pub mod reconcile_command;
+pub mod send_money_command;
+mod table;
@@ -0,0 +1,54 @@
+use crate::table;
+use serde::{Deserialize, Serialize};
+
+pub const USAGE: &str = "usage: main migrate [--json]";
+
+#[derive(PartialEq, Debug)]
+pub struct MigrateArgs {
+    /// Prints JSON instead of a table.
+    pub json: bool,
+}
+
+impl MigrateArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        match args {
+            [] => Ok(Self { json: false }),
+            [json] if json == "--json" => Ok(Self { json: true }),
+            [arg, ..] => Err(format!("unknown option: {}", arg)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct MigrationDto {
+    pub version: i64,
+    pub description: String,
+    /// Whether the migration was pending and has been applied by this run.
+    pub applied_now: bool,
+}
+
+pub fn format_migrations(migrations: &[MigrationDto], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(migrations).unwrap() + "\n";
+    }
+    table::format_table(
+        &["VERSION", "DESCRIPTION", "STATUS"],
+        &migrations
+            .iter()
+            .map(|migration| {
+                vec![
+                    migration.version.to_string(),
+                    migration.description.clone(),
+                    if migration.applied_now {
+                        "applied now"
+                    } else {
+                        "applied before"
+                    }
+                    .to_string(),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
@@ -0,0 +1,325 @@
+use crate::table;
+use application::inbound_ports::{
+    Inconsistency, ReconcileCommand, ReconcileUseCase, ReconciliationReport,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "usage: main reconcile [--repair] [--output <file>] [--json]";
+
+#[derive(PartialEq, Debug)]
+pub struct ReconcileArgs {
+    pub repair: bool,
+    /// Defaults to the standard output.
+    pub output: Option<PathBuf>,
+    /// Writes JSON instead of a table.
+    pub json: bool,
+}
+
+impl ReconcileArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut reconcile_args = Self {
+            repair: false,
+            output: None,
+            json: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--repair" => reconcile_args.repair = true,
+                "--json" => reconcile_args.json = true,
+                "--output" => match args.next() {
+                    Some(value) => reconcile_args.output = Some(PathBuf::from(value)),
+                    None => return Err(format!("{} needs a value", arg)),
+                },
+                _ => return Err(format!("unknown option: {}", arg)),
+            }
+        }
+        Ok(reconcile_args)
+    }
+}
+
+/**
+ * The report of a reconciliation, e.g. `{"reconciled_at":"2019-08-09 09:00:00","repair":false,
+ * "checked_activity_count":3,"checked_snapshot_count":1,"unresolved_count":1,
+ * "inconsistencies":[{"type":"UNKNOWN_ACCOUNT","activity_id":13,"account_id":9}]}`.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ReconciliationReportDto {
+    pub reconciled_at: String,
+    pub repair: bool,
+    pub checked_activity_count: usize,
+    pub checked_snapshot_count: usize,
+    /// The number of inconsistencies which have not been repaired.
+    pub unresolved_count: usize,
+    pub inconsistencies: Vec<InconsistencyDto>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InconsistencyDto {
+    MissingCounterpart {
+        activity_id: i64,
+        source_account_id: i64,
+        target_account_id: i64,
+        /// Either WITHDRAWAL or DEPOSIT.
+        missing: String,
+    },
+    UnknownAccount {
+        activity_id: i64,
+        account_id: i64,
+    },
+    SnapshotMismatch {
+        account_id: i64,
+        withdrawal_balance: i64,
+        deposit_balance: i64,
+        replayed_withdrawal_balance: i64,
+        replayed_deposit_balance: i64,
+        repaired: bool,
+    },
+}
+
+impl From<&ReconciliationReport> for ReconciliationReportDto {
+    fn from(report: &ReconciliationReport) -> Self {
+        Self {
+            reconciled_at: report.reconciled_at.to_string(),
+            repair: report.repair,
+            checked_activity_count: report.checked_activity_count,
+            checked_snapshot_count: report.checked_snapshot_count,
+            unresolved_count: report
+                .inconsistencies
+                .iter()
+                .filter(|inconsistency| !inconsistency.is_repaired())
+                .count(),
+            inconsistencies: report
+                .inconsistencies
+                .iter()
+                .map(InconsistencyDto::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<&Inconsistency> for InconsistencyDto {
+    fn from(inconsistency: &Inconsistency) -> Self {
+        match inconsistency {
+            Inconsistency::MissingCounterpart {
+                activity_id,
+                source_account_id,
+                target_account_id,
+                missing_withdrawal,
+            } => InconsistencyDto::MissingCounterpart {
+                activity_id: activity_id.0,
+                source_account_id: source_account_id.0,
+                target_account_id: target_account_id.0,
+                missing: if *missing_withdrawal {
+                    "WITHDRAWAL"
+                } else {
+                    "DEPOSIT"
+                }
+                .to_string(),
+            },
+            Inconsistency::UnknownAccount {
+                activity_id,
+                account_id,
+            } => InconsistencyDto::UnknownAccount {
+                activity_id: activity_id.0,
+                account_id: account_id.0,
+            },
+            Inconsistency::SnapshotMismatch {
+                snapshot_balances,
+                repaired,
+            } => InconsistencyDto::SnapshotMismatch {
+                account_id: snapshot_balances.account_id.0,
+                withdrawal_balance: to_i64(&snapshot_balances.withdrawal_balance.amount),
+                deposit_balance: to_i64(&snapshot_balances.deposit_balance.amount),
+                replayed_withdrawal_balance: to_i64(
+                    &snapshot_balances.replayed_withdrawal_balance.amount,
+                ),
+                replayed_deposit_balance: to_i64(
+                    &snapshot_balances.replayed_deposit_balance.amount,
+                ),
+                repaired: *repaired,
+            },
+        }
+    }
+}
+
+pub async fn run(
+    reconcile_use_case: &dyn ReconcileUseCase,
+    args: &ReconcileArgs,
+) -> ReconciliationReportDto {
+    let report = reconcile_use_case
+        .reconcile(ReconcileCommand::new(args.repair))
+        .await;
+    ReconciliationReportDto::from(&report)
+}
+
+/**
+ * Renders the report as pretty printed JSON, see [ReconciliationReportDto] for the schema, or
+ * its inconsistencies as a table.
+ */
+pub fn format_report(report: &ReconciliationReportDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(report).unwrap() + "\n";
+    }
+    table::format_table(
+        &["TYPE", "ACTIVITY", "ACCOUNT", "DETAILS", "RESOLVED"],
+        &report
+            .inconsistencies
+            .iter()
+            .map(|inconsistency| match inconsistency {
+                InconsistencyDto::MissingCounterpart {
+                    activity_id,
+                    source_account_id,
+                    target_account_id,
+                    missing,
+                } => vec![
+                    "missing counterpart".to_string(),
+                    activity_id.to_string(),
+                    source_account_id.to_string(),
+                    format!(
+                        "{} of the transfer to {} missing",
+                        missing.to_lowercase(),
+                        target_account_id
+                    ),
+                    "no".to_string(),
+                ],
+                InconsistencyDto::UnknownAccount {
+                    activity_id,
+                    account_id,
+                } => vec![
+                    "unknown account".to_string(),
+                    activity_id.to_string(),
+                    account_id.to_string(),
+                    String::new(),
+                    "no".to_string(),
+                ],
+                InconsistencyDto::SnapshotMismatch {
+                    account_id,
+                    withdrawal_balance,
+                    deposit_balance,
+                    replayed_withdrawal_balance,
+                    replayed_deposit_balance,
+                    repaired,
+                } => vec![
+                    "snapshot mismatch".to_string(),
+                    String::new(),
+                    account_id.to_string(),
+                    format!(
+                        "withdrawals {} deposits {}, replayed {} and {}",
+                        withdrawal_balance,
+                        deposit_balance,
+                        replayed_withdrawal_balance,
+                        replayed_deposit_balance
+                    ),
+                    if *repaired { "repaired" } else { "no" }.to_string(),
+                ],
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn format_summary(report: &ReconciliationReportDto) -> String {
+    format!(
+        "{} activities and {} snapshots checked, {} inconsistencies found, {} unresolved",
+        report.checked_activity_count,
+        report.checked_snapshot_count,
+        report.inconsistencies.len(),
+        report.unresolved_count
+    )
+}
+
+fn to_i64(amount: &impl ToString) -> i64 {
+    amount.to_string().parse::<i64>().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::AccountSnapshotBalances;
+    use chrono::NaiveDate;
+    use domain::{
+        ar::{account::AccountId, activity::ActivityId},
+        vo::money::Money,
+    };
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(ReconcileArgs {
+                repair: true,
+                output: Some(PathBuf::from("report.json")),
+                json: true,
+            }),
+            ReconcileArgs::parse(&args(&["--repair", "--output", "report.json", "--json"]))
+        );
+        assert_eq!(
+            Err("--output needs a value".to_string()),
+            ReconcileArgs::parse(&args(&["--output"]))
+        );
+        assert_eq!(
+            Err("unknown option: --fix".to_string()),
+            ReconcileArgs::parse(&args(&["--fix"]))
+        );
+    }
+
+    #[test]
+    fn test_format_report() {
+        // Given a transfer missing its deposit and a repaired snapshot
+        let report = ReconciliationReport {
+            reconciled_at: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            repair: true,
+            checked_activity_count: 3,
+            checked_snapshot_count: 1,
+            inconsistencies: vec![
+                Inconsistency::MissingCounterpart {
+                    activity_id: ActivityId(13),
+                    source_account_id: AccountId(1),
+                    target_account_id: AccountId(2),
+                    missing_withdrawal: false,
+                },
+                Inconsistency::SnapshotMismatch {
+                    snapshot_balances: AccountSnapshotBalances {
+                        account_id: AccountId(2),
+                        withdrawal_balance: Money::of(0),
+                        deposit_balance: Money::of(0),
+                        replayed_withdrawal_balance: Money::of(0),
+                        replayed_deposit_balance: Money::of(500),
+                    },
+                    repaired: true,
+                },
+            ],
+        };
+
+        // When
+        let report = ReconciliationReportDto::from(&report);
+        let json: serde_json::Value = serde_json::from_str(&format_report(&report, true)).unwrap();
+
+        // Then
+        assert_eq!(1, json["unresolved_count"]);
+        assert_eq!("MISSING_COUNTERPART", json["inconsistencies"][0]["type"]);
+        assert_eq!("DEPOSIT", json["inconsistencies"][0]["missing"]);
+        assert_eq!("SNAPSHOT_MISMATCH", json["inconsistencies"][1]["type"]);
+        assert_eq!(500, json["inconsistencies"][1]["replayed_deposit_balance"]);
+        assert_eq!(true, json["inconsistencies"][1]["repaired"]);
+        // And as a table
+        assert_eq!(
+            concat!(
+                "TYPE                 ACTIVITY  ACCOUNT  DETAILS                                       RESOLVED\n",
+                "missing counterpart        13        1  deposit of the transfer to 2 missing          no\n",
+                "snapshot mismatch                    2  withdrawals 0 deposits 0, replayed 0 and 500  repaired\n",
+            ),
+            format_report(&report, false)
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
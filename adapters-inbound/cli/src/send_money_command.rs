@@ -0,0 +1,160 @@
+use crate::table;
+use application::inbound_ports::{Principal, SendMoneyCommand, SendMoneyUseCase, TransferReceipt};
+use domain::{ar::account::AccountId, vo::money::Money};
+use serde::{Deserialize, Serialize};
+
+pub const USAGE: &str =
+    "usage: main send <source account id> <target account id> <amount> [--json]";
+
+#[derive(PartialEq, Debug)]
+pub struct SendMoneyArgs {
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    /// Prints JSON instead of a table.
+    pub json: bool,
+}
+
+impl SendMoneyArgs {
+    // Functions
+
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let json = args.iter().any(|arg| arg == "--json");
+        let values: Vec<i64> = args
+            .iter()
+            .filter(|arg| *arg != "--json")
+            .map(|arg| {
+                arg.parse::<i64>()
+                    .ok()
+                    .filter(|number| *number > 0)
+                    .ok_or_else(|| format!("invalid number: {}", arg))
+            })
+            .collect::<Result<_, _>>()?;
+        let [source_account_id, target_account_id, amount] = values[..] else {
+            return Err("expected a source and a target account id and an amount".to_string());
+        };
+        Ok(Self {
+            source_account_id,
+            target_account_id,
+            amount,
+            json,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TransferReceiptDto {
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub fee: i64,
+}
+
+impl From<&TransferReceipt> for TransferReceiptDto {
+    fn from(receipt: &TransferReceipt) -> Self {
+        Self {
+            source_account_id: receipt.source_account_id.0,
+            target_account_id: receipt.target_account_id.0,
+            amount: receipt.money.amount.to_string().parse::<i64>().unwrap(),
+            fee: receipt.fee.amount.to_string().parse::<i64>().unwrap(),
+        }
+    }
+}
+
+pub async fn run(
+    send_money_use_case: &dyn SendMoneyUseCase,
+    args: &SendMoneyArgs,
+) -> Result<String, String> {
+    let receipt = send_money_use_case
+        .send_money(SendMoneyCommand::new(
+            AccountId(args.source_account_id),
+            AccountId(args.target_account_id),
+            Money::of(args.amount.into()),
+            Principal::system(),
+        ))
+        .await
+        .map_err(|error| error.to_string())?;
+    Ok(format_receipt(
+        &TransferReceiptDto::from(&receipt),
+        args.json,
+    ))
+}
+
+pub fn format_receipt(receipt: &TransferReceiptDto, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(receipt).unwrap() + "\n";
+    }
+    table::format_table(
+        &["SOURCE", "TARGET", "AMOUNT", "FEE"],
+        &[vec![
+            receipt.source_account_id.to_string(),
+            receipt.target_account_id.to_string(),
+            receipt.amount.to_string(),
+            receipt.fee.to_string(),
+        ]],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::SendMoneyError;
+    use async_trait::async_trait;
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        SendMoneyUseCaseImpl {}
+        #[async_trait]
+        impl SendMoneyUseCase for SendMoneyUseCaseImpl {
+            async fn send_money(
+                &self,
+                command: SendMoneyCommand,
+            ) -> Result<TransferReceipt, SendMoneyError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        // Given
+        let mut send_money_use_case = MockSendMoneyUseCaseImpl::new();
+        send_money_use_case
+            .expect_send_money()
+            .times(1)
+            .with(eq(SendMoneyCommand::new(
+                AccountId(1),
+                AccountId(2),
+                Money::of(500),
+                Principal::system(),
+            )))
+            .returning(|command| {
+                Ok(TransferReceipt {
+                    source_account_id: command.source_account_id,
+                    target_account_id: command.target_account_id,
+                    money: command.money,
+                    fee: Money::of(1),
+                })
+            });
+        let send_money_args = SendMoneyArgs::parse(&args(&["1", "2", "500", "--json"])).unwrap();
+
+        // When
+        let output = run(&send_money_use_case, &send_money_args).await.unwrap();
+
+        // Then
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(500, json["amount"]);
+        assert_eq!(1, json["fee"]);
+        assert_eq!(
+            Err("invalid number: -5".to_string()),
+            SendMoneyArgs::parse(&args(&["1", "2", "-5"]))
+        );
+        assert_eq!(
+            Err("expected a source and a target account id and an amount".to_string()),
+            SendMoneyArgs::parse(&args(&["1", "2"]))
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
@@ -0,0 +1,54 @@
+/**
+ * Renders the rows below the header with each column padded to its widest value, numbers
+ * aligned to the right.
+ */
+pub fn format_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+    let header: Vec<String> = header.iter().map(|title| title.to_string()).collect();
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| {
+                if value.parse::<f64>().is_ok() {
+                    format!("{:>width$}", value, width = width)
+                } else {
+                    format!("{:<width$}", value, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_table() {
+        assert_eq!(
+            concat!(
+                "ACCOUNT  CURRENCY  BALANCE\n",
+                "      1  EUR          -500\n",
+                "    100  USD             7\n",
+            ),
+            format_table(
+                &["ACCOUNT", "CURRENCY", "BALANCE"],
+                &[
+                    vec!["1".to_string(), "EUR".to_string(), "-500".to_string()],
+                    vec!["100".to_string(), "USD".to_string(), "7".to_string()],
+                ]
+            )
+        );
+    }
+}
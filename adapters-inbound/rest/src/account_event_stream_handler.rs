@@ -0,0 +1,388 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use crate::authentication;
+use application::{
+    in_process_domain_event_publisher::DomainEventSubscriber,
+    inbound_ports::{AccountActivity, AccountActivityError, AccountActivityUseCase},
+};
+use domain::ar::{account::AccountId, activity::ActivityId, domain_event::DomainEvent};
+use salvo::{
+    http::{body::BodySender, header},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+static ACCOUNT_ACTIVITY_USE_CASE: OnceLock<Box<dyn AccountActivityUseCase>> = OnceLock::new();
+static ACCOUNT_ACTIVITY_BROADCAST: OnceLock<Arc<AccountActivityBroadcast>> = OnceLock::new();
+
+const LAST_EVENT_ID: &str = "last-event-id";
+const PAGE_SIZE: usize = 100;
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+pub fn set_dependencies(
+    aauc: Box<dyn AccountActivityUseCase>,
+    account_activity_broadcast: Arc<AccountActivityBroadcast>,
+) {
+    ACCOUNT_ACTIVITY_USE_CASE.set(aauc).unwrap();
+    ACCOUNT_ACTIVITY_BROADCAST
+        .set(account_activity_broadcast)
+        .unwrap();
+}
+
+// GET /accounts/<accountId>/events
+pub fn get_routes() -> Router {
+    Router::with_path("accounts/<accountId:num>/events").get(stream_account_events)
+}
+
+/**
+ * Broadcasts the ids of the accounts with new activities to the open event streams, which then
+ * load the activities themselves. A stream that lags behind simply catches up from its last id.
+ */
+#[derive(Debug)]
+pub struct AccountActivityBroadcast {
+    sender: broadcast::Sender<AccountId>,
+}
+
+impl AccountActivityBroadcast {
+    // Functions
+
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl DomainEventSubscriber for AccountActivityBroadcast {
+    async fn on_domain_event(&self, domain_event: &DomainEvent) {
+        match domain_event {
+            DomainEvent::MoneyWithdrawn { account_id, .. }
+            | DomainEvent::MoneyDeposited { account_id, .. } => {
+                // there may be no open stream at all
+                let _ = self.sender.send(account_id.clone());
+            }
+            DomainEvent::TransferCompleted { .. } | DomainEvent::TransferRejected { .. } => {}
+        }
+    }
+}
+
+/// The `balance` is the balance of the account right after the activity.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct AccountActivityDto {
+    pub activity_id: i64,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub timestamp: String,
+    pub balance: i64,
+}
+
+impl From<&AccountActivity> for AccountActivityDto {
+    fn from(account_activity: &AccountActivity) -> Self {
+        let activity = &account_activity.activity;
+        Self {
+            activity_id: activity.id.as_ref().unwrap().0,
+            source_account_id: activity.source_account_id.0,
+            target_account_id: activity.target_account_id.0,
+            amount: activity.money.amount.to_string().parse::<i64>().unwrap(),
+            timestamp: activity.timestamp.to_string(),
+            balance: account_activity
+                .balance
+                .amount
+                .to_string()
+                .parse::<i64>()
+                .unwrap(),
+        }
+    }
+}
+
+/**
+ * Streams the activities of the account as Server-Sent Events whose id is the activity id.
+ * A client resuming with the `Last-Event-ID` header first receives everything it missed, any
+ * other client receives the activities stored from then on.
+ */
+#[handler]
+async fn stream_account_events(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+    let last_activity_id = match req.headers().get(LAST_EVENT_ID) {
+        None => None,
+        Some(value) => match value
+            .to_str()
+            .ok()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+        {
+            Some(last_activity_id) => Some(last_activity_id),
+            None => {
+                res.status_code(StatusCode::BAD_REQUEST);
+                return;
+            }
+        },
+    };
+    let last_activity_id = match ACCOUNT_ACTIVITY_USE_CASE
+        .get()
+        .unwrap()
+        .open_activity_stream(account_id.clone(), authentication::principal(depot))
+        .await
+    {
+        Ok(current_activity_id) => last_activity_id.or(current_activity_id.map(|id| id.0)),
+        Err(AccountActivityError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to view the account");
+            return;
+        }
+    };
+
+    // subscribed before catching up, so no activity stored in between is missed
+    let receiver = ACCOUNT_ACTIVITY_BROADCAST.get().unwrap().sender.subscribe();
+    res.status_code(StatusCode::OK);
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("text/event-stream"),
+    );
+    res.headers_mut().insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_static("no-cache"),
+    );
+    let sender = res.channel();
+    tokio::spawn(send_account_events(
+        account_id,
+        last_activity_id,
+        receiver,
+        sender,
+    ));
+}
+
+/**
+ * Sends the activities stored after the last sent one whenever the account changes, until the
+ * client disconnects.
+ */
+async fn send_account_events(
+    account_id: AccountId,
+    mut last_activity_id: Option<i64>,
+    mut receiver: broadcast::Receiver<AccountId>,
+    mut sender: BodySender,
+) {
+    let account_activity_use_case = ACCOUNT_ACTIVITY_USE_CASE.get().unwrap();
+    loop {
+        loop {
+            let account_activities = account_activity_use_case
+                .list_activities_after(
+                    account_id.clone(),
+                    last_activity_id.map(ActivityId),
+                    PAGE_SIZE,
+                )
+                .await;
+            for account_activity in &account_activities {
+                let dto = AccountActivityDto::from(account_activity);
+                if sender.send_data(format_event(&dto)).await.is_err() {
+                    return;
+                }
+                last_activity_id = Some(dto.activity_id);
+            }
+            if account_activities.len() < PAGE_SIZE {
+                break;
+            }
+        }
+
+        loop {
+            match tokio::time::timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+                Ok(Ok(changed_account_id)) if changed_account_id == account_id => break,
+                Ok(Ok(_)) => {}
+                Ok(Err(RecvError::Lagged(_))) => break,
+                Ok(Err(RecvError::Closed)) => return,
+                // a comment keeps proxies from closing the connection and detects gone clients
+                Err(_) => {
+                    if sender.send_data(": keep-alive\n\n").await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn format_event(dto: &AccountActivityDto) -> String {
+    format!(
+        "id: {}\nevent: activity\ndata: {}\n\n",
+        dto.activity_id,
+        serde_json::to_string(dto).unwrap()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::inbound_ports::Principal;
+    use chrono::NaiveDate;
+    use domain::{
+        ar::activity::Activity,
+        vo::{money::Money, role::Role},
+    };
+    use mockall::{mock, predicate::eq};
+    use salvo::{
+        http::{ResBody, body::Body},
+        test::TestClient,
+    };
+    use std::pin::Pin;
+
+    mock! {
+        #[derive(Debug)]
+        AccountActivityUseCaseImpl {}
+        #[async_trait]
+        impl AccountActivityUseCase for AccountActivityUseCaseImpl {
+            async fn list_activities_after(
+                &self,
+                account_id: AccountId,
+                after_activity_id: Option<ActivityId>,
+                limit: usize,
+            ) -> Vec<AccountActivity>;
+            async fn open_activity_stream(
+                &self,
+                account_id: AccountId,
+                principal: Principal,
+            ) -> Result<Option<ActivityId>, AccountActivityError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_account_events() {
+        // Given alice may view the account, whose last activity is 43, but mallory may not
+        let mut aauc = Box::new(MockAccountActivityUseCaseImpl::new());
+        aauc.expect_open_activity_stream()
+            .with(eq(AccountId(42)), eq(customer("alice")))
+            .returning(|_, _| Ok(Some(ActivityId(43))));
+        aauc.expect_open_activity_stream()
+            .with(eq(AccountId(42)), eq(customer("mallory")))
+            .returning(|_, _| Err(AccountActivityError::Unauthorized));
+        // And the client has seen activity 41
+        aauc.expect_list_activities_after()
+            .times(1)
+            .with(eq(AccountId(42)), eq(Some(ActivityId(41))), eq(PAGE_SIZE))
+            .returning(|_, _, _| vec![account_activity(42, 700)]);
+        aauc.expect_list_activities_after()
+            .times(1)
+            .with(eq(AccountId(42)), eq(Some(ActivityId(42))), eq(PAGE_SIZE))
+            .returning(|_, _, _| vec![account_activity(43, 400)]);
+        aauc.expect_list_activities_after()
+            .times(1)
+            .with(eq(AccountId(42)), eq(Some(ActivityId(43))), eq(PAGE_SIZE))
+            .returning(|_, _, _| vec![account_activity(44, 100)]);
+        let account_activity_broadcast = Arc::new(AccountActivityBroadcast::new(16));
+        super::set_dependencies(aauc, account_activity_broadcast.clone());
+
+        let service = service_for("alice");
+
+        // When the client resumes
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/42/events")
+            .add_header(LAST_EVENT_ID, "41", true)
+            .send(&service)
+            .await;
+
+        // Then it receives the missed activity
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        let mut body = res.take_body();
+        assert_eq!(
+            "id: 42\nevent: activity\ndata: {\"activity_id\":42,\"source_account_id\":42,\"target_account_id\":43,\"amount\":300,\"timestamp\":\"2019-08-09 09:00:00\",\"balance\":700}\n\n",
+            next_chunk(&mut body).await
+        );
+
+        // When another account and then the account itself change
+        account_activity_broadcast
+            .on_domain_event(&money_withdrawn(AccountId(7)))
+            .await;
+        account_activity_broadcast
+            .on_domain_event(&money_withdrawn(AccountId(42)))
+            .await;
+
+        // Then only the new activity of the account is sent
+        assert!(next_chunk(&mut body).await.starts_with("id: 43\n"));
+
+        // When a client connects without the last event id
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/42/events")
+            .send(&service)
+            .await;
+
+        // Then it is not replayed the history, only the activities stored after the last one
+        assert!(
+            next_chunk(&mut res.take_body())
+                .await
+                .starts_with("id: 44\n")
+        );
+
+        // When a customer without a mandate connects
+        let status_code = TestClient::get("http://127.0.0.1:8080/accounts/42/events")
+            .send(&service_for("mallory"))
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::FORBIDDEN, status_code);
+
+        // When the last event id is not an activity id
+        let status_code = TestClient::get("http://127.0.0.1:8080/accounts/42/events")
+            .add_header(LAST_EVENT_ID, "latest", true)
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    }
+
+    fn service_for(subject: &'static str) -> Service {
+        Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs(subject))
+                .push(super::get_routes()),
+        )
+    }
+
+    fn customer(subject: &str) -> Principal {
+        Principal::new(subject.to_string(), Role::Customer)
+    }
+
+    async fn next_chunk(body: &mut ResBody) -> String {
+        let frame = std::future::poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx))
+            .await
+            .unwrap()
+            .unwrap();
+        String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap()
+    }
+
+    fn account_activity(id: i64, balance: i128) -> AccountActivity {
+        AccountActivity::new(
+            Activity::with_id(
+                Some(ActivityId(id)),
+                AccountId(42),
+                AccountId(42),
+                AccountId(43),
+                NaiveDate::from_ymd_opt(2019, 8, 9)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                Money::of(300),
+                None,
+            ),
+            Money::of(balance),
+        )
+    }
+
+    fn money_withdrawn(account_id: AccountId) -> DomainEvent {
+        DomainEvent::MoneyWithdrawn {
+            account_id,
+            target_account_id: AccountId(43),
+            money: Money::of(300),
+            timestamp: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+        }
+    }
+}
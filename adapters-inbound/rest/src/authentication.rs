@@ -0,0 +1,386 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use application::inbound_ports::Principal;
+use domain::vo::role::Role;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use salvo::{http::header, prelude::*};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::{Span, field, info};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+static AUTHENTICATOR: OnceLock<Authenticator> = OnceLock::new();
+
+pub fn set_dependencies(authenticator: Authenticator) {
+    AUTHENTICATOR.set(authenticator).unwrap();
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JwtAlgorithm {
+    /// Signed with a shared secret.
+    Hs256,
+    /// Signed with an RSA private key, verified with the public key in PEM format.
+    Rs256,
+}
+
+/**
+ * Authenticates a request either by an API key in the `X-Api-Key` header or by a JWT in the
+ * `Authorization: Bearer <token>` header, whose `sub` claim is the principal and whose `role`
+ * claim, if any, its role. Only the SHA-256 digests of the API keys are kept, so that the
+ * configuration does not hold the keys themselves.
+ */
+#[derive(Default)]
+pub struct Authenticator {
+    principals_by_api_key_digest: HashMap<String, Principal>,
+    jwt_verifier: Option<(DecodingKey, Validation)>,
+}
+
+impl std::fmt::Debug for Authenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("api_keys", &self.principals_by_api_key_digest.len())
+            .field(
+                "jwt_algorithms",
+                &self
+                    .jwt_verifier
+                    .as_ref()
+                    .map(|(_, validation)| &validation.algorithms),
+            )
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    /// Customers unless stated otherwise.
+    role: Option<String>,
+}
+
+impl Authenticator {
+    // Functions
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Methods
+
+    /**
+     * # Arguments
+     *
+     * * `key_sha256` - The hex SHA-256 digest of the key.
+     */
+    pub fn with_api_key(mut self, subject: String, role: Role, key_sha256: &str) -> Self {
+        self.principals_by_api_key_digest
+            .insert(key_sha256.to_lowercase(), Principal::new(subject, role));
+        self
+    }
+
+    /**
+     * Accepts the JWTs signed with the key which have not expired and, if given, are issued by the
+     * issuer for the audience.
+     *
+     * # Arguments
+     *
+     * * `key` - The secret for HS256 or the public key in PEM format for RS256.
+     */
+    pub fn with_jwt(
+        mut self,
+        algorithm: JwtAlgorithm,
+        key: &[u8],
+        issuer: Option<&str>,
+        audience: Option<&str>,
+    ) -> Result<Self, String> {
+        let (decoding_key, mut validation) = match algorithm {
+            JwtAlgorithm::Hs256 => (
+                DecodingKey::from_secret(key),
+                Validation::new(Algorithm::HS256),
+            ),
+            JwtAlgorithm::Rs256 => (
+                DecodingKey::from_rsa_pem(key).map_err(|error| error.to_string())?,
+                Validation::new(Algorithm::RS256),
+            ),
+        };
+        if let Some(issuer) = issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        self.jwt_verifier = Some((decoding_key, validation));
+        Ok(self)
+    }
+
+    pub fn is_configured(&self) -> bool {
+        !self.principals_by_api_key_digest.is_empty() || self.jwt_verifier.is_some()
+    }
+
+    /**
+     * @return the principal or why the credentials are rejected.
+     */
+    pub fn authenticate(
+        &self,
+        authorization: Option<&str>,
+        api_key: Option<&str>,
+    ) -> Result<Principal, String> {
+        if let Some(authorization) = authorization {
+            let token = authorization
+                .strip_prefix("Bearer ")
+                .ok_or("expected a bearer token")?;
+            let (decoding_key, validation) = self
+                .jwt_verifier
+                .as_ref()
+                .ok_or("tokens are not accepted")?;
+            let claims = jsonwebtoken::decode::<Claims>(token, decoding_key, validation)
+                .map_err(|error| format!("invalid token: {}", error))?
+                .claims;
+            let role = match claims.role {
+                Some(role) => role
+                    .parse()
+                    .map_err(|error| format!("invalid token: {}", error))?,
+                None => Role::Customer,
+            };
+            return Ok(Principal::new(claims.sub, role));
+        }
+        if let Some(api_key) = api_key {
+            let digest = hex::encode(Sha256::digest(api_key.as_bytes()));
+            return self
+                .principals_by_api_key_digest
+                .get(&digest)
+                .cloned()
+                .ok_or("invalid API key".to_string());
+        }
+        Err("missing credentials".to_string())
+    }
+}
+
+/**
+ * Rejects the request unless it is authenticated, otherwise the handlers behind it take the
+ * principal from the depot with [principal].
+ */
+#[handler]
+pub async fn authenticate(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+    ctrl: &mut FlowCtrl,
+) {
+    let authorization = req.header::<String>(header::AUTHORIZATION);
+    let api_key = req.header::<String>(API_KEY_HEADER);
+    match AUTHENTICATOR
+        .get()
+        .unwrap()
+        .authenticate(authorization.as_deref(), api_key.as_deref())
+    {
+        Ok(principal) => {
+            Span::current()
+                .record("principal", principal.subject.as_str())
+                .record("role", field::display(principal.role));
+            depot.inject(principal);
+        }
+        Err(error) => {
+            info!(%error, "authentication failed");
+            res.status_code(StatusCode::UNAUTHORIZED);
+            res.add_header(header::WWW_AUTHENTICATE, "Bearer", true)
+                .unwrap();
+            res.render(error);
+            ctrl.skip_rest();
+        }
+    }
+}
+
+/**
+ * @return the principal authenticated by [authenticate].
+ */
+pub fn principal(depot: &Depot) -> Principal {
+    depot.obtain::<Principal>().unwrap().clone()
+}
+
+/// Stands in for [authenticate] in the tests of the handlers, authenticating a customer.
+#[cfg(test)]
+pub(crate) struct AuthenticatedAs(pub &'static str);
+
+#[cfg(test)]
+#[handler]
+impl AuthenticatedAs {
+    async fn handle(&self, depot: &mut Depot) {
+        depot.inject(Principal::new(self.0.to_string(), Role::Customer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+    use salvo::test::{ResponseExt, TestClient};
+    use serde::Serialize;
+
+    const HS256_SECRET: &[u8] = b"a secret of at least 32 bytes...";
+
+    #[derive(Serialize)]
+    struct TestClaims<'a> {
+        sub: &'a str,
+        iss: &'a str,
+        exp: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        role: Option<&'a str>,
+    }
+
+    fn token(algorithm: Algorithm, key: &EncodingKey, iss: &str, expires_in: i64) -> String {
+        token_with_role(algorithm, key, iss, expires_in, None)
+    }
+
+    fn token_with_role(
+        algorithm: Algorithm,
+        key: &EncodingKey,
+        iss: &str,
+        expires_in: i64,
+        role: Option<&str>,
+    ) -> String {
+        let claims = TestClaims {
+            sub: "alice",
+            iss,
+            exp: chrono::Utc::now().timestamp() + expires_in,
+            role,
+        };
+        jsonwebtoken::encode(&Header::new(algorithm), &claims, key).unwrap()
+    }
+
+    #[test]
+    fn test_authenticates_jwts() {
+        // Given an RS256 public key
+        let authenticator = Authenticator::new()
+            .with_jwt(
+                JwtAlgorithm::Rs256,
+                include_bytes!("../tests/jwt-rs256.pub.pem"),
+                Some("https://id.example.com"),
+                None,
+            )
+            .unwrap();
+        let private_key =
+            EncodingKey::from_rsa_pem(include_bytes!("../tests/jwt-rs256.pem")).unwrap();
+        let bearer = |token: String| format!("Bearer {}", token);
+
+        // When a token signed with the private key is presented
+        let valid = token(Algorithm::RS256, &private_key, "https://id.example.com", 60);
+
+        // Then its subject is the principal, a customer unless its role says otherwise
+        assert_eq!(
+            authenticator.authenticate(Some(&bearer(valid)), None),
+            Ok(Principal::new("alice".to_string(), Role::Customer))
+        );
+        let support = token_with_role(
+            Algorithm::RS256,
+            &private_key,
+            "https://id.example.com",
+            60,
+            Some("support"),
+        );
+        assert_eq!(
+            authenticator.authenticate(Some(&bearer(support)), None),
+            Ok(Principal::new("alice".to_string(), Role::Support))
+        );
+
+        // When tokens which are expired, issued by another issuer or signed with another key or
+        // algorithm are presented
+        let expired = token(
+            Algorithm::RS256,
+            &private_key,
+            "https://id.example.com",
+            -120,
+        );
+        let foreign = token(
+            Algorithm::RS256,
+            &private_key,
+            "https://other.example.com",
+            60,
+        );
+        let forged = token(
+            Algorithm::HS256,
+            &EncodingKey::from_secret(include_bytes!("../tests/jwt-rs256.pub.pem")),
+            "https://id.example.com",
+            60,
+        );
+        let unknown_role = token_with_role(
+            Algorithm::RS256,
+            &private_key,
+            "https://id.example.com",
+            60,
+            Some("root"),
+        );
+
+        // Then they are rejected
+        for token in [expired, foreign, forged, unknown_role] {
+            let result = authenticator.authenticate(Some(&bearer(token)), None);
+            assert!(result.unwrap_err().starts_with("invalid token"));
+        }
+        assert_eq!(
+            authenticator.authenticate(Some("Basic YWxpY2U6c2VjcmV0"), None),
+            Err("expected a bearer token".to_string())
+        );
+    }
+
+    #[handler]
+    async fn whoami(depot: &mut Depot, res: &mut Response) {
+        res.render(principal(depot).subject);
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        // Given an API key and an HS256 secret
+        let authenticator = Authenticator::new()
+            .with_api_key(
+                "reporting".to_string(),
+                Role::Support,
+                &hex::encode(Sha256::digest(b"key-of-reporting")),
+            )
+            .with_jwt(JwtAlgorithm::Hs256, HS256_SECRET, None, None)
+            .unwrap();
+        super::set_dependencies(authenticator);
+
+        let service = Service::new(Router::new().hoop(authenticate).get(whoami));
+        let token = token(
+            Algorithm::HS256,
+            &EncodingKey::from_secret(HS256_SECRET),
+            "",
+            60,
+        );
+
+        // When requests are sent with an API key and a token
+        for (name, value, expected) in [
+            (API_KEY_HEADER, "key-of-reporting".to_string(), "reporting"),
+            ("authorization", format!("Bearer {}", token), "alice"),
+        ] {
+            let mut res = TestClient::get("http://127.0.0.1:8080/")
+                .add_header(name, value, true)
+                .send(&service)
+                .await;
+
+            // Then the handler is called with the principal
+            assert_eq!(StatusCode::OK, res.status_code.unwrap());
+            assert_eq!(res.take_string().await.unwrap(), expected);
+        }
+
+        // When requests are sent with a wrong API key and without credentials
+        for (name, value, expected) in [
+            (API_KEY_HEADER, "key-of-someone", "invalid API key"),
+            ("accept", "text/plain", "missing credentials"),
+        ] {
+            let mut res = TestClient::get("http://127.0.0.1:8080/")
+                .add_header(name, value, true)
+                .send(&service)
+                .await;
+
+            // Then they are rejected
+            assert_eq!(StatusCode::UNAUTHORIZED, res.status_code.unwrap());
+            assert_eq!(
+                res.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+                "Bearer"
+            );
+            assert_eq!(res.take_string().await.unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    FxQuote, FxQuoteId, FxTransferUseCase, SendMoneyCommand, SendMoneyError,
+};
+use domain::{ar::account::AccountId, vo::money::Money};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static FX_TRANSFER_USE_CASE: OnceLock<Box<dyn FxTransferUseCase>> = OnceLock::new();
+
+pub fn set_dependencies(ftuc: Box<dyn FxTransferUseCase>) {
+    FX_TRANSFER_USE_CASE.set(ftuc).unwrap();
+}
+
+// POST /accounts/fx-quote/<sourceAccountId>/<targetAccountId>/<amount>
+// POST /accounts/fx-send/<sourceAccountId>/<quoteId>
+pub fn get_routes() -> Router {
+    Router::with_path("accounts")
+        .push(
+            Router::with_path("fx-quote/<sourceAccountId:num>/<targetAccountId:num>/<amount:num>")
+                .post(quote_fx_transfer),
+        )
+        .push(Router::with_path("fx-send/<sourceAccountId:num>/<quoteId>").post(send_money_fx))
+}
+
+/// The `source_amount` plus the `fee_amount` is debited in the source currency and the
+/// `target_amount` is credited in the target currency.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct FxQuoteDto {
+    pub quote_id: String,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub source_amount: i64,
+    pub source_currency: String,
+    pub fee_amount: i64,
+    pub target_amount: i64,
+    pub target_currency: String,
+    pub exchange_rate: String,
+    pub expires_at: String,
+}
+
+impl From<FxQuote> for FxQuoteDto {
+    fn from(quote: FxQuote) -> Self {
+        Self {
+            quote_id: quote.id.0,
+            source_account_id: quote.source_account_id.0,
+            target_account_id: quote.target_account_id.0,
+            source_amount: quote
+                .source_money
+                .amount
+                .to_string()
+                .parse::<i64>()
+                .unwrap(),
+            source_currency: quote.exchange_rate.source_currency.to_string(),
+            fee_amount: quote.fee.amount.to_string().parse::<i64>().unwrap(),
+            target_amount: quote
+                .target_money
+                .amount
+                .to_string()
+                .parse::<i64>()
+                .unwrap(),
+            target_currency: quote.exchange_rate.target_currency.to_string(),
+            exchange_rate: quote.exchange_rate.to_string(),
+            expires_at: quote.expires_at.to_string(),
+        }
+    }
+}
+
+#[handler]
+async fn quote_fx_transfer(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let command = SendMoneyCommand::new(
+        AccountId(req.param::<i64>("sourceAccountId").unwrap()),
+        AccountId(req.param::<i64>("targetAccountId").unwrap()),
+        Money::of(req.param::<i64>("amount").unwrap() as i128),
+        authentication::principal(depot),
+    );
+
+    let result = FX_TRANSFER_USE_CASE
+        .get()
+        .unwrap()
+        .quote_fx_transfer(command)
+        .await;
+    render(result, res);
+}
+
+#[handler]
+async fn send_money_fx(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let source_account_id = AccountId(req.param::<i64>("sourceAccountId").unwrap());
+    let quote_id = FxQuoteId(req.param::<String>("quoteId").unwrap());
+
+    let result = FX_TRANSFER_USE_CASE
+        .get()
+        .unwrap()
+        .send_money_fx(
+            source_account_id,
+            quote_id,
+            authentication::principal(depot),
+        )
+        .await;
+    render(result, res);
+}
+
+fn render(result: Result<FxQuote, SendMoneyError>, res: &mut Response) {
+    match result {
+        Ok(quote) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(FxQuoteDto::from(quote)));
+        }
+        Err(error) => {
+            res.status_code(match error {
+                SendMoneyError::QuoteNotFound => StatusCode::NOT_FOUND,
+                SendMoneyError::Unauthorized => StatusCode::FORBIDDEN,
+                _ => StatusCode::UNPROCESSABLE_ENTITY,
+            });
+            res.render(error.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::inbound_ports::Principal;
+    use chrono::NaiveDate;
+    use domain::vo::role::Role;
+    use domain::vo::{currency::Currency, exchange_rate::ExchangeRate};
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        FxTransferUseCaseImpl {}
+        #[async_trait]
+        impl FxTransferUseCase for FxTransferUseCaseImpl {
+            async fn quote_fx_transfer(
+                &self,
+                command: SendMoneyCommand,
+            ) -> Result<FxQuote, SendMoneyError>;
+            async fn send_money_fx(
+                &self,
+                source_account_id: AccountId,
+                quote_id: FxQuoteId,
+                principal: Principal,
+            ) -> Result<FxQuote, SendMoneyError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fx_transfer() {
+        // Given
+        let quote = FxQuote {
+            id: FxQuoteId("a1b2".to_string()),
+            source_account_id: AccountId(41),
+            target_account_id: AccountId(3),
+            source_money: Money::of(100),
+            target_money: Money::of(108),
+            fee: Money::of(2),
+            exchange_rate: ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500),
+            expires_at: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 30)
+                .unwrap(),
+        };
+        let mut ftuc = Box::new(MockFxTransferUseCaseImpl::new());
+        ftuc.expect_quote_fx_transfer()
+            .times(1)
+            .with(eq(SendMoneyCommand::new(
+                AccountId(41),
+                AccountId(3),
+                Money::of(100),
+                Principal::new("alice".to_string(), Role::Customer),
+            )))
+            .return_const(Ok(quote.clone()));
+        ftuc.expect_send_money_fx()
+            .with(
+                eq(AccountId(41)),
+                eq(FxQuoteId("a1b2".to_string())),
+                eq(Principal::new("alice".to_string(), Role::Customer)),
+            )
+            .return_const(Ok(quote));
+        ftuc.expect_send_money_fx()
+            .return_const(Err(SendMoneyError::QuoteNotFound));
+        super::set_dependencies(ftuc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When the transfer is quoted
+        let mut res = TestClient::post("http://127.0.0.1:8080/accounts/fx-quote/41/3/100")
+            .send(&service)
+            .await;
+
+        // Then the quote shows both amounts and the rate
+        let expected = FxQuoteDto {
+            quote_id: "a1b2".to_string(),
+            source_account_id: 41,
+            target_account_id: 3,
+            source_amount: 100,
+            source_currency: "EUR".to_string(),
+            fee_amount: 2,
+            target_amount: 108,
+            target_currency: "USD".to_string(),
+            exchange_rate: "EUR/USD 1.084500".to_string(),
+            expires_at: "2019-08-09 09:00:30".to_string(),
+        };
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(expected, res.take_json::<FxQuoteDto>().await.unwrap());
+
+        // When the quote is used
+        let mut res = TestClient::post("http://127.0.0.1:8080/accounts/fx-send/41/a1b2")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(expected, res.take_json::<FxQuoteDto>().await.unwrap());
+
+        // When an unknown quote is used
+        let res = TestClient::post("http://127.0.0.1:8080/accounts/fx-send/41/c3d4")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::NOT_FOUND, res.status_code.unwrap());
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::OnceLock;
+
+use application::inbound_ports::{
+    CheckReadinessQuery, ComponentHealth, HealthStatus, ReadinessReport,
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static CHECK_READINESS_QUERY: OnceLock<Box<dyn CheckReadinessQuery>> = OnceLock::new();
+
+pub fn set_dependencies(crq: Box<dyn CheckReadinessQuery>) {
+    CHECK_READINESS_QUERY.set(crq).unwrap();
+}
+
+// GET /health/live
+// GET /health/ready
+pub fn get_routes() -> Router {
+    Router::with_path("health")
+        .push(Router::with_path("live").get(check_liveness))
+        .push(Router::with_path("ready").get(check_readiness))
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ComponentHealthDto {
+    pub name: String,
+    pub status: String,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl From<ComponentHealth> for ComponentHealthDto {
+    fn from(component_health: ComponentHealth) -> Self {
+        Self {
+            name: component_health.name,
+            status: status_of(component_health.status),
+            latency_ms: component_health.latency.as_micros() as f64 / 1_000.0,
+            detail: component_health.detail,
+        }
+    }
+}
+
+/// The status is either `UP` or `DOWN`, the components are only checked for readiness.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct HealthDto {
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentHealthDto>,
+}
+
+impl From<ReadinessReport> for HealthDto {
+    fn from(readiness_report: ReadinessReport) -> Self {
+        Self {
+            status: status_of(readiness_report.status),
+            components: readiness_report
+                .components
+                .into_iter()
+                .map(ComponentHealthDto::from)
+                .collect(),
+        }
+    }
+}
+
+fn status_of(health_status: HealthStatus) -> String {
+    match health_status {
+        HealthStatus::Up => "UP",
+        HealthStatus::Down => "DOWN",
+    }
+    .to_string()
+}
+
+/// The process answers, which is all there is to check, a restart would not fix anything else.
+#[handler]
+async fn check_liveness(res: &mut Response) {
+    res.status_code(StatusCode::OK);
+    res.render(Json(HealthDto {
+        status: status_of(HealthStatus::Up),
+        components: vec![],
+    }));
+}
+
+#[handler]
+async fn check_readiness(res: &mut Response) {
+    let readiness_report = CHECK_READINESS_QUERY.get().unwrap().check_readiness().await;
+
+    res.status_code(match readiness_report.status {
+        HealthStatus::Up => StatusCode::OK,
+        HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+    });
+    res.render(Json(HealthDto::from(readiness_report)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use salvo::test::{ResponseExt, TestClient};
+    use std::time::Duration;
+
+    mock! {
+        #[derive(Debug)]
+        CheckReadinessQueryImpl {}
+        #[async_trait]
+        impl CheckReadinessQuery for CheckReadinessQueryImpl {
+            async fn check_readiness(&self) -> ReadinessReport;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        // Given a database which is up and a worker which is stopped
+        let mut crq = Box::new(MockCheckReadinessQueryImpl::new());
+        crq.expect_check_readiness()
+            .times(1)
+            .returning(|| ReadinessReport {
+                status: HealthStatus::Down,
+                components: vec![
+                    ComponentHealth {
+                        name: "database".to_string(),
+                        status: HealthStatus::Up,
+                        latency: Duration::from_micros(1_500),
+                        detail: None,
+                    },
+                    ComponentHealth {
+                        name: "worker:outbox-relay".to_string(),
+                        status: HealthStatus::Down,
+                        latency: Duration::ZERO,
+                        detail: Some("stopped".to_string()),
+                    },
+                ],
+            });
+        super::set_dependencies(crq);
+
+        let service = Service::new(super::get_routes());
+
+        // When the liveness is requested
+        let mut res = TestClient::get("http://127.0.0.1:8080/health/live")
+            .send(&service)
+            .await;
+
+        // Then the process is alive regardless of its components
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            res.take_json::<HealthDto>().await.unwrap(),
+            HealthDto {
+                status: "UP".to_string(),
+                components: vec![],
+            }
+        );
+
+        // When the readiness is requested
+        let mut res = TestClient::get("http://127.0.0.1:8080/health/ready")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status_code.unwrap());
+        assert_eq!(
+            res.take_json::<HealthDto>().await.unwrap(),
+            HealthDto {
+                status: "DOWN".to_string(),
+                components: vec![
+                    ComponentHealthDto {
+                        name: "database".to_string(),
+                        status: "UP".to_string(),
+                        latency_ms: 1.5,
+                        detail: None,
+                    },
+                    ComponentHealthDto {
+                        name: "worker:outbox-relay".to_string(),
+                        status: "DOWN".to_string(),
+                        latency_ms: 0.0,
+                        detail: Some("stopped".to_string()),
+                    },
+                ],
+            }
+        );
+    }
+}
@@ -1 +1,14 @@
+pub mod account_event_stream_handler;
+pub mod authentication;
+pub mod fx_transfer_handler;
+pub mod health_handler;
+pub mod metrics_handler;
+pub mod overdraft_limit_handler;
+pub mod request_tracing;
+pub mod scheduled_transfer_handler;
+pub mod send_money_batch_handler;
 pub mod send_money_handler;
+pub mod standing_order_handler;
+pub mod statement_handler;
+pub mod transfer_limits_handler;
+pub mod webhook_handler;
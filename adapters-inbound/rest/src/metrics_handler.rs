@@ -0,0 +1,72 @@
+use std::sync::{Arc, OnceLock};
+
+use application::inbound_ports::RenderMetricsQuery;
+use salvo::{http::header, prelude::*};
+
+static RENDER_METRICS_QUERY: OnceLock<Arc<dyn RenderMetricsQuery>> = OnceLock::new();
+
+pub fn set_dependencies(rmq: Arc<dyn RenderMetricsQuery>) {
+    RENDER_METRICS_QUERY.set(rmq).unwrap();
+}
+
+// GET /metrics
+pub fn get_routes() -> Router {
+    Router::with_path("metrics").get(render_metrics)
+}
+
+#[handler]
+async fn render_metrics(res: &mut Response) {
+    let metrics = RENDER_METRICS_QUERY.get().unwrap().render_metrics();
+
+    res.status_code(StatusCode::OK);
+    res.add_header(
+        header::CONTENT_TYPE,
+        "text/plain; version=0.0.4; charset=utf-8",
+        true,
+    )
+    .unwrap();
+    res.write_body(metrics).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        RenderMetricsQueryImpl {}
+        impl RenderMetricsQuery for RenderMetricsQueryImpl {
+            fn render_metrics(&self) -> String;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        // Given
+        let mut rmq = MockRenderMetricsQueryImpl::new();
+        rmq.expect_render_metrics()
+            .times(1)
+            .returning(|| "buckpal_transfers_attempted_total 3\n".to_string());
+        super::set_dependencies(Arc::new(rmq));
+
+        let service = Service::new(super::get_routes());
+
+        // When
+        let mut res = TestClient::get("http://127.0.0.1:8080/metrics")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/plain; version=0.0.4; charset=utf-8"
+        );
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "buckpal_transfers_attempted_total 3\n"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    ChangeOverdraftLimitCommand, ChangeOverdraftLimitError, ChangeOverdraftLimitUseCase,
+};
+use domain::{
+    ar::{account::AccountId, overdraft_limit_change::OverdraftLimitChange},
+    vo::money::Money,
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static CHANGE_OVERDRAFT_LIMIT_USE_CASE: OnceLock<Box<dyn ChangeOverdraftLimitUseCase>> =
+    OnceLock::new();
+
+pub fn set_dependencies(coluc: Box<dyn ChangeOverdraftLimitUseCase>) {
+    CHANGE_OVERDRAFT_LIMIT_USE_CASE.set(coluc).unwrap();
+}
+
+// PUT /accounts/<accountId>/overdraft-limit
+// GET /accounts/<accountId>/overdraft-limit/changes
+pub fn get_routes() -> Router {
+    Router::with_path("accounts/<accountId:num>/overdraft-limit")
+        .put(change_overdraft_limit)
+        .push(Router::with_path("changes").get(list_overdraft_limit_changes))
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ChangeOverdraftLimitDto {
+    pub overdraft_limit: i64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct OverdraftLimitChangeDto {
+    pub previous_limit: i64,
+    pub new_limit: i64,
+    pub changed_by: String,
+    pub changed_at: String,
+}
+
+impl From<OverdraftLimitChange> for OverdraftLimitChangeDto {
+    fn from(change: OverdraftLimitChange) -> Self {
+        Self {
+            previous_limit: change
+                .previous_limit
+                .amount
+                .to_string()
+                .parse::<i64>()
+                .unwrap(),
+            new_limit: change.new_limit.amount.to_string().parse::<i64>().unwrap(),
+            changed_by: change.changed_by,
+            changed_at: change.changed_at.to_string(),
+        }
+    }
+}
+
+#[handler]
+async fn change_overdraft_limit(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+    let Ok(dto) = req.parse_json::<ChangeOverdraftLimitDto>().await else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+    let command = ChangeOverdraftLimitCommand::new(
+        account_id,
+        Money::of(dto.overdraft_limit as i128),
+        authentication::principal(depot),
+    );
+
+    match CHANGE_OVERDRAFT_LIMIT_USE_CASE
+        .get()
+        .unwrap()
+        .change_overdraft_limit(command)
+        .await
+    {
+        Ok(()) => {
+            res.status_code(StatusCode::OK);
+        }
+        Err(ChangeOverdraftLimitError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to change the limits of the account");
+        }
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+    }
+}
+
+#[handler]
+async fn list_overdraft_limit_changes(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+
+    let Ok(changes) = CHANGE_OVERDRAFT_LIMIT_USE_CASE
+        .get()
+        .unwrap()
+        .list_overdraft_limit_changes(account_id, authentication::principal(depot))
+        .await
+    else {
+        res.status_code(StatusCode::FORBIDDEN);
+        res.render("not authorized to view the account");
+        return;
+    };
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        changes
+            .into_iter()
+            .map(OverdraftLimitChangeDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::{authorization_policy::Denied, inbound_ports::Principal};
+    use chrono::NaiveDate;
+    use domain::vo::role::Role;
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        ChangeOverdraftLimitUseCaseImpl {}
+        #[async_trait]
+        impl ChangeOverdraftLimitUseCase for ChangeOverdraftLimitUseCaseImpl {
+            async fn change_overdraft_limit(
+                &self,
+                command: ChangeOverdraftLimitCommand,
+            ) -> Result<(), ChangeOverdraftLimitError>;
+            async fn list_overdraft_limit_changes(
+                &self,
+                account_id: AccountId,
+                principal: Principal,
+            ) -> Result<Vec<OverdraftLimitChange>, Denied>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overdraft_limit() {
+        // Given
+        let mut coluc = Box::new(MockChangeOverdraftLimitUseCaseImpl::new());
+        coluc
+            .expect_change_overdraft_limit()
+            .times(1)
+            .with(eq(ChangeOverdraftLimitCommand::new(
+                AccountId(41),
+                Money::of(5_000),
+                alice(),
+            )))
+            .returning(|_| Ok(()));
+        coluc
+            .expect_change_overdraft_limit()
+            .times(1)
+            .with(eq(ChangeOverdraftLimitCommand::new(
+                AccountId(41),
+                Money::of(-1),
+                alice(),
+            )))
+            .returning(|_| Err(ChangeOverdraftLimitError::NegativeLimit));
+        coluc
+            .expect_change_overdraft_limit()
+            .times(1)
+            .with(eq(ChangeOverdraftLimitCommand::new(
+                AccountId(43),
+                Money::of(5_000),
+                alice(),
+            )))
+            .returning(|_| Err(ChangeOverdraftLimitError::Unauthorized));
+        coluc
+            .expect_list_overdraft_limit_changes()
+            .times(1)
+            .with(eq(AccountId(41)), eq(alice()))
+            .returning(|account_id, _| {
+                Ok(vec![OverdraftLimitChange::new(
+                    account_id,
+                    Money::of(0),
+                    Money::of(5_000),
+                    "alice".to_string(),
+                    NaiveDate::from_ymd_opt(2030, 1, 1)
+                        .unwrap()
+                        .and_hms_opt(8, 0, 0)
+                        .unwrap(),
+                )])
+            });
+        super::set_dependencies(coluc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When the overdraft limit is changed
+        let status_code = TestClient::put("http://127.0.0.1:8080/accounts/41/overdraft-limit")
+            .json(&ChangeOverdraftLimitDto {
+                overdraft_limit: 5_000,
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::OK, status_code);
+
+        // When the overdraft limit is negative
+        let status_code = TestClient::put("http://127.0.0.1:8080/accounts/41/overdraft-limit")
+            .json(&ChangeOverdraftLimitDto {
+                overdraft_limit: -1,
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+
+        // When the principal may not change the limits of the account
+        let status_code = TestClient::put("http://127.0.0.1:8080/accounts/43/overdraft-limit")
+            .json(&ChangeOverdraftLimitDto {
+                overdraft_limit: 5_000,
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::FORBIDDEN, status_code);
+
+        // When the changes are listed
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/41/overdraft-limit/changes")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            vec![OverdraftLimitChangeDto {
+                previous_limit: 0,
+                new_limit: 5_000,
+                changed_by: "alice".to_string(),
+                changed_at: "2030-01-01 08:00:00".to_string(),
+            }],
+            res.take_json::<Vec<OverdraftLimitChangeDto>>()
+                .await
+                .unwrap()
+        );
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+}
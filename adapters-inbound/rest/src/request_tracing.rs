@@ -0,0 +1,100 @@
+use std::time::Instant;
+
+use salvo::prelude::*;
+use tracing::{Instrument, field, info, info_span};
+use uuid::Uuid;
+
+/// Taken from the client, e.g. a gateway, or generated, and returned with the response.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAXIMUM_REQUEST_ID_LENGTH: usize = 64;
+
+/**
+ * Runs the rest of the request within a span carrying its request id, so that the events of the
+ * use cases and the persistence it calls can be correlated. The principal and its role are
+ * recorded once the request is authenticated.
+ */
+#[handler]
+pub async fn trace_request(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+    ctrl: &mut FlowCtrl,
+) {
+    let request_id = req
+        .header::<String>(REQUEST_ID_HEADER)
+        .filter(|request_id| is_valid_request_id(request_id))
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+        principal = field::Empty,
+        role = field::Empty,
+    );
+    res.add_header(REQUEST_ID_HEADER, &request_id, true)
+        .unwrap();
+
+    let started_at = Instant::now();
+    async {
+        ctrl.call_next(req, depot, res).await;
+        info!(
+            status = res.status_code.unwrap_or(StatusCode::OK).as_u16(),
+            latency_ms = started_at.elapsed().as_micros() as f64 / 1_000.0,
+            "request finished"
+        );
+    }
+    .instrument(span)
+    .await;
+}
+
+/// Anything else is replaced rather than written into the logs.
+fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAXIMUM_REQUEST_ID_LENGTH
+        && request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use salvo::test::TestClient;
+
+    #[handler]
+    async fn ok(res: &mut Response) {
+        res.status_code(StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        let service = Service::new(Router::new().hoop(trace_request).get(ok));
+
+        // When a request carries a request id
+        let res = TestClient::get("http://127.0.0.1:8080/")
+            .add_header(REQUEST_ID_HEADER, "gateway-4711", true)
+            .send(&service)
+            .await;
+
+        // Then it is returned
+        assert_eq!(StatusCode::NO_CONTENT, res.status_code.unwrap());
+        assert_eq!(
+            res.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "gateway-4711"
+        );
+
+        // When a request carries none or one which is not fit for the logs
+        for request_id in [None, Some("two words")] {
+            let mut req = TestClient::get("http://127.0.0.1:8080/");
+            if let Some(request_id) = request_id {
+                req = req.add_header(REQUEST_ID_HEADER, request_id, true);
+            }
+            let res = req.send(&service).await;
+
+            // Then one is generated
+            let generated = res.headers().get(REQUEST_ID_HEADER).unwrap();
+            assert!(Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+        }
+    }
+}
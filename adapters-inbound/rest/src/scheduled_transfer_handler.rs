@@ -0,0 +1,269 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    ScheduleTransferCommand, ScheduleTransferError, ScheduleTransferUseCase,
+};
+use chrono::NaiveDate;
+use domain::{
+    ar::{
+        account::AccountId,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId, ScheduledTransferStatus},
+    },
+    vo::money::Money,
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static SCHEDULE_TRANSFER_USE_CASE: OnceLock<Box<dyn ScheduleTransferUseCase>> = OnceLock::new();
+
+pub fn set_dependencies(stuc: Box<dyn ScheduleTransferUseCase>) {
+    SCHEDULE_TRANSFER_USE_CASE.set(stuc).unwrap();
+}
+
+// POST /accounts/schedule/<sourceAccountId>/<targetAccountId>/<amount>/<executionDate>
+// GET /accounts/<accountId>/scheduled-transfers
+// DELETE /accounts/scheduled-transfers/<scheduledTransferId>
+pub fn get_routes() -> Router {
+    Router::with_path("accounts")
+        .push(
+            Router::with_path(
+                "schedule/<sourceAccountId:num>/<targetAccountId:num>/<amount:num>/<executionDate>",
+            )
+            .post(schedule_transfer),
+        )
+        .push(
+            Router::with_path("<accountId:num>/scheduled-transfers").get(list_scheduled_transfers),
+        )
+        .push(
+            Router::with_path("scheduled-transfers/<scheduledTransferId:num>")
+                .delete(cancel_scheduled_transfer),
+        )
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ScheduledTransferIdDto {
+    pub id: i64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct ScheduledTransferDto {
+    pub id: Option<i64>,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub execution_date: String,
+    pub status: String,
+    pub executed_at: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+impl From<ScheduledTransfer> for ScheduledTransferDto {
+    fn from(st: ScheduledTransfer) -> Self {
+        Self {
+            id: st.id.map(|id| id.0),
+            source_account_id: st.source_account_id.0,
+            target_account_id: st.target_account_id.0,
+            amount: st.money.amount.to_string().parse::<i64>().unwrap(),
+            execution_date: st.execution_date.to_string(),
+            status: match st.status {
+                ScheduledTransferStatus::Pending => "PENDING",
+                ScheduledTransferStatus::Executing => "EXECUTING",
+                ScheduledTransferStatus::Executed => "EXECUTED",
+                ScheduledTransferStatus::Failed => "FAILED",
+                ScheduledTransferStatus::Cancelled => "CANCELLED",
+            }
+            .to_string(),
+            executed_at: st.executed_at.map(|t| t.to_string()),
+            failure_reason: st.failure_reason,
+        }
+    }
+}
+
+#[handler]
+async fn schedule_transfer(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    // executionDate is a calendar date (YYYY-MM-DD), the transfer is due at the start of that day
+    let Ok(execution_date) =
+        NaiveDate::parse_from_str(&req.param::<String>("executionDate").unwrap(), "%Y-%m-%d")
+    else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+    let command = ScheduleTransferCommand::new(
+        AccountId(req.param::<i64>("sourceAccountId").unwrap()),
+        AccountId(req.param::<i64>("targetAccountId").unwrap()),
+        Money::of(req.param::<i64>("amount").unwrap() as i128),
+        execution_date.and_hms_opt(0, 0, 0).unwrap(),
+        authentication::principal(depot),
+    );
+
+    match SCHEDULE_TRANSFER_USE_CASE
+        .get()
+        .unwrap()
+        .schedule_transfer(command)
+        .await
+    {
+        Ok(id) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(ScheduledTransferIdDto { id: id.0 }));
+        }
+        Err(ScheduleTransferError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+        }
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+    }
+}
+
+#[handler]
+async fn list_scheduled_transfers(req: &mut Request, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+
+    let scheduled_transfers = SCHEDULE_TRANSFER_USE_CASE
+        .get()
+        .unwrap()
+        .list_scheduled_transfers(account_id)
+        .await;
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        scheduled_transfers
+            .into_iter()
+            .map(ScheduledTransferDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[handler]
+async fn cancel_scheduled_transfer(req: &mut Request, res: &mut Response) {
+    let id = ScheduledTransferId(req.param::<i64>("scheduledTransferId").unwrap());
+
+    if SCHEDULE_TRANSFER_USE_CASE
+        .get()
+        .unwrap()
+        .cancel_scheduled_transfer(id)
+        .await
+    {
+        res.status_code(StatusCode::OK);
+    } else {
+        // unknown, or no longer pending
+        res.status_code(StatusCode::CONFLICT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::inbound_ports::Principal;
+    use domain::vo::role::Role;
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        ScheduleTransferUseCaseImpl {}
+        #[async_trait]
+        impl ScheduleTransferUseCase for ScheduleTransferUseCaseImpl {
+            async fn schedule_transfer(
+                &self,
+                command: ScheduleTransferCommand,
+            ) -> Result<ScheduledTransferId, ScheduleTransferError>;
+            async fn list_scheduled_transfers(&self, account_id: AccountId) -> Vec<ScheduledTransfer>;
+            async fn cancel_scheduled_transfer(&self, id: ScheduledTransferId) -> bool;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_transfers() {
+        // Given
+        let execution_date = NaiveDate::from_ymd_opt(2030, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let mut stuc = Box::new(MockScheduleTransferUseCaseImpl::new());
+        stuc.expect_schedule_transfer()
+            .times(1)
+            .with(eq(ScheduleTransferCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                execution_date,
+                Principal::new("alice".to_string(), Role::Customer),
+            )))
+            .returning(|_| Ok(ScheduledTransferId(7)));
+        stuc.expect_list_scheduled_transfers()
+            .times(1)
+            .with(eq(AccountId(41)))
+            .returning(move |_| {
+                let mut st = ScheduledTransfer::new(
+                    AccountId(41),
+                    AccountId(42),
+                    Money::of(500),
+                    execution_date,
+                    "alice".to_string(),
+                    Role::Customer,
+                );
+                st.id = Some(ScheduledTransferId(7));
+                vec![st]
+            });
+        stuc.expect_cancel_scheduled_transfer()
+            .times(1)
+            .with(eq(ScheduledTransferId(7)))
+            .return_const(true);
+        super::set_dependencies(stuc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When a transfer is scheduled
+        let mut res =
+            TestClient::post("http://127.0.0.1:8080/accounts/schedule/41/42/500/2030-01-01")
+                .send(&service)
+                .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            ScheduledTransferIdDto { id: 7 },
+            res.take_json::<ScheduledTransferIdDto>().await.unwrap()
+        );
+
+        // When the scheduled transfers are listed
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/41/scheduled-transfers")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        let transfers = res.take_json::<Vec<ScheduledTransferDto>>().await.unwrap();
+        assert_eq!(1, transfers.len());
+        assert_eq!("PENDING", transfers[0].status);
+
+        // When the scheduled transfer is cancelled
+        let status_code =
+            TestClient::delete("http://127.0.0.1:8080/accounts/scheduled-transfers/7")
+                .send(&service)
+                .await
+                .status_code
+                .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::OK, status_code);
+
+        // When the execution date is malformed
+        let status_code =
+            TestClient::post("http://127.0.0.1:8080/accounts/schedule/41/42/500/tomorrow")
+                .send(&service)
+                .await
+                .status_code
+                .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    BatchMode, Principal, SendMoneyBatchCommand, SendMoneyBatchUseCase, SendMoneyCommand,
+    SendMoneyError, TransferReceipt,
+};
+use domain::{ar::account::AccountId, vo::money::Money};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static SEND_MONEY_BATCH_USE_CASE: OnceLock<Box<dyn SendMoneyBatchUseCase>> = OnceLock::new();
+
+pub fn set_dependencies(smbuc: Box<dyn SendMoneyBatchUseCase>) {
+    SEND_MONEY_BATCH_USE_CASE.set(smbuc).unwrap();
+}
+
+// POST /transfers/batch
+pub fn get_routes() -> Router {
+    Router::with_path("transfers/batch").post(send_money_batch)
+}
+
+/// `mode` is `ALL_OR_NOTHING` or `BEST_EFFORT`.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct SendMoneyBatchDto {
+    pub mode: String,
+    pub transfers: Vec<TransferDto>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TransferDto {
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+}
+
+/// `status` is `SUCCEEDED` or `REJECTED`, the `fee` is only set for succeeded transfers
+/// and the `reason` only for rejected ones.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TransferResultDto {
+    pub index: usize,
+    pub status: String,
+    pub fee: Option<i64>,
+    pub reason: Option<String>,
+}
+
+impl TransferResultDto {
+    fn of(index: usize, result: Result<TransferReceipt, SendMoneyError>) -> Self {
+        match result {
+            Ok(receipt) => Self {
+                index,
+                status: "SUCCEEDED".to_string(),
+                fee: Some(receipt.fee.amount.to_string().parse::<i64>().unwrap()),
+                reason: None,
+            },
+            Err(error) => Self {
+                index,
+                status: "REJECTED".to_string(),
+                fee: None,
+                reason: Some(error.to_string()),
+            },
+        }
+    }
+}
+
+fn map_to_command(dto: SendMoneyBatchDto, principal: Principal) -> Option<SendMoneyBatchCommand> {
+    let mode = match dto.mode.as_str() {
+        "ALL_OR_NOTHING" => BatchMode::AllOrNothing,
+        "BEST_EFFORT" => BatchMode::BestEffort,
+        _ => return None,
+    };
+    let commands = dto
+        .transfers
+        .into_iter()
+        .map(|t| {
+            SendMoneyCommand::new(
+                AccountId(t.source_account_id),
+                AccountId(t.target_account_id),
+                Money::of(t.amount as i128),
+                principal.clone(),
+            )
+        })
+        .collect();
+    Some(SendMoneyBatchCommand::new(commands, mode))
+}
+
+#[handler]
+async fn send_money_batch(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(command) = req
+        .parse_json::<SendMoneyBatchDto>()
+        .await
+        .ok()
+        .and_then(|dto| map_to_command(dto, authentication::principal(depot)))
+    else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+
+    let results = SEND_MONEY_BATCH_USE_CASE
+        .get()
+        .unwrap()
+        .send_money_batch(command)
+        .await;
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| TransferResultDto::of(index, result))
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use domain::vo::role::Role;
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        SendMoneyBatchUseCaseImpl {}
+        #[async_trait]
+        impl SendMoneyBatchUseCase for SendMoneyBatchUseCaseImpl {
+            async fn send_money_batch(
+                &self,
+                command: SendMoneyBatchCommand,
+            ) -> Vec<Result<TransferReceipt, SendMoneyError>>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_money_batch() {
+        // Given
+        let mut smbuc = Box::new(MockSendMoneyBatchUseCaseImpl::new());
+        smbuc
+            .expect_send_money_batch()
+            .times(1)
+            .with(eq(SendMoneyBatchCommand::new(
+                vec![
+                    SendMoneyCommand::new(
+                        AccountId(41),
+                        AccountId(42),
+                        Money::of(500),
+                        Principal::new("alice".to_string(), Role::Customer),
+                    ),
+                    SendMoneyCommand::new(
+                        AccountId(41),
+                        AccountId(43),
+                        Money::of(300),
+                        Principal::new("alice".to_string(), Role::Customer),
+                    ),
+                ],
+                BatchMode::BestEffort,
+            )))
+            .returning(|_| {
+                vec![
+                    Ok(TransferReceipt::new(
+                        AccountId(41),
+                        AccountId(42),
+                        Money::of(500),
+                        Money::of(5),
+                    )),
+                    Err(SendMoneyError::InsufficientFunds),
+                ]
+            });
+        super::set_dependencies(smbuc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When
+        let mut res = TestClient::post("http://127.0.0.1:8080/transfers/batch")
+            .json(&SendMoneyBatchDto {
+                mode: "BEST_EFFORT".to_string(),
+                transfers: vec![
+                    TransferDto {
+                        source_account_id: 41,
+                        target_account_id: 42,
+                        amount: 500,
+                    },
+                    TransferDto {
+                        source_account_id: 41,
+                        target_account_id: 43,
+                        amount: 300,
+                    },
+                ],
+            })
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            vec![
+                TransferResultDto {
+                    index: 0,
+                    status: "SUCCEEDED".to_string(),
+                    fee: Some(5),
+                    reason: None,
+                },
+                TransferResultDto {
+                    index: 1,
+                    status: "REJECTED".to_string(),
+                    fee: None,
+                    reason: Some("insufficient funds".to_string()),
+                },
+            ],
+            res.take_json::<Vec<TransferResultDto>>().await.unwrap()
+        );
+
+        // When the mode is unknown
+        let status_code = TestClient::post("http://127.0.0.1:8080/transfers/batch")
+            .json(&SendMoneyBatchDto {
+                mode: "SOMETIMES".to_string(),
+                transfers: vec![],
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    }
+}
@@ -1,12 +1,16 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
-use application::inbound_ports::{SendMoneyCommand, SendMoneyUseCase};
+use crate::authentication;
+use application::inbound_ports::{
+    SendMoneyCommand, SendMoneyError, SendMoneyUseCase, TransferReceipt,
+};
 use domain::{ar::account::AccountId, vo::money::Money};
 use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
 
-static SEND_MONEY_USE_CASE: OnceLock<Box<dyn SendMoneyUseCase>> = OnceLock::new();
+static SEND_MONEY_USE_CASE: OnceLock<Arc<dyn SendMoneyUseCase>> = OnceLock::new();
 
-pub fn set_dependencies(smuc: Box<dyn SendMoneyUseCase>) {
+pub fn set_dependencies(smuc: Arc<dyn SendMoneyUseCase>) {
     SEND_MONEY_USE_CASE.set(smuc).unwrap();
 }
 
@@ -18,58 +22,116 @@ pub fn get_routes() -> Router {
     )
 }
 
+/// The source account has been charged the `total` of the `amount` and the `fee`.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TransferReceiptDto {
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub fee: i64,
+    pub total: i64,
+}
+
+impl From<TransferReceipt> for TransferReceiptDto {
+    fn from(receipt: TransferReceipt) -> Self {
+        Self {
+            source_account_id: receipt.source_account_id.0,
+            target_account_id: receipt.target_account_id.0,
+            amount: receipt.money.amount.to_string().parse::<i64>().unwrap(),
+            fee: receipt.fee.amount.to_string().parse::<i64>().unwrap(),
+            total: receipt.total().amount.to_string().parse::<i64>().unwrap(),
+        }
+    }
+}
+
 #[handler]
-async fn send_money(req: &mut Request, res: &mut Response) {
+async fn send_money(req: &mut Request, depot: &mut Depot, res: &mut Response) {
     let command = SendMoneyCommand::new(
         AccountId(req.param::<i64>("sourceAccountId").unwrap()),
         AccountId(req.param::<i64>("targetAccountId").unwrap()),
         Money::of(req.param::<i64>("amount").unwrap() as i128),
+        authentication::principal(depot),
     );
 
-    SEND_MONEY_USE_CASE.get().unwrap().send_money(command).await;
-
-    res.status_code(StatusCode::OK);
+    match SEND_MONEY_USE_CASE.get().unwrap().send_money(command).await {
+        Ok(receipt) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(TransferReceiptDto::from(receipt)));
+        }
+        Err(error @ SendMoneyError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render(error.to_string());
+        }
+        Err(error) => {
+            res.status_code(StatusCode::UNPROCESSABLE_ENTITY);
+            res.render(error.to_string());
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::inbound_ports::Principal;
+    use domain::vo::role::Role;
     use mockall::{mock, predicate::eq};
-    use salvo::test::TestClient;
+    use salvo::test::{ResponseExt, TestClient};
 
     mock! {
         #[derive(Debug)]
         SendMoneyUseCaseImpl {}
         #[async_trait]
         impl SendMoneyUseCase for SendMoneyUseCaseImpl {
-            async fn send_money(&self, command: SendMoneyCommand) -> bool;
+            async fn send_money(
+                &self,
+                command: SendMoneyCommand,
+            ) -> Result<TransferReceipt, SendMoneyError>;
         }
     }
 
     #[tokio::test]
     async fn test_send_money() {
         // Given
-        let mut smuc = Box::new(MockSendMoneyUseCaseImpl::new());
+        let mut smuc = MockSendMoneyUseCaseImpl::new();
         smuc.expect_send_money()
             .times(12)
             .with(eq(SendMoneyCommand::new(
                 AccountId(41),
                 AccountId(42),
                 Money::of(500),
+                Principal::new("alice".to_string(), Role::Customer),
             )))
-            .return_const(true);
-        super::set_dependencies(smuc);
+            .return_const(Ok(TransferReceipt::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                Money::of(5),
+            )));
+        super::set_dependencies(Arc::new(smuc));
 
-        let service = Service::new(super::get_routes());
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
 
         // When
-        let status_code = TestClient::post("http://127.0.0.1:8080/accounts/send/41/42/500")
+        let mut res = TestClient::post("http://127.0.0.1:8080/accounts/send/41/42/500")
             .send(&service)
-            .await
-            .status_code
-            .unwrap();
+            .await;
 
-        // Then
-        assert_eq!(StatusCode::OK, status_code);
+        // Then the receipt shows the fee
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            TransferReceiptDto {
+                source_account_id: 41,
+                target_account_id: 42,
+                amount: 500,
+                fee: 5,
+                total: 505,
+            },
+            res.take_json::<TransferReceiptDto>().await.unwrap()
+        );
     }
 }
@@ -0,0 +1,428 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    CreateStandingOrderCommand, Principal, StandingOrderError, StandingOrderUseCase,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use domain::{
+    ar::{
+        account::AccountId,
+        standing_order::{
+            StandingOrder, StandingOrderExecution, StandingOrderExecutionStatus, StandingOrderId,
+            StandingOrderStatus,
+        },
+    },
+    vo::{money::Money, recurrence::Recurrence},
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static STANDING_ORDER_USE_CASE: OnceLock<Box<dyn StandingOrderUseCase>> = OnceLock::new();
+
+pub fn set_dependencies(souc: Box<dyn StandingOrderUseCase>) {
+    STANDING_ORDER_USE_CASE.set(souc).unwrap();
+}
+
+// POST /accounts/standing-orders
+// GET /accounts/<accountId>/standing-orders
+// GET /accounts/standing-orders/<standingOrderId>/executions
+// POST /accounts/standing-orders/<standingOrderId>/pause
+// POST /accounts/standing-orders/<standingOrderId>/resume
+// DELETE /accounts/standing-orders/<standingOrderId>
+pub fn get_routes() -> Router {
+    Router::with_path("accounts")
+        .push(Router::with_path("standing-orders").post(create_standing_order))
+        .push(Router::with_path("<accountId:num>/standing-orders").get(list_standing_orders))
+        .push(
+            Router::with_path("standing-orders/<standingOrderId:num>")
+                .delete(cancel_standing_order)
+                .push(Router::with_path("executions").get(list_standing_order_executions))
+                .push(Router::with_path("pause").post(pause_standing_order))
+                .push(Router::with_path("resume").post(resume_standing_order)),
+        )
+}
+
+/// `recurrence` is `DAILY`, `WEEKLY`, `MONTHLY` or a cron expression like `0 8 1 * *`,
+/// dates are `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct CreateStandingOrderDto {
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub recurrence: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub max_occurrences: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StandingOrderIdDto {
+    pub id: i64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StandingOrderDto {
+    pub id: Option<i64>,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub recurrence: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub max_occurrences: Option<u32>,
+    pub executed_occurrences: u32,
+    pub next_execution_date: Option<String>,
+    pub status: String,
+}
+
+impl From<StandingOrder> for StandingOrderDto {
+    fn from(so: StandingOrder) -> Self {
+        Self {
+            id: so.id.map(|id| id.0),
+            source_account_id: so.source_account_id.0,
+            target_account_id: so.target_account_id.0,
+            amount: so.money.amount.to_string().parse::<i64>().unwrap(),
+            recurrence: so.recurrence.to_string(),
+            start_date: so.start_date.to_string(),
+            end_date: so.end_date.map(|t| t.to_string()),
+            max_occurrences: so.max_occurrences,
+            executed_occurrences: so.executed_occurrences,
+            next_execution_date: so.next_execution_date.map(|t| t.to_string()),
+            status: match so.status {
+                StandingOrderStatus::Active => "ACTIVE",
+                StandingOrderStatus::Paused => "PAUSED",
+                StandingOrderStatus::Cancelled => "CANCELLED",
+                StandingOrderStatus::Completed => "COMPLETED",
+            }
+            .to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StandingOrderExecutionDto {
+    pub id: Option<i64>,
+    pub scheduled_for: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub status: String,
+    pub failure_reason: Option<String>,
+}
+
+impl From<StandingOrderExecution> for StandingOrderExecutionDto {
+    fn from(e: StandingOrderExecution) -> Self {
+        Self {
+            id: e.id.map(|id| id.0),
+            scheduled_for: e.scheduled_for.to_string(),
+            started_at: e.started_at.to_string(),
+            finished_at: e.finished_at.map(|t| t.to_string()),
+            status: match e.status {
+                StandingOrderExecutionStatus::Started => "STARTED",
+                StandingOrderExecutionStatus::Succeeded => "SUCCEEDED",
+                StandingOrderExecutionStatus::Failed => "FAILED",
+            }
+            .to_string(),
+            failure_reason: e.failure_reason,
+        }
+    }
+}
+
+fn parse_date_time(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn map_to_command(
+    dto: CreateStandingOrderDto,
+    principal: Principal,
+) -> Option<CreateStandingOrderCommand> {
+    let end_date = match dto.end_date {
+        Some(end_date) => Some(parse_date_time(&end_date)?),
+        None => None,
+    };
+    Some(CreateStandingOrderCommand {
+        source_account_id: AccountId(dto.source_account_id),
+        target_account_id: AccountId(dto.target_account_id),
+        money: Money::of(dto.amount as i128),
+        recurrence: dto.recurrence.parse::<Recurrence>().ok()?,
+        start_date: parse_date_time(&dto.start_date)?,
+        end_date,
+        max_occurrences: dto.max_occurrences,
+        principal,
+    })
+}
+
+#[handler]
+async fn create_standing_order(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(command) = req
+        .parse_json::<CreateStandingOrderDto>()
+        .await
+        .ok()
+        .and_then(|dto| map_to_command(dto, authentication::principal(depot)))
+    else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+
+    match STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .create_standing_order(command)
+        .await
+    {
+        Ok(id) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(StandingOrderIdDto { id: id.0 }));
+        }
+        Err(StandingOrderError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+        }
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+    }
+}
+
+#[handler]
+async fn list_standing_orders(req: &mut Request, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+
+    let standing_orders = STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .list_standing_orders(account_id)
+        .await;
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        standing_orders
+            .into_iter()
+            .map(StandingOrderDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[handler]
+async fn list_standing_order_executions(req: &mut Request, res: &mut Response) {
+    let id = StandingOrderId(req.param::<i64>("standingOrderId").unwrap());
+
+    let executions = STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .list_standing_order_executions(id)
+        .await;
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        executions
+            .into_iter()
+            .map(StandingOrderExecutionDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[handler]
+async fn pause_standing_order(req: &mut Request, res: &mut Response) {
+    let id = StandingOrderId(req.param::<i64>("standingOrderId").unwrap());
+    let changed = STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .pause_standing_order(id)
+        .await;
+    res.status_code(status_code_of_change(changed));
+}
+
+#[handler]
+async fn resume_standing_order(req: &mut Request, res: &mut Response) {
+    let id = StandingOrderId(req.param::<i64>("standingOrderId").unwrap());
+    let changed = STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .resume_standing_order(id)
+        .await;
+    res.status_code(status_code_of_change(changed));
+}
+
+#[handler]
+async fn cancel_standing_order(req: &mut Request, res: &mut Response) {
+    let id = StandingOrderId(req.param::<i64>("standingOrderId").unwrap());
+    let changed = STANDING_ORDER_USE_CASE
+        .get()
+        .unwrap()
+        .cancel_standing_order(id)
+        .await;
+    res.status_code(status_code_of_change(changed));
+}
+
+fn status_code_of_change(changed: bool) -> StatusCode {
+    if changed {
+        StatusCode::OK
+    } else {
+        // unknown, or not in a state that allows the change
+        StatusCode::CONFLICT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use domain::vo::role::Role;
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        StandingOrderUseCaseImpl {}
+        #[async_trait]
+        impl StandingOrderUseCase for StandingOrderUseCaseImpl {
+            async fn create_standing_order(
+                &self,
+                command: CreateStandingOrderCommand,
+            ) -> Result<StandingOrderId, StandingOrderError>;
+            async fn list_standing_orders(&self, account_id: AccountId) -> Vec<StandingOrder>;
+            async fn list_standing_order_executions(
+                &self,
+                id: StandingOrderId,
+            ) -> Vec<StandingOrderExecution>;
+            async fn pause_standing_order(&self, id: StandingOrderId) -> bool;
+            async fn resume_standing_order(&self, id: StandingOrderId) -> bool;
+            async fn cancel_standing_order(&self, id: StandingOrderId) -> bool;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_standing_orders() {
+        // Given
+        let start_date = NaiveDate::from_ymd_opt(2030, 1, 1)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let mut souc = Box::new(MockStandingOrderUseCaseImpl::new());
+        souc.expect_create_standing_order()
+            .times(1)
+            .with(eq(CreateStandingOrderCommand {
+                source_account_id: AccountId(41),
+                target_account_id: AccountId(42),
+                money: Money::of(500),
+                recurrence: "0 8 1 * *".parse().unwrap(),
+                start_date,
+                end_date: None,
+                max_occurrences: Some(12),
+                principal: Principal::new("alice".to_string(), Role::Customer),
+            }))
+            .returning(|_| Ok(StandingOrderId(3)));
+        souc.expect_list_standing_orders()
+            .times(1)
+            .with(eq(AccountId(41)))
+            .returning(move |_| {
+                let mut so = StandingOrder::new(
+                    AccountId(41),
+                    AccountId(42),
+                    Money::of(500),
+                    Recurrence::Monthly,
+                    start_date,
+                    None,
+                    Some(12),
+                    "alice".to_string(),
+                    Role::Customer,
+                );
+                so.id = Some(StandingOrderId(3));
+                vec![so]
+            });
+        souc.expect_pause_standing_order()
+            .times(1)
+            .with(eq(StandingOrderId(3)))
+            .return_const(true);
+        souc.expect_cancel_standing_order()
+            .times(1)
+            .with(eq(StandingOrderId(3)))
+            .return_const(false);
+        super::set_dependencies(souc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When a standing order is created
+        let mut res = TestClient::post("http://127.0.0.1:8080/accounts/standing-orders")
+            .json(&CreateStandingOrderDto {
+                source_account_id: 41,
+                target_account_id: 42,
+                amount: 500,
+                recurrence: "0 8 1 * *".to_string(),
+                start_date: "2030-01-01T08:00:00".to_string(),
+                end_date: None,
+                max_occurrences: Some(12),
+            })
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            StandingOrderIdDto { id: 3 },
+            res.take_json::<StandingOrderIdDto>().await.unwrap()
+        );
+
+        // When the standing orders are listed
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/41/standing-orders")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        let standing_orders = res.take_json::<Vec<StandingOrderDto>>().await.unwrap();
+        assert_eq!(1, standing_orders.len());
+        assert_eq!("MONTHLY", standing_orders[0].recurrence);
+        assert_eq!("ACTIVE", standing_orders[0].status);
+
+        // When the standing order is paused
+        let status_code =
+            TestClient::post("http://127.0.0.1:8080/accounts/standing-orders/3/pause")
+                .send(&service)
+                .await
+                .status_code
+                .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::OK, status_code);
+
+        // When the standing order cannot be cancelled
+        let status_code = TestClient::delete("http://127.0.0.1:8080/accounts/standing-orders/3")
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::CONFLICT, status_code);
+
+        // When the recurrence is invalid
+        let status_code = TestClient::post("http://127.0.0.1:8080/accounts/standing-orders")
+            .json(&CreateStandingOrderDto {
+                source_account_id: 41,
+                target_account_id: 42,
+                amount: 500,
+                recurrence: "fortnightly".to_string(),
+                start_date: "2030-01-01".to_string(),
+                end_date: None,
+                max_occurrences: None,
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    }
+}
@@ -0,0 +1,331 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    AccountActivity, GenerateStatementError, GenerateStatementQuery, Statement,
+};
+use chrono::NaiveDate;
+use domain::{ar::account::AccountId, vo::money::Money};
+use salvo::{http::header, prelude::*};
+use serde::{Deserialize, Serialize};
+
+static GENERATE_STATEMENT_QUERY: OnceLock<Box<dyn GenerateStatementQuery>> = OnceLock::new();
+
+pub fn set_dependencies(gsq: Box<dyn GenerateStatementQuery>) {
+    GENERATE_STATEMENT_QUERY.set(gsq).unwrap();
+}
+
+// GET /accounts/<accountId>/statement?from=<yyyy-mm-dd>&to=<yyyy-mm-dd>&format=<json|csv>
+pub fn get_routes() -> Router {
+    Router::with_path("accounts/<accountId:num>/statement").get(generate_statement)
+}
+
+/// The `amount` is signed from the point of view of the account, withdrawals are negative.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StatementLineDto {
+    pub activity_id: i64,
+    pub timestamp: String,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub balance: i64,
+}
+
+impl StatementLineDto {
+    fn of(account_id: &AccountId, account_activity: &AccountActivity) -> Self {
+        let activity = &account_activity.activity;
+        let mut amount = Money::of(0);
+        if &activity.target_account_id == account_id {
+            amount = amount.plus(&activity.money);
+        }
+        if &activity.source_account_id == account_id {
+            amount = amount.minus(&activity.money);
+        }
+        Self {
+            activity_id: activity.id.as_ref().unwrap().0,
+            timestamp: activity.timestamp.to_string(),
+            source_account_id: activity.source_account_id.0,
+            target_account_id: activity.target_account_id.0,
+            amount: to_amount(&amount),
+            balance: to_amount(&account_activity.balance),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct StatementDto {
+    pub account_id: i64,
+    pub from: String,
+    pub to: String,
+    pub opening_balance: i64,
+    pub activities: Vec<StatementLineDto>,
+    pub closing_balance: i64,
+}
+
+impl From<Statement> for StatementDto {
+    fn from(statement: Statement) -> Self {
+        Self {
+            account_id: statement.account_id.0,
+            from: statement.from.to_string(),
+            to: statement.to.to_string(),
+            opening_balance: to_amount(&statement.opening_balance),
+            activities: statement
+                .activities
+                .iter()
+                .map(|account_activity| {
+                    StatementLineDto::of(&statement.account_id, account_activity)
+                })
+                .collect(),
+            closing_balance: to_amount(&statement.closing_balance),
+        }
+    }
+}
+
+impl StatementDto {
+    /**
+     * Renders the statement as CSV, framed by a row with the opening and one with the closing
+     * balance.
+     */
+    fn to_csv(&self) -> String {
+        let mut csv =
+            "entry,activity_id,timestamp,source_account_id,target_account_id,amount,balance\n"
+                .to_string();
+        csv.push_str(&format!(
+            "OPENING_BALANCE,,{},,,,{}\n",
+            self.from, self.opening_balance
+        ));
+        for line in &self.activities {
+            csv.push_str(&format!(
+                "ACTIVITY,{},{},{},{},{},{}\n",
+                line.activity_id,
+                line.timestamp,
+                line.source_account_id,
+                line.target_account_id,
+                line.amount,
+                line.balance
+            ));
+        }
+        csv.push_str(&format!(
+            "CLOSING_BALANCE,,{},,,,{}\n",
+            self.to, self.closing_balance
+        ));
+        csv
+    }
+}
+
+fn to_amount(money: &Money) -> i64 {
+    money.amount.to_string().parse::<i64>().unwrap()
+}
+
+#[handler]
+async fn generate_statement(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+    let (Some(from), Some(to)) = (parse_date(req, "from"), parse_date(req, "to")) else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+    let format = req.query::<String>("format").unwrap_or("json".to_string());
+    if format != "json" && format != "csv" {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    }
+
+    match GENERATE_STATEMENT_QUERY
+        .get()
+        .unwrap()
+        .generate_statement(account_id, from, to, authentication::principal(depot))
+        .await
+    {
+        Ok(statement) => {
+            let dto = StatementDto::from(statement);
+            res.status_code(StatusCode::OK);
+            if format == "csv" {
+                res.add_header(
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"statement-{}-{}-{}.csv\"",
+                        dto.account_id, dto.from, dto.to
+                    ),
+                    true,
+                )
+                .unwrap();
+                res.render(Text::Csv(dto.to_csv()));
+            } else {
+                res.render(Json(dto));
+            }
+        }
+        Err(GenerateStatementError::InvalidPeriod) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+        Err(GenerateStatementError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to view the account");
+        }
+    }
+}
+
+fn parse_date(req: &Request, name: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&req.query::<String>(name)?, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::inbound_ports::Principal;
+    use domain::{
+        ar::activity::{Activity, ActivityId},
+        vo::role::Role,
+    };
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        GenerateStatementQueryImpl {}
+        #[async_trait]
+        impl GenerateStatementQuery for GenerateStatementQueryImpl {
+            async fn generate_statement(
+                &self,
+                account_id: AccountId,
+                from: NaiveDate,
+                to: NaiveDate,
+                principal: Principal,
+            ) -> Result<Statement, GenerateStatementError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_statement() {
+        // Given
+        let mut gsq = Box::new(MockGenerateStatementQueryImpl::new());
+        gsq.expect_generate_statement()
+            .times(2)
+            .with(eq(AccountId(42)), eq(date(1)), eq(date(31)), eq(alice()))
+            .returning(|account_id, from, to, _| {
+                Ok(Statement {
+                    account_id,
+                    from,
+                    to,
+                    opening_balance: Money::of(1000),
+                    activities: vec![AccountActivity::new(
+                        Activity::with_id(
+                            Some(ActivityId(7)),
+                            AccountId(42),
+                            AccountId(42),
+                            AccountId(43),
+                            date(9).and_hms_opt(9, 0, 0).unwrap(),
+                            Money::of(300),
+                            None,
+                        ),
+                        Money::of(700),
+                    )],
+                    closing_balance: Money::of(700),
+                })
+            });
+        gsq.expect_generate_statement()
+            .times(1)
+            .with(eq(AccountId(42)), eq(date(31)), eq(date(1)), eq(alice()))
+            .returning(|_, _, _, _| Err(GenerateStatementError::InvalidPeriod));
+        gsq.expect_generate_statement()
+            .times(1)
+            .with(eq(AccountId(43)), eq(date(1)), eq(date(31)), eq(alice()))
+            .returning(|_, _, _, _| Err(GenerateStatementError::Unauthorized));
+        super::set_dependencies(gsq);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When
+        let mut res = TestClient::get(
+            "http://127.0.0.1:8080/accounts/42/statement?from=2019-08-01&to=2019-08-31",
+        )
+        .send(&service)
+        .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            StatementDto {
+                account_id: 42,
+                from: "2019-08-01".to_string(),
+                to: "2019-08-31".to_string(),
+                opening_balance: 1000,
+                activities: vec![StatementLineDto {
+                    activity_id: 7,
+                    timestamp: "2019-08-09 09:00:00".to_string(),
+                    source_account_id: 42,
+                    target_account_id: 43,
+                    amount: -300,
+                    balance: 700,
+                }],
+                closing_balance: 700,
+            },
+            res.take_json::<StatementDto>().await.unwrap()
+        );
+
+        // When
+        let mut res = TestClient::get(
+            "http://127.0.0.1:8080/accounts/42/statement?from=2019-08-01&to=2019-08-31&format=csv",
+        )
+        .send(&service)
+        .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            "entry,activity_id,timestamp,source_account_id,target_account_id,amount,balance\n\
+             OPENING_BALANCE,,2019-08-01,,,,1000\n\
+             ACTIVITY,7,2019-08-09 09:00:00,42,43,-300,700\n\
+             CLOSING_BALANCE,,2019-08-31,,,,700\n",
+            res.take_string().await.unwrap()
+        );
+
+        // When the period ends before it starts
+        let status_code = TestClient::get(
+            "http://127.0.0.1:8080/accounts/42/statement?from=2019-08-31&to=2019-08-01",
+        )
+        .send(&service)
+        .await
+        .status_code
+        .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+
+        // When the principal may not view the account
+        let status_code = TestClient::get(
+            "http://127.0.0.1:8080/accounts/43/statement?from=2019-08-01&to=2019-08-31",
+        )
+        .send(&service)
+        .await
+        .status_code
+        .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::FORBIDDEN, status_code);
+
+        // When the format is unknown
+        let status_code = TestClient::get(
+            "http://127.0.0.1:8080/accounts/42/statement?from=2019-08-01&to=2019-08-31&format=pdf",
+        )
+        .send(&service)
+        .await
+        .status_code
+        .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2019, 8, day).unwrap()
+    }
+}
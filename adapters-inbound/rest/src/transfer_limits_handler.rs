@@ -0,0 +1,228 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    ChangeTransferLimitsCommand, ChangeTransferLimitsError, TransferLimitsUseCase,
+};
+use domain::{
+    ar::account::AccountId,
+    vo::{money::Money, transfer_limits::TransferLimits},
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static TRANSFER_LIMITS_USE_CASE: OnceLock<Box<dyn TransferLimitsUseCase>> = OnceLock::new();
+
+pub fn set_dependencies(tluc: Box<dyn TransferLimitsUseCase>) {
+    TRANSFER_LIMITS_USE_CASE.set(tluc).unwrap();
+}
+
+// GET /accounts/<accountId>/transfer-limits
+// PUT /accounts/<accountId>/transfer-limits
+pub fn get_routes() -> Router {
+    Router::with_path("accounts/<accountId:num>/transfer-limits")
+        .get(get_transfer_limits)
+        .put(change_transfer_limits)
+}
+
+/// Limits which are not set are not restricted (GET) or fall back to the defaults (PUT).
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct TransferLimitsDto {
+    pub daily_amount: Option<i64>,
+    pub monthly_amount: Option<i64>,
+    pub daily_count: Option<u32>,
+}
+
+impl From<TransferLimits> for TransferLimitsDto {
+    fn from(limits: TransferLimits) -> Self {
+        Self {
+            daily_amount: limits
+                .daily_amount
+                .map(|limit| limit.amount.to_string().parse::<i64>().unwrap()),
+            monthly_amount: limits
+                .monthly_amount
+                .map(|limit| limit.amount.to_string().parse::<i64>().unwrap()),
+            daily_count: limits.daily_count,
+        }
+    }
+}
+
+impl From<TransferLimitsDto> for TransferLimits {
+    fn from(dto: TransferLimitsDto) -> Self {
+        TransferLimits::new(
+            dto.daily_amount.map(|limit| Money::of(limit as i128)),
+            dto.monthly_amount.map(|limit| Money::of(limit as i128)),
+            dto.daily_count,
+        )
+    }
+}
+
+#[handler]
+async fn get_transfer_limits(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+
+    let Ok(transfer_limits) = TRANSFER_LIMITS_USE_CASE
+        .get()
+        .unwrap()
+        .get_transfer_limits(account_id, authentication::principal(depot))
+        .await
+    else {
+        res.status_code(StatusCode::FORBIDDEN);
+        res.render("not authorized to view the account");
+        return;
+    };
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(TransferLimitsDto::from(transfer_limits)));
+}
+
+#[handler]
+async fn change_transfer_limits(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+    let Ok(dto) = req.parse_json::<TransferLimitsDto>().await else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+    let command =
+        ChangeTransferLimitsCommand::new(account_id, dto.into(), authentication::principal(depot));
+
+    match TRANSFER_LIMITS_USE_CASE
+        .get()
+        .unwrap()
+        .change_transfer_limits(command)
+        .await
+    {
+        Ok(()) => {
+            res.status_code(StatusCode::OK);
+        }
+        Err(ChangeTransferLimitsError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to change the limits of the account");
+        }
+        Err(ChangeTransferLimitsError::NegativeLimit) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::{authorization_policy::Denied, inbound_ports::Principal};
+    use domain::vo::role::Role;
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        TransferLimitsUseCaseImpl {}
+        #[async_trait]
+        impl TransferLimitsUseCase for TransferLimitsUseCaseImpl {
+            async fn change_transfer_limits(
+                &self,
+                command: ChangeTransferLimitsCommand,
+            ) -> Result<(), ChangeTransferLimitsError>;
+            async fn get_transfer_limits(
+                &self,
+                account_id: AccountId,
+                principal: Principal,
+            ) -> Result<TransferLimits, Denied>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_limits() {
+        // Given
+        let mut tluc = Box::new(MockTransferLimitsUseCaseImpl::new());
+        tluc.expect_change_transfer_limits()
+            .times(1)
+            .with(eq(ChangeTransferLimitsCommand::new(
+                AccountId(41),
+                TransferLimits::new(Some(Money::of(2_000)), None, Some(5)),
+                alice(),
+            )))
+            .returning(|_| Ok(()));
+        tluc.expect_change_transfer_limits()
+            .times(1)
+            .with(eq(ChangeTransferLimitsCommand::new(
+                AccountId(43),
+                TransferLimits::unlimited(),
+                alice(),
+            )))
+            .returning(|_| Err(ChangeTransferLimitsError::Unauthorized));
+        tluc.expect_get_transfer_limits()
+            .times(1)
+            .with(eq(AccountId(41)), eq(alice()))
+            .return_const(Ok(TransferLimits::new(
+                Some(Money::of(2_000)),
+                Some(Money::of(50_000)),
+                Some(5),
+            )));
+        tluc.expect_get_transfer_limits()
+            .times(1)
+            .with(eq(AccountId(43)), eq(alice()))
+            .return_const(Err(Denied));
+        super::set_dependencies(tluc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When the limits of the account are overridden
+        let status_code = TestClient::put("http://127.0.0.1:8080/accounts/41/transfer-limits")
+            .json(&TransferLimitsDto {
+                daily_amount: Some(2_000),
+                monthly_amount: None,
+                daily_count: Some(5),
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::OK, status_code);
+
+        // When the limits in effect are requested
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/41/transfer-limits")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            TransferLimitsDto {
+                daily_amount: Some(2_000),
+                monthly_amount: Some(50_000),
+                daily_count: Some(5),
+            },
+            res.take_json::<TransferLimitsDto>().await.unwrap()
+        );
+
+        // When the principal may not change or view the limits of the account
+        let status_code = TestClient::put("http://127.0.0.1:8080/accounts/43/transfer-limits")
+            .json(&TransferLimitsDto {
+                daily_amount: None,
+                monthly_amount: None,
+                daily_count: None,
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+        let res = TestClient::get("http://127.0.0.1:8080/accounts/43/transfer-limits")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::FORBIDDEN, status_code);
+        assert_eq!(StatusCode::FORBIDDEN, res.status_code.unwrap());
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+}
@@ -0,0 +1,403 @@
+use std::sync::OnceLock;
+
+use crate::authentication;
+use application::inbound_ports::{
+    SubscribeWebhookCommand, SubscribeWebhookError, WebhookSubscriptionUseCase,
+};
+use domain::ar::{
+    account::AccountId,
+    domain_event::DomainEvent,
+    webhook_subscription::{
+        WebhookDelivery, WebhookDeliveryStatus, WebhookSubscription, WebhookSubscriptionId,
+    },
+};
+use salvo::prelude::*;
+use serde::{Deserialize, Serialize};
+
+static WEBHOOK_SUBSCRIPTION_USE_CASE: OnceLock<Box<dyn WebhookSubscriptionUseCase>> =
+    OnceLock::new();
+
+pub fn set_dependencies(wsuc: Box<dyn WebhookSubscriptionUseCase>) {
+    WEBHOOK_SUBSCRIPTION_USE_CASE.set(wsuc).unwrap();
+}
+
+// POST /accounts/<accountId>/webhooks
+// GET /accounts/<accountId>/webhooks
+// DELETE /accounts/webhooks/<subscriptionId>
+// GET /accounts/webhooks/<subscriptionId>/deliveries
+pub fn get_routes() -> Router {
+    Router::with_path("accounts")
+        .push(
+            Router::with_path("<accountId:num>/webhooks")
+                .post(subscribe_webhook)
+                .get(list_webhook_subscriptions),
+        )
+        .push(
+            Router::with_path("webhooks/<subscriptionId:num>")
+                .delete(unsubscribe_webhook)
+                .push(Router::with_path("deliveries").get(list_webhook_deliveries)),
+        )
+}
+
+/// The `secret` is the key of the HMAC-SHA256 signature of each payload.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct SubscribeWebhookDto {
+    pub url: String,
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WebhookSubscriptionIdDto {
+    pub id: i64,
+}
+
+/// The secret is never returned.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WebhookSubscriptionDto {
+    pub id: Option<i64>,
+    pub account_id: i64,
+    pub url: String,
+    pub created_at: String,
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionDto {
+    fn from(ws: WebhookSubscription) -> Self {
+        Self {
+            id: ws.id.map(|id| id.0),
+            account_id: ws.account_id.0,
+            url: ws.url,
+            created_at: ws.created_at.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WebhookDeliveryDto {
+    pub id: Option<i64>,
+    pub event_type: String,
+    pub counterpart_account_id: i64,
+    pub amount: i64,
+    pub timestamp: String,
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: Option<String>,
+    pub last_error: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDto {
+    fn from(wd: WebhookDelivery) -> Self {
+        let (event_type, counterpart_account_id, money) = match &wd.domain_event {
+            DomainEvent::MoneyWithdrawn {
+                target_account_id,
+                money,
+                ..
+            } => ("MONEY_WITHDRAWN", target_account_id, money),
+            DomainEvent::MoneyDeposited {
+                source_account_id,
+                money,
+                ..
+            } => ("MONEY_DEPOSITED", source_account_id, money),
+            DomainEvent::TransferCompleted {
+                target_account_id,
+                money,
+                ..
+            } => ("TRANSFER_COMPLETED", target_account_id, money),
+            DomainEvent::TransferRejected {
+                target_account_id,
+                money,
+                ..
+            } => ("TRANSFER_REJECTED", target_account_id, money),
+        };
+        Self {
+            id: wd.id.map(|id| id.0),
+            event_type: event_type.to_string(),
+            counterpart_account_id: counterpart_account_id.0,
+            amount: money.amount.to_string().parse::<i64>().unwrap(),
+            timestamp: wd.domain_event.timestamp().to_string(),
+            next_attempt_at: (wd.status == WebhookDeliveryStatus::Pending)
+                .then(|| wd.next_attempt_at.to_string()),
+            status: match wd.status {
+                WebhookDeliveryStatus::Pending => "PENDING",
+                WebhookDeliveryStatus::Delivered => "DELIVERED",
+                WebhookDeliveryStatus::DeadLettered => "DEAD_LETTERED",
+            }
+            .to_string(),
+            attempts: wd.attempts,
+            last_error: wd.last_error,
+            finished_at: wd.finished_at.map(|t| t.to_string()),
+        }
+    }
+}
+
+#[handler]
+async fn subscribe_webhook(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+    let Ok(dto) = req.parse_json::<SubscribeWebhookDto>().await else {
+        res.status_code(StatusCode::BAD_REQUEST);
+        return;
+    };
+
+    match WEBHOOK_SUBSCRIPTION_USE_CASE
+        .get()
+        .unwrap()
+        .subscribe_webhook(SubscribeWebhookCommand::new(
+            account_id,
+            dto.url,
+            dto.secret,
+            authentication::principal(depot),
+        ))
+        .await
+    {
+        Ok(id) => {
+            res.status_code(StatusCode::OK);
+            res.render(Json(WebhookSubscriptionIdDto { id: id.0 }));
+        }
+        Err(SubscribeWebhookError::InvalidUrl | SubscribeWebhookError::MissingSecret) => {
+            res.status_code(StatusCode::BAD_REQUEST);
+        }
+        Err(SubscribeWebhookError::Unauthorized) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to view the account");
+        }
+    }
+}
+
+#[handler]
+async fn list_webhook_subscriptions(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let account_id = AccountId(req.param::<i64>("accountId").unwrap());
+
+    let Ok(webhook_subscriptions) = WEBHOOK_SUBSCRIPTION_USE_CASE
+        .get()
+        .unwrap()
+        .list_webhook_subscriptions(account_id, authentication::principal(depot))
+        .await
+    else {
+        res.status_code(StatusCode::FORBIDDEN);
+        res.render("not authorized to view the account");
+        return;
+    };
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        webhook_subscriptions
+            .into_iter()
+            .map(WebhookSubscriptionDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[handler]
+async fn unsubscribe_webhook(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let id = WebhookSubscriptionId(req.param::<i64>("subscriptionId").unwrap());
+    match WEBHOOK_SUBSCRIPTION_USE_CASE
+        .get()
+        .unwrap()
+        .unsubscribe_webhook(id, authentication::principal(depot))
+        .await
+    {
+        Ok(true) => {
+            res.status_code(StatusCode::OK);
+        }
+        Ok(false) => {
+            res.status_code(StatusCode::NOT_FOUND);
+        }
+        Err(_) => {
+            res.status_code(StatusCode::FORBIDDEN);
+            res.render("not authorized to view the account");
+        }
+    }
+}
+
+#[handler]
+async fn list_webhook_deliveries(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let id = WebhookSubscriptionId(req.param::<i64>("subscriptionId").unwrap());
+
+    let Ok(webhook_deliveries) = WEBHOOK_SUBSCRIPTION_USE_CASE
+        .get()
+        .unwrap()
+        .list_webhook_deliveries(id, authentication::principal(depot))
+        .await
+    else {
+        res.status_code(StatusCode::FORBIDDEN);
+        res.render("not authorized to view the account");
+        return;
+    };
+
+    res.status_code(StatusCode::OK);
+    res.render(Json(
+        webhook_deliveries
+            .into_iter()
+            .map(WebhookDeliveryDto::from)
+            .collect::<Vec<_>>(),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::AuthenticatedAs;
+    use application::{authorization_policy::Denied, inbound_ports::Principal};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::{
+        ar::webhook_subscription::WebhookDeliveryId,
+        vo::{money::Money, role::Role},
+    };
+    use mockall::{mock, predicate::eq};
+    use salvo::test::{ResponseExt, TestClient};
+
+    mock! {
+        #[derive(Debug)]
+        WebhookSubscriptionUseCaseImpl {}
+        #[async_trait]
+        impl WebhookSubscriptionUseCase for WebhookSubscriptionUseCaseImpl {
+            async fn subscribe_webhook(
+                &self,
+                command: SubscribeWebhookCommand,
+            ) -> Result<WebhookSubscriptionId, SubscribeWebhookError>;
+            async fn list_webhook_subscriptions(
+                &self,
+                account_id: AccountId,
+                principal: Principal,
+            ) -> Result<Vec<WebhookSubscription>, Denied>;
+            async fn unsubscribe_webhook(
+                &self,
+                id: WebhookSubscriptionId,
+                principal: Principal,
+            ) -> Result<bool, Denied>;
+            async fn list_webhook_deliveries(
+                &self,
+                id: WebhookSubscriptionId,
+                principal: Principal,
+            ) -> Result<Vec<WebhookDelivery>, Denied>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhooks() {
+        // Given
+        let mut wsuc = Box::new(MockWebhookSubscriptionUseCaseImpl::new());
+        wsuc.expect_subscribe_webhook()
+            .times(1)
+            .with(eq(SubscribeWebhookCommand::new(
+                AccountId(42),
+                "https://partner.example/hook".to_string(),
+                "secret".to_string(),
+                alice(),
+            )))
+            .returning(|_| Ok(WebhookSubscriptionId(7)));
+        wsuc.expect_subscribe_webhook()
+            .times(1)
+            .returning(|_| Err(SubscribeWebhookError::InvalidUrl));
+        wsuc.expect_list_webhook_subscriptions()
+            .times(1)
+            .with(eq(AccountId(43)), eq(alice()))
+            .returning(|_, _| Err(Denied));
+        wsuc.expect_list_webhook_deliveries()
+            .times(1)
+            .with(eq(WebhookSubscriptionId(7)), eq(alice()))
+            .returning(|id, _| {
+                let mut webhook_delivery = WebhookDelivery::new(
+                    id,
+                    DomainEvent::MoneyDeposited {
+                        account_id: AccountId(42),
+                        source_account_id: AccountId(41),
+                        money: Money::of(500),
+                        timestamp: timestamp(),
+                    },
+                );
+                webhook_delivery.id = Some(WebhookDeliveryId(3));
+                webhook_delivery.dead_letter(timestamp(), "503 Service Unavailable".to_string());
+                Ok(vec![webhook_delivery])
+            });
+        wsuc.expect_unsubscribe_webhook()
+            .times(1)
+            .with(eq(WebhookSubscriptionId(8)), eq(alice()))
+            .return_const(Ok(false));
+        super::set_dependencies(wsuc);
+
+        let service = Service::new(
+            Router::new()
+                .hoop(AuthenticatedAs("alice"))
+                .push(super::get_routes()),
+        );
+
+        // When a webhook is subscribed
+        let mut res = TestClient::post("http://127.0.0.1:8080/accounts/42/webhooks")
+            .json(&SubscribeWebhookDto {
+                url: "https://partner.example/hook".to_string(),
+                secret: "secret".to_string(),
+            })
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        assert_eq!(
+            WebhookSubscriptionIdDto { id: 7 },
+            res.take_json::<WebhookSubscriptionIdDto>().await.unwrap()
+        );
+
+        // When the url is invalid
+        let status_code = TestClient::post("http://127.0.0.1:8080/accounts/42/webhooks")
+            .json(&SubscribeWebhookDto {
+                url: "partner.example".to_string(),
+                secret: "secret".to_string(),
+            })
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::BAD_REQUEST, status_code);
+
+        // When the subscriptions of an account the principal may not view are listed
+        let status_code = TestClient::get("http://127.0.0.1:8080/accounts/43/webhooks")
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::FORBIDDEN, status_code);
+
+        // When the delivery log is listed
+        let mut res = TestClient::get("http://127.0.0.1:8080/accounts/webhooks/7/deliveries")
+            .send(&service)
+            .await;
+
+        // Then
+        assert_eq!(StatusCode::OK, res.status_code.unwrap());
+        let webhook_deliveries = res.take_json::<Vec<WebhookDeliveryDto>>().await.unwrap();
+        assert_eq!(1, webhook_deliveries.len());
+        assert_eq!("MONEY_DEPOSITED", webhook_deliveries[0].event_type);
+        assert_eq!("DEAD_LETTERED", webhook_deliveries[0].status);
+        assert_eq!(None, webhook_deliveries[0].next_attempt_at);
+        assert_eq!(
+            Some("503 Service Unavailable".to_string()),
+            webhook_deliveries[0].last_error
+        );
+
+        // When an unknown subscription is removed
+        let status_code = TestClient::delete("http://127.0.0.1:8080/accounts/webhooks/8")
+            .send(&service)
+            .await
+            .status_code
+            .unwrap();
+
+        // Then
+        assert_eq!(StatusCode::NOT_FOUND, status_code);
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
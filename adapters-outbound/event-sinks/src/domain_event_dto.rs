@@ -0,0 +1,96 @@
+use domain::{ar::domain_event::DomainEvent, vo::money::Money};
+use serde::{Deserialize, Serialize};
+
+/**
+ * The JSON representation of a [DomainEvent] handed to consumers outside of the process,
+ * e.g. `{"type":"MoneyDeposited","account_id":42,"source_account_id":41,"amount":500,...}`.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(tag = "type")]
+pub enum DomainEventDto {
+    MoneyWithdrawn {
+        account_id: i64,
+        target_account_id: i64,
+        amount: i64,
+        timestamp: String,
+    },
+    MoneyDeposited {
+        account_id: i64,
+        source_account_id: i64,
+        amount: i64,
+        timestamp: String,
+    },
+    TransferCompleted {
+        source_account_id: i64,
+        target_account_id: i64,
+        amount: i64,
+        fee: i64,
+        timestamp: String,
+    },
+    TransferRejected {
+        source_account_id: i64,
+        target_account_id: i64,
+        amount: i64,
+        reason: String,
+        timestamp: String,
+    },
+}
+
+impl From<&DomainEvent> for DomainEventDto {
+    fn from(domain_event: &DomainEvent) -> Self {
+        match domain_event {
+            DomainEvent::MoneyWithdrawn {
+                account_id,
+                target_account_id,
+                money,
+                timestamp,
+            } => DomainEventDto::MoneyWithdrawn {
+                account_id: account_id.0,
+                target_account_id: target_account_id.0,
+                amount: to_amount(money),
+                timestamp: timestamp.to_string(),
+            },
+            DomainEvent::MoneyDeposited {
+                account_id,
+                source_account_id,
+                money,
+                timestamp,
+            } => DomainEventDto::MoneyDeposited {
+                account_id: account_id.0,
+                source_account_id: source_account_id.0,
+                amount: to_amount(money),
+                timestamp: timestamp.to_string(),
+            },
+            DomainEvent::TransferCompleted {
+                source_account_id,
+                target_account_id,
+                money,
+                fee,
+                timestamp,
+            } => DomainEventDto::TransferCompleted {
+                source_account_id: source_account_id.0,
+                target_account_id: target_account_id.0,
+                amount: to_amount(money),
+                fee: to_amount(fee),
+                timestamp: timestamp.to_string(),
+            },
+            DomainEvent::TransferRejected {
+                source_account_id,
+                target_account_id,
+                money,
+                reason,
+                timestamp,
+            } => DomainEventDto::TransferRejected {
+                source_account_id: source_account_id.0,
+                target_account_id: target_account_id.0,
+                amount: to_amount(money),
+                reason: reason.clone(),
+                timestamp: timestamp.to_string(),
+            },
+        }
+    }
+}
+
+fn to_amount(money: &Money) -> i64 {
+    money.amount.to_string().parse::<i64>().unwrap()
+}
@@ -0,0 +1,65 @@
+use application::outbound_ports::DomainEventSink;
+use async_trait::async_trait;
+use domain::ar::domain_event::DomainEvent;
+use std::sync::Arc;
+
+/**
+ * Hands every event to all sinks in turn. The delivery fails at the first sink which does not
+ * accept the event, so the sinks before it receive the event again when it is retried.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct FanOutDomainEventSink {
+    domain_event_sinks: Vec<Arc<dyn DomainEventSink>>,
+}
+
+impl FanOutDomainEventSink {
+    // Functions
+
+    pub fn new(domain_event_sinks: Vec<Arc<dyn DomainEventSink>>) -> Self {
+        Self { domain_event_sinks }
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for FanOutDomainEventSink {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        for domain_event_sink in &self.domain_event_sinks {
+            domain_event_sink.deliver(domain_event).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::in_memory_sink::InMemoryDomainEventSink;
+    use chrono::NaiveDate;
+    use domain::{ar::account::AccountId, vo::money::Money};
+
+    #[tokio::test]
+    async fn test_event_is_delivered_to_all_sinks() {
+        // Given
+        let first = Arc::new(InMemoryDomainEventSink::new());
+        let second = Arc::new(InMemoryDomainEventSink::new());
+        let sink_under_test = FanOutDomainEventSink::new(vec![first.clone(), second.clone()]);
+        let domain_event = DomainEvent::MoneyDeposited {
+            account_id: AccountId(42),
+            source_account_id: AccountId(41),
+            money: Money::of(500),
+            timestamp: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+        };
+
+        // When
+        let delivered = sink_under_test.deliver(&domain_event).await;
+
+        // Then
+        assert_eq!(Ok(()), delivered);
+        assert_eq!(vec![domain_event.clone()], first.domain_events());
+        assert_eq!(vec![domain_event], second.domain_events());
+    }
+}
@@ -0,0 +1,40 @@
+use application::outbound_ports::DomainEventSink;
+use async_trait::async_trait;
+use domain::ar::domain_event::DomainEvent;
+use std::sync::Mutex;
+
+/**
+ * Keeps the delivered events in memory, e.g. to inspect them in tests.
+ */
+#[derive(Default, Debug)]
+pub struct InMemoryDomainEventSink {
+    domain_events: Mutex<Vec<DomainEvent>>,
+}
+
+impl InMemoryDomainEventSink {
+    // Functions
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Methods
+
+    /**
+     * @return the delivered events in the order of their delivery.
+     */
+    pub fn domain_events(&self) -> Vec<DomainEvent> {
+        self.domain_events.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for InMemoryDomainEventSink {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        self.domain_events
+            .lock()
+            .unwrap()
+            .push(domain_event.clone());
+        Ok(())
+    }
+}
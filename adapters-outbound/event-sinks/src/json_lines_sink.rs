@@ -0,0 +1,115 @@
+use crate::domain_event_dto::DomainEventDto;
+use application::outbound_ports::DomainEventSink;
+use async_trait::async_trait;
+use domain::ar::domain_event::DomainEvent;
+use std::{fs::OpenOptions, io::Write, path::Path, sync::Mutex};
+use tracing::debug;
+
+/**
+ * Writes every event as one line of JSON, e.g. to stdout or to a file picked up by a log shipper.
+ */
+// #[singleton]
+pub struct JsonLinesDomainEventSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonLinesDomainEventSink {
+    // Functions
+
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(Box::new(std::io::stdout()))
+    }
+
+    /**
+     * Appends to the file, which is created if it does not exist.
+     */
+    pub fn append_to_file(path: &Path) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Ok(Self::new(Box::new(file)))
+    }
+}
+
+impl std::fmt::Debug for JsonLinesDomainEventSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesDomainEventSink")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for JsonLinesDomainEventSink {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        let line = serde_json::to_string(&DomainEventDto::from(domain_event))
+            .map_err(|error| error.to_string())?;
+        debug!(%line, "deliver");
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{}", line)
+            .and_then(|_| writer.flush())
+            .map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::{ar::account::AccountId, vo::money::Money};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_writes_one_json_line_per_event() {
+        // Given
+        let buffer = SharedBuffer::default();
+        let sink_under_test = JsonLinesDomainEventSink::new(Box::new(buffer.clone()));
+        let timestamp = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+
+        // When
+        for reason in ["insufficient funds", "maximum transfer threshold exceeded"] {
+            sink_under_test
+                .deliver(&DomainEvent::TransferRejected {
+                    source_account_id: AccountId(41),
+                    target_account_id: AccountId(42),
+                    money: Money::of(500),
+                    reason: reason.to_string(),
+                    timestamp,
+                })
+                .await
+                .unwrap();
+        }
+
+        // Then
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(2, lines.len());
+        assert_eq!(
+            r#"{"type":"TransferRejected","source_account_id":41,"target_account_id":42,"amount":500,"reason":"insufficient funds","timestamp":"2019-08-09 09:00:00"}"#,
+            lines[0]
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod domain_event_dto;
+pub mod fan_out_sink;
+pub mod in_memory_sink;
+pub mod json_lines_sink;
+pub mod signed_webhook_client;
+pub mod webhook_sink;
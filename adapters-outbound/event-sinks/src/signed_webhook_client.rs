@@ -0,0 +1,234 @@
+use crate::domain_event_dto::DomainEventDto;
+use application::outbound_ports::WebhookPort;
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::ar::webhook_subscription::{WebhookDelivery, WebhookSubscription};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::debug;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+// a partner which does not answer in time is retried like one which fails
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/**
+ * The JSON payload posted to a webhook subscription.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct WebhookPayloadDto {
+    pub delivery_id: Option<i64>,
+    pub subscription_id: i64,
+    pub event: DomainEventDto,
+}
+
+/**
+ * Posts webhook deliveries with the HMAC-SHA256 of the timestamp and the body, keyed with the
+ * secret of the subscription, in the `X-Webhook-Signature: sha256=<hex>` header. The timestamp is
+ * sent in the `X-Webhook-Timestamp` header in seconds since the epoch, so that a partner can
+ * reject a replayed delivery. Any response other than a success status is treated as a failed
+ * delivery.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct SignedWebhookClient {
+    client: reqwest::Client,
+}
+
+impl SignedWebhookClient {
+    // Functions
+
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .connect_timeout(CONNECT_TIMEOUT)
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap(),
+        }
+    }
+}
+
+impl Default for SignedWebhookClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+ * Signs `<timestamp>.<body>`.
+ * @return the value of the signature header of the body sent at the timestamp.
+ */
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[async_trait]
+impl WebhookPort for SignedWebhookClient {
+    async fn post_webhook(
+        &self,
+        webhook_subscription: &WebhookSubscription,
+        webhook_delivery: &WebhookDelivery,
+    ) -> Result<(), String> {
+        let payload = WebhookPayloadDto {
+            delivery_id: webhook_delivery.id.as_ref().map(|id| id.0),
+            subscription_id: webhook_delivery.subscription_id.0,
+            event: DomainEventDto::from(&webhook_delivery.domain_event),
+        };
+        let body = serde_json::to_string(&payload).map_err(|error| error.to_string())?;
+        let timestamp = Utc::now().timestamp();
+        let mut request = self
+            .client
+            .post(&webhook_subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(
+                SIGNATURE_HEADER,
+                sign(&webhook_subscription.secret, timestamp, body.as_bytes()),
+            );
+        if let Some(delivery_id) = payload.delivery_id {
+            request = request.header(DELIVERY_HEADER, delivery_id);
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|error| error.to_string())?;
+        debug!(
+            url = %webhook_subscription.url,
+            ?webhook_delivery,
+            result = %response.status(),
+            "post_webhook"
+        );
+        if !response.status().is_success() {
+            return Err(response.status().to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::{
+        ar::{
+            account::AccountId,
+            domain_event::DomainEvent,
+            webhook_subscription::{WebhookDeliveryId, WebhookSubscriptionId},
+        },
+        vo::money::Money,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /**
+     * Answers a single request with the status and returns the received request.
+     */
+    async fn stub_server(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // the head and the body may arrive separately
+            let mut request = String::new();
+            while !is_complete(&request) {
+                let mut buffer = vec![0; 4096];
+                let length = stream.read(&mut buffer).await.unwrap();
+                request.push_str(&String::from_utf8_lossy(&buffer[..length]));
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                status
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    fn is_complete(request: &str) -> bool {
+        let Some((head, body)) = request.split_once("\r\n\r\n") else {
+            return false;
+        };
+        let content_length = head
+            .lines()
+            .find_map(|line| {
+                line.to_lowercase()
+                    .strip_prefix("content-length: ")
+                    .map(str::to_string)
+            })
+            .map_or(0, |content_length| content_length.parse::<usize>().unwrap());
+        body.len() >= content_length
+    }
+
+    #[tokio::test]
+    async fn test_posts_signed_payload() {
+        // Given
+        let (url, stub) = stub_server("204 No Content").await;
+        let timestamp = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let webhook_subscription =
+            WebhookSubscription::new(AccountId(42), url, "secret".to_string(), timestamp);
+        let mut webhook_delivery = WebhookDelivery::new(
+            WebhookSubscriptionId(7),
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(42),
+                source_account_id: AccountId(41),
+                money: Money::of(500),
+                timestamp,
+            },
+        );
+        webhook_delivery.id = Some(WebhookDeliveryId(3));
+
+        // When
+        let posted = SignedWebhookClient::new()
+            .post_webhook(&webhook_subscription, &webhook_delivery)
+            .await;
+
+        // Then
+        assert_eq!(Ok(()), posted);
+        let request = stub.await.unwrap();
+        let (head, body) = request.split_once("\r\n\r\n").unwrap();
+        assert_eq!(
+            r#"{"delivery_id":3,"subscription_id":7,"event":{"type":"MoneyDeposited","account_id":42,"source_account_id":41,"amount":500,"timestamp":"2019-08-09 09:00:00"}}"#,
+            body
+        );
+        // And the partner can verify the body and the timestamp with the shared secret
+        let head = head.to_lowercase();
+        let sent_at = head
+            .lines()
+            .find_map(|line| line.strip_prefix("x-webhook-timestamp: "))
+            .unwrap()
+            .parse::<i64>()
+            .unwrap();
+        assert!((Utc::now().timestamp() - sent_at).abs() < 60);
+        assert!(head.contains(&format!(
+            "x-webhook-signature: {}",
+            sign("secret", sent_at, body.as_bytes())
+        )));
+        assert!(head.contains("x-webhook-delivery: 3"));
+        assert_ne!(
+            sign("secret", sent_at, body.as_bytes()),
+            sign("other", sent_at, body.as_bytes())
+        );
+        // And a replayed body does not match the signature of another timestamp
+        assert_ne!(
+            sign("secret", sent_at, body.as_bytes()),
+            sign("secret", sent_at + 1, body.as_bytes())
+        );
+    }
+}
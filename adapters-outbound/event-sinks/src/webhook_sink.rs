@@ -0,0 +1,120 @@
+use crate::domain_event_dto::DomainEventDto;
+use application::outbound_ports::DomainEventSink;
+use async_trait::async_trait;
+use domain::ar::domain_event::DomainEvent;
+use tracing::debug;
+
+/**
+ * Posts every event as JSON to a webhook. Any response other than a success status is
+ * treated as a failed delivery.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct WebhookDomainEventSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookDomainEventSink {
+    // Functions
+
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for WebhookDomainEventSink {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        let body = serde_json::to_string(&DomainEventDto::from(domain_event))
+            .map_err(|error| error.to_string())?;
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|error| error.to_string())?;
+        debug!(
+            url = %self.url,
+            ?domain_event,
+            result = %response.status(),
+            "deliver"
+        );
+        if !response.status().is_success() {
+            return Err(response.status().to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::{ar::account::AccountId, vo::money::Money};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /**
+     * Answers each request with the next status and returns the received requests.
+     */
+    async fn stub_server(
+        statuses: Vec<&'static str>,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = vec![];
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let length = stream.read(&mut request).await.unwrap();
+                requests.push(String::from_utf8_lossy(&request[..length]).to_string());
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_a_failed_delivery() {
+        // Given a webhook which fails once
+        let (url, stub) = stub_server(vec!["503 Service Unavailable", "204 No Content"]).await;
+        let sink_under_test = WebhookDomainEventSink::new(url);
+        let domain_event = DomainEvent::MoneyDeposited {
+            account_id: AccountId(42),
+            source_account_id: AccountId(41),
+            money: Money::of(500),
+            timestamp: NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+        };
+
+        // When the event is delivered twice
+        let first = sink_under_test.deliver(&domain_event).await;
+        let second = sink_under_test.deliver(&domain_event).await;
+
+        // Then only the second delivery succeeds
+        assert_eq!(Err("503 Service Unavailable".to_string()), first);
+        assert_eq!(Ok(()), second);
+        // And the event was posted as JSON
+        let requests = stub.await.unwrap();
+        assert!(requests[1].starts_with("POST /events HTTP/1.1"));
+        assert!(requests[1].ends_with(
+            r#"{"type":"MoneyDeposited","account_id":42,"source_account_id":41,"amount":500,"timestamp":"2019-08-09 09:00:00"}"#
+        ));
+    }
+}
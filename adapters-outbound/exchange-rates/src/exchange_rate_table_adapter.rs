@@ -0,0 +1,145 @@
+use application::outbound_ports::ExchangeRatePort;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+use domain::vo::{
+    currency::Currency,
+    exchange_rate::{ExchangeRate, ExchangeRateQuote},
+};
+use std::{collections::HashMap, path::Path};
+use tracing::debug;
+
+/**
+ * Quotes the rates of a static rate table, e.g. read from a file at startup.
+ * Only the listed currency pairs are traded, the inverse of a rate is not derived.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct ExchangeRateTableAdapter {
+    exchange_rates: HashMap<(Currency, Currency), ExchangeRate>,
+    quote_time_to_live: Duration,
+}
+
+impl ExchangeRateTableAdapter {
+    // Functions
+
+    /// # Arguments
+    ///
+    /// * `exchange_rates` - The rates of the traded currency pairs.
+    /// * `quote_time_to_live` - How long a quoted rate is honoured.
+    pub fn new(exchange_rates: Vec<ExchangeRate>, quote_time_to_live: Duration) -> Self {
+        Self {
+            exchange_rates: exchange_rates
+                .into_iter()
+                .map(|rate| {
+                    (
+                        (rate.source_currency.clone(), rate.target_currency.clone()),
+                        rate,
+                    )
+                })
+                .collect(),
+            quote_time_to_live,
+        }
+    }
+
+    /**
+     * Parses a rate table with one rate like `EUR/USD 1.0845` per line.
+     * Blank lines and lines starting with `#` are skipped.
+     * @return the error of the first line which is not an exchange rate.
+     */
+    pub fn parse(rate_table: &str, quote_time_to_live: Duration) -> Result<Self, String> {
+        let exchange_rates = rate_table
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'))
+            .map(|(index, line)| {
+                line.parse()
+                    .map_err(|error| format!("line {}: {}", index + 1, error))
+            })
+            .collect::<Result<Vec<ExchangeRate>, String>>()?;
+        Ok(Self::new(exchange_rates, quote_time_to_live))
+    }
+
+    pub fn from_file(path: &Path, quote_time_to_live: Duration) -> Result<Self, String> {
+        let rate_table = std::fs::read_to_string(path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Self::parse(&rate_table, quote_time_to_live)
+    }
+}
+
+#[async_trait]
+impl ExchangeRatePort for ExchangeRateTableAdapter {
+    async fn quote_exchange_rate(
+        &self,
+        source_currency: Currency,
+        target_currency: Currency,
+        now: NaiveDateTime,
+    ) -> Option<ExchangeRateQuote> {
+        let quote = self
+            .exchange_rates
+            .get(&(source_currency.clone(), target_currency.clone()))
+            .map(|rate| ExchangeRateQuote::new(rate.clone(), now + self.quote_time_to_live));
+        debug!(
+            %source_currency,
+            %target_currency,
+            %now,
+            result = ?quote,
+            "quote_exchange_rate"
+        );
+        quote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn test_quotes_rate_of_table_until_it_expires() {
+        // Given
+        let adapter_under_test = ExchangeRateTableAdapter::parse(
+            "
+            # source/target rate
+            EUR/USD 1.0845
+
+            USD/EUR 0.9221
+            ",
+            Duration::seconds(30),
+        )
+        .unwrap();
+        let now = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+
+        // When
+        let quote = adapter_under_test
+            .quote_exchange_rate(Currency::of("EUR"), Currency::of("USD"), now)
+            .await;
+
+        // Then
+        assert_eq!(
+            Some(ExchangeRateQuote::new(
+                ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500),
+                now + Duration::seconds(30),
+            )),
+            quote
+        );
+        assert_eq!(
+            None,
+            adapter_under_test
+                .quote_exchange_rate(Currency::of("EUR"), Currency::of("CHF"), now)
+                .await
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_rate_table() {
+        let result =
+            ExchangeRateTableAdapter::parse("EUR/USD 1.0845\nEUR/CHF", Duration::seconds(30));
+        assert_eq!(
+            Some("line 2: not an exchange rate: EUR/CHF".to_string()),
+            result.err()
+        );
+    }
+}
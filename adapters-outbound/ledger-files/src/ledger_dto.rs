@@ -0,0 +1,123 @@
+use application::inbound_ports::{Ledger, LedgerTransfer};
+use domain::ar::activity::Activity;
+use serde::{Deserialize, Serialize};
+
+/// Raised whenever a field is renamed or removed, new fields may be added within a version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/**
+ * The general-ledger file, e.g. `{"schema_version":1,"exported_at":"2019-08-09 09:00:00",
+ * "period_from":"2019-08-01","period_to":null,"transfers":[...]}`.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LedgerDto {
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub period_from: Option<String>,
+    pub period_to: Option<String>,
+    pub transfers: Vec<LedgerTransferDto>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LedgerTransferDto {
+    pub transfer_id: i64,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub complete: bool,
+    pub entries: Vec<LedgerEntryDto>,
+}
+
+/// The source account is debited with the withdrawal, the target account credited with the deposit.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LedgerEntryDto {
+    pub activity_id: i64,
+    pub side: String,
+    pub account_id: i64,
+    pub counterpart_account_id: i64,
+    pub timestamp: String,
+    pub amount: i64,
+    pub exchange_rate: Option<String>,
+}
+
+impl From<&Ledger> for LedgerDto {
+    fn from(ledger: &Ledger) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            exported_at: ledger.exported_at.to_string(),
+            period_from: ledger.from.map(|from| from.to_string()),
+            period_to: ledger.to.map(|to| to.to_string()),
+            transfers: ledger
+                .transfers
+                .iter()
+                .map(LedgerTransferDto::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<&LedgerTransfer> for LedgerTransferDto {
+    fn from(transfer: &LedgerTransfer) -> Self {
+        let mut entries = vec![];
+        if let Some(withdrawal) = &transfer.withdrawal {
+            entries.push(LedgerEntryDto::of("DEBIT", withdrawal));
+        }
+        if let Some(deposit) = &transfer.deposit {
+            entries.push(LedgerEntryDto::of("CREDIT", deposit));
+        }
+        Self {
+            transfer_id: transfer.transfer_id.0,
+            source_account_id: transfer.source_account_id.0,
+            target_account_id: transfer.target_account_id.0,
+            complete: transfer.is_complete(),
+            entries,
+        }
+    }
+}
+
+impl LedgerEntryDto {
+    fn of(side: &str, activity: &Activity) -> Self {
+        let counterpart_account_id = if activity.owner_account_id == activity.source_account_id {
+            &activity.target_account_id
+        } else {
+            &activity.source_account_id
+        };
+        Self {
+            activity_id: activity.id.as_ref().unwrap().0,
+            side: side.to_string(),
+            account_id: activity.owner_account_id.0,
+            counterpart_account_id: counterpart_account_id.0,
+            timestamp: activity.timestamp.to_string(),
+            amount: activity.money.amount.to_string().parse::<i64>().unwrap(),
+            exchange_rate: activity
+                .exchange_rate
+                .as_ref()
+                .map(|exchange_rate| exchange_rate.to_string()),
+        }
+    }
+}
+
+impl LedgerDto {
+    /**
+     * Renders a row per entry, the entries of a transfer one after the other.
+     */
+    pub fn to_csv(&self) -> String {
+        let mut csv = "transfer_id,complete,activity_id,side,account_id,counterpart_account_id,timestamp,amount,exchange_rate\n".to_string();
+        for transfer in &self.transfers {
+            for entry in &transfer.entries {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    transfer.transfer_id,
+                    transfer.complete,
+                    entry.activity_id,
+                    entry.side,
+                    entry.account_id,
+                    entry.counterpart_account_id,
+                    entry.timestamp,
+                    entry.amount,
+                    entry.exchange_rate.as_deref().unwrap_or("")
+                ));
+            }
+        }
+        csv
+    }
+}
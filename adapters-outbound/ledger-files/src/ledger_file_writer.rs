@@ -0,0 +1,199 @@
+use crate::ledger_dto::LedgerDto;
+use application::{
+    inbound_ports::{Ledger, LedgerFile},
+    outbound_ports::LedgerFilePort,
+};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::{fs::OpenOptions, io::Write, path::PathBuf};
+use tracing::debug;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LedgerFileFormat {
+    Csv,
+    /// Pretty printed, see [LedgerDto] for the schema.
+    Json,
+}
+
+/**
+ * Writes every ledger to a new file in the directory, named after the time of the export, and
+ * its SHA-256 checksum to a file of the same name ending in `.sha256`, as `sha256sum` would.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct LedgerFileWriter {
+    directory: PathBuf,
+    format: LedgerFileFormat,
+}
+
+impl LedgerFileWriter {
+    // Functions
+
+    pub fn new(directory: PathBuf, format: LedgerFileFormat) -> Self {
+        Self { directory, format }
+    }
+}
+
+#[async_trait]
+impl LedgerFilePort for LedgerFileWriter {
+    async fn write_ledger_file(&self, ledger: &Ledger) -> Result<LedgerFile, String> {
+        let dto = LedgerDto::from(ledger);
+        let (content, extension) = match self.format {
+            LedgerFileFormat::Csv => (dto.to_csv(), "csv"),
+            LedgerFileFormat::Json => (
+                serde_json::to_string_pretty(&dto).map_err(|error| error.to_string())? + "\n",
+                "json",
+            ),
+        };
+        let file_name = format!(
+            "ledger-{}.{}",
+            ledger.exported_at.format("%Y%m%dT%H%M%S%3f"),
+            extension
+        );
+        let checksum = hex::encode(Sha256::digest(content.as_bytes()));
+
+        let path = self.directory.join(&file_name);
+        write_new_file(&path, &content)?;
+        write_new_file(
+            &self.directory.join(format!("{}.sha256", file_name)),
+            &format!("{}  {}\n", checksum, file_name),
+        )?;
+        debug!(?path, %checksum, "write_ledger_file");
+        Ok(LedgerFile {
+            path: path.to_string_lossy().into_owned(),
+            checksum,
+        })
+    }
+}
+
+/**
+ * Fails instead of overwriting a file exported before.
+ */
+fn write_new_file(path: &PathBuf, content: &str) -> Result<(), String> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use application::inbound_ports::LedgerTransfer;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::{
+        ar::{
+            account::AccountId,
+            activity::{Activity, ActivityId},
+        },
+        vo::{currency::Currency, exchange_rate::ExchangeRate, money::Money},
+    };
+
+    #[tokio::test]
+    async fn test_write_ledger_file() {
+        // Given an exchanged transfer and a deposit whose withdrawal is missing
+        let directory = std::env::temp_dir().join(format!("ledger-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let exchange_rate = ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500);
+        let ledger = Ledger {
+            exported_at: timestamp(),
+            from: NaiveDate::from_ymd_opt(2019, 8, 1),
+            to: None,
+            transfers: vec![
+                LedgerTransfer {
+                    transfer_id: ActivityId(11),
+                    source_account_id: AccountId(1),
+                    target_account_id: AccountId(2),
+                    withdrawal: Some(
+                        activity(11, 1, 1, 2, 1000).exchanged_at(exchange_rate.clone()),
+                    ),
+                    deposit: Some(activity(12, 2, 1, 2, 1085).exchanged_at(exchange_rate)),
+                },
+                LedgerTransfer {
+                    transfer_id: ActivityId(13),
+                    source_account_id: AccountId(3),
+                    target_account_id: AccountId(2),
+                    withdrawal: None,
+                    deposit: Some(activity(13, 2, 3, 2, 70)),
+                },
+            ],
+        };
+
+        // When
+        let csv_file = LedgerFileWriter::new(directory.clone(), LedgerFileFormat::Csv)
+            .write_ledger_file(&ledger)
+            .await
+            .unwrap();
+        let json_file = LedgerFileWriter::new(directory.clone(), LedgerFileFormat::Json)
+            .write_ledger_file(&ledger)
+            .await
+            .unwrap();
+
+        // Then
+        let csv = std::fs::read_to_string(&csv_file.path).unwrap();
+        assert_eq!(
+            "transfer_id,complete,activity_id,side,account_id,counterpart_account_id,timestamp,amount,exchange_rate\n\
+             11,true,11,DEBIT,1,2,2019-08-09 09:00:00,1000,EUR/USD 1.084500\n\
+             11,true,12,CREDIT,2,1,2019-08-09 09:00:00,1085,EUR/USD 1.084500\n\
+             13,false,13,CREDIT,2,3,2019-08-09 09:00:00,70,\n",
+            csv
+        );
+        assert_eq!(
+            directory
+                .join("ledger-20190809T090000000.csv")
+                .to_string_lossy(),
+            csv_file.path
+        );
+        assert_eq!(
+            hex::encode(Sha256::digest(csv.as_bytes())),
+            csv_file.checksum
+        );
+        assert_eq!(
+            format!("{}  ledger-20190809T090000000.csv\n", csv_file.checksum),
+            std::fs::read_to_string(format!("{}.sha256", csv_file.path)).unwrap()
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_file.path).unwrap()).unwrap();
+        assert_eq!(1, json["schema_version"]);
+        assert_eq!("2019-08-01", json["period_from"]);
+        assert_eq!("CREDIT", json["transfers"][0]["entries"][1]["side"]);
+        assert_eq!(false, json["transfers"][1]["complete"]);
+
+        // When the same export is written again
+        let result = LedgerFileWriter::new(directory.clone(), LedgerFileFormat::Csv)
+            .write_ledger_file(&ledger)
+            .await;
+
+        // Then the file is not overwritten
+        assert!(result.is_err());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    fn activity(
+        id: i64,
+        owner_account_id: i64,
+        source_account_id: i64,
+        target_account_id: i64,
+        amount: i128,
+    ) -> Activity {
+        Activity::with_id(
+            Some(ActivityId(id)),
+            AccountId(owner_account_id),
+            AccountId(source_account_id),
+            AccountId(target_account_id),
+            timestamp(),
+            Money::of(amount),
+            None,
+        )
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
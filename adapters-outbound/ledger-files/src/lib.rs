@@ -0,0 +1,2 @@
+mod ledger_dto;
+pub mod ledger_file_writer;
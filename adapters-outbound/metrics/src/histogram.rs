@@ -0,0 +1,64 @@
+use std::{fmt::Write, sync::Mutex};
+
+/**
+ * Counts the observed values per bucket, whose upper bounds are inclusive, together with their
+ * sum, as a Prometheus histogram.
+ */
+#[derive(Debug)]
+pub(crate) struct Histogram {
+    upper_bounds: &'static [f64],
+    state: Mutex<HistogramState>,
+}
+
+#[derive(Debug)]
+struct HistogramState {
+    /// One more than there are upper bounds, for the values above all of them.
+    bucket_counts: Vec<u64>,
+    sum: f64,
+}
+
+impl Histogram {
+    pub(crate) fn new(upper_bounds: &'static [f64]) -> Self {
+        Self {
+            upper_bounds,
+            state: Mutex::new(HistogramState {
+                bucket_counts: vec![0; upper_bounds.len() + 1],
+                sum: 0.0,
+            }),
+        }
+    }
+
+    pub(crate) fn observe(&self, value: f64) {
+        let bucket = self
+            .upper_bounds
+            .iter()
+            .position(|upper_bound| value <= *upper_bound)
+            .unwrap_or(self.upper_bounds.len());
+        let mut state = self.state.lock().unwrap();
+        state.bucket_counts[bucket] += 1;
+        state.sum += value;
+    }
+
+    /**
+     * Writes the cumulative bucket counts, the sum and the count.
+     */
+    pub(crate) fn render(&self, name: &str, help: &str, output: &mut String) {
+        let state = self.state.lock().unwrap();
+        writeln!(output, "# HELP {} {}", name, help).unwrap();
+        writeln!(output, "# TYPE {} histogram", name).unwrap();
+        let mut count = 0;
+        for (upper_bound, bucket_count) in self.upper_bounds.iter().zip(&state.bucket_counts) {
+            count += bucket_count;
+            writeln!(
+                output,
+                "{}_bucket{{le=\"{}\"}} {}",
+                name, upper_bound, count
+            )
+            .unwrap();
+        }
+        count += state.bucket_counts[self.upper_bounds.len()];
+        writeln!(output, "{}_bucket{{le=\"+Inf\"}} {}", name, count).unwrap();
+        writeln!(output, "{}_sum {}", name, state.sum).unwrap();
+        writeln!(output, "{}_count {}", name, count).unwrap();
+    }
+}
@@ -0,0 +1,2 @@
+mod histogram;
+pub mod prometheus_metrics;
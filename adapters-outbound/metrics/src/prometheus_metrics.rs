@@ -0,0 +1,235 @@
+use crate::histogram::Histogram;
+use application::{
+    inbound_ports::{RenderMetricsQuery, SendMoneyError},
+    outbound_ports::MetricsPort,
+};
+use domain::vo::money::Money;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+/// In seconds, from half a millisecond to a second.
+const LATENCY_BUCKETS: [f64; 10] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0,
+];
+const AMOUNT_BUCKETS: [f64; 6] = [10.0, 100.0, 1_000.0, 10_000.0, 100_000.0, 1_000_000.0];
+const ACTIVITY_COUNT_BUCKETS: [f64; 8] = [0.0, 1.0, 5.0, 10.0, 50.0, 100.0, 500.0, 1_000.0];
+
+/**
+ * Keeps the metrics in memory since the start of the process and renders them in the Prometheus
+ * text exposition format when scraped.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct PrometheusMetrics {
+    transfers_attempted: AtomicU64,
+    transfers_succeeded: AtomicU64,
+    transfers_rejected: Mutex<BTreeMap<&'static str, u64>>,
+    transfer_amount: Histogram,
+    account_lock_waits: AtomicU64,
+    account_lock_wait_micros: AtomicU64,
+    load_account_duration: Histogram,
+    loaded_activities: Histogram,
+    update_activities_duration: Histogram,
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self {
+            transfers_attempted: AtomicU64::new(0),
+            transfers_succeeded: AtomicU64::new(0),
+            transfers_rejected: Mutex::new(BTreeMap::new()),
+            transfer_amount: Histogram::new(&AMOUNT_BUCKETS),
+            account_lock_waits: AtomicU64::new(0),
+            account_lock_wait_micros: AtomicU64::new(0),
+            load_account_duration: Histogram::new(&LATENCY_BUCKETS),
+            loaded_activities: Histogram::new(&ACTIVITY_COUNT_BUCKETS),
+            update_activities_duration: Histogram::new(&LATENCY_BUCKETS),
+        }
+    }
+}
+
+impl PrometheusMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn reason_of(error: &SendMoneyError) -> &'static str {
+    match error {
+        SendMoneyError::ThresholdExceeded => "threshold_exceeded",
+        SendMoneyError::LimitExceeded(_) => "limit_exceeded",
+        SendMoneyError::InsufficientFunds => "insufficient_funds",
+        SendMoneyError::DepositRejected => "deposit_rejected",
+        SendMoneyError::BatchAborted => "batch_aborted",
+        SendMoneyError::CurrencyMismatch => "currency_mismatch",
+        SendMoneyError::ExchangeRateUnavailable => "exchange_rate_unavailable",
+        SendMoneyError::QuoteNotFound => "quote_not_found",
+        SendMoneyError::QuoteExpired => "quote_expired",
+        SendMoneyError::Unauthorized => "unauthorized",
+    }
+}
+
+fn render_counter(name: &str, help: &str, value: impl std::fmt::Display, output: &mut String) {
+    writeln!(output, "# HELP {} {}", name, help).unwrap();
+    writeln!(output, "# TYPE {} counter", name).unwrap();
+    writeln!(output, "{} {}", name, value).unwrap();
+}
+
+impl MetricsPort for PrometheusMetrics {
+    fn record_transfer_attempted(&self) {
+        self.transfers_attempted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_transfer_succeeded(&self, money: &Money) {
+        self.transfers_succeeded.fetch_add(1, Ordering::Relaxed);
+        self.transfer_amount
+            .observe(money.amount.to_string().parse().unwrap());
+    }
+
+    fn record_transfer_rejected(&self, error: &SendMoneyError) {
+        *self
+            .transfers_rejected
+            .lock()
+            .unwrap()
+            .entry(reason_of(error))
+            .or_insert(0) += 1;
+    }
+
+    fn record_lock_wait(&self, duration: Duration) {
+        self.account_lock_waits.fetch_add(1, Ordering::Relaxed);
+        self.account_lock_wait_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn record_load_account(&self, duration: Duration, activity_count: usize) {
+        self.load_account_duration.observe(duration.as_secs_f64());
+        self.loaded_activities.observe(activity_count as f64);
+    }
+
+    fn record_update_activities(&self, duration: Duration) {
+        self.update_activities_duration
+            .observe(duration.as_secs_f64());
+    }
+}
+
+impl RenderMetricsQuery for PrometheusMetrics {
+    fn render_metrics(&self) -> String {
+        let mut output = String::new();
+        render_counter(
+            "buckpal_transfers_attempted_total",
+            "Transfers attempted.",
+            self.transfers_attempted.load(Ordering::Relaxed),
+            &mut output,
+        );
+        render_counter(
+            "buckpal_transfers_succeeded_total",
+            "Transfers booked.",
+            self.transfers_succeeded.load(Ordering::Relaxed),
+            &mut output,
+        );
+        writeln!(
+            output,
+            "# HELP buckpal_transfers_rejected_total Transfers rejected by reason."
+        )
+        .unwrap();
+        writeln!(output, "# TYPE buckpal_transfers_rejected_total counter").unwrap();
+        for (reason, count) in self.transfers_rejected.lock().unwrap().iter() {
+            writeln!(
+                output,
+                "buckpal_transfers_rejected_total{{reason=\"{}\"}} {}",
+                reason, count
+            )
+            .unwrap();
+        }
+        self.transfer_amount.render(
+            "buckpal_transfer_amount",
+            "Amounts of the transfers booked.",
+            &mut output,
+        );
+        render_counter(
+            "buckpal_account_lock_waits_total",
+            "Accounts locked by transfers.",
+            self.account_lock_waits.load(Ordering::Relaxed),
+            &mut output,
+        );
+        render_counter(
+            "buckpal_account_lock_wait_seconds_total",
+            "Time transfers waited for account locks.",
+            self.account_lock_wait_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+            &mut output,
+        );
+        self.load_account_duration.render(
+            "buckpal_load_account_duration_seconds",
+            "Latency of loading an account with its activity window.",
+            &mut output,
+        );
+        self.loaded_activities.render(
+            "buckpal_loaded_activities",
+            "Activities loaded with an account per activity window.",
+            &mut output,
+        );
+        self.update_activities_duration.render(
+            "buckpal_update_activities_duration_seconds",
+            "Latency of storing the new activities of accounts.",
+            &mut output,
+        );
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_recorded_metrics() {
+        // Given a booked and two rejected transfers
+        let metrics = PrometheusMetrics::new();
+        for _ in 0..3 {
+            metrics.record_transfer_attempted();
+        }
+        metrics.record_transfer_succeeded(&Money::of(500));
+        metrics.record_transfer_rejected(&SendMoneyError::InsufficientFunds);
+        metrics.record_transfer_rejected(&SendMoneyError::InsufficientFunds);
+
+        // And an account loaded with 7 activities
+        metrics.record_load_account(Duration::from_millis(2), 7);
+
+        // When
+        let rendered = metrics.render_metrics();
+
+        // Then
+        let lines: Vec<&str> = rendered.lines().collect();
+        for expected in [
+            "# TYPE buckpal_transfers_attempted_total counter",
+            "buckpal_transfers_attempted_total 3",
+            "buckpal_transfers_succeeded_total 1",
+            "buckpal_transfers_rejected_total{reason=\"insufficient_funds\"} 2",
+            "buckpal_transfer_amount_bucket{le=\"100\"} 0",
+            "buckpal_transfer_amount_bucket{le=\"1000\"} 1",
+            "buckpal_transfer_amount_bucket{le=\"+Inf\"} 1",
+            "buckpal_transfer_amount_sum 500",
+            "# TYPE buckpal_load_account_duration_seconds histogram",
+            "buckpal_load_account_duration_seconds_bucket{le=\"0.001\"} 0",
+            "buckpal_load_account_duration_seconds_bucket{le=\"0.0025\"} 1",
+            "buckpal_load_account_duration_seconds_count 1",
+            "buckpal_loaded_activities_bucket{le=\"5\"} 0",
+            "buckpal_loaded_activities_bucket{le=\"10\"} 1",
+            "buckpal_update_activities_duration_seconds_count 0",
+        ] {
+            assert!(
+                lines.contains(&expected),
+                "{} not in\n{}",
+                expected,
+                rendered
+            );
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    account_event_repository::AccountEventEntity,
+    account_repository::{AccountEntity, OverdraftLimitChangeEntity},
+    activity_repository::ActivityEntity,
+};
+use chrono::NaiveDateTime;
+
+pub const ACCOUNT_OPENED: &str = "ACCOUNT_OPENED";
+pub const OVERDRAFT_LIMIT_CHANGED: &str = "OVERDRAFT_LIMIT_CHANGED";
+pub const ACCOUNT_CLOSED: &str = "ACCOUNT_CLOSED";
+pub const MONEY_WITHDRAWN: &str = "MONEY_WITHDRAWN";
+pub const MONEY_DEPOSITED: &str = "MONEY_DEPOSITED";
+
+/**
+ * An activity is a withdrawal in the stream of its owner if the owner is debited, a deposit otherwise.
+ */
+pub fn map_to_money_event_entity(
+    activity_id: i64,
+    activity_entity: &ActivityEntity,
+) -> AccountEventEntity {
+    let withdrawn = activity_entity.owner_account_id == activity_entity.source_account_id;
+    AccountEventEntity {
+        id: None,
+        account_id: activity_entity.owner_account_id,
+        event_type: if withdrawn {
+            MONEY_WITHDRAWN
+        } else {
+            MONEY_DEPOSITED
+        }
+        .to_string(),
+        timestamp: activity_entity.timestamp,
+        currency: None,
+        overdraft_limit: None,
+        activity_id: Some(activity_id),
+        counterpart_account_id: Some(if withdrawn {
+            activity_entity.target_account_id
+        } else {
+            activity_entity.source_account_id
+        }),
+        amount: Some(activity_entity.amount),
+        exchange_rate_source_currency: activity_entity.exchange_rate_source_currency.clone(),
+        exchange_rate_target_currency: activity_entity.exchange_rate_target_currency.clone(),
+        exchange_rate: activity_entity.exchange_rate,
+    }
+}
+
+pub fn map_to_account_opened_event_entity(
+    account_entity: &AccountEntity,
+    opened_at: NaiveDateTime,
+) -> AccountEventEntity {
+    AccountEventEntity {
+        id: None,
+        account_id: account_entity.id.unwrap(),
+        event_type: ACCOUNT_OPENED.to_string(),
+        timestamp: opened_at,
+        currency: Some(account_entity.currency.clone()),
+        overdraft_limit: Some(account_entity.overdraft_limit),
+        activity_id: None,
+        counterpart_account_id: None,
+        amount: None,
+        exchange_rate_source_currency: None,
+        exchange_rate_target_currency: None,
+        exchange_rate: None,
+    }
+}
+
+pub fn map_to_overdraft_limit_changed_event_entity(
+    overdraft_limit_change_entity: &OverdraftLimitChangeEntity,
+) -> AccountEventEntity {
+    AccountEventEntity {
+        id: None,
+        account_id: overdraft_limit_change_entity.account_id,
+        event_type: OVERDRAFT_LIMIT_CHANGED.to_string(),
+        timestamp: overdraft_limit_change_entity.changed_at,
+        currency: None,
+        overdraft_limit: Some(overdraft_limit_change_entity.new_limit),
+        activity_id: None,
+        counterpart_account_id: None,
+        amount: None,
+        exchange_rate_source_currency: None,
+        exchange_rate_target_currency: None,
+        exchange_rate: None,
+    }
+}
+
+pub fn map_to_account_closed_event_entity(
+    account_id: i64,
+    closed_at: NaiveDateTime,
+) -> AccountEventEntity {
+    AccountEventEntity {
+        id: None,
+        account_id,
+        event_type: ACCOUNT_CLOSED.to_string(),
+        timestamp: closed_at,
+        currency: None,
+        overdraft_limit: None,
+        activity_id: None,
+        counterpart_account_id: None,
+        amount: None,
+        exchange_rate_source_currency: None,
+        exchange_rate_target_currency: None,
+        exchange_rate: None,
+    }
+}
+
+/**
+ * Maps a withdrawal or deposit back to the activity it was recorded for.
+ */
+pub fn map_to_activity_entity(account_event_entity: &AccountEventEntity) -> ActivityEntity {
+    let account_id = account_event_entity.account_id;
+    let counterpart_account_id = account_event_entity.counterpart_account_id.unwrap();
+    let (source_account_id, target_account_id) =
+        if account_event_entity.event_type == MONEY_WITHDRAWN {
+            (account_id, counterpart_account_id)
+        } else {
+            (counterpart_account_id, account_id)
+        };
+    ActivityEntity {
+        id: account_event_entity.activity_id,
+        timestamp: account_event_entity.timestamp,
+        owner_account_id: account_id,
+        source_account_id,
+        target_account_id,
+        amount: account_event_entity.amount.unwrap(),
+        exchange_rate_source_currency: account_event_entity.exchange_rate_source_currency.clone(),
+        exchange_rate_target_currency: account_event_entity.exchange_rate_target_currency.clone(),
+        exchange_rate: account_event_entity.exchange_rate,
+    }
+}
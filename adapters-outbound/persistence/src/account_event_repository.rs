@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait AccountEventRepository: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the events of the account with an ID greater than `after_event_id`, oldest first.
+     */
+    async fn find_events_after(
+        &self,
+        account_id: i64,
+        after_event_id: i64,
+    ) -> Vec<AccountEventEntity>;
+    async fn find_snapshot(&self, account_id: i64) -> Option<AccountSnapshotEntity>;
+    /**
+     * Replaces the snapshot of the account.
+     */
+    async fn save_snapshot(&self, account_snapshot_entity: AccountSnapshotEntity);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccountEventRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl AccountEventRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AccountEventRepository for AccountEventRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_events_after(
+        &self,
+        account_id: i64,
+        after_event_id: i64,
+    ) -> Vec<AccountEventEntity> {
+        let rows = sqlx::query_as::<_, AccountEventEntity>(
+            "
+            SELECT * FROM account_event_entity
+            WHERE account_id = ?
+            AND id > ?
+            ORDER BY id
+            ",
+        )
+        .bind(account_id)
+        .bind(after_event_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_snapshot(&self, account_id: i64) -> Option<AccountSnapshotEntity> {
+        sqlx::query_as::<_, AccountSnapshotEntity>(
+            "
+            SELECT * FROM account_snapshot_entity
+            WHERE account_id = ?
+            ",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_snapshot(&self, account_snapshot_entity: AccountSnapshotEntity) {
+        sqlx::query(
+            "
+            INSERT OR REPLACE INTO account_snapshot_entity (account_id, last_event_id, last_timestamp, currency,
+                overdraft_limit, withdrawal_balance, deposit_balance, closed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(account_snapshot_entity.account_id)
+        .bind(account_snapshot_entity.last_event_id)
+        .bind(account_snapshot_entity.last_timestamp)
+        .bind(account_snapshot_entity.currency)
+        .bind(account_snapshot_entity.overdraft_limit)
+        .bind(account_snapshot_entity.withdrawal_balance)
+        .bind(account_snapshot_entity.deposit_balance)
+        .bind(account_snapshot_entity.closed_at)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+/**
+ * Appends an event to the stream of its account on the connection, which is part of the
+ * transaction changing the state the event describes.
+ */
+pub(crate) async fn append_account_event(
+    connection: &mut SqliteConnection,
+    account_event_entity: &AccountEventEntity,
+) {
+    sqlx::query(
+        "
+        INSERT INTO account_event_entity (account_id, event_type, timestamp, currency, overdraft_limit, activity_id,
+            counterpart_account_id, amount, exchange_rate_source_currency, exchange_rate_target_currency, exchange_rate)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(account_event_entity.account_id)
+    .bind(&account_event_entity.event_type)
+    .bind(account_event_entity.timestamp)
+    .bind(&account_event_entity.currency)
+    .bind(account_event_entity.overdraft_limit)
+    .bind(account_event_entity.activity_id)
+    .bind(account_event_entity.counterpart_account_id)
+    .bind(account_event_entity.amount)
+    .bind(&account_event_entity.exchange_rate_source_currency)
+    .bind(&account_event_entity.exchange_rate_target_currency)
+    .bind(account_event_entity.exchange_rate)
+    .execute(&mut *connection)
+    .await
+    .unwrap();
+}
+
+#[derive(FromRow, Clone, PartialEq, Hash, Debug)]
+pub struct AccountEventEntity {
+    pub id: Option<i64>,
+    pub account_id: i64,
+    pub event_type: String,
+    pub timestamp: NaiveDateTime,
+    pub currency: Option<String>,
+    pub overdraft_limit: Option<i64>,
+    pub activity_id: Option<i64>,
+    pub counterpart_account_id: Option<i64>,
+    pub amount: Option<i64>,
+    pub exchange_rate_source_currency: Option<String>,
+    pub exchange_rate_target_currency: Option<String>,
+    pub exchange_rate: Option<i64>,
+}
+
+#[derive(FromRow, Clone, PartialEq, Hash, Debug)]
+pub struct AccountSnapshotEntity {
+    pub account_id: i64,
+    pub last_event_id: i64,
+    // the latest timestamp of the events up to the last event
+    pub last_timestamp: NaiveDateTime,
+    pub currency: String,
+    pub overdraft_limit: i64,
+    pub withdrawal_balance: i64,
+    pub deposit_balance: i64,
+    pub closed_at: Option<NaiveDateTime>,
+}
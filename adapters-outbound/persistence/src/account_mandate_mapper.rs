@@ -0,0 +1,52 @@
+use crate::account_mandate_repository::{AccountMandateEntity, AuthorizationDenialEntity};
+use domain::ar::{
+    account::AccountId,
+    account_mandate::{AccountMandate, MandateKind},
+    authorization_denial::AuthorizationDenial,
+};
+
+pub fn map_to_account_mandate(entity: AccountMandateEntity) -> AccountMandate {
+    AccountMandate::new(
+        AccountId(entity.account_id),
+        entity.customer,
+        map_to_kind(&entity.kind),
+        entity.granted_at,
+    )
+}
+
+pub fn map_to_account_mandate_entity(account_mandate: AccountMandate) -> AccountMandateEntity {
+    AccountMandateEntity {
+        account_id: account_mandate.account_id.0,
+        customer: account_mandate.customer,
+        kind: map_to_kind_column(account_mandate.kind).to_string(),
+        granted_at: account_mandate.granted_at,
+    }
+}
+
+pub fn map_to_authorization_denial_entity(
+    authorization_denial: AuthorizationDenial,
+) -> AuthorizationDenialEntity {
+    AuthorizationDenialEntity {
+        id: None,
+        subject: authorization_denial.subject,
+        role: authorization_denial.role.to_string(),
+        action: authorization_denial.action,
+        account_id: authorization_denial.account_id.0,
+        denied_at: authorization_denial.denied_at,
+    }
+}
+
+fn map_to_kind(kind: &str) -> MandateKind {
+    match kind {
+        "OWNER" => MandateKind::Owner,
+        "SIGNATORY" => MandateKind::Signatory,
+        _ => panic!("unknown mandate kind: {}", kind),
+    }
+}
+
+fn map_to_kind_column(kind: MandateKind) -> &'static str {
+    match kind {
+        MandateKind::Owner => "OWNER",
+        MandateKind::Signatory => "SIGNATORY",
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{account_mandate_mapper, account_mandate_repository::AccountMandateRepository};
+use application::outbound_ports::{
+    LoadAccountMandatePort, RecordAuthorizationDenialPort, UpdateAccountMandatePort,
+};
+use async_trait::async_trait;
+use domain::ar::{
+    account::AccountId, account_mandate::AccountMandate, authorization_denial::AuthorizationDenial,
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccountMandatePersistenceAdapter {
+    account_mandate_repository: Box<dyn AccountMandateRepository>,
+}
+
+impl AccountMandatePersistenceAdapter {
+    // #[inject]
+    pub fn new(account_mandate_repository: Box<dyn AccountMandateRepository>) -> Self {
+        Self {
+            account_mandate_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadAccountMandatePort for AccountMandatePersistenceAdapter {
+    async fn load_account_mandates(&self, account_id: AccountId) -> Vec<AccountMandate> {
+        let entities = self
+            .account_mandate_repository
+            .find_mandates_by_account(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entities, "find_mandates_by_account");
+        entities
+            .into_iter()
+            .map(account_mandate_mapper::map_to_account_mandate)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateAccountMandatePort for AccountMandatePersistenceAdapter {
+    async fn grant_account_mandate(&self, account_mandate: AccountMandate) {
+        let entity = account_mandate_mapper::map_to_account_mandate_entity(account_mandate);
+        debug!(account_mandate_entity = ?entity, "save_mandate");
+        self.account_mandate_repository.save_mandate(entity).await;
+    }
+}
+
+#[async_trait]
+impl RecordAuthorizationDenialPort for AccountMandatePersistenceAdapter {
+    async fn record_authorization_denial(&self, authorization_denial: AuthorizationDenial) {
+        let entity =
+            account_mandate_mapper::map_to_authorization_denial_entity(authorization_denial);
+        debug!(authorization_denial_entity = ?entity, "insert_authorization_denial");
+        self.account_mandate_repository
+            .insert_authorization_denial(entity)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account_mandate_repository::{AccountMandateEntity, AuthorizationDenialEntity};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::{ar::account_mandate::MandateKind, vo::role::Role};
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        AccountMandateRepositoryImpl {}
+        #[async_trait]
+        impl AccountMandateRepository for AccountMandateRepositoryImpl {
+            async fn find_mandates_by_account(&self, account_id: i64) -> Vec<AccountMandateEntity>;
+            async fn save_mandate(&self, account_mandate_entity: AccountMandateEntity);
+            async fn insert_authorization_denial(
+                &self,
+                authorization_denial_entity: AuthorizationDenialEntity,
+            );
+        }
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 18)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_maps_mandates_and_denials() {
+        // Given an account with an owner and a signatory
+        let mut repository = Box::new(MockAccountMandateRepositoryImpl::new());
+        repository
+            .expect_find_mandates_by_account()
+            .with(eq(41))
+            .returning(|account_id| {
+                ["OWNER", "SIGNATORY"]
+                    .into_iter()
+                    .zip(["alice", "bob"])
+                    .map(|(kind, customer)| AccountMandateEntity {
+                        account_id,
+                        customer: customer.to_string(),
+                        kind: kind.to_string(),
+                        granted_at: timestamp(),
+                    })
+                    .collect()
+            });
+
+        // Then a denial is stored with its role and action
+        repository
+            .expect_insert_authorization_denial()
+            .times(1)
+            .with(eq(AuthorizationDenialEntity {
+                id: None,
+                subject: "carol".to_string(),
+                role: "support".to_string(),
+                action: "debit_account".to_string(),
+                account_id: 41,
+                denied_at: timestamp(),
+            }))
+            .return_const(());
+
+        // When
+        let adapter_under_test = AccountMandatePersistenceAdapter::new(repository);
+        let mandates = adapter_under_test
+            .load_account_mandates(AccountId(41))
+            .await;
+        adapter_under_test
+            .record_authorization_denial(AuthorizationDenial::new(
+                "carol".to_string(),
+                Role::Support,
+                "debit_account".to_string(),
+                AccountId(41),
+                timestamp(),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(
+            mandates,
+            vec![
+                AccountMandate::new(
+                    AccountId(41),
+                    "alice".to_string(),
+                    MandateKind::Owner,
+                    timestamp()
+                ),
+                AccountMandate::new(
+                    AccountId(41),
+                    "bob".to_string(),
+                    MandateKind::Signatory,
+                    timestamp()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait AccountMandateRepository: Send + Sync + std::fmt::Debug {
+    async fn find_mandates_by_account(&self, account_id: i64) -> Vec<AccountMandateEntity>;
+    /**
+     * Inserts the mandate or replaces the one of the customer on the account.
+     */
+    async fn save_mandate(&self, account_mandate_entity: AccountMandateEntity);
+    async fn insert_authorization_denial(
+        &self,
+        authorization_denial_entity: AuthorizationDenialEntity,
+    );
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccountMandateRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl AccountMandateRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AccountMandateRepository for AccountMandateRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_mandates_by_account(&self, account_id: i64) -> Vec<AccountMandateEntity> {
+        let rows = sqlx::query_as::<_, AccountMandateEntity>(
+            "
+            SELECT * FROM account_mandate_entity
+            WHERE account_id = ?
+            ORDER BY customer
+            ",
+        )
+        .bind(account_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_mandate(&self, account_mandate_entity: AccountMandateEntity) {
+        sqlx::query(
+            "
+            INSERT OR REPLACE INTO account_mandate_entity (account_id, customer, kind, granted_at)
+            VALUES (?, ?, ?, ?)
+            ",
+        )
+        .bind(account_mandate_entity.account_id)
+        .bind(account_mandate_entity.customer)
+        .bind(account_mandate_entity.kind)
+        .bind(account_mandate_entity.granted_at)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn insert_authorization_denial(
+        &self,
+        authorization_denial_entity: AuthorizationDenialEntity,
+    ) {
+        sqlx::query(
+            "
+            INSERT INTO authorization_denial_entity (subject, role, action, account_id, denied_at)
+            VALUES (?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(authorization_denial_entity.subject)
+        .bind(authorization_denial_entity.role)
+        .bind(authorization_denial_entity.action)
+        .bind(authorization_denial_entity.account_id)
+        .bind(authorization_denial_entity.denied_at)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct AccountMandateEntity {
+    pub account_id: i64,
+    pub customer: String,
+    pub kind: String,
+    pub granted_at: NaiveDateTime,
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct AuthorizationDenialEntity {
+    pub id: Option<i64>,
+    pub subject: String,
+    pub role: String,
+    pub action: String,
+    pub account_id: i64,
+    pub denied_at: NaiveDateTime,
+}
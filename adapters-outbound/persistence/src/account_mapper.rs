@@ -1,10 +1,21 @@
-use crate::{account_repository::AccountEntity, activity_repository::ActivityEntity};
+use crate::{
+    account_repository::{AccountEntity, OverdraftLimitChangeEntity, TransferLimitsEntity},
+    activity_repository::ActivityEntity,
+};
+use application::inbound_ports::{AccountDetails, ImportedAccount};
 use domain::{
     ar::{
         account::{AccountId, Account},
         activity::{Activity, ActivityId},
+        overdraft_limit_change::OverdraftLimitChange,
+    },
+    vo::{
+        activity_window::ActivityWindow,
+        currency::Currency,
+        exchange_rate::ExchangeRate,
+        money::Money,
+        transfer_limits::{TransferLimits, TransferVolume},
     },
-    vo::{activity_window::ActivityWindow, money::Money},
 };
 
 pub fn map_to_account(
@@ -15,28 +26,52 @@ pub fn map_to_account(
 ) -> Account {
     let baseline_balance =
         Money::substract(&Money::of(deposit_balance), &Money::of(withdrawal_balance));
-    Account::with_id(
+    let closed = account.closed_at.is_some();
+    let account = Account::with_id(
         AccountId(account.id.unwrap()),
+        account.currency.parse().unwrap(),
         baseline_balance,
+        Money::of(account.overdraft_limit as i128),
         map_to_activity_window(activities),
-    )
+    );
+    if closed {
+        account.closed()
+    } else {
+        account
+    }
+}
+
+pub fn map_to_account_details(account: AccountEntity) -> AccountDetails {
+    AccountDetails {
+        account_id: AccountId(account.id.unwrap()),
+        currency: account.currency.parse().unwrap(),
+        overdraft_limit: Money::of(account.overdraft_limit as i128),
+        closed_at: account.closed_at,
+    }
+}
+
+pub fn map_to_new_account_entity(currency: &Currency, overdraft_limit: &Money) -> AccountEntity {
+    AccountEntity {
+        id: None,
+        currency: currency.to_string(),
+        overdraft_limit: map_to_amount(overdraft_limit),
+        closed_at: None,
+    }
 }
 
 fn map_to_activity_window(activities: Vec<ActivityEntity>) -> ActivityWindow {
-    ActivityWindow::new(
-        activities
-            .iter()
-            .map(|ae| {
-                Activity::with_id(
-                    Some(ActivityId(ae.id.unwrap())),
-                    AccountId(ae.owner_account_id),
-                    AccountId(ae.source_account_id),
-                    AccountId(ae.target_account_id),
-                    ae.timestamp,
-                    Money::of(ae.amount as i128),
-                )
-            })
-            .collect(),
+    ActivityWindow::new(activities.iter().map(map_to_activity).collect())
+}
+
+pub fn map_to_activity(ae: &ActivityEntity) -> Activity {
+    Activity::with_id(
+        Some(ActivityId(ae.id.unwrap())),
+        AccountId(ae.owner_account_id),
+        AccountId(ae.source_account_id),
+        AccountId(ae.target_account_id),
+        ae.timestamp,
+        Money::of(ae.amount as i128),
+        map_to_exchange_rate(ae),
     )
 }
 
@@ -53,5 +88,108 @@ pub fn map_to_activity_entity(activity: &Activity) -> ActivityEntity {
         source_account_id: activity.source_account_id.0,
         target_account_id: activity.target_account_id.0,
         amount,
+        exchange_rate_source_currency: activity
+            .exchange_rate
+            .as_ref()
+            .map(|exchange_rate| exchange_rate.source_currency.to_string()),
+        exchange_rate_target_currency: activity
+            .exchange_rate
+            .as_ref()
+            .map(|exchange_rate| exchange_rate.target_currency.to_string()),
+        exchange_rate: activity
+            .exchange_rate
+            .as_ref()
+            .map(|exchange_rate| exchange_rate.rate as i64),
+    }
+}
+
+/**
+ * @return the activities of the accounts which are not persisted yet
+ */
+pub fn map_to_new_activity_entities(accounts: &[Account]) -> Vec<ActivityEntity> {
+    accounts
+        .iter()
+        .flat_map(|account| &account.activity_window.activities)
+        .filter(|activity| activity.id.is_none())
+        .map(map_to_activity_entity)
+        .collect()
+}
+
+fn map_to_exchange_rate(activity_entity: &ActivityEntity) -> Option<ExchangeRate> {
+    match (
+        &activity_entity.exchange_rate_source_currency,
+        &activity_entity.exchange_rate_target_currency,
+        activity_entity.exchange_rate,
+    ) {
+        (Some(source_currency), Some(target_currency), Some(rate)) => Some(ExchangeRate::new(
+            source_currency.parse().unwrap(),
+            target_currency.parse().unwrap(),
+            rate as i128,
+        )),
+        _ => None,
+    }
+}
+
+pub fn map_to_overdraft_limit_change(entity: OverdraftLimitChangeEntity) -> OverdraftLimitChange {
+    OverdraftLimitChange::new(
+        AccountId(entity.account_id),
+        Money::of(entity.previous_limit as i128),
+        Money::of(entity.new_limit as i128),
+        entity.changed_by,
+        entity.changed_at,
+    )
+}
+
+pub fn map_to_overdraft_limit_change_entity(
+    change: OverdraftLimitChange,
+) -> OverdraftLimitChangeEntity {
+    OverdraftLimitChangeEntity {
+        id: None,
+        account_id: change.account_id.0,
+        previous_limit: map_to_amount(&change.previous_limit),
+        new_limit: map_to_amount(&change.new_limit),
+        changed_by: change.changed_by,
+        changed_at: change.changed_at,
+    }
+}
+
+pub fn map_to_transfer_limits(entity: TransferLimitsEntity) -> TransferLimits {
+    TransferLimits::new(
+        entity
+            .daily_amount_limit
+            .map(|limit| Money::of(limit as i128)),
+        entity
+            .monthly_amount_limit
+            .map(|limit| Money::of(limit as i128)),
+        entity.daily_count_limit.map(|limit| limit as u32),
+    )
+}
+
+pub fn map_to_transfer_limits_entity(
+    account_id: AccountId,
+    transfer_limits: TransferLimits,
+) -> TransferLimitsEntity {
+    TransferLimitsEntity {
+        account_id: account_id.0,
+        daily_amount_limit: transfer_limits.daily_amount.as_ref().map(map_to_amount),
+        monthly_amount_limit: transfer_limits.monthly_amount.as_ref().map(map_to_amount),
+        daily_count_limit: transfer_limits.daily_count.map(|limit| limit as i64),
+    }
+}
+
+pub fn map_to_transfer_volume(amount: i128, count: i64) -> TransferVolume {
+    TransferVolume::new(Money::of(amount), count as u32)
+}
+
+pub fn map_to_amount(money: &Money) -> i64 {
+    money.amount.to_string().parse::<i64>().unwrap()
+}
+
+pub fn map_to_imported_account_entity(imported_account: &ImportedAccount) -> AccountEntity {
+    AccountEntity {
+        id: Some(imported_account.account_id.0),
+        currency: imported_account.currency.to_string(),
+        overdraft_limit: map_to_amount(&imported_account.overdraft_limit),
+        closed_at: None,
     }
 }
@@ -1,17 +1,42 @@
 use crate::{
     account_mapper, account_repository::AccountRepository, activity_repository::ActivityRepository,
+    outbox_mapper,
+};
+use application::{
+    inbound_ports::AccountDetails,
+    no_op_metrics::NoOpMetrics,
+    outbound_ports::{
+        LoadAccountActivityPort, LoadAccountDetailsPort, LoadAccountPort,
+        LoadOverdraftLimitChangePort, LoadTransferLimitsPort, MetricsPort,
+        UpdateAccountDetailsPort, UpdateAccountStatePort, UpdateOverdraftLimitPort,
+        UpdateTransferLimitsPort,
+    },
 };
-use application::outbound_ports::{LoadAccountPort, UpdateAccountStatePort};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use domain::ar::account::{Account, AccountId};
-use log::debug;
+use domain::{
+    ar::{
+        account::{Account, AccountId},
+        activity::{Activity, ActivityId},
+        domain_event::DomainEvent,
+        outbox_message::OutboxMessage,
+        overdraft_limit_change::OverdraftLimitChange,
+    },
+    vo::{
+        currency::Currency,
+        money::Money,
+        transfer_limits::{TransferLimits, TransferVolume},
+    },
+};
+use std::{sync::Arc, time::Instant};
+use tracing::{debug, instrument};
 
 // #[singleton]
 #[derive(Debug)]
 pub struct AccountPersistenceAdapter {
     account_repository: Box<dyn AccountRepository>,
     activity_repository: Box<dyn ActivityRepository>,
+    metrics_port: Arc<dyn MetricsPort>,
 }
 
 impl AccountPersistenceAdapter {
@@ -23,31 +48,37 @@ impl AccountPersistenceAdapter {
         Self {
             account_repository,
             activity_repository,
+            metrics_port: Arc::new(NoOpMetrics {}),
         }
     }
+
+    pub fn with_metrics(mut self, metrics_port: Arc<dyn MetricsPort>) -> Self {
+        self.metrics_port = metrics_port;
+        self
+    }
 }
 
 #[async_trait]
 impl LoadAccountPort for AccountPersistenceAdapter {
-    async fn load_account(
-        &self,
-        account_id: AccountId,
-        baseline_date: NaiveDateTime,
-    ) -> Account {
+    #[instrument(skip_all, fields(account_id = account_id.0, %baseline_date))]
+    async fn load_account(&self, account_id: AccountId, baseline_date: NaiveDateTime) -> Account {
+        let started_at = Instant::now();
         let account = self
             .account_repository
             .find_by_id(account_id.0)
             .await
             .unwrap_or_else(|| panic!("EntityNotFoundException"));
-        debug!("find_by_id(id = {:?}) = {:?}", account_id, account);
+        debug!(id = ?account_id, result = ?account, "find_by_id");
 
         let activities = self
             .activity_repository
             .find_by_owner_since(account_id.0, baseline_date)
             .await;
         debug!(
-            "find_by_owner_since(owner_account_id = {:?}, timestamp = {}) = {:?}",
-            account_id, baseline_date, activities
+            owner_account_id = ?account_id,
+            timestamp = %baseline_date,
+            result = ?activities,
+            "find_by_owner_since",
         );
 
         let withdrawal_balance = self
@@ -56,8 +87,10 @@ impl LoadAccountPort for AccountPersistenceAdapter {
             .await
             .unwrap_or(0);
         debug!(
-            "get_withdrawal_balance_until(account_id = {:?}, until = {}) = {:?}",
-            account_id, baseline_date, withdrawal_balance
+            ?account_id,
+            until = %baseline_date,
+            result = ?withdrawal_balance,
+            "get_withdrawal_balance_until",
         );
 
         let deposit_balance = self
@@ -66,35 +99,288 @@ impl LoadAccountPort for AccountPersistenceAdapter {
             .await
             .unwrap_or(0);
         debug!(
-            "get_deposit_balance_until(account_id = {:?}, until = {}) = {:?}",
-            account_id, baseline_date, deposit_balance
+            ?account_id,
+            until = %baseline_date,
+            result = ?deposit_balance,
+            "get_deposit_balance_until",
         );
 
-        account_mapper::map_to_account(account, activities, withdrawal_balance, deposit_balance)
+        let activity_count = activities.len();
+        let account = account_mapper::map_to_account(
+            account,
+            activities,
+            withdrawal_balance,
+            deposit_balance,
+        );
+        self.metrics_port
+            .record_load_account(started_at.elapsed(), activity_count);
+        account
     }
 }
 
 #[async_trait]
 impl UpdateAccountStatePort for AccountPersistenceAdapter {
     async fn update_activities(&self, account: Account) {
+        let started_at = Instant::now();
         for activity in &account.activity_window.activities {
             if activity.id.is_none() {
                 let ae = account_mapper::map_to_activity_entity(activity);
-                debug!("save(activity_entity = {:?}", ae);
+                debug!(activity_entity = ?ae, "save");
                 self.activity_repository.save(ae).await;
             }
         }
+        self.metrics_port
+            .record_update_activities(started_at.elapsed());
+    }
+
+    async fn update_accounts(&self, accounts: Vec<Account>) {
+        let started_at = Instant::now();
+        let activity_entities = account_mapper::map_to_new_activity_entities(&accounts);
+        debug!(?activity_entities, "save_all");
+        self.activity_repository.save_all(activity_entities).await;
+        self.metrics_port
+            .record_update_activities(started_at.elapsed());
+    }
+
+    async fn update_accounts_and_record_events(
+        &self,
+        accounts: Vec<Account>,
+        domain_events: Vec<DomainEvent>,
+    ) {
+        let started_at = Instant::now();
+        let activity_entities = account_mapper::map_to_new_activity_entities(&accounts);
+        let outbox_message_entities: Vec<_> = domain_events
+            .into_iter()
+            .map(|domain_event| {
+                outbox_mapper::map_to_outbox_message_entity(OutboxMessage::new(domain_event))
+            })
+            .collect();
+        debug!(
+            ?activity_entities,
+            ?outbox_message_entities,
+            "save_all_with_outbox_messages"
+        );
+        self.activity_repository
+            .save_all_with_outbox_messages(activity_entities, outbox_message_entities)
+            .await;
+        self.metrics_port
+            .record_update_activities(started_at.elapsed());
+    }
+}
+
+#[async_trait]
+impl LoadAccountActivityPort for AccountPersistenceAdapter {
+    async fn load_activities_after(
+        &self,
+        account_id: AccountId,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<Activity> {
+        let after_id = after_activity_id.map_or(0, |id| id.0);
+        let activities = self
+            .activity_repository
+            .find_by_owner_after_id(account_id.0, after_id, limit as i64)
+            .await;
+        debug!(
+            owner_account_id = ?account_id,
+            %after_id,
+            %limit,
+            result = ?activities,
+            "find_by_owner_after_id",
+        );
+        activities
+            .iter()
+            .map(account_mapper::map_to_activity)
+            .collect()
+    }
+
+    async fn load_balance_until(
+        &self,
+        account_id: AccountId,
+        activity_id: Option<ActivityId>,
+    ) -> Money {
+        let Some(activity_id) = activity_id else {
+            return Money::of(0);
+        };
+        let balance = self
+            .activity_repository
+            .get_balance_until_id(account_id.0, activity_id.0)
+            .await;
+        debug!(?account_id, until_id = ?activity_id, result = %balance, "get_balance_until_id");
+        Money::of(balance)
+    }
+
+    async fn load_last_activity_id(&self, account_id: AccountId) -> Option<ActivityId> {
+        let last_id = self
+            .activity_repository
+            .find_last_id_by_owner(account_id.0)
+            .await;
+        debug!(owner_account_id = ?account_id, result = ?last_id, "find_last_id_by_owner");
+        last_id.map(ActivityId)
+    }
+
+    async fn load_activities_between(
+        &self,
+        account_id: AccountId,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Vec<Activity> {
+        let activities = self
+            .activity_repository
+            .find_by_owner_between(account_id.0, since, until)
+            .await;
+        debug!(
+            owner_account_id = ?account_id,
+            %since,
+            %until,
+            result = ?activities,
+            "find_by_owner_between",
+        );
+        activities
+            .iter()
+            .map(account_mapper::map_to_activity)
+            .collect()
+    }
+
+    async fn load_balance_before(&self, account_id: AccountId, until: NaiveDateTime) -> Money {
+        let withdrawal_balance = self
+            .activity_repository
+            .get_withdrawal_balance_until(account_id.0, until)
+            .await
+            .unwrap_or(0);
+        debug!(?account_id, %until, result = ?withdrawal_balance, "get_withdrawal_balance_until");
+
+        let deposit_balance = self
+            .activity_repository
+            .get_deposit_balance_until(account_id.0, until)
+            .await
+            .unwrap_or(0);
+        debug!(?account_id, %until, result = ?deposit_balance, "get_deposit_balance_until");
+
+        Money::substract(&Money::of(deposit_balance), &Money::of(withdrawal_balance))
+    }
+}
+
+#[async_trait]
+impl LoadOverdraftLimitChangePort for AccountPersistenceAdapter {
+    async fn load_overdraft_limit_changes(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<OverdraftLimitChange> {
+        let entities = self
+            .account_repository
+            .find_overdraft_limit_changes(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entities, "find_overdraft_limit_changes");
+        entities
+            .into_iter()
+            .map(account_mapper::map_to_overdraft_limit_change)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateOverdraftLimitPort for AccountPersistenceAdapter {
+    async fn update_overdraft_limit(&self, overdraft_limit_change: OverdraftLimitChange) {
+        let entity = account_mapper::map_to_overdraft_limit_change_entity(overdraft_limit_change);
+        debug!(overdraft_limit_change_entity = ?entity, "update_overdraft_limit");
+        self.account_repository.update_overdraft_limit(entity).await;
+    }
+}
+
+#[async_trait]
+impl LoadTransferLimitsPort for AccountPersistenceAdapter {
+    async fn load_transfer_limits(&self, account_id: AccountId) -> TransferLimits {
+        let entity = self
+            .account_repository
+            .find_transfer_limits(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entity, "find_transfer_limits");
+        entity
+            .map(account_mapper::map_to_transfer_limits)
+            .unwrap_or_default()
+    }
+
+    async fn load_outgoing_transfer_volume(
+        &self,
+        account_id: AccountId,
+        since: NaiveDateTime,
+        fee_collection_account_id: Option<AccountId>,
+    ) -> TransferVolume {
+        let (amount, count) = self
+            .activity_repository
+            .get_withdrawal_volume_since(
+                account_id.0,
+                since,
+                fee_collection_account_id.as_ref().map(|id| id.0),
+            )
+            .await;
+        debug!(
+            ?account_id,
+            %since,
+            ?fee_collection_account_id,
+            %amount,
+            %count,
+            "get_withdrawal_volume_since"
+        );
+        account_mapper::map_to_transfer_volume(amount, count)
+    }
+}
+
+#[async_trait]
+impl UpdateTransferLimitsPort for AccountPersistenceAdapter {
+    async fn update_transfer_limits(&self, account_id: AccountId, transfer_limits: TransferLimits) {
+        let entity = account_mapper::map_to_transfer_limits_entity(account_id, transfer_limits);
+        debug!(transfer_limits_entity = ?entity, "update_transfer_limits");
+        self.account_repository.update_transfer_limits(entity).await;
+    }
+}
+
+#[async_trait]
+impl LoadAccountDetailsPort for AccountPersistenceAdapter {
+    async fn load_account_details(&self, account_id: AccountId) -> Option<AccountDetails> {
+        let account = self.account_repository.find_by_id(account_id.0).await;
+        debug!(id = ?account_id, result = ?account, "find_by_id");
+        account.map(account_mapper::map_to_account_details)
+    }
+}
+
+#[async_trait]
+impl UpdateAccountDetailsPort for AccountPersistenceAdapter {
+    async fn open_account(
+        &self,
+        currency: Currency,
+        overdraft_limit: Money,
+        opened_at: NaiveDateTime,
+    ) -> AccountId {
+        let account_entity = account_mapper::map_to_new_account_entity(&currency, &overdraft_limit);
+        debug!(?account_entity, ?opened_at, "insert");
+        AccountId(
+            self.account_repository
+                .insert(account_entity, opened_at)
+                .await,
+        )
+    }
+
+    async fn close_account(&self, account_id: AccountId, closed_at: NaiveDateTime) {
+        debug!(?account_id, ?closed_at, "close");
+        self.account_repository.close(account_id.0, closed_at).await;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{account_repository::AccountEntity, activity_repository::ActivityEntity};
+    use crate::{
+        account_repository::{AccountEntity, OverdraftLimitChangeEntity, TransferLimitsEntity},
+        activity_repository::ActivityEntity,
+        outbox_repository::OutboxMessageEntity,
+    };
     use chrono::{NaiveDate, NaiveTime};
     use domain::{
+        ar::activity::ActivityId,
         testdata::{default_account, default_activity},
-        vo::{activity_window::ActivityWindow, money::Money},
+        vo::{activity_window::ActivityWindow, currency::Currency, money::Money},
     };
     use mockall::{mock, predicate::eq};
 
@@ -104,6 +390,18 @@ mod tests {
         #[async_trait]
         impl AccountRepository for AccountRepositoryImpl {
             async fn find_by_id(&self, id: i64) -> Option<AccountEntity>;
+            async fn insert(&self, account_entity: AccountEntity, opened_at: NaiveDateTime) -> i64;
+            async fn close(&self, account_id: i64, closed_at: NaiveDateTime);
+            async fn update_overdraft_limit(
+                &self,
+                overdraft_limit_change_entity: OverdraftLimitChangeEntity,
+            );
+            async fn find_overdraft_limit_changes(
+                &self,
+                account_id: i64,
+            ) -> Vec<OverdraftLimitChangeEntity>;
+            async fn find_transfer_limits(&self, account_id: i64) -> Option<TransferLimitsEntity>;
+            async fn update_transfer_limits(&self, transfer_limits_entity: TransferLimitsEntity);
         }
     }
 
@@ -127,7 +425,40 @@ mod tests {
                 account_id: i64,
                 until: NaiveDateTime,
             ) -> Option<i128>;
+            async fn get_withdrawal_volume_since(
+                &self,
+                account_id: i64,
+                since: NaiveDateTime,
+                excluded_target_account_id: Option<i64>,
+            ) -> (i128, i64);
+            async fn find_by_owner_between(
+                &self,
+                owner_account_id: i64,
+                since: NaiveDateTime,
+                until: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn find_by_owner_after_id(
+                &self,
+                owner_account_id: i64,
+                after_id: i64,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn find_after_id_between(
+                &self,
+                after_id: i64,
+                since: Option<NaiveDateTime>,
+                until: Option<NaiveDateTime>,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128;
+            async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64>;
             async fn save(&self, activity_entity: ActivityEntity);
+            async fn save_all(&self, activity_entities: Vec<ActivityEntity>);
+            async fn save_all_with_outbox_messages(
+                &self,
+                activity_entities: Vec<ActivityEntity>,
+                outbox_message_entities: Vec<OutboxMessageEntity>,
+            );
         }
     }
 
@@ -144,7 +475,14 @@ mod tests {
         account_repository
             .expect_find_by_id()
             .with(eq(account_id.0))
-            .returning(|id| Some(AccountEntity { id: Some(id) }));
+            .returning(|id| {
+                Some(AccountEntity {
+                    id: Some(id),
+                    currency: "EUR".to_string(),
+                    overdraft_limit: 300,
+                    closed_at: None,
+                })
+            });
 
         let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
         activity_repository
@@ -162,6 +500,9 @@ mod tests {
                         source_account_id: 1,
                         target_account_id: 2,
                         amount: 1000,
+                        exchange_rate_source_currency: None,
+                        exchange_rate_target_currency: None,
+                        exchange_rate: None,
                     },
                     ActivityEntity {
                         id: Some(7),
@@ -173,6 +514,9 @@ mod tests {
                         source_account_id: 2,
                         target_account_id: 1,
                         amount: 1000,
+                        exchange_rate_source_currency: Some("USD".to_string()),
+                        exchange_rate_target_currency: Some("EUR".to_string()),
+                        exchange_rate: Some(922_000),
                     },
                 ]
             });
@@ -195,6 +539,85 @@ mod tests {
         // Then
         assert_eq!(2, account.activity_window.activities.len());
         assert_eq!(Money::of(500), account.calculate_balance());
+        assert_eq!(Money::of(300), account.get_overdraft_limit());
+        assert_eq!(Currency::of("EUR"), account.get_currency());
+        assert_eq!(
+            Some("USD/EUR 0.922000".to_string()),
+            account.activity_window.activities[1]
+                .exchange_rate
+                .as_ref()
+                .map(ToString::to_string)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_opens_and_loads_closed_account() {
+        // Given
+        let opened_at = NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 8, 9).unwrap(),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        );
+        let mut account_repository = Box::new(MockAccountRepositoryImpl::new());
+        account_repository
+            .expect_insert()
+            .times(1)
+            .with(
+                eq(AccountEntity {
+                    id: None,
+                    currency: "USD".to_string(),
+                    overdraft_limit: 500,
+                    closed_at: None,
+                }),
+                eq(opened_at),
+            )
+            .return_const(7);
+        account_repository
+            .expect_find_by_id()
+            .with(eq(7))
+            .returning(move |id| {
+                Some(AccountEntity {
+                    id: Some(id),
+                    currency: "USD".to_string(),
+                    overdraft_limit: 500,
+                    closed_at: Some(opened_at),
+                })
+            });
+        let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
+        activity_repository
+            .expect_find_by_owner_since()
+            .returning(|_, _| vec![]);
+        activity_repository
+            .expect_get_withdrawal_balance_until()
+            .return_const(None);
+        activity_repository
+            .expect_get_deposit_balance_until()
+            .return_const(None);
+
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+
+        // When
+        let account_id = adapter_under_test
+            .open_account(Currency::of("USD"), Money::of(500), opened_at)
+            .await;
+        let account_details = adapter_under_test
+            .load_account_details(account_id.clone())
+            .await;
+        let account = adapter_under_test
+            .load_account(account_id.clone(), opened_at)
+            .await;
+
+        // Then
+        assert_eq!(
+            Some(AccountDetails {
+                account_id: AccountId(7),
+                currency: Currency::of("USD"),
+                overdraft_limit: Money::of(500),
+                closed_at: Some(opened_at),
+            }),
+            account_details
+        );
+        assert!(account.is_closed());
     }
 
     #[tokio::test]
@@ -202,10 +625,12 @@ mod tests {
         // Given
         let account = default_account()
             .with_baseline_balance(Money::of(555))
-            .with_activity_window(ActivityWindow::new(vec![default_activity()
-                .with_id(None)
-                .with_money(Money::of(1))
-                .build()]))
+            .with_activity_window(ActivityWindow::new(vec![
+                default_activity()
+                    .with_id(None)
+                    .with_money(Money::of(1))
+                    .build(),
+            ]))
             .build();
 
         let account_repository = Box::new(MockAccountRepositoryImpl::new());
@@ -217,4 +642,207 @@ mod tests {
             AccountPersistenceAdapter::new(account_repository, activity_repository);
         adapter_under_test.update_activities(account).await;
     }
+
+    #[tokio::test]
+    async fn test_updates_accounts_in_one_unit_of_work() {
+        // Given two accounts with one new and one persisted activity each
+        let accounts: Vec<Account> = [1, 2]
+            .into_iter()
+            .map(|account_id| {
+                default_account()
+                    .with_account_id(AccountId(account_id))
+                    .with_activity_window(ActivityWindow::new(vec![
+                        default_activity()
+                            .with_id(None)
+                            .with_owner_account(AccountId(account_id))
+                            .build(),
+                        default_activity().with_id(Some(ActivityId(5))).build(),
+                    ]))
+                    .build()
+            })
+            .collect();
+
+        // Then only the new activities are saved together
+        let account_repository = Box::new(MockAccountRepositoryImpl::new());
+        let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
+        activity_repository.expect_save().never();
+        activity_repository
+            .expect_save_all()
+            .times(1)
+            .withf(|activity_entities| {
+                activity_entities
+                    .iter()
+                    .map(|ae| ae.owner_account_id)
+                    .eq([1, 2])
+            })
+            .return_const(());
+
+        // When
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+        adapter_under_test.update_accounts(accounts).await;
+    }
+
+    #[tokio::test]
+    async fn test_records_events_together_with_the_new_activities() {
+        // Given an account with a new withdrawal
+        let mut account = default_account()
+            .with_account_id(AccountId(1))
+            .with_baseline_balance(Money::of(555))
+            .build();
+        account.withdraw(Money::of(500), AccountId(2));
+        let domain_events = account.take_domain_events();
+
+        // Then the activity and the event are saved together
+        let account_repository = Box::new(MockAccountRepositoryImpl::new());
+        let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
+        activity_repository.expect_save_all().never();
+        activity_repository
+            .expect_save_all_with_outbox_messages()
+            .times(1)
+            .withf(|activity_entities, outbox_message_entities| {
+                activity_entities.len() == 1
+                    && outbox_message_entities.len() == 1
+                    && outbox_message_entities[0].event_type == "MONEY_WITHDRAWN"
+                    && outbox_message_entities[0].account_id == Some(1)
+                    && outbox_message_entities[0].target_account_id == 2
+                    && outbox_message_entities[0].amount == 500
+                    && outbox_message_entities[0].processed_at.is_none()
+            })
+            .return_const(());
+
+        // When
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+        adapter_under_test
+            .update_accounts_and_record_events(vec![account], domain_events)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_updates_overdraft_limit() {
+        // Given
+        let changed_at = NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 8, 9).unwrap(),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        );
+        let mut account_repository = Box::new(MockAccountRepositoryImpl::new());
+        account_repository
+            .expect_update_overdraft_limit()
+            .times(1)
+            .with(eq(OverdraftLimitChangeEntity {
+                id: None,
+                account_id: 1,
+                previous_limit: 0,
+                new_limit: 5000,
+                changed_by: "alice".to_string(),
+                changed_at,
+            }))
+            .return_const(());
+        let activity_repository = Box::new(MockActivityRepositoryImpl::new());
+
+        // When
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+        adapter_under_test
+            .update_overdraft_limit(OverdraftLimitChange::new(
+                AccountId(1),
+                Money::of(0),
+                Money::of(5000),
+                "alice".to_string(),
+                changed_at,
+            ))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_loads_transfer_limits() {
+        // Given an account which only overrides the daily count
+        let mut account_repository = Box::new(MockAccountRepositoryImpl::new());
+        account_repository
+            .expect_find_transfer_limits()
+            .with(eq(1))
+            .returning(|account_id| {
+                Some(TransferLimitsEntity {
+                    account_id,
+                    daily_amount_limit: None,
+                    monthly_amount_limit: None,
+                    daily_count_limit: Some(5),
+                })
+            });
+        let activity_repository = Box::new(MockActivityRepositoryImpl::new());
+
+        // When
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+        let transfer_limits = adapter_under_test.load_transfer_limits(AccountId(1)).await;
+
+        // Then
+        assert_eq!(TransferLimits::new(None, None, Some(5)), transfer_limits);
+    }
+
+    #[tokio::test]
+    async fn test_loads_activities_after_the_last_seen_one() {
+        // Given
+        let timestamp = NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 8, 9).unwrap(),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        );
+        let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
+        activity_repository
+            .expect_find_by_owner_after_id()
+            .times(1)
+            .with(eq(1), eq(41), eq(100))
+            .returning(move |_, _, _| {
+                vec![ActivityEntity {
+                    id: Some(42),
+                    timestamp,
+                    owner_account_id: 1,
+                    source_account_id: 1,
+                    target_account_id: 2,
+                    amount: 300,
+                    exchange_rate_source_currency: None,
+                    exchange_rate_target_currency: None,
+                    exchange_rate: None,
+                }]
+            });
+        activity_repository
+            .expect_get_balance_until_id()
+            .times(1)
+            .with(eq(1), eq(41))
+            .return_const(1000);
+        let account_repository = Box::new(MockAccountRepositoryImpl::new());
+
+        // When
+        let adapter_under_test =
+            AccountPersistenceAdapter::new(account_repository, activity_repository);
+        let activities = adapter_under_test
+            .load_activities_after(AccountId(1), Some(ActivityId(41)), 100)
+            .await;
+        let balance = adapter_under_test
+            .load_balance_until(AccountId(1), Some(ActivityId(41)))
+            .await;
+
+        // Then
+        assert_eq!(
+            vec![Activity::with_id(
+                Some(ActivityId(42)),
+                AccountId(1),
+                AccountId(1),
+                AccountId(2),
+                timestamp,
+                Money::of(300),
+                None,
+            )],
+            activities
+        );
+        assert_eq!(Money::of(1000), balance);
+        // And nothing is stored before the first activity
+        assert_eq!(
+            Money::of(0),
+            adapter_under_test
+                .load_balance_until(AccountId(1), None)
+                .await
+        );
+    }
 }
@@ -1,9 +1,31 @@
+use crate::{account_event_mapper, account_event_repository};
 use async_trait::async_trait;
-use sqlx::{Row, SqlitePool};
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, Row, SqlitePool};
+use tracing::instrument;
 
 #[async_trait]
 pub trait AccountRepository: Send + Sync + std::fmt::Debug {
     async fn find_by_id(&self, id: i64) -> Option<AccountEntity>;
+    /**
+     * Inserts the account together with the event opening it.
+     * @return the id of the new account.
+     */
+    async fn insert(&self, account_entity: AccountEntity, opened_at: NaiveDateTime) -> i64;
+    /**
+     * Marks the account as closed together with the event closing it.
+     */
+    async fn close(&self, account_id: i64, closed_at: NaiveDateTime);
+    async fn update_overdraft_limit(
+        &self,
+        overdraft_limit_change_entity: OverdraftLimitChangeEntity,
+    );
+    async fn find_overdraft_limit_changes(
+        &self,
+        account_id: i64,
+    ) -> Vec<OverdraftLimitChangeEntity>;
+    async fn find_transfer_limits(&self, account_id: i64) -> Option<TransferLimitsEntity>;
+    async fn update_transfer_limits(&self, transfer_limits_entity: TransferLimitsEntity);
 }
 
 // #[singleton]
@@ -21,10 +43,11 @@ impl AccountRepositoryImpl {
 
 #[async_trait]
 impl AccountRepository for AccountRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
     async fn find_by_id(&self, id: i64) -> Option<AccountEntity> {
         let row = sqlx::query(
             "
-            SELECT id FROM account_entity
+            SELECT id, currency, overdraft_limit, closed_at FROM account_entity
             WHERE id = ?
             ",
         )
@@ -34,14 +57,210 @@ impl AccountRepository for AccountRepositoryImpl {
         if let Ok(row) = row {
             let ae = AccountEntity {
                 id: row.try_get("id").unwrap(),
+                currency: row.try_get("currency").unwrap(),
+                overdraft_limit: row.try_get("overdraft_limit").unwrap(),
+                closed_at: row.try_get("closed_at").unwrap(),
             };
             return Some(ae);
         }
         None
     }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn insert(&self, account_entity: AccountEntity, opened_at: NaiveDateTime) -> i64 {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        let id = sqlx::query(
+            "
+            INSERT INTO account_entity (currency, overdraft_limit)
+            VALUES (?, ?)
+            ",
+        )
+        .bind(&account_entity.currency)
+        .bind(account_entity.overdraft_limit)
+        .execute(&mut *tx)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+        account_event_repository::append_account_event(
+            &mut tx,
+            &account_event_mapper::map_to_account_opened_event_entity(
+                &AccountEntity {
+                    id: Some(id),
+                    ..account_entity
+                },
+                opened_at,
+            ),
+        )
+        .await;
+        tx.commit().await.unwrap();
+        id
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn close(&self, account_id: i64, closed_at: NaiveDateTime) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        sqlx::query(
+            "
+            UPDATE account_entity
+            SET closed_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(closed_at)
+        .bind(account_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        account_event_repository::append_account_event(
+            &mut tx,
+            &account_event_mapper::map_to_account_closed_event_entity(account_id, closed_at),
+        )
+        .await;
+        tx.commit().await.unwrap();
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn update_overdraft_limit(
+        &self,
+        overdraft_limit_change_entity: OverdraftLimitChangeEntity,
+    ) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        account_event_repository::append_account_event(
+            &mut tx,
+            &account_event_mapper::map_to_overdraft_limit_changed_event_entity(
+                &overdraft_limit_change_entity,
+            ),
+        )
+        .await;
+        sqlx::query(
+            "
+            UPDATE account_entity
+            SET overdraft_limit = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(overdraft_limit_change_entity.new_limit)
+        .bind(overdraft_limit_change_entity.account_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        sqlx::query(
+            "
+            INSERT INTO overdraft_limit_change_entity (account_id, previous_limit, new_limit, changed_by, changed_at)
+            VALUES (?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(overdraft_limit_change_entity.account_id)
+        .bind(overdraft_limit_change_entity.previous_limit)
+        .bind(overdraft_limit_change_entity.new_limit)
+        .bind(overdraft_limit_change_entity.changed_by)
+        .bind(overdraft_limit_change_entity.changed_at)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_overdraft_limit_changes(
+        &self,
+        account_id: i64,
+    ) -> Vec<OverdraftLimitChangeEntity> {
+        let rows = sqlx::query_as::<_, OverdraftLimitChangeEntity>(
+            "
+            SELECT * FROM overdraft_limit_change_entity
+            WHERE account_id = ?
+            ORDER BY changed_at, id
+            ",
+        )
+        .bind(account_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_transfer_limits(&self, account_id: i64) -> Option<TransferLimitsEntity> {
+        sqlx::query_as::<_, TransferLimitsEntity>(
+            "
+            SELECT id AS account_id, daily_amount_limit, monthly_amount_limit, daily_count_limit
+            FROM account_entity
+            WHERE id = ?
+            ",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn update_transfer_limits(&self, transfer_limits_entity: TransferLimitsEntity) {
+        sqlx::query(
+            "
+            UPDATE account_entity
+            SET daily_amount_limit = ?, monthly_amount_limit = ?, daily_count_limit = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(transfer_limits_entity.daily_amount_limit)
+        .bind(transfer_limits_entity.monthly_amount_limit)
+        .bind(transfer_limits_entity.daily_count_limit)
+        .bind(transfer_limits_entity.account_id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+/**
+ * @return those of the ids which belong to an account, ordered by id.
+ */
+pub(crate) async fn find_existing_account_ids(
+    db_pool: &SqlitePool,
+    account_ids: Vec<i64>,
+) -> Vec<i64> {
+    let sql = format!(
+        "SELECT id FROM account_entity WHERE id IN ({}) ORDER BY id",
+        vec!["?"; account_ids.len()].join(", ")
+    );
+    let mut query = sqlx::query_scalar::<_, i64>(&sql);
+    for account_id in account_ids {
+        query = query.bind(account_id);
+    }
+    let rows = query.fetch_all(db_pool).await;
+    if let Ok(rows) = rows {
+        return rows;
+    }
+    vec![]
 }
 
 #[derive(PartialEq, Hash, Debug)]
 pub struct AccountEntity {
     pub id: Option<i64>,
+    pub currency: String,
+    pub overdraft_limit: i64,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct OverdraftLimitChangeEntity {
+    pub id: Option<i64>,
+    pub account_id: i64,
+    pub previous_limit: i64,
+    pub new_limit: i64,
+    pub changed_by: String,
+    pub changed_at: NaiveDateTime,
+}
+
+/// Limits which are not set fall back to the default transfer limits.
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct TransferLimitsEntity {
+    pub account_id: i64,
+    pub daily_amount_limit: Option<i64>,
+    pub monthly_amount_limit: Option<i64>,
+    pub daily_count_limit: Option<i64>,
 }
@@ -1,6 +1,11 @@
+use crate::{
+    account_event_mapper, account_event_repository,
+    outbox_repository::{self, OutboxMessageEntity},
+};
 use async_trait::async_trait;
 use chrono::NaiveDateTime;
-use sqlx::{FromRow, Row, SqlitePool};
+use sqlx::{FromRow, Row, SqliteConnection, SqlitePool};
+use tracing::instrument;
 
 #[async_trait]
 pub trait ActivityRepository: Send + Sync + std::fmt::Debug {
@@ -19,7 +24,67 @@ pub trait ActivityRepository: Send + Sync + std::fmt::Debug {
         account_id: i64,
         until: NaiveDateTime,
     ) -> Option<i128>;
+    /**
+     * Sums and counts the withdrawals of the account since `since`, except those to the
+     * excluded target account.
+     */
+    async fn get_withdrawal_volume_since(
+        &self,
+        account_id: i64,
+        since: NaiveDateTime,
+        excluded_target_account_id: Option<i64>,
+    ) -> (i128, i64);
+    /**
+     * Finds the activities of the owner from `since` (inclusive) to `until` (exclusive), ordered
+     * by timestamp and id.
+     */
+    async fn find_by_owner_between(
+        &self,
+        owner_account_id: i64,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Vec<ActivityEntity>;
+    /**
+     * Finds at most `limit` activities of the owner stored after the given id, ordered by id.
+     */
+    async fn find_by_owner_after_id(
+        &self,
+        owner_account_id: i64,
+        after_id: i64,
+        limit: i64,
+    ) -> Vec<ActivityEntity>;
+    /**
+     * Finds at most `limit` activities of all accounts stored after the given id from `since`
+     * (inclusive) to `until` (exclusive), ordered by id. A missing bound is not applied.
+     */
+    async fn find_after_id_between(
+        &self,
+        after_id: i64,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+        limit: i64,
+    ) -> Vec<ActivityEntity>;
+    /**
+     * @return the deposits minus the withdrawals of the account up to and including the given id.
+     */
+    async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128;
+    /**
+     * @return the id of the last activity of the owner, if any.
+     */
+    async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64>;
     async fn save(&self, activity_entity: ActivityEntity);
+    /**
+     * Saves all activities in a single transaction, either all of them are stored or none.
+     */
+    async fn save_all(&self, activity_entities: Vec<ActivityEntity>);
+    /**
+     * Saves all activities and the outbox messages describing them in a single transaction.
+     */
+    async fn save_all_with_outbox_messages(
+        &self,
+        activity_entities: Vec<ActivityEntity>,
+        outbox_message_entities: Vec<OutboxMessageEntity>,
+    );
 }
 
 // #[singleton]
@@ -37,6 +102,7 @@ impl ActivityRepositoryImpl {
 
 #[async_trait]
 impl ActivityRepository for ActivityRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
     async fn find_by_owner_since(
         &self,
         owner_account_id: i64,
@@ -59,6 +125,7 @@ impl ActivityRepository for ActivityRepositoryImpl {
         vec![]
     }
 
+    #[instrument(level = "debug", skip_all)]
     async fn get_deposit_balance_until(
         &self,
         account_id: i64,
@@ -84,6 +151,7 @@ impl ActivityRepository for ActivityRepositoryImpl {
         None
     }
 
+    #[instrument(level = "debug", skip_all)]
     async fn get_withdrawal_balance_until(
         &self,
         account_id: i64,
@@ -109,22 +177,221 @@ impl ActivityRepository for ActivityRepositoryImpl {
         None
     }
 
-    async fn save(&self, activity_entity: ActivityEntity) {
-        sqlx::query(
+    #[instrument(level = "debug", skip_all)]
+    async fn get_withdrawal_volume_since(
+        &self,
+        account_id: i64,
+        since: NaiveDateTime,
+        excluded_target_account_id: Option<i64>,
+    ) -> (i128, i64) {
+        // no target is excluded by NULL
+        let row = sqlx::query(
+            "
+            SELECT COALESCE(SUM(amount), 0) AS amount, COUNT(*) AS count FROM activity_entity
+            WHERE source_account_id = ?
+            AND owner_account_id = ?
+            AND timestamp >= ?
+            AND target_account_id IS NOT ?
+            ",
+        )
+        .bind(account_id)
+        .bind(account_id)
+        .bind(since)
+        .bind(excluded_target_account_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .unwrap();
+        let amount: i64 = row.try_get("amount").unwrap();
+        let count: i64 = row.try_get("count").unwrap();
+        (amount as i128, count)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_owner_between(
+        &self,
+        owner_account_id: i64,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Vec<ActivityEntity> {
+        let rows = sqlx::query_as::<_, ActivityEntity>(
+            "
+            SELECT * FROM activity_entity
+            WHERE owner_account_id = ?
+            AND timestamp >= ?
+            AND timestamp < ?
+            ORDER BY timestamp, id
+            ",
+        )
+        .bind(owner_account_id)
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_owner_after_id(
+        &self,
+        owner_account_id: i64,
+        after_id: i64,
+        limit: i64,
+    ) -> Vec<ActivityEntity> {
+        let rows = sqlx::query_as::<_, ActivityEntity>(
+            "
+            SELECT * FROM activity_entity
+            WHERE owner_account_id = ?
+            AND id > ?
+            ORDER BY id
+            LIMIT ?
+            ",
+        )
+        .bind(owner_account_id)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_after_id_between(
+        &self,
+        after_id: i64,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+        limit: i64,
+    ) -> Vec<ActivityEntity> {
+        let rows = sqlx::query_as::<_, ActivityEntity>(
             "
-            INSERT INTO activity_entity (timestamp, owner_account_id, source_account_id, target_account_id, amount)
-            VALUES (?, ?, ?, ?, ?)
+            SELECT * FROM activity_entity
+            WHERE id > ?
+            AND (? IS NULL OR timestamp >= ?)
+            AND (? IS NULL OR timestamp < ?)
+            ORDER BY id
+            LIMIT ?
             ",
         )
-        .bind(activity_entity.timestamp)
-        .bind(activity_entity.owner_account_id)
-        .bind(activity_entity.source_account_id)
-        .bind(activity_entity.target_account_id)
-        .bind(activity_entity.amount)
-        .execute(&self.db_pool)
+        .bind(after_id)
+        .bind(since)
+        .bind(since)
+        .bind(until)
+        .bind(until)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128 {
+        let row = sqlx::query(
+            "
+            SELECT COALESCE(SUM(CASE WHEN target_account_id = ? THEN amount ELSE 0 END), 0)
+                - COALESCE(SUM(CASE WHEN source_account_id = ? THEN amount ELSE 0 END), 0) AS balance
+            FROM activity_entity
+            WHERE owner_account_id = ?
+            AND id <= ?
+            ",
+        )
+        .bind(account_id)
+        .bind(account_id)
+        .bind(account_id)
+        .bind(until_id)
+        .fetch_one(&self.db_pool)
         .await
         .unwrap();
+        let balance: i64 = row.try_get("balance").unwrap();
+        balance as i128
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64> {
+        sqlx::query_scalar::<_, Option<i64>>(
+            "
+            SELECT MAX(id) FROM activity_entity
+            WHERE owner_account_id = ?
+            ",
+        )
+        .bind(owner_account_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .unwrap()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save(&self, activity_entity: ActivityEntity) {
+        let mut connection = self.db_pool.acquire().await.unwrap();
+        insert_activity(&mut connection, &activity_entity).await;
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_all(&self, activity_entities: Vec<ActivityEntity>) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        for activity_entity in activity_entities {
+            insert_activity(&mut tx, &activity_entity).await;
+        }
+        tx.commit().await.unwrap();
     }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_all_with_outbox_messages(
+        &self,
+        activity_entities: Vec<ActivityEntity>,
+        outbox_message_entities: Vec<OutboxMessageEntity>,
+    ) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        for activity_entity in activity_entities {
+            insert_activity(&mut tx, &activity_entity).await;
+        }
+        for outbox_message_entity in outbox_message_entities {
+            outbox_repository::insert_outbox_message(&mut tx, &outbox_message_entity).await;
+        }
+        tx.commit().await.unwrap();
+    }
+}
+
+/**
+ * Inserts a new activity on the connection, which may be part of a larger transaction.
+ * The activity is appended to the event stream of its owner on the same connection.
+ */
+pub(crate) async fn insert_activity(
+    connection: &mut SqliteConnection,
+    activity_entity: &ActivityEntity,
+) {
+    let activity_id = sqlx::query(
+        "
+        INSERT INTO activity_entity (timestamp, owner_account_id, source_account_id, target_account_id, amount,
+            exchange_rate_source_currency, exchange_rate_target_currency, exchange_rate)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(activity_entity.timestamp)
+    .bind(activity_entity.owner_account_id)
+    .bind(activity_entity.source_account_id)
+    .bind(activity_entity.target_account_id)
+    .bind(activity_entity.amount)
+    .bind(&activity_entity.exchange_rate_source_currency)
+    .bind(&activity_entity.exchange_rate_target_currency)
+    .bind(activity_entity.exchange_rate)
+    .execute(&mut *connection)
+    .await
+    .unwrap()
+    .last_insert_rowid();
+    account_event_repository::append_account_event(
+        connection,
+        &account_event_mapper::map_to_money_event_entity(activity_id, activity_entity),
+    )
+    .await;
 }
 
 #[derive(FromRow, PartialEq, Hash, Debug)]
@@ -135,4 +402,8 @@ pub struct ActivityEntity {
     pub source_account_id: i64,
     pub target_account_id: i64,
     pub amount: i64,
+    // the rate in millionths at which the money was exchanged, if it was exchanged
+    pub exchange_rate_source_currency: Option<String>,
+    pub exchange_rate_target_currency: Option<String>,
+    pub exchange_rate: Option<i64>,
 }
@@ -0,0 +1,406 @@
+use crate::{
+    account_event_mapper::{
+        self, ACCOUNT_CLOSED, ACCOUNT_OPENED, MONEY_DEPOSITED, MONEY_WITHDRAWN,
+        OVERDRAFT_LIMIT_CHANGED,
+    },
+    account_event_repository::{AccountEventRepository, AccountSnapshotEntity},
+    account_mapper,
+    account_repository::AccountEntity,
+    activity_repository::ActivityRepository,
+    outbox_mapper,
+};
+use application::outbound_ports::{LoadAccountPort, UpdateAccountStatePort};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::{
+    account::{Account, AccountId},
+    domain_event::DomainEvent,
+    outbox_message::OutboxMessage,
+};
+use tracing::{debug, instrument};
+
+/**
+ * Stores accounts as streams of events and rebuilds them by replaying the events.
+ * The events before the baseline date are folded into a snapshot once there are at least
+ * `snapshot_interval` of them, so that later loads only replay the events after the snapshot.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct EventSourcedAccountPersistenceAdapter {
+    account_event_repository: Box<dyn AccountEventRepository>,
+    activity_repository: Box<dyn ActivityRepository>,
+    snapshot_interval: usize,
+}
+
+impl EventSourcedAccountPersistenceAdapter {
+    // #[inject]
+    pub fn new(
+        account_event_repository: Box<dyn AccountEventRepository>,
+        activity_repository: Box<dyn ActivityRepository>,
+        snapshot_interval: usize,
+    ) -> Self {
+        Self {
+            account_event_repository,
+            activity_repository,
+            snapshot_interval,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadAccountPort for EventSourcedAccountPersistenceAdapter {
+    #[instrument(skip_all, fields(account_id = account_id.0, %baseline_date))]
+    async fn load_account(&self, account_id: AccountId, baseline_date: NaiveDateTime) -> Account {
+        // a snapshot taken after the baseline date contains activities of the window
+        let snapshot = self
+            .account_event_repository
+            .find_snapshot(account_id.0)
+            .await
+            .filter(|snapshot| snapshot.last_timestamp < baseline_date);
+        debug!(?account_id, result = ?snapshot, "find_snapshot");
+
+        let after_event_id = snapshot
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.last_event_id);
+        let events = self
+            .account_event_repository
+            .find_events_after(account_id.0, after_event_id)
+            .await;
+        debug!(?account_id, %after_event_id, result = ?events, "find_events_after");
+
+        let mut state = snapshot.clone();
+        let mut activities = Vec::new();
+        // the state including all events before the baseline date, up to the first event after it
+        let mut snapshot_state = snapshot;
+        let mut snapshot_events = 0;
+        for event in &events {
+            let state = state.get_or_insert_with(|| AccountSnapshotEntity {
+                account_id: account_id.0,
+                last_event_id: 0,
+                last_timestamp: event.timestamp,
+                currency: String::new(),
+                overdraft_limit: 0,
+                withdrawal_balance: 0,
+                deposit_balance: 0,
+                closed_at: None,
+            });
+            match event.event_type.as_str() {
+                ACCOUNT_OPENED => {
+                    state.currency = event.currency.clone().unwrap();
+                    state.overdraft_limit = event.overdraft_limit.unwrap();
+                }
+                OVERDRAFT_LIMIT_CHANGED => state.overdraft_limit = event.overdraft_limit.unwrap(),
+                ACCOUNT_CLOSED => state.closed_at = Some(event.timestamp),
+                MONEY_WITHDRAWN if event.timestamp < baseline_date => {
+                    state.withdrawal_balance += event.amount.unwrap()
+                }
+                MONEY_DEPOSITED if event.timestamp < baseline_date => {
+                    state.deposit_balance += event.amount.unwrap()
+                }
+                MONEY_WITHDRAWN | MONEY_DEPOSITED => {
+                    activities.push(account_event_mapper::map_to_activity_entity(event))
+                }
+                event_type => panic!("unknown account event type: {}", event_type),
+            }
+            if activities.is_empty() && event.timestamp < baseline_date {
+                state.last_event_id = event.id.unwrap();
+                state.last_timestamp = state.last_timestamp.max(event.timestamp);
+                snapshot_state = Some(state.clone());
+                snapshot_events += 1;
+            }
+        }
+
+        let state = state.unwrap_or_else(|| panic!("EntityNotFoundException"));
+        if let Some(snapshot_state) =
+            snapshot_state.filter(|_| snapshot_events >= self.snapshot_interval)
+        {
+            debug!(account_snapshot_entity = ?snapshot_state, "save_snapshot");
+            self.account_event_repository
+                .save_snapshot(snapshot_state)
+                .await;
+        }
+
+        account_mapper::map_to_account(
+            AccountEntity {
+                id: Some(account_id.0),
+                currency: state.currency,
+                overdraft_limit: state.overdraft_limit,
+                closed_at: state.closed_at,
+            },
+            activities,
+            state.withdrawal_balance as i128,
+            state.deposit_balance as i128,
+        )
+    }
+}
+
+#[async_trait]
+impl UpdateAccountStatePort for EventSourcedAccountPersistenceAdapter {
+    async fn update_activities(&self, account: Account) {
+        // the activities are appended to the event stream of their owner when they are saved
+        for activity in &account.activity_window.activities {
+            if activity.id.is_none() {
+                let ae = account_mapper::map_to_activity_entity(activity);
+                debug!(activity_entity = ?ae, "save");
+                self.activity_repository.save(ae).await;
+            }
+        }
+    }
+
+    async fn update_accounts(&self, accounts: Vec<Account>) {
+        let activity_entities = account_mapper::map_to_new_activity_entities(&accounts);
+        debug!(?activity_entities, "save_all");
+        self.activity_repository.save_all(activity_entities).await;
+    }
+
+    async fn update_accounts_and_record_events(
+        &self,
+        accounts: Vec<Account>,
+        domain_events: Vec<DomainEvent>,
+    ) {
+        let activity_entities = account_mapper::map_to_new_activity_entities(&accounts);
+        let outbox_message_entities: Vec<_> = domain_events
+            .into_iter()
+            .map(|domain_event| {
+                outbox_mapper::map_to_outbox_message_entity(OutboxMessage::new(domain_event))
+            })
+            .collect();
+        debug!(
+            ?activity_entities,
+            ?outbox_message_entities,
+            "save_all_with_outbox_messages"
+        );
+        self.activity_repository
+            .save_all_with_outbox_messages(activity_entities, outbox_message_entities)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_event_repository::AccountEventEntity, activity_repository::ActivityEntity,
+        outbox_repository::OutboxMessageEntity,
+    };
+    use chrono::NaiveDate;
+    use domain::vo::{currency::Currency, money::Money};
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        AccountEventRepositoryImpl {}
+        #[async_trait]
+        impl AccountEventRepository for AccountEventRepositoryImpl {
+            async fn find_events_after(
+                &self,
+                account_id: i64,
+                after_event_id: i64,
+            ) -> Vec<AccountEventEntity>;
+            async fn find_snapshot(&self, account_id: i64) -> Option<AccountSnapshotEntity>;
+            async fn save_snapshot(&self, account_snapshot_entity: AccountSnapshotEntity);
+        }
+    }
+
+    mock! {
+        #[derive(Debug)]
+        ActivityRepositoryImpl {}
+        #[async_trait]
+        impl ActivityRepository for ActivityRepositoryImpl {
+            async fn find_by_owner_since(
+                &self,
+                owner_account_id: i64,
+                timestamp: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn get_deposit_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_volume_since(
+                &self,
+                account_id: i64,
+                since: NaiveDateTime,
+                excluded_target_account_id: Option<i64>,
+            ) -> (i128, i64);
+            async fn find_by_owner_between(
+                &self,
+                owner_account_id: i64,
+                since: NaiveDateTime,
+                until: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn find_by_owner_after_id(
+                &self,
+                owner_account_id: i64,
+                after_id: i64,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn find_after_id_between(
+                &self,
+                after_id: i64,
+                since: Option<NaiveDateTime>,
+                until: Option<NaiveDateTime>,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128;
+            async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64>;
+            async fn save(&self, activity_entity: ActivityEntity);
+            async fn save_all(&self, activity_entities: Vec<ActivityEntity>);
+            async fn save_all_with_outbox_messages(
+                &self,
+                activity_entities: Vec<ActivityEntity>,
+                outbox_message_entities: Vec<OutboxMessageEntity>,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replays_events_and_snapshots_those_before_the_baseline_date() {
+        // Given an account opened with a deposit and a limit change before the baseline date
+        // And a withdrawal after it
+        let account_id = AccountId(1);
+        let mut account_event_repository = Box::new(MockAccountEventRepositoryImpl::new());
+        account_event_repository
+            .expect_find_snapshot()
+            .with(eq(1))
+            .returning(|_| None);
+        account_event_repository
+            .expect_find_events_after()
+            .with(eq(1), eq(0))
+            .returning(|_, _| {
+                vec![
+                    event(1, ACCOUNT_OPENED, timestamp(8, 9)),
+                    money_event(2, MONEY_DEPOSITED, timestamp(8, 10), 1000),
+                    event(3, OVERDRAFT_LIMIT_CHANGED, timestamp(8, 11)),
+                    money_event(4, MONEY_WITHDRAWN, timestamp(8, 20), 300),
+                ]
+            });
+        // Then the events before the baseline date are snapshotted
+        account_event_repository
+            .expect_save_snapshot()
+            .times(1)
+            .with(eq(AccountSnapshotEntity {
+                account_id: 1,
+                last_event_id: 3,
+                last_timestamp: timestamp(8, 11),
+                currency: "EUR".to_string(),
+                overdraft_limit: 200,
+                withdrawal_balance: 0,
+                deposit_balance: 1000,
+                closed_at: None,
+            }))
+            .return_const(());
+        let activity_repository = Box::new(MockActivityRepositoryImpl::new());
+
+        // When
+        let adapter_under_test = EventSourcedAccountPersistenceAdapter::new(
+            account_event_repository,
+            activity_repository,
+            3,
+        );
+        let account = adapter_under_test
+            .load_account(account_id, timestamp(8, 15))
+            .await;
+
+        // Then
+        assert_eq!(1, account.activity_window.activities.len());
+        assert_eq!(Money::of(700), account.calculate_balance());
+        assert_eq!(Money::of(200), account.get_overdraft_limit());
+        assert_eq!(Currency::of("EUR"), account.get_currency());
+    }
+
+    #[tokio::test]
+    async fn test_replays_events_after_the_snapshot() {
+        // Given a snapshot before the baseline date
+        let account_id = AccountId(1);
+        let mut account_event_repository = Box::new(MockAccountEventRepositoryImpl::new());
+        account_event_repository
+            .expect_find_snapshot()
+            .with(eq(1))
+            .returning(|account_id| {
+                Some(AccountSnapshotEntity {
+                    account_id,
+                    last_event_id: 3,
+                    last_timestamp: timestamp(8, 11),
+                    currency: "EUR".to_string(),
+                    overdraft_limit: 200,
+                    withdrawal_balance: 0,
+                    deposit_balance: 1000,
+                    closed_at: None,
+                })
+            });
+        // And a withdrawal after it
+        account_event_repository
+            .expect_find_events_after()
+            .with(eq(1), eq(3))
+            .returning(|_, _| vec![money_event(4, MONEY_WITHDRAWN, timestamp(8, 20), 300)]);
+        account_event_repository.expect_save_snapshot().never();
+        let activity_repository = Box::new(MockActivityRepositoryImpl::new());
+
+        // When
+        let adapter_under_test = EventSourcedAccountPersistenceAdapter::new(
+            account_event_repository,
+            activity_repository,
+            3,
+        );
+        let account = adapter_under_test
+            .load_account(account_id, timestamp(8, 15))
+            .await;
+
+        // Then
+        assert_eq!(1, account.activity_window.activities.len());
+        assert_eq!(Money::of(700), account.calculate_balance());
+        assert_eq!(Money::of(200), account.get_overdraft_limit());
+    }
+
+    fn event(id: i64, event_type: &str, timestamp: NaiveDateTime) -> AccountEventEntity {
+        AccountEventEntity {
+            id: Some(id),
+            account_id: 1,
+            event_type: event_type.to_string(),
+            timestamp,
+            currency: (event_type == ACCOUNT_OPENED).then(|| "EUR".to_string()),
+            overdraft_limit: Some(if event_type == ACCOUNT_OPENED { 0 } else { 200 }),
+            activity_id: None,
+            counterpart_account_id: None,
+            amount: None,
+            exchange_rate_source_currency: None,
+            exchange_rate_target_currency: None,
+            exchange_rate: None,
+        }
+    }
+
+    fn money_event(
+        id: i64,
+        event_type: &str,
+        timestamp: NaiveDateTime,
+        amount: i64,
+    ) -> AccountEventEntity {
+        AccountEventEntity {
+            id: Some(id),
+            account_id: 1,
+            event_type: event_type.to_string(),
+            timestamp,
+            currency: None,
+            overdraft_limit: None,
+            activity_id: Some(id * 10),
+            counterpart_account_id: Some(2),
+            amount: Some(amount),
+            exchange_rate_source_currency: None,
+            exchange_rate_target_currency: None,
+            exchange_rate: None,
+        }
+    }
+
+    fn timestamp(month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2018, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::health_repository::HealthRepository;
+use application::outbound_ports::CheckDatabasePort;
+use async_trait::async_trait;
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct HealthPersistenceAdapter {
+    health_repository: Box<dyn HealthRepository>,
+    migration_versions: Vec<i64>,
+}
+
+impl HealthPersistenceAdapter {
+    /**
+     * # Arguments
+     *
+     * * `migration_versions` - The versions of the migrations embedded in this build.
+     */
+    // #[inject]
+    pub fn new(health_repository: Box<dyn HealthRepository>, migration_versions: Vec<i64>) -> Self {
+        Self {
+            health_repository,
+            migration_versions,
+        }
+    }
+}
+
+#[async_trait]
+impl CheckDatabasePort for HealthPersistenceAdapter {
+    async fn ping_database(&self) -> Result<(), String> {
+        let result = self.health_repository.ping().await;
+        debug!(?result, "ping");
+        result
+    }
+
+    async fn load_pending_migrations(&self) -> Result<Vec<i64>, String> {
+        let applied_migration_versions = self
+            .health_repository
+            .find_applied_migration_versions()
+            .await;
+        debug!(result = ?applied_migration_versions, "find_applied_migration_versions");
+        let applied_migration_versions = applied_migration_versions?;
+        Ok(self
+            .migration_versions
+            .iter()
+            .filter(|version| !applied_migration_versions.contains(version))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::mock;
+
+    mock! {
+        #[derive(Debug)]
+        HealthRepositoryImpl {}
+        #[async_trait]
+        impl HealthRepository for HealthRepositoryImpl {
+            async fn ping(&self) -> Result<(), String>;
+            async fn find_applied_migration_versions(&self) -> Result<Vec<i64>, String>;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_pending_migrations() {
+        // Given a database which lacks the latest migration of this build
+        let mut health_repository = MockHealthRepositoryImpl::new();
+        health_repository
+            .expect_find_applied_migration_versions()
+            .times(1)
+            .returning(|| Ok(vec![20260101000000, 20260201000000]));
+        let health_persistence_adapter = HealthPersistenceAdapter::new(
+            Box::new(health_repository),
+            vec![20260101000000, 20260201000000, 20260301000000],
+        );
+
+        // When
+        let pending_migrations = health_persistence_adapter.load_pending_migrations().await;
+
+        // Then
+        assert_eq!(pending_migrations, Ok(vec![20260301000000]));
+    }
+}
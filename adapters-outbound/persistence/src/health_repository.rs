@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use tracing::instrument;
+
+#[async_trait]
+pub trait HealthRepository: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the error of a trivial query, if any.
+     */
+    async fn ping(&self) -> Result<(), String>;
+    /**
+     * @return the versions of the migrations which have been applied successfully.
+     */
+    async fn find_applied_migration_versions(&self) -> Result<Vec<i64>, String>;
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct HealthRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl HealthRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl HealthRepository for HealthRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn ping(&self) -> Result<(), String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.db_pool)
+            .await
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_applied_migration_versions(&self) -> Result<Vec<i64>, String> {
+        sqlx::query_scalar::<_, i64>(
+            "
+            SELECT version
+            FROM _sqlx_migrations
+            WHERE success = TRUE
+            ORDER BY version
+            ",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(|error| error.to_string())
+    }
+}
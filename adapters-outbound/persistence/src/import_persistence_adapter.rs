@@ -0,0 +1,188 @@
+use crate::{
+    account_mapper,
+    import_repository::{ImportProgressEntity, ImportRepository},
+};
+use application::{
+    inbound_ports::ImportedAccount,
+    outbound_ports::{LoadImportPort, UpdateImportStatePort},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::{account::AccountId, activity::Activity};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ImportPersistenceAdapter {
+    import_repository: Box<dyn ImportRepository>,
+}
+
+impl ImportPersistenceAdapter {
+    // #[inject]
+    pub fn new(import_repository: Box<dyn ImportRepository>) -> Self {
+        Self { import_repository }
+    }
+}
+
+#[async_trait]
+impl LoadImportPort for ImportPersistenceAdapter {
+    async fn load_import_progress(&self, source: String) -> usize {
+        let entity = self.import_repository.find_progress(source.clone()).await;
+        debug!(?source, result = ?entity, "find_progress");
+        entity.map_or(0, |entity| entity.last_line_number as usize)
+    }
+
+    async fn load_existing_account_ids(&self, account_ids: Vec<AccountId>) -> Vec<AccountId> {
+        let ids = account_ids.iter().map(|account_id| account_id.0).collect();
+        let existing_ids = self.import_repository.find_existing_account_ids(ids).await;
+        debug!(?account_ids, result = ?existing_ids, "find_existing_account_ids");
+        existing_ids.into_iter().map(AccountId).collect()
+    }
+}
+
+#[async_trait]
+impl UpdateImportStatePort for ImportPersistenceAdapter {
+    async fn save_import_batch(
+        &self,
+        source: String,
+        last_line_number: usize,
+        accounts: Vec<ImportedAccount>,
+        activities: Vec<Activity>,
+        opened_at: NaiveDateTime,
+    ) {
+        let import_progress_entity = ImportProgressEntity {
+            source,
+            last_line_number: last_line_number as i64,
+            updated_at: opened_at,
+        };
+        let account_entities = accounts
+            .iter()
+            .map(account_mapper::map_to_imported_account_entity)
+            .collect::<Vec<_>>();
+        let activity_entities = activities
+            .iter()
+            .map(account_mapper::map_to_activity_entity)
+            .collect::<Vec<_>>();
+        debug!(?import_progress_entity, ?account_entities, ?activity_entities, "save_batch");
+        self.import_repository
+            .save_batch(import_progress_entity, account_entities, activity_entities)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account_repository::AccountEntity, activity_repository::ActivityEntity};
+    use chrono::NaiveDate;
+    use domain::vo::{currency::Currency, money::Money};
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        ImportRepositoryImpl {}
+        #[async_trait]
+        impl ImportRepository for ImportRepositoryImpl {
+            async fn find_progress(&self, source: String) -> Option<ImportProgressEntity>;
+            async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64>;
+            async fn save_batch(
+                &self,
+                import_progress_entity: ImportProgressEntity,
+                account_entities: Vec<AccountEntity>,
+                activity_entities: Vec<ActivityEntity>,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_batch() {
+        // Given the first 10 lines have been imported and account 1 exists
+        let mut repository = Box::new(MockImportRepositoryImpl::new());
+        repository
+            .expect_find_progress()
+            .times(1)
+            .with(eq("legacy.csv".to_string()))
+            .returning(|source| {
+                Some(ImportProgressEntity {
+                    source,
+                    last_line_number: 10,
+                    updated_at: timestamp(),
+                })
+            });
+        repository
+            .expect_find_existing_account_ids()
+            .times(1)
+            .with(eq(vec![1, 5]))
+            .returning(|_| vec![1]);
+        repository
+            .expect_save_batch()
+            .times(1)
+            .with(
+                eq(ImportProgressEntity {
+                    source: "legacy.csv".to_string(),
+                    last_line_number: 12,
+                    updated_at: timestamp(),
+                }),
+                eq(vec![AccountEntity {
+                    id: Some(5),
+                    currency: "USD".to_string(),
+                    overdraft_limit: 200,
+                    closed_at: None,
+                }]),
+                eq(vec![ActivityEntity {
+                    id: None,
+                    timestamp: timestamp(),
+                    owner_account_id: 5,
+                    source_account_id: 1,
+                    target_account_id: 5,
+                    amount: 500,
+                    exchange_rate_source_currency: None,
+                    exchange_rate_target_currency: None,
+                    exchange_rate: None,
+                }]),
+            )
+            .return_const(());
+
+        let adapter_under_test = ImportPersistenceAdapter::new(repository);
+
+        // When
+        let progress = adapter_under_test
+            .load_import_progress("legacy.csv".to_string())
+            .await;
+        let existing_account_ids = adapter_under_test
+            .load_existing_account_ids(vec![AccountId(1), AccountId(5)])
+            .await;
+
+        // Then
+        assert_eq!(10, progress);
+        assert_eq!(vec![AccountId(1)], existing_account_ids);
+
+        // When
+        adapter_under_test
+            .save_import_batch(
+                "legacy.csv".to_string(),
+                12,
+                vec![ImportedAccount {
+                    account_id: AccountId(5),
+                    currency: Currency::of("USD"),
+                    overdraft_limit: Money::of(200),
+                }],
+                vec![Activity::new(
+                    AccountId(5),
+                    AccountId(1),
+                    AccountId(5),
+                    timestamp(),
+                    Money::of(500),
+                )],
+                timestamp(),
+            )
+            .await;
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    account_event_mapper, account_event_repository,
+    account_repository::{self, AccountEntity},
+    activity_repository::{self, ActivityEntity},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait ImportRepository: Send + Sync + std::fmt::Debug {
+    async fn find_progress(&self, source: String) -> Option<ImportProgressEntity>;
+    /**
+     * @return those of the ids which belong to an account.
+     */
+    async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64>;
+    /**
+     * Inserts the accounts, which are opened at the time of the progress, and the activities and
+     * replaces the progress of the source in a single transaction.
+     */
+    async fn save_batch(
+        &self,
+        import_progress_entity: ImportProgressEntity,
+        account_entities: Vec<AccountEntity>,
+        activity_entities: Vec<ActivityEntity>,
+    );
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ImportRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl ImportRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ImportRepository for ImportRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_progress(&self, source: String) -> Option<ImportProgressEntity> {
+        sqlx::query_as::<_, ImportProgressEntity>(
+            "
+            SELECT * FROM import_progress_entity
+            WHERE source = ?
+            ",
+        )
+        .bind(source)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64> {
+        account_repository::find_existing_account_ids(&self.db_pool, account_ids).await
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_batch(
+        &self,
+        import_progress_entity: ImportProgressEntity,
+        account_entities: Vec<AccountEntity>,
+        activity_entities: Vec<ActivityEntity>,
+    ) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        for account_entity in account_entities {
+            sqlx::query(
+                "
+                INSERT INTO account_entity (id, currency, overdraft_limit)
+                VALUES (?, ?, ?)
+                ",
+            )
+            .bind(account_entity.id)
+            .bind(&account_entity.currency)
+            .bind(account_entity.overdraft_limit)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+            account_event_repository::append_account_event(
+                &mut tx,
+                &account_event_mapper::map_to_account_opened_event_entity(
+                    &account_entity,
+                    import_progress_entity.updated_at,
+                ),
+            )
+            .await;
+        }
+        for activity_entity in activity_entities {
+            activity_repository::insert_activity(&mut tx, &activity_entity).await;
+        }
+        sqlx::query(
+            "
+            INSERT OR REPLACE INTO import_progress_entity (source, last_line_number, updated_at)
+            VALUES (?, ?, ?)
+            ",
+        )
+        .bind(import_progress_entity.source)
+        .bind(import_progress_entity.last_line_number)
+        .bind(import_progress_entity.updated_at)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct ImportProgressEntity {
+    pub source: String,
+    pub last_line_number: i64,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,29 @@
+use crate::{account_mapper::map_to_amount, interest_accrual_repository::InterestAccrualEntity};
+use domain::{
+    ar::{account::AccountId, interest_accrual::InterestAccrual},
+    vo::money::Money,
+};
+
+pub fn map_to_interest_accrual(entity: InterestAccrualEntity) -> InterestAccrual {
+    let mut interest_accrual = InterestAccrual::new(
+        AccountId(entity.account_id),
+        entity.accrual_date,
+        Money::of(entity.balance as i128),
+    );
+    if let Some(interest) = entity.capitalised_interest {
+        interest_accrual.capitalise(Money::of(interest as i128));
+    }
+    interest_accrual
+}
+
+pub fn map_to_interest_accrual_entity(interest_accrual: InterestAccrual) -> InterestAccrualEntity {
+    InterestAccrualEntity {
+        account_id: interest_accrual.account_id.0,
+        accrual_date: interest_accrual.accrual_date,
+        balance: map_to_amount(&interest_accrual.balance),
+        capitalised_interest: interest_accrual
+            .capitalised_interest
+            .as_ref()
+            .map(map_to_amount),
+    }
+}
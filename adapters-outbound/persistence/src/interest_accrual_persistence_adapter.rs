@@ -0,0 +1,190 @@
+use crate::{
+    account_mapper, interest_accrual_mapper,
+    interest_accrual_repository::InterestAccrualRepository, outbox_mapper,
+};
+use application::outbound_ports::{LoadInterestAccrualPort, UpdateInterestAccrualPort};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use domain::ar::{
+    account::{Account, AccountId},
+    domain_event::DomainEvent,
+    interest_accrual::InterestAccrual,
+    outbox_message::OutboxMessage,
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct InterestAccrualPersistenceAdapter {
+    interest_accrual_repository: Box<dyn InterestAccrualRepository>,
+}
+
+impl InterestAccrualPersistenceAdapter {
+    // #[inject]
+    pub fn new(interest_accrual_repository: Box<dyn InterestAccrualRepository>) -> Self {
+        Self {
+            interest_accrual_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadInterestAccrualPort for InterestAccrualPersistenceAdapter {
+    async fn load_interest_bearing_account_ids(&self) -> Vec<AccountId> {
+        let ids = self
+            .interest_accrual_repository
+            .find_interest_bearing_account_ids()
+            .await;
+        debug!(result = ?ids, "find_interest_bearing_account_ids");
+        ids.into_iter().map(AccountId).collect()
+    }
+
+    async fn load_last_accrual_date(&self, account_id: AccountId) -> Option<NaiveDate> {
+        let last_accrual_date = self
+            .interest_accrual_repository
+            .find_last_accrual_date(account_id.0)
+            .await;
+        debug!(?account_id, result = ?last_accrual_date, "find_last_accrual_date");
+        last_accrual_date
+    }
+
+    async fn load_interest_accruals(
+        &self,
+        account_id: AccountId,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Vec<InterestAccrual> {
+        let entities = self
+            .interest_accrual_repository
+            .find_by_account_between(account_id.0, from, until)
+            .await;
+        debug!(?account_id, %from, %until, result = ?entities, "find_by_account_between");
+        entities
+            .into_iter()
+            .map(interest_accrual_mapper::map_to_interest_accrual)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateInterestAccrualPort for InterestAccrualPersistenceAdapter {
+    async fn save_interest_accrual(
+        &self,
+        interest_accrual: InterestAccrual,
+        accounts: Vec<Account>,
+        domain_events: Vec<DomainEvent>,
+    ) {
+        let entity = interest_accrual_mapper::map_to_interest_accrual_entity(interest_accrual);
+        let activity_entities: Vec<_> = accounts
+            .iter()
+            .flat_map(|account| &account.activity_window.activities)
+            .filter(|activity| activity.id.is_none())
+            .map(account_mapper::map_to_activity_entity)
+            .collect();
+        let outbox_message_entities: Vec<_> = domain_events
+            .into_iter()
+            .map(|domain_event| {
+                outbox_mapper::map_to_outbox_message_entity(OutboxMessage::new(domain_event))
+            })
+            .collect();
+        debug!(
+            interest_accrual_entity = ?entity,
+            ?activity_entities,
+            ?outbox_message_entities,
+            "save"
+        );
+        self.interest_accrual_repository
+            .save(entity, activity_entities, outbox_message_entities)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        activity_repository::ActivityEntity, interest_accrual_repository::InterestAccrualEntity,
+        outbox_repository::OutboxMessageEntity,
+    };
+    use domain::{
+        testdata::{default_account, default_activity},
+        vo::{activity_window::ActivityWindow, money::Money},
+    };
+    use mockall::mock;
+
+    mock! {
+        #[derive(Debug)]
+        InterestAccrualRepositoryImpl {}
+        #[async_trait]
+        impl InterestAccrualRepository for InterestAccrualRepositoryImpl {
+            async fn find_interest_bearing_account_ids(&self) -> Vec<i64>;
+            async fn find_last_accrual_date(&self, account_id: i64) -> Option<NaiveDate>;
+            async fn find_by_account_between(
+                &self,
+                account_id: i64,
+                from: NaiveDate,
+                until: NaiveDate,
+            ) -> Vec<InterestAccrualEntity>;
+            async fn save(
+                &self,
+                interest_accrual_entity: InterestAccrualEntity,
+                activity_entities: Vec<ActivityEntity>,
+                outbox_message_entities: Vec<OutboxMessageEntity>,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saves_capitalised_interest_with_its_activities() {
+        // Given an accrual which capitalises the interest of the month
+        let accrual_date = NaiveDate::from_ymd_opt(2019, 8, 31).unwrap();
+        let mut interest_accrual =
+            InterestAccrual::new(AccountId(1), accrual_date, Money::of(12_000));
+        interest_accrual.capitalise(Money::of(12));
+        let account = default_account()
+            .with_account_id(AccountId(1))
+            .with_activity_window(ActivityWindow::new(vec![
+                default_activity()
+                    .with_owner_account(AccountId(1))
+                    .with_source_account(AccountId(100))
+                    .with_target_account(AccountId(1))
+                    .with_money(Money::of(12))
+                    .build(),
+            ]))
+            .build();
+
+        // Then the accrual is saved together with the interest activity and its event
+        let mut repository = Box::new(MockInterestAccrualRepositoryImpl::new());
+        repository
+            .expect_save()
+            .times(1)
+            .withf(move |entity, activity_entities, outbox_message_entities| {
+                *entity
+                    == InterestAccrualEntity {
+                        account_id: 1,
+                        accrual_date,
+                        balance: 12_000,
+                        capitalised_interest: Some(12),
+                    }
+                    && activity_entities.len() == 1
+                    && activity_entities[0].amount == 12
+                    && outbox_message_entities.len() == 1
+            })
+            .return_const(());
+
+        // When
+        let adapter_under_test = InterestAccrualPersistenceAdapter::new(repository);
+        adapter_under_test
+            .save_interest_accrual(
+                interest_accrual,
+                vec![account],
+                vec![DomainEvent::MoneyDeposited {
+                    account_id: AccountId(1),
+                    source_account_id: AccountId(100),
+                    money: Money::of(12),
+                    timestamp: accrual_date.and_hms_opt(0, 0, 0).unwrap(),
+                }],
+            )
+            .await;
+    }
+}
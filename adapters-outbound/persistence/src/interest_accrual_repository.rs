@@ -0,0 +1,148 @@
+use crate::{
+    activity_repository::{self, ActivityEntity},
+    outbox_repository::{self, OutboxMessageEntity},
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{FromRow, Row, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait InterestAccrualRepository: Send + Sync + std::fmt::Debug {
+    async fn find_interest_bearing_account_ids(&self) -> Vec<i64>;
+    async fn find_last_accrual_date(&self, account_id: i64) -> Option<NaiveDate>;
+    async fn find_by_account_between(
+        &self,
+        account_id: i64,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Vec<InterestAccrualEntity>;
+    /**
+     * Saves the accrual, the activities and the outbox messages in a single transaction.
+     * Nothing is saved if the account has already been accrued for the day.
+     */
+    async fn save(
+        &self,
+        interest_accrual_entity: InterestAccrualEntity,
+        activity_entities: Vec<ActivityEntity>,
+        outbox_message_entities: Vec<OutboxMessageEntity>,
+    );
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct InterestAccrualRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl InterestAccrualRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl InterestAccrualRepository for InterestAccrualRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_interest_bearing_account_ids(&self) -> Vec<i64> {
+        let rows = sqlx::query(
+            "
+            SELECT id FROM account_entity
+            WHERE interest_bearing = 1
+            ORDER BY id
+            ",
+        )
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows.iter().map(|row| row.try_get("id").unwrap()).collect();
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_last_accrual_date(&self, account_id: i64) -> Option<NaiveDate> {
+        sqlx::query_scalar::<_, Option<NaiveDate>>(
+            "
+            SELECT MAX(accrual_date) FROM interest_accrual_entity
+            WHERE account_id = ?
+            ",
+        )
+        .bind(account_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_account_between(
+        &self,
+        account_id: i64,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Vec<InterestAccrualEntity> {
+        let rows = sqlx::query_as::<_, InterestAccrualEntity>(
+            "
+            SELECT * FROM interest_accrual_entity
+            WHERE account_id = ?
+            AND accrual_date >= ?
+            AND accrual_date <= ?
+            ORDER BY accrual_date
+            ",
+        )
+        .bind(account_id)
+        .bind(from)
+        .bind(until)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save(
+        &self,
+        interest_accrual_entity: InterestAccrualEntity,
+        activity_entities: Vec<ActivityEntity>,
+        outbox_message_entities: Vec<OutboxMessageEntity>,
+    ) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        let inserted = sqlx::query(
+            "
+            INSERT OR IGNORE INTO interest_accrual_entity (account_id, accrual_date, balance, capitalised_interest)
+            VALUES (?, ?, ?, ?)
+            ",
+        )
+        .bind(interest_accrual_entity.account_id)
+        .bind(interest_accrual_entity.accrual_date)
+        .bind(interest_accrual_entity.balance)
+        .bind(interest_accrual_entity.capitalised_interest)
+        .execute(&mut *tx)
+        .await
+        .unwrap()
+        .rows_affected();
+        if inserted == 0 {
+            // accrued concurrently, the interest must not be paid twice
+            tx.rollback().await.unwrap();
+            return;
+        }
+        for activity_entity in activity_entities {
+            activity_repository::insert_activity(&mut tx, &activity_entity).await;
+        }
+        for outbox_message_entity in outbox_message_entities {
+            outbox_repository::insert_outbox_message(&mut tx, &outbox_message_entity).await;
+        }
+        tx.commit().await.unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct InterestAccrualEntity {
+    pub account_id: i64,
+    pub accrual_date: NaiveDate,
+    pub balance: i64,
+    pub capitalised_interest: Option<i64>,
+}
@@ -0,0 +1,17 @@
+use crate::ledger_export_repository::LedgerExportEntity;
+use application::inbound_ports::LedgerExport;
+
+pub fn map_to_ledger_export_entity(ledger_export: &LedgerExport) -> LedgerExportEntity {
+    LedgerExportEntity {
+        id: None,
+        exported_at: ledger_export.exported_at,
+        period_from: ledger_export.from,
+        period_to: ledger_export.to,
+        first_activity_id: ledger_export.first_activity_id.as_ref().map(|id| id.0),
+        last_activity_id: ledger_export.last_activity_id.as_ref().map(|id| id.0),
+        transfer_count: ledger_export.transfer_count as i64,
+        incomplete_transfer_count: ledger_export.incomplete_transfer_count as i64,
+        file_path: ledger_export.file.path.clone(),
+        checksum: ledger_export.file.checksum.clone(),
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    account_mapper, activity_repository::ActivityRepository, ledger_export_mapper,
+    ledger_export_repository::LedgerExportRepository,
+};
+use application::{
+    inbound_ports::LedgerExport,
+    outbound_ports::{LoadLedgerPort, UpdateLedgerExportStatePort},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::activity::{Activity, ActivityId};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct LedgerExportPersistenceAdapter {
+    activity_repository: Box<dyn ActivityRepository>,
+    ledger_export_repository: Box<dyn LedgerExportRepository>,
+}
+
+impl LedgerExportPersistenceAdapter {
+    // #[inject]
+    pub fn new(
+        activity_repository: Box<dyn ActivityRepository>,
+        ledger_export_repository: Box<dyn LedgerExportRepository>,
+    ) -> Self {
+        Self {
+            activity_repository,
+            ledger_export_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadLedgerPort for LedgerExportPersistenceAdapter {
+    async fn load_last_exported_activity_id(&self) -> Option<ActivityId> {
+        let last_activity_id = self
+            .ledger_export_repository
+            .find_last_exported_activity_id()
+            .await;
+        debug!(result = ?last_activity_id, "find_last_exported_activity_id");
+        last_activity_id.map(ActivityId)
+    }
+
+    async fn load_ledger_activities(
+        &self,
+        after_activity_id: Option<ActivityId>,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+        limit: usize,
+    ) -> Vec<Activity> {
+        let after_id = after_activity_id.map_or(0, |id| id.0);
+        let entities = self
+            .activity_repository
+            .find_after_id_between(after_id, since, until, limit as i64)
+            .await;
+        debug!(?after_id, ?since, ?until, ?limit, result = ?entities, "find_after_id_between");
+        entities
+            .iter()
+            .map(account_mapper::map_to_activity)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateLedgerExportStatePort for LedgerExportPersistenceAdapter {
+    async fn save_ledger_export(&self, ledger_export: &LedgerExport) {
+        let entity = ledger_export_mapper::map_to_ledger_export_entity(ledger_export);
+        debug!(ledger_export_entity = ?entity, "save");
+        self.ledger_export_repository.save(entity).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        activity_repository::ActivityEntity, ledger_export_repository::LedgerExportEntity,
+        outbox_repository::OutboxMessageEntity,
+    };
+    use application::inbound_ports::LedgerFile;
+    use chrono::NaiveDate;
+    use domain::{ar::account::AccountId, vo::money::Money};
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        ActivityRepositoryImpl {}
+        #[async_trait]
+        impl ActivityRepository for ActivityRepositoryImpl {
+            async fn find_by_owner_since(
+                &self,
+                owner_account_id: i64,
+                timestamp: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn get_deposit_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_volume_since(
+                &self,
+                account_id: i64,
+                since: NaiveDateTime,
+                excluded_target_account_id: Option<i64>,
+            ) -> (i128, i64);
+            async fn find_by_owner_between(
+                &self,
+                owner_account_id: i64,
+                since: NaiveDateTime,
+                until: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn find_by_owner_after_id(
+                &self,
+                owner_account_id: i64,
+                after_id: i64,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn find_after_id_between(
+                &self,
+                after_id: i64,
+                since: Option<NaiveDateTime>,
+                until: Option<NaiveDateTime>,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128;
+            async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64>;
+            async fn save(&self, activity_entity: ActivityEntity);
+            async fn save_all(&self, activity_entities: Vec<ActivityEntity>);
+            async fn save_all_with_outbox_messages(
+                &self,
+                activity_entities: Vec<ActivityEntity>,
+                outbox_message_entities: Vec<OutboxMessageEntity>,
+            );
+        }
+    }
+
+    mock! {
+        #[derive(Debug)]
+        LedgerExportRepositoryImpl {}
+        #[async_trait]
+        impl LedgerExportRepository for LedgerExportRepositoryImpl {
+            async fn find_last_exported_activity_id(&self) -> Option<i64>;
+            async fn save(&self, ledger_export_entity: LedgerExportEntity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_the_activities_after_the_last_export() {
+        // Given activity 10 has been exported
+        let mut ledger_export_repository = Box::new(MockLedgerExportRepositoryImpl::new());
+        ledger_export_repository
+            .expect_find_last_exported_activity_id()
+            .times(1)
+            .returning(|| Some(10));
+        ledger_export_repository
+            .expect_save()
+            .times(1)
+            .with(eq(LedgerExportEntity {
+                id: None,
+                exported_at: timestamp(),
+                period_from: None,
+                period_to: None,
+                first_activity_id: Some(11),
+                last_activity_id: Some(11),
+                transfer_count: 1,
+                incomplete_transfer_count: 1,
+                file_path: "ledger.csv".to_string(),
+                checksum: "00".to_string(),
+            }))
+            .return_const(());
+        let mut activity_repository = Box::new(MockActivityRepositoryImpl::new());
+        activity_repository
+            .expect_find_after_id_between()
+            .times(1)
+            .with(eq(10), eq(None), eq(Some(timestamp())), eq(100))
+            .returning(|_, _, _, _| {
+                vec![ActivityEntity {
+                    id: Some(11),
+                    timestamp: timestamp(),
+                    owner_account_id: 1,
+                    source_account_id: 1,
+                    target_account_id: 2,
+                    amount: 500,
+                    exchange_rate_source_currency: None,
+                    exchange_rate_target_currency: None,
+                    exchange_rate: None,
+                }]
+            });
+
+        let adapter_under_test =
+            LedgerExportPersistenceAdapter::new(activity_repository, ledger_export_repository);
+
+        // When
+        let last_activity_id = adapter_under_test.load_last_exported_activity_id().await;
+        let activities = adapter_under_test
+            .load_ledger_activities(last_activity_id, None, Some(timestamp()), 100)
+            .await;
+
+        // Then
+        assert_eq!(
+            vec![Activity::with_id(
+                Some(ActivityId(11)),
+                AccountId(1),
+                AccountId(1),
+                AccountId(2),
+                timestamp(),
+                Money::of(500),
+                None,
+            )],
+            activities
+        );
+
+        // When
+        adapter_under_test
+            .save_ledger_export(&LedgerExport {
+                exported_at: timestamp(),
+                from: None,
+                to: None,
+                first_activity_id: Some(ActivityId(11)),
+                last_activity_id: Some(ActivityId(11)),
+                transfer_count: 1,
+                incomplete_transfer_count: 1,
+                file: LedgerFile {
+                    path: "ledger.csv".to_string(),
+                    checksum: "00".to_string(),
+                },
+            })
+            .await;
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
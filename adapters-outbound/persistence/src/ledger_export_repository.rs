@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use sqlx::{FromRow, Row, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait LedgerExportRepository: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the greatest last activity id of all exports, none if none exported an activity.
+     */
+    async fn find_last_exported_activity_id(&self) -> Option<i64>;
+    async fn save(&self, ledger_export_entity: LedgerExportEntity);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct LedgerExportRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl LedgerExportRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl LedgerExportRepository for LedgerExportRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_last_exported_activity_id(&self) -> Option<i64> {
+        let row = sqlx::query(
+            "
+            SELECT MAX(last_activity_id) AS last_activity_id FROM ledger_export_entity
+            ",
+        )
+        .fetch_one(&self.db_pool)
+        .await
+        .unwrap();
+        row.try_get("last_activity_id").unwrap()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save(&self, ledger_export_entity: LedgerExportEntity) {
+        sqlx::query(
+            "
+            INSERT INTO ledger_export_entity (exported_at, period_from, period_to, first_activity_id, last_activity_id,
+                transfer_count, incomplete_transfer_count, file_path, checksum)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(ledger_export_entity.exported_at)
+        .bind(ledger_export_entity.period_from)
+        .bind(ledger_export_entity.period_to)
+        .bind(ledger_export_entity.first_activity_id)
+        .bind(ledger_export_entity.last_activity_id)
+        .bind(ledger_export_entity.transfer_count)
+        .bind(ledger_export_entity.incomplete_transfer_count)
+        .bind(ledger_export_entity.file_path)
+        .bind(ledger_export_entity.checksum)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct LedgerExportEntity {
+    pub id: Option<i64>,
+    pub exported_at: NaiveDateTime,
+    pub period_from: Option<NaiveDate>,
+    pub period_to: Option<NaiveDate>,
+    pub first_activity_id: Option<i64>,
+    pub last_activity_id: Option<i64>,
+    pub transfer_count: i64,
+    pub incomplete_transfer_count: i64,
+    pub file_path: String,
+    pub checksum: String,
+}
@@ -1,4 +1,35 @@
+mod account_event_mapper;
+pub mod account_event_repository;
+mod account_mandate_mapper;
+pub mod account_mandate_persistence_adapter;
+pub mod account_mandate_repository;
 mod account_mapper;
 pub mod account_persistence_adapter;
 pub mod account_repository;
 pub mod activity_repository;
+pub mod event_sourced_account_persistence_adapter;
+pub mod health_persistence_adapter;
+pub mod health_repository;
+pub mod import_persistence_adapter;
+pub mod import_repository;
+mod interest_accrual_mapper;
+pub mod interest_accrual_persistence_adapter;
+pub mod interest_accrual_repository;
+mod ledger_export_mapper;
+pub mod ledger_export_persistence_adapter;
+pub mod ledger_export_repository;
+mod outbox_mapper;
+pub mod outbox_persistence_adapter;
+pub mod outbox_repository;
+mod reconciliation_mapper;
+pub mod reconciliation_persistence_adapter;
+pub mod reconciliation_repository;
+mod scheduled_transfer_mapper;
+pub mod scheduled_transfer_persistence_adapter;
+pub mod scheduled_transfer_repository;
+mod standing_order_mapper;
+pub mod standing_order_persistence_adapter;
+pub mod standing_order_repository;
+mod webhook_mapper;
+pub mod webhook_persistence_adapter;
+pub mod webhook_repository;
@@ -0,0 +1,132 @@
+use crate::{account_mapper::map_to_amount, outbox_repository::OutboxMessageEntity};
+use domain::{
+    ar::{
+        account::AccountId,
+        domain_event::DomainEvent,
+        outbox_message::{OutboxMessage, OutboxMessageId},
+    },
+    vo::money::Money,
+};
+
+pub fn map_to_outbox_message(entity: OutboxMessageEntity) -> OutboxMessage {
+    let source_account_id = AccountId(entity.source_account_id);
+    let target_account_id = AccountId(entity.target_account_id);
+    let money = Money::of(entity.amount as i128);
+    let timestamp = entity.timestamp;
+    let domain_event = match entity.event_type.as_str() {
+        "MONEY_WITHDRAWN" => DomainEvent::MoneyWithdrawn {
+            account_id: source_account_id,
+            target_account_id,
+            money,
+            timestamp,
+        },
+        "MONEY_DEPOSITED" => DomainEvent::MoneyDeposited {
+            account_id: target_account_id,
+            source_account_id,
+            money,
+            timestamp,
+        },
+        "TRANSFER_COMPLETED" => DomainEvent::TransferCompleted {
+            source_account_id,
+            target_account_id,
+            money,
+            fee: Money::of(entity.fee.unwrap_or(0) as i128),
+            timestamp,
+        },
+        "TRANSFER_REJECTED" => DomainEvent::TransferRejected {
+            source_account_id,
+            target_account_id,
+            money,
+            reason: entity.reason.unwrap_or_default(),
+            timestamp,
+        },
+        event_type => panic!("unknown domain event type: {}", event_type),
+    };
+    OutboxMessage::with_id(
+        entity.id.map(OutboxMessageId),
+        domain_event,
+        entity.attempts as u32,
+        entity.next_attempt_at,
+        entity.last_error,
+        entity.processed_at,
+    )
+}
+
+pub fn map_to_outbox_message_entity(outbox_message: OutboxMessage) -> OutboxMessageEntity {
+    let (event_type, account_id, source_account_id, target_account_id, money, fee, reason) =
+        match &outbox_message.domain_event {
+            DomainEvent::MoneyWithdrawn {
+                account_id,
+                target_account_id,
+                money,
+                ..
+            } => (
+                "MONEY_WITHDRAWN",
+                Some(account_id),
+                account_id,
+                target_account_id,
+                money,
+                None,
+                None,
+            ),
+            DomainEvent::MoneyDeposited {
+                account_id,
+                source_account_id,
+                money,
+                ..
+            } => (
+                "MONEY_DEPOSITED",
+                Some(account_id),
+                source_account_id,
+                account_id,
+                money,
+                None,
+                None,
+            ),
+            DomainEvent::TransferCompleted {
+                source_account_id,
+                target_account_id,
+                money,
+                fee,
+                ..
+            } => (
+                "TRANSFER_COMPLETED",
+                None,
+                source_account_id,
+                target_account_id,
+                money,
+                Some(fee),
+                None,
+            ),
+            DomainEvent::TransferRejected {
+                source_account_id,
+                target_account_id,
+                money,
+                reason,
+                ..
+            } => (
+                "TRANSFER_REJECTED",
+                None,
+                source_account_id,
+                target_account_id,
+                money,
+                None,
+                Some(reason.clone()),
+            ),
+        };
+    OutboxMessageEntity {
+        id: outbox_message.id.as_ref().map(|id| id.0),
+        event_type: event_type.to_string(),
+        account_id: account_id.map(|account_id| account_id.0),
+        source_account_id: source_account_id.0,
+        target_account_id: target_account_id.0,
+        amount: map_to_amount(money),
+        fee: fee.map(map_to_amount),
+        reason,
+        timestamp: outbox_message.domain_event.timestamp(),
+        attempts: outbox_message.attempts as i64,
+        next_attempt_at: outbox_message.next_attempt_at,
+        last_error: outbox_message.last_error,
+        processed_at: outbox_message.processed_at,
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{outbox_mapper, outbox_repository::OutboxRepository};
+use application::outbound_ports::{DomainEventPublisher, LoadOutboxPort, UpdateOutboxStatePort};
+use async_trait::async_trait;
+use domain::ar::{domain_event::DomainEvent, outbox_message::OutboxMessage};
+use tracing::debug;
+
+/**
+ * Publishes domain events by storing them in the outbox, from where they are relayed.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct OutboxPersistenceAdapter {
+    outbox_repository: Box<dyn OutboxRepository>,
+}
+
+impl OutboxPersistenceAdapter {
+    // #[inject]
+    pub fn new(outbox_repository: Box<dyn OutboxRepository>) -> Self {
+        Self { outbox_repository }
+    }
+}
+
+#[async_trait]
+impl DomainEventPublisher for OutboxPersistenceAdapter {
+    async fn publish(&self, domain_events: Vec<DomainEvent>) {
+        let entities: Vec<_> = domain_events
+            .into_iter()
+            .map(|domain_event| {
+                outbox_mapper::map_to_outbox_message_entity(OutboxMessage::new(domain_event))
+            })
+            .collect();
+        debug!(outbox_message_entities = ?entities, "save_all");
+        self.outbox_repository.save_all(entities).await;
+    }
+}
+
+#[async_trait]
+impl LoadOutboxPort for OutboxPersistenceAdapter {
+    async fn load_pending_outbox_messages(&self, limit: usize) -> Vec<OutboxMessage> {
+        let entities = self.outbox_repository.find_unprocessed(limit as i64).await;
+        debug!(%limit, result = ?entities, "find_unprocessed");
+        entities
+            .into_iter()
+            .map(outbox_mapper::map_to_outbox_message)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateOutboxStatePort for OutboxPersistenceAdapter {
+    async fn save_outbox_message(&self, outbox_message: OutboxMessage) {
+        let entity = outbox_mapper::map_to_outbox_message_entity(outbox_message);
+        debug!(outbox_message_entity = ?entity, "update_delivery_state");
+        self.outbox_repository.update_delivery_state(entity).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbox_repository::OutboxMessageEntity;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::{
+        ar::{account::AccountId, outbox_message::OutboxMessageId},
+        vo::money::Money,
+    };
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        OutboxRepositoryImpl {}
+        #[async_trait]
+        impl OutboxRepository for OutboxRepositoryImpl {
+            async fn find_unprocessed(&self, limit: i64) -> Vec<OutboxMessageEntity>;
+            async fn save_all(&self, outbox_message_entities: Vec<OutboxMessageEntity>);
+            async fn update_delivery_state(&self, outbox_message_entity: OutboxMessageEntity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_pending_messages_and_marks_them_processed() {
+        // Given a deposit waiting in the outbox
+        let mut repository = Box::new(MockOutboxRepositoryImpl::new());
+        repository
+            .expect_find_unprocessed()
+            .with(eq(10))
+            .returning(|_| vec![deposit_entity(None)]);
+        // And the processed marker is stored
+        repository
+            .expect_update_delivery_state()
+            .times(1)
+            .with(eq(deposit_entity(Some(timestamp()))))
+            .return_const(());
+
+        // When
+        let adapter_under_test = OutboxPersistenceAdapter::new(repository);
+        let mut outbox_messages = adapter_under_test.load_pending_outbox_messages(10).await;
+
+        // Then
+        assert_eq!(1, outbox_messages.len());
+        assert_eq!(Some(OutboxMessageId(7)), outbox_messages[0].id);
+        assert_eq!(
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(42),
+                source_account_id: AccountId(41),
+                money: Money::of(500),
+                timestamp: timestamp(),
+            },
+            outbox_messages[0].domain_event
+        );
+
+        // When
+        let mut outbox_message = outbox_messages.remove(0);
+        outbox_message.mark_processed(timestamp());
+        adapter_under_test.save_outbox_message(outbox_message).await;
+    }
+
+    fn deposit_entity(processed_at: Option<NaiveDateTime>) -> OutboxMessageEntity {
+        OutboxMessageEntity {
+            id: Some(7),
+            event_type: "MONEY_DEPOSITED".to_string(),
+            account_id: Some(42),
+            source_account_id: 41,
+            target_account_id: 42,
+            amount: 500,
+            fee: None,
+            reason: None,
+            timestamp: timestamp(),
+            attempts: 0,
+            next_attempt_at: timestamp(),
+            last_error: None,
+            processed_at,
+        }
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait OutboxRepository: Send + Sync + std::fmt::Debug {
+    async fn find_unprocessed(&self, limit: i64) -> Vec<OutboxMessageEntity>;
+    /**
+     * Saves all new messages in a single transaction, either all of them are stored or none.
+     */
+    async fn save_all(&self, outbox_message_entities: Vec<OutboxMessageEntity>);
+    async fn update_delivery_state(&self, outbox_message_entity: OutboxMessageEntity);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct OutboxRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl OutboxRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_unprocessed(&self, limit: i64) -> Vec<OutboxMessageEntity> {
+        let rows = sqlx::query_as::<_, OutboxMessageEntity>(
+            "
+            SELECT * FROM outbox_message_entity
+            WHERE processed_at IS NULL
+            ORDER BY id
+            LIMIT ?
+            ",
+        )
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_all(&self, outbox_message_entities: Vec<OutboxMessageEntity>) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        for outbox_message_entity in outbox_message_entities {
+            insert_outbox_message(&mut tx, &outbox_message_entity).await;
+        }
+        tx.commit().await.unwrap();
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn update_delivery_state(&self, outbox_message_entity: OutboxMessageEntity) {
+        sqlx::query(
+            "
+            UPDATE outbox_message_entity
+            SET attempts = ?, next_attempt_at = ?, last_error = ?, processed_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(outbox_message_entity.attempts)
+        .bind(outbox_message_entity.next_attempt_at)
+        .bind(outbox_message_entity.last_error)
+        .bind(outbox_message_entity.processed_at)
+        .bind(outbox_message_entity.id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+/**
+ * Inserts a new message on the connection, which may be part of a larger transaction.
+ */
+pub(crate) async fn insert_outbox_message(
+    connection: &mut SqliteConnection,
+    outbox_message_entity: &OutboxMessageEntity,
+) {
+    sqlx::query(
+        "
+        INSERT INTO outbox_message_entity (event_type, account_id, source_account_id, target_account_id, amount,
+            fee, reason, timestamp, attempts, next_attempt_at, last_error, processed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(&outbox_message_entity.event_type)
+    .bind(outbox_message_entity.account_id)
+    .bind(outbox_message_entity.source_account_id)
+    .bind(outbox_message_entity.target_account_id)
+    .bind(outbox_message_entity.amount)
+    .bind(outbox_message_entity.fee)
+    .bind(&outbox_message_entity.reason)
+    .bind(outbox_message_entity.timestamp)
+    .bind(outbox_message_entity.attempts)
+    .bind(outbox_message_entity.next_attempt_at)
+    .bind(&outbox_message_entity.last_error)
+    .bind(outbox_message_entity.processed_at)
+    .execute(&mut *connection)
+    .await
+    .unwrap();
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct OutboxMessageEntity {
+    pub id: Option<i64>,
+    pub event_type: String,
+    // the owner of the activity of money events
+    pub account_id: Option<i64>,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub fee: Option<i64>,
+    pub reason: Option<String>,
+    pub timestamp: NaiveDateTime,
+    pub attempts: i64,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub processed_at: Option<NaiveDateTime>,
+}
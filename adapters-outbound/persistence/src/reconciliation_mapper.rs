@@ -0,0 +1,19 @@
+use crate::reconciliation_repository::SnapshotBalancesEntity;
+use application::inbound_ports::AccountSnapshotBalances;
+use domain::{ar::account::AccountId, vo::money::Money};
+
+pub fn map_to_account_snapshot_balances(
+    snapshot_balances_entity: &SnapshotBalancesEntity,
+) -> AccountSnapshotBalances {
+    AccountSnapshotBalances {
+        account_id: AccountId(snapshot_balances_entity.account_id),
+        withdrawal_balance: Money::of(snapshot_balances_entity.withdrawal_balance as i128),
+        deposit_balance: Money::of(snapshot_balances_entity.deposit_balance as i128),
+        replayed_withdrawal_balance: Money::of(
+            snapshot_balances_entity.replayed_withdrawal_balance as i128,
+        ),
+        replayed_deposit_balance: Money::of(
+            snapshot_balances_entity.replayed_deposit_balance as i128,
+        ),
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    account_mapper, activity_repository::ActivityRepository, reconciliation_mapper,
+    reconciliation_repository::ReconciliationRepository,
+};
+use application::{
+    inbound_ports::AccountSnapshotBalances,
+    outbound_ports::{LoadReconciliationPort, RepairAccountSnapshotPort},
+};
+use async_trait::async_trait;
+use domain::ar::{
+    account::AccountId,
+    activity::{Activity, ActivityId},
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ReconciliationPersistenceAdapter {
+    activity_repository: Box<dyn ActivityRepository>,
+    reconciliation_repository: Box<dyn ReconciliationRepository>,
+}
+
+impl ReconciliationPersistenceAdapter {
+    // #[inject]
+    pub fn new(
+        activity_repository: Box<dyn ActivityRepository>,
+        reconciliation_repository: Box<dyn ReconciliationRepository>,
+    ) -> Self {
+        Self {
+            activity_repository,
+            reconciliation_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadReconciliationPort for ReconciliationPersistenceAdapter {
+    async fn load_activities_after(
+        &self,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<Activity> {
+        let after_id = after_activity_id.map_or(0, |id| id.0);
+        let entities = self
+            .activity_repository
+            .find_after_id_between(after_id, None, None, limit as i64)
+            .await;
+        debug!(?after_id, ?limit, result = ?entities, "find_after_id_between");
+        entities
+            .iter()
+            .map(account_mapper::map_to_activity)
+            .collect()
+    }
+
+    async fn load_existing_account_ids(&self, account_ids: Vec<AccountId>) -> Vec<AccountId> {
+        let account_ids: Vec<i64> = account_ids.iter().map(|account_id| account_id.0).collect();
+        let existing_account_ids = self
+            .reconciliation_repository
+            .find_existing_account_ids(account_ids.clone())
+            .await;
+        debug!(?account_ids, result = ?existing_account_ids, "find_existing_account_ids");
+        existing_account_ids.into_iter().map(AccountId).collect()
+    }
+
+    async fn load_account_snapshot_balances(
+        &self,
+        after_account_id: Option<AccountId>,
+        limit: usize,
+    ) -> Vec<AccountSnapshotBalances> {
+        let after_account_id = after_account_id.map_or(0, |account_id| account_id.0);
+        let entities = self
+            .reconciliation_repository
+            .find_snapshot_balances(after_account_id, limit as i64)
+            .await;
+        debug!(?after_account_id, ?limit, result = ?entities, "find_snapshot_balances");
+        entities
+            .iter()
+            .map(reconciliation_mapper::map_to_account_snapshot_balances)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl RepairAccountSnapshotPort for ReconciliationPersistenceAdapter {
+    async fn discard_account_snapshot(&self, account_id: AccountId) {
+        debug!(?account_id, "delete_snapshot");
+        self.reconciliation_repository
+            .delete_snapshot(account_id.0)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        activity_repository::ActivityEntity, outbox_repository::OutboxMessageEntity,
+        reconciliation_repository::SnapshotBalancesEntity,
+    };
+    use chrono::NaiveDateTime;
+    use domain::vo::money::Money;
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        ActivityRepositoryImpl {}
+        #[async_trait]
+        impl ActivityRepository for ActivityRepositoryImpl {
+            async fn find_by_owner_since(
+                &self,
+                owner_account_id: i64,
+                timestamp: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn get_deposit_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_balance_until(
+                &self,
+                account_id: i64,
+                until: NaiveDateTime,
+            ) -> Option<i128>;
+            async fn get_withdrawal_volume_since(
+                &self,
+                account_id: i64,
+                since: NaiveDateTime,
+                excluded_target_account_id: Option<i64>,
+            ) -> (i128, i64);
+            async fn find_by_owner_between(
+                &self,
+                owner_account_id: i64,
+                since: NaiveDateTime,
+                until: NaiveDateTime,
+            ) -> Vec<ActivityEntity>;
+            async fn find_by_owner_after_id(
+                &self,
+                owner_account_id: i64,
+                after_id: i64,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn find_after_id_between(
+                &self,
+                after_id: i64,
+                since: Option<NaiveDateTime>,
+                until: Option<NaiveDateTime>,
+                limit: i64,
+            ) -> Vec<ActivityEntity>;
+            async fn get_balance_until_id(&self, account_id: i64, until_id: i64) -> i128;
+            async fn find_last_id_by_owner(&self, owner_account_id: i64) -> Option<i64>;
+            async fn save(&self, activity_entity: ActivityEntity);
+            async fn save_all(&self, activity_entities: Vec<ActivityEntity>);
+            async fn save_all_with_outbox_messages(
+                &self,
+                activity_entities: Vec<ActivityEntity>,
+                outbox_message_entities: Vec<OutboxMessageEntity>,
+            );
+        }
+    }
+
+    mock! {
+        #[derive(Debug)]
+        ReconciliationRepositoryImpl {}
+        #[async_trait]
+        impl ReconciliationRepository for ReconciliationRepositoryImpl {
+            async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64>;
+            async fn find_snapshot_balances(
+                &self,
+                after_account_id: i64,
+                limit: i64,
+            ) -> Vec<SnapshotBalancesEntity>;
+            async fn delete_snapshot(&self, account_id: i64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_and_discards_account_snapshots() {
+        // Given the snapshot of account 2 misses a deposit
+        let mut reconciliation_repository = Box::new(MockReconciliationRepositoryImpl::new());
+        reconciliation_repository
+            .expect_find_snapshot_balances()
+            .times(1)
+            .with(eq(1), eq(100))
+            .returning(|_, _| {
+                vec![SnapshotBalancesEntity {
+                    account_id: 2,
+                    withdrawal_balance: 70,
+                    deposit_balance: 0,
+                    replayed_withdrawal_balance: 70,
+                    replayed_deposit_balance: 500,
+                }]
+            });
+        reconciliation_repository
+            .expect_delete_snapshot()
+            .times(1)
+            .with(eq(2))
+            .return_const(());
+        let activity_repository = Box::new(MockActivityRepositoryImpl::new());
+
+        let adapter_under_test =
+            ReconciliationPersistenceAdapter::new(activity_repository, reconciliation_repository);
+
+        // When
+        let snapshot_balances = adapter_under_test
+            .load_account_snapshot_balances(Some(AccountId(1)), 100)
+            .await;
+
+        // Then
+        assert_eq!(
+            vec![AccountSnapshotBalances {
+                account_id: AccountId(2),
+                withdrawal_balance: Money::of(70),
+                deposit_balance: Money::of(0),
+                replayed_withdrawal_balance: Money::of(70),
+                replayed_deposit_balance: Money::of(500),
+            }],
+            snapshot_balances
+        );
+        assert!(!snapshot_balances[0].is_consistent());
+
+        // When
+        adapter_under_test
+            .discard_account_snapshot(AccountId(2))
+            .await;
+    }
+}
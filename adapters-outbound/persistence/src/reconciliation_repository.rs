@@ -0,0 +1,100 @@
+use crate::{
+    account_event_mapper::{MONEY_DEPOSITED, MONEY_WITHDRAWN},
+    account_repository,
+};
+use async_trait::async_trait;
+use sqlx::{FromRow, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait ReconciliationRepository: Send + Sync + std::fmt::Debug {
+    /**
+     * @return those of the ids which belong to an account.
+     */
+    async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64>;
+    /**
+     * @return at most `limit` snapshots of the accounts with an id greater than `after_account_id`
+     * along with the balances replayed from the events up to their last event, ordered by account id.
+     */
+    async fn find_snapshot_balances(
+        &self,
+        after_account_id: i64,
+        limit: i64,
+    ) -> Vec<SnapshotBalancesEntity>;
+    async fn delete_snapshot(&self, account_id: i64);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ReconciliationRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl ReconciliationRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ReconciliationRepository for ReconciliationRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_existing_account_ids(&self, account_ids: Vec<i64>) -> Vec<i64> {
+        account_repository::find_existing_account_ids(&self.db_pool, account_ids).await
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_snapshot_balances(
+        &self,
+        after_account_id: i64,
+        limit: i64,
+    ) -> Vec<SnapshotBalancesEntity> {
+        let rows = sqlx::query_as::<_, SnapshotBalancesEntity>(
+            "
+            SELECT s.account_id, s.withdrawal_balance, s.deposit_balance,
+                COALESCE(SUM(CASE WHEN e.event_type = ? THEN e.amount END), 0) AS replayed_withdrawal_balance,
+                COALESCE(SUM(CASE WHEN e.event_type = ? THEN e.amount END), 0) AS replayed_deposit_balance
+            FROM account_snapshot_entity s
+            LEFT JOIN account_event_entity e ON e.account_id = s.account_id AND e.id <= s.last_event_id
+            WHERE s.account_id > ?
+            GROUP BY s.account_id
+            ORDER BY s.account_id
+            LIMIT ?
+            ",
+        )
+        .bind(MONEY_WITHDRAWN)
+        .bind(MONEY_DEPOSITED)
+        .bind(after_account_id)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn delete_snapshot(&self, account_id: i64) {
+        sqlx::query(
+            "
+            DELETE FROM account_snapshot_entity
+            WHERE account_id = ?
+            ",
+        )
+        .bind(account_id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct SnapshotBalancesEntity {
+    pub account_id: i64,
+    pub withdrawal_balance: i64,
+    pub deposit_balance: i64,
+    pub replayed_withdrawal_balance: i64,
+    pub replayed_deposit_balance: i64,
+}
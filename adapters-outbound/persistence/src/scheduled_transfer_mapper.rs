@@ -0,0 +1,69 @@
+use crate::scheduled_transfer_repository::ScheduledTransferEntity;
+use domain::{
+    ar::{
+        account::AccountId,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId, ScheduledTransferStatus},
+    },
+    vo::money::Money,
+};
+
+pub fn map_to_scheduled_transfer(entity: ScheduledTransferEntity) -> ScheduledTransfer {
+    ScheduledTransfer::with_id(
+        entity.id.map(ScheduledTransferId),
+        AccountId(entity.source_account_id),
+        AccountId(entity.target_account_id),
+        Money::of(entity.amount as i128),
+        entity.execution_date,
+        map_to_status(&entity.status),
+        entity.claimed_at,
+        entity.executed_at,
+        entity.failure_reason,
+        entity.created_by,
+        entity.created_by_role.parse().unwrap(),
+    )
+}
+
+pub fn map_to_scheduled_transfer_entity(
+    scheduled_transfer: ScheduledTransfer,
+) -> ScheduledTransferEntity {
+    let amount = scheduled_transfer
+        .money
+        .amount
+        .to_string()
+        .parse::<i128>()
+        .unwrap() as i64;
+    ScheduledTransferEntity {
+        id: scheduled_transfer.id.map(|id| id.0),
+        source_account_id: scheduled_transfer.source_account_id.0,
+        target_account_id: scheduled_transfer.target_account_id.0,
+        amount,
+        execution_date: scheduled_transfer.execution_date,
+        status: map_to_status_column(scheduled_transfer.status).to_string(),
+        claimed_at: scheduled_transfer.claimed_at,
+        executed_at: scheduled_transfer.executed_at,
+        failure_reason: scheduled_transfer.failure_reason,
+        created_by: scheduled_transfer.created_by,
+        created_by_role: scheduled_transfer.created_by_role.to_string(),
+    }
+}
+
+fn map_to_status(status: &str) -> ScheduledTransferStatus {
+    match status {
+        "PENDING" => ScheduledTransferStatus::Pending,
+        "EXECUTING" => ScheduledTransferStatus::Executing,
+        "EXECUTED" => ScheduledTransferStatus::Executed,
+        "FAILED" => ScheduledTransferStatus::Failed,
+        "CANCELLED" => ScheduledTransferStatus::Cancelled,
+        _ => panic!("unknown scheduled transfer status: {}", status),
+    }
+}
+
+pub fn map_to_status_column(status: ScheduledTransferStatus) -> &'static str {
+    match status {
+        ScheduledTransferStatus::Pending => "PENDING",
+        ScheduledTransferStatus::Executing => "EXECUTING",
+        ScheduledTransferStatus::Executed => "EXECUTED",
+        ScheduledTransferStatus::Failed => "FAILED",
+        ScheduledTransferStatus::Cancelled => "CANCELLED",
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    scheduled_transfer_mapper, scheduled_transfer_repository::ScheduledTransferRepository,
+};
+use application::outbound_ports::{LoadScheduledTransferPort, UpdateScheduledTransferStatePort};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::{
+    account::AccountId,
+    scheduled_transfer::{ScheduledTransfer, ScheduledTransferId, ScheduledTransferStatus},
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ScheduledTransferPersistenceAdapter {
+    scheduled_transfer_repository: Box<dyn ScheduledTransferRepository>,
+}
+
+impl ScheduledTransferPersistenceAdapter {
+    // #[inject]
+    pub fn new(scheduled_transfer_repository: Box<dyn ScheduledTransferRepository>) -> Self {
+        Self {
+            scheduled_transfer_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadScheduledTransferPort for ScheduledTransferPersistenceAdapter {
+    async fn load_scheduled_transfer(&self, id: ScheduledTransferId) -> Option<ScheduledTransfer> {
+        let entity = self.scheduled_transfer_repository.find_by_id(id.0).await;
+        debug!(?id, result = ?entity, "find_by_id");
+        entity.map(scheduled_transfer_mapper::map_to_scheduled_transfer)
+    }
+
+    async fn load_scheduled_transfers_of_account(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<ScheduledTransfer> {
+        let entities = self
+            .scheduled_transfer_repository
+            .find_by_account(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entities, "find_by_account");
+        entities
+            .into_iter()
+            .map(scheduled_transfer_mapper::map_to_scheduled_transfer)
+            .collect()
+    }
+
+    async fn load_due_scheduled_transfers(&self, now: NaiveDateTime) -> Vec<ScheduledTransfer> {
+        let entities = self
+            .scheduled_transfer_repository
+            .find_pending_until(now)
+            .await;
+        debug!(until = %now, result = ?entities, "find_pending_until");
+        entities
+            .into_iter()
+            .map(scheduled_transfer_mapper::map_to_scheduled_transfer)
+            .collect()
+    }
+
+    async fn load_scheduled_transfers_claimed_before(
+        &self,
+        claimed_before: NaiveDateTime,
+    ) -> Vec<ScheduledTransfer> {
+        let entities = self
+            .scheduled_transfer_repository
+            .find_executing_claimed_before(claimed_before)
+            .await;
+        debug!(
+            %claimed_before,
+            result = ?entities,
+            "find_executing_claimed_before"
+        );
+        entities
+            .into_iter()
+            .map(scheduled_transfer_mapper::map_to_scheduled_transfer)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateScheduledTransferStatePort for ScheduledTransferPersistenceAdapter {
+    async fn save_scheduled_transfer(
+        &self,
+        scheduled_transfer: ScheduledTransfer,
+    ) -> ScheduledTransferId {
+        let entity =
+            scheduled_transfer_mapper::map_to_scheduled_transfer_entity(scheduled_transfer);
+        debug!(scheduled_transfer_entity = ?entity, "save");
+        ScheduledTransferId(self.scheduled_transfer_repository.save(entity).await)
+    }
+
+    async fn update_scheduled_transfer_state(
+        &self,
+        scheduled_transfer: ScheduledTransfer,
+        expected_status: ScheduledTransferStatus,
+    ) -> bool {
+        let entity =
+            scheduled_transfer_mapper::map_to_scheduled_transfer_entity(scheduled_transfer);
+        let expected_status =
+            scheduled_transfer_mapper::map_to_status_column(expected_status).to_string();
+        debug!(scheduled_transfer_entity = ?entity, %expected_status, "update_state");
+        self.scheduled_transfer_repository
+            .update_state(entity, expected_status)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduled_transfer_repository::ScheduledTransferEntity;
+    use chrono::{NaiveDate, NaiveTime};
+    use domain::vo::{money::Money, role::Role};
+    use mockall::{
+        mock,
+        predicate::{eq, function},
+    };
+
+    mock! {
+        #[derive(Debug)]
+        ScheduledTransferRepositoryImpl {}
+        #[async_trait]
+        impl ScheduledTransferRepository for ScheduledTransferRepositoryImpl {
+            async fn find_by_id(&self, id: i64) -> Option<ScheduledTransferEntity>;
+            async fn find_by_account(&self, account_id: i64) -> Vec<ScheduledTransferEntity>;
+            async fn find_pending_until(&self, until: NaiveDateTime) -> Vec<ScheduledTransferEntity>;
+            async fn find_executing_claimed_before(
+                &self,
+                claimed_before: NaiveDateTime,
+            ) -> Vec<ScheduledTransferEntity>;
+            async fn save(&self, scheduled_transfer_entity: ScheduledTransferEntity) -> i64;
+            async fn update_state(
+                &self,
+                scheduled_transfer_entity: ScheduledTransferEntity,
+                expected_status: String,
+            ) -> bool;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_due_scheduled_transfers() {
+        // Given
+        let mut repository = Box::new(MockScheduledTransferRepositoryImpl::new());
+        repository
+            .expect_find_pending_until()
+            .with(eq(execution_date()))
+            .returning(|_until| {
+                vec![ScheduledTransferEntity {
+                    id: Some(3),
+                    source_account_id: 1,
+                    target_account_id: 2,
+                    amount: 500,
+                    execution_date: execution_date(),
+                    status: "PENDING".to_string(),
+                    claimed_at: None,
+                    executed_at: None,
+                    failure_reason: None,
+                    created_by: "alice".to_string(),
+                    created_by_role: "customer".to_string(),
+                }]
+            });
+
+        // When
+        let adapter_under_test = ScheduledTransferPersistenceAdapter::new(repository);
+        let transfers = adapter_under_test
+            .load_due_scheduled_transfers(execution_date())
+            .await;
+
+        // Then
+        assert_eq!(1, transfers.len());
+        assert_eq!(Some(ScheduledTransferId(3)), transfers[0].id);
+        assert_eq!(ScheduledTransferStatus::Pending, transfers[0].status);
+        assert_eq!(Money::of(500), transfers[0].money);
+        assert_eq!("alice", transfers[0].created_by);
+        assert_eq!(Role::Customer, transfers[0].created_by_role);
+    }
+
+    #[tokio::test]
+    async fn test_saves_scheduled_transfer() {
+        // Given
+        let mut repository = Box::new(MockScheduledTransferRepositoryImpl::new());
+        repository
+            .expect_save()
+            .times(1)
+            .with(function(|e: &ScheduledTransferEntity| {
+                e.id.is_none()
+                    && e.status == "PENDING"
+                    && e.amount == 500
+                    && e.created_by == "alice"
+                    && e.created_by_role == "customer"
+            }))
+            .return_const(9);
+
+        // When
+        let adapter_under_test = ScheduledTransferPersistenceAdapter::new(repository);
+        let id = adapter_under_test
+            .save_scheduled_transfer(ScheduledTransfer::new(
+                AccountId(1),
+                AccountId(2),
+                Money::of(500),
+                execution_date(),
+                "alice".to_string(),
+                Role::Customer,
+            ))
+            .await;
+
+        // Then
+        assert_eq!(ScheduledTransferId(9), id);
+    }
+
+    #[tokio::test]
+    async fn test_updates_state_only_from_the_expected_status() {
+        // Given a transfer that was claimed by another executor
+        let mut repository = Box::new(MockScheduledTransferRepositoryImpl::new());
+        repository
+            .expect_update_state()
+            .times(1)
+            .with(
+                function(|e: &ScheduledTransferEntity| {
+                    e.id == Some(3)
+                        && e.status == "EXECUTING"
+                        && e.claimed_at == Some(execution_date())
+                }),
+                eq("PENDING".to_string()),
+            )
+            .return_const(false);
+
+        // When
+        let mut scheduled_transfer = ScheduledTransfer::new(
+            AccountId(1),
+            AccountId(2),
+            Money::of(500),
+            execution_date(),
+            "alice".to_string(),
+            Role::Customer,
+        );
+        scheduled_transfer.id = Some(ScheduledTransferId(3));
+        scheduled_transfer.start_execution(execution_date());
+        let adapter_under_test = ScheduledTransferPersistenceAdapter::new(repository);
+        let claimed = adapter_under_test
+            .update_scheduled_transfer_state(scheduled_transfer, ScheduledTransferStatus::Pending)
+            .await;
+
+        // Then
+        assert!(!claimed);
+    }
+
+    fn execution_date() -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 9, 1).unwrap(),
+            NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        )
+    }
+}
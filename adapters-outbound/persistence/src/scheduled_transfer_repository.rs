@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait ScheduledTransferRepository: Send + Sync + std::fmt::Debug {
+    async fn find_by_id(&self, id: i64) -> Option<ScheduledTransferEntity>;
+    async fn find_by_account(&self, account_id: i64) -> Vec<ScheduledTransferEntity>;
+    async fn find_pending_until(&self, until: NaiveDateTime) -> Vec<ScheduledTransferEntity>;
+    async fn find_executing_claimed_before(
+        &self,
+        claimed_before: NaiveDateTime,
+    ) -> Vec<ScheduledTransferEntity>;
+    async fn save(&self, scheduled_transfer_entity: ScheduledTransferEntity) -> i64;
+    async fn update_state(
+        &self,
+        scheduled_transfer_entity: ScheduledTransferEntity,
+        expected_status: String,
+    ) -> bool;
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ScheduledTransferRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl ScheduledTransferRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ScheduledTransferRepository for ScheduledTransferRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_id(&self, id: i64) -> Option<ScheduledTransferEntity> {
+        sqlx::query_as::<_, ScheduledTransferEntity>(
+            "
+            SELECT * FROM scheduled_transfer_entity
+            WHERE id = ?
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_account(&self, account_id: i64) -> Vec<ScheduledTransferEntity> {
+        let rows = sqlx::query_as::<_, ScheduledTransferEntity>(
+            "
+            SELECT * FROM scheduled_transfer_entity
+            WHERE source_account_id = ?
+            OR target_account_id = ?
+            ORDER BY execution_date
+            ",
+        )
+        .bind(account_id)
+        .bind(account_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_pending_until(&self, until: NaiveDateTime) -> Vec<ScheduledTransferEntity> {
+        let rows = sqlx::query_as::<_, ScheduledTransferEntity>(
+            "
+            SELECT * FROM scheduled_transfer_entity
+            WHERE status = 'PENDING'
+            AND execution_date <= ?
+            ORDER BY execution_date, id
+            ",
+        )
+        .bind(until)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_executing_claimed_before(
+        &self,
+        claimed_before: NaiveDateTime,
+    ) -> Vec<ScheduledTransferEntity> {
+        let rows = sqlx::query_as::<_, ScheduledTransferEntity>(
+            "
+            SELECT * FROM scheduled_transfer_entity
+            WHERE status = 'EXECUTING'
+            AND (claimed_at IS NULL OR claimed_at <= ?)
+            ORDER BY execution_date, id
+            ",
+        )
+        .bind(claimed_before)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save(&self, scheduled_transfer_entity: ScheduledTransferEntity) -> i64 {
+        sqlx::query(
+            "
+            INSERT INTO scheduled_transfer_entity (source_account_id, target_account_id, amount, execution_date, status, claimed_at, executed_at, failure_reason, created_by, created_by_role)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(scheduled_transfer_entity.source_account_id)
+        .bind(scheduled_transfer_entity.target_account_id)
+        .bind(scheduled_transfer_entity.amount)
+        .bind(scheduled_transfer_entity.execution_date)
+        .bind(scheduled_transfer_entity.status)
+        .bind(scheduled_transfer_entity.claimed_at)
+        .bind(scheduled_transfer_entity.executed_at)
+        .bind(scheduled_transfer_entity.failure_reason)
+        .bind(scheduled_transfer_entity.created_by)
+        .bind(scheduled_transfer_entity.created_by_role)
+        .execute(&self.db_pool)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn update_state(
+        &self,
+        scheduled_transfer_entity: ScheduledTransferEntity,
+        expected_status: String,
+    ) -> bool {
+        // compare and set, so that a claimed transfer is neither executed twice nor cancelled
+        sqlx::query(
+            "
+            UPDATE scheduled_transfer_entity
+            SET status = ?, claimed_at = ?, executed_at = ?, failure_reason = ?
+            WHERE id = ?
+            AND status = ?
+            ",
+        )
+        .bind(scheduled_transfer_entity.status)
+        .bind(scheduled_transfer_entity.claimed_at)
+        .bind(scheduled_transfer_entity.executed_at)
+        .bind(scheduled_transfer_entity.failure_reason)
+        .bind(scheduled_transfer_entity.id)
+        .bind(expected_status)
+        .execute(&self.db_pool)
+        .await
+        .unwrap()
+        .rows_affected()
+            == 1
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct ScheduledTransferEntity {
+    pub id: Option<i64>,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub execution_date: NaiveDateTime,
+    pub status: String,
+    pub claimed_at: Option<NaiveDateTime>,
+    pub executed_at: Option<NaiveDateTime>,
+    pub failure_reason: Option<String>,
+    pub created_by: String,
+    pub created_by_role: String,
+}
@@ -0,0 +1,106 @@
+use crate::standing_order_repository::{StandingOrderEntity, StandingOrderExecutionEntity};
+use domain::{
+    ar::{
+        account::AccountId,
+        standing_order::{
+            StandingOrder, StandingOrderExecution, StandingOrderExecutionId,
+            StandingOrderExecutionStatus, StandingOrderId, StandingOrderStatus,
+        },
+    },
+    vo::money::Money,
+};
+
+pub fn map_to_standing_order(entity: StandingOrderEntity) -> StandingOrder {
+    StandingOrder {
+        id: entity.id.map(StandingOrderId),
+        source_account_id: AccountId(entity.source_account_id),
+        target_account_id: AccountId(entity.target_account_id),
+        money: Money::of(entity.amount as i128),
+        recurrence: entity
+            .recurrence
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid recurrence: {}", entity.recurrence)),
+        start_date: entity.start_date,
+        end_date: entity.end_date,
+        max_occurrences: entity.max_occurrences.map(|max| max as u32),
+        executed_occurrences: entity.executed_occurrences as u32,
+        next_execution_date: entity.next_execution_date,
+        status: match entity.status.as_str() {
+            "ACTIVE" => StandingOrderStatus::Active,
+            "PAUSED" => StandingOrderStatus::Paused,
+            "CANCELLED" => StandingOrderStatus::Cancelled,
+            "COMPLETED" => StandingOrderStatus::Completed,
+            status => panic!("unknown standing order status: {}", status),
+        },
+        created_by: entity.created_by,
+        created_by_role: entity.created_by_role.parse().unwrap(),
+    }
+}
+
+pub fn map_to_standing_order_entity(standing_order: StandingOrder) -> StandingOrderEntity {
+    let amount = standing_order
+        .money
+        .amount
+        .to_string()
+        .parse::<i128>()
+        .unwrap() as i64;
+    StandingOrderEntity {
+        id: standing_order.id.map(|id| id.0),
+        source_account_id: standing_order.source_account_id.0,
+        target_account_id: standing_order.target_account_id.0,
+        amount,
+        recurrence: standing_order.recurrence.to_string(),
+        start_date: standing_order.start_date,
+        end_date: standing_order.end_date,
+        max_occurrences: standing_order.max_occurrences.map(|max| max as i64),
+        executed_occurrences: standing_order.executed_occurrences as i64,
+        next_execution_date: standing_order.next_execution_date,
+        status: match standing_order.status {
+            StandingOrderStatus::Active => "ACTIVE",
+            StandingOrderStatus::Paused => "PAUSED",
+            StandingOrderStatus::Cancelled => "CANCELLED",
+            StandingOrderStatus::Completed => "COMPLETED",
+        }
+        .to_string(),
+        created_by: standing_order.created_by,
+        created_by_role: standing_order.created_by_role.to_string(),
+    }
+}
+
+pub fn map_to_standing_order_execution(
+    entity: StandingOrderExecutionEntity,
+) -> StandingOrderExecution {
+    StandingOrderExecution {
+        id: entity.id.map(StandingOrderExecutionId),
+        standing_order_id: StandingOrderId(entity.standing_order_id),
+        scheduled_for: entity.scheduled_for,
+        started_at: entity.started_at,
+        finished_at: entity.finished_at,
+        status: match entity.status.as_str() {
+            "STARTED" => StandingOrderExecutionStatus::Started,
+            "SUCCEEDED" => StandingOrderExecutionStatus::Succeeded,
+            "FAILED" => StandingOrderExecutionStatus::Failed,
+            status => panic!("unknown standing order execution status: {}", status),
+        },
+        failure_reason: entity.failure_reason,
+    }
+}
+
+pub fn map_to_standing_order_execution_entity(
+    execution: StandingOrderExecution,
+) -> StandingOrderExecutionEntity {
+    StandingOrderExecutionEntity {
+        id: execution.id.map(|id| id.0),
+        standing_order_id: execution.standing_order_id.0,
+        scheduled_for: execution.scheduled_for,
+        started_at: execution.started_at,
+        finished_at: execution.finished_at,
+        status: match execution.status {
+            StandingOrderExecutionStatus::Started => "STARTED",
+            StandingOrderExecutionStatus::Succeeded => "SUCCEEDED",
+            StandingOrderExecutionStatus::Failed => "FAILED",
+        }
+        .to_string(),
+        failure_reason: execution.failure_reason,
+    }
+}
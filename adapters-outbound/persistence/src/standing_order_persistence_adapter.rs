@@ -0,0 +1,228 @@
+use crate::{standing_order_mapper, standing_order_repository::StandingOrderRepository};
+use application::outbound_ports::{LoadStandingOrderPort, UpdateStandingOrderStatePort};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::{
+    account::AccountId,
+    standing_order::{
+        StandingOrder, StandingOrderExecution, StandingOrderExecutionId, StandingOrderId,
+    },
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct StandingOrderPersistenceAdapter {
+    standing_order_repository: Box<dyn StandingOrderRepository>,
+}
+
+impl StandingOrderPersistenceAdapter {
+    // #[inject]
+    pub fn new(standing_order_repository: Box<dyn StandingOrderRepository>) -> Self {
+        Self {
+            standing_order_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadStandingOrderPort for StandingOrderPersistenceAdapter {
+    async fn load_standing_order(&self, id: StandingOrderId) -> Option<StandingOrder> {
+        let entity = self.standing_order_repository.find_by_id(id.0).await;
+        debug!(?id, result = ?entity, "find_by_id");
+        entity.map(standing_order_mapper::map_to_standing_order)
+    }
+
+    async fn load_standing_orders_of_account(&self, account_id: AccountId) -> Vec<StandingOrder> {
+        let entities = self
+            .standing_order_repository
+            .find_by_account(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entities, "find_by_account");
+        entities
+            .into_iter()
+            .map(standing_order_mapper::map_to_standing_order)
+            .collect()
+    }
+
+    async fn load_due_standing_orders(&self, now: NaiveDateTime) -> Vec<StandingOrder> {
+        let entities = self
+            .standing_order_repository
+            .find_active_due_until(now)
+            .await;
+        debug!(until = %now, result = ?entities, "find_active_due_until");
+        entities
+            .into_iter()
+            .map(standing_order_mapper::map_to_standing_order)
+            .collect()
+    }
+
+    async fn load_standing_order_executions(
+        &self,
+        id: StandingOrderId,
+    ) -> Vec<StandingOrderExecution> {
+        let entities = self.standing_order_repository.find_executions(id.0).await;
+        debug!(standing_order_id = ?id, result = ?entities, "find_executions");
+        entities
+            .into_iter()
+            .map(standing_order_mapper::map_to_standing_order_execution)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateStandingOrderStatePort for StandingOrderPersistenceAdapter {
+    async fn save_standing_order(&self, standing_order: StandingOrder) -> StandingOrderId {
+        let entity = standing_order_mapper::map_to_standing_order_entity(standing_order);
+        debug!(standing_order_entity = ?entity, "save");
+        StandingOrderId(self.standing_order_repository.save(entity).await)
+    }
+
+    async fn claim_standing_order_execution(
+        &self,
+        standing_order: StandingOrder,
+        execution: StandingOrderExecution,
+    ) -> Option<StandingOrderExecutionId> {
+        let standing_order_entity =
+            standing_order_mapper::map_to_standing_order_entity(standing_order);
+        let execution_entity =
+            standing_order_mapper::map_to_standing_order_execution_entity(execution);
+        debug!(?standing_order_entity, ?execution_entity, "claim_execution");
+        self.standing_order_repository
+            .claim_execution(standing_order_entity, execution_entity)
+            .await
+            .map(StandingOrderExecutionId)
+    }
+
+    async fn save_standing_order_execution(&self, execution: StandingOrderExecution) {
+        let entity = standing_order_mapper::map_to_standing_order_execution_entity(execution);
+        debug!(execution_entity = ?entity, "save_execution");
+        self.standing_order_repository.save_execution(entity).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standing_order_repository::{StandingOrderEntity, StandingOrderExecutionEntity};
+    use chrono::NaiveDate;
+    use domain::{
+        ar::standing_order::StandingOrderStatus,
+        vo::{money::Money, recurrence::Recurrence, role::Role},
+    };
+    use mockall::{
+        mock,
+        predicate::{eq, function},
+    };
+
+    mock! {
+        #[derive(Debug)]
+        StandingOrderRepositoryImpl {}
+        #[async_trait]
+        impl StandingOrderRepository for StandingOrderRepositoryImpl {
+            async fn find_by_id(&self, id: i64) -> Option<StandingOrderEntity>;
+            async fn find_by_account(&self, account_id: i64) -> Vec<StandingOrderEntity>;
+            async fn find_active_due_until(&self, until: NaiveDateTime) -> Vec<StandingOrderEntity>;
+            async fn save(&self, standing_order_entity: StandingOrderEntity) -> i64;
+            async fn find_executions(&self, standing_order_id: i64) -> Vec<StandingOrderExecutionEntity>;
+            async fn claim_execution(
+                &self,
+                standing_order_entity: StandingOrderEntity,
+                execution_entity: StandingOrderExecutionEntity,
+            ) -> Option<i64>;
+            async fn save_execution(&self, execution_entity: StandingOrderExecutionEntity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_standing_order() {
+        // Given
+        let mut repository = Box::new(MockStandingOrderRepositoryImpl::new());
+        repository.expect_find_by_id().with(eq(3)).returning(|id| {
+            Some(StandingOrderEntity {
+                id: Some(id),
+                source_account_id: 1,
+                target_account_id: 2,
+                amount: 100,
+                recurrence: "0 8 1 * *".to_string(),
+                start_date: start_date(),
+                end_date: None,
+                max_occurrences: Some(12),
+                executed_occurrences: 1,
+                next_execution_date: Some(start_date()),
+                status: "PAUSED".to_string(),
+                created_by: "alice".to_string(),
+                created_by_role: "customer".to_string(),
+            })
+        });
+
+        // When
+        let adapter_under_test = StandingOrderPersistenceAdapter::new(repository);
+        let standing_order = adapter_under_test
+            .load_standing_order(StandingOrderId(3))
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(StandingOrderStatus::Paused, standing_order.status);
+        assert_eq!(
+            "0 8 1 * *".parse::<Recurrence>().unwrap(),
+            standing_order.recurrence
+        );
+        assert_eq!(Some(12), standing_order.max_occurrences);
+        assert_eq!(Money::of(100), standing_order.money);
+        assert_eq!("alice", standing_order.created_by);
+        assert_eq!(Role::Customer, standing_order.created_by_role);
+    }
+
+    #[tokio::test]
+    async fn test_claims_execution() {
+        // Given
+        let mut repository = Box::new(MockStandingOrderRepositoryImpl::new());
+        repository
+            .expect_claim_execution()
+            .times(1)
+            .with(
+                function(|so: &StandingOrderEntity| {
+                    so.executed_occurrences == 1 && so.recurrence == "MONTHLY"
+                }),
+                function(|e: &StandingOrderExecutionEntity| {
+                    e.status == "STARTED" && e.scheduled_for == start_date()
+                }),
+            )
+            .return_const(Some(5));
+
+        let mut standing_order = StandingOrder::new(
+            AccountId(1),
+            AccountId(2),
+            Money::of(100),
+            Recurrence::Monthly,
+            start_date(),
+            None,
+            None,
+            "alice".to_string(),
+            Role::Customer,
+        );
+        standing_order.id = Some(StandingOrderId(3));
+        let scheduled_for = standing_order.advance().unwrap();
+
+        // When
+        let adapter_under_test = StandingOrderPersistenceAdapter::new(repository);
+        let execution_id = adapter_under_test
+            .claim_standing_order_execution(
+                standing_order,
+                StandingOrderExecution::started(StandingOrderId(3), scheduled_for, start_date()),
+            )
+            .await;
+
+        // Then
+        assert_eq!(Some(StandingOrderExecutionId(5)), execution_id);
+    }
+
+    fn start_date() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 1)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap()
+    }
+}
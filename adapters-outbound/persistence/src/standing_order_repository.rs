@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait StandingOrderRepository: Send + Sync + std::fmt::Debug {
+    async fn find_by_id(&self, id: i64) -> Option<StandingOrderEntity>;
+    async fn find_by_account(&self, account_id: i64) -> Vec<StandingOrderEntity>;
+    async fn find_active_due_until(&self, until: NaiveDateTime) -> Vec<StandingOrderEntity>;
+    async fn save(&self, standing_order_entity: StandingOrderEntity) -> i64;
+    async fn find_executions(&self, standing_order_id: i64) -> Vec<StandingOrderExecutionEntity>;
+    async fn claim_execution(
+        &self,
+        standing_order_entity: StandingOrderEntity,
+        execution_entity: StandingOrderExecutionEntity,
+    ) -> Option<i64>;
+    async fn save_execution(&self, execution_entity: StandingOrderExecutionEntity);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct StandingOrderRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl StandingOrderRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    async fn update<'e, E: Executor<'e, Database = Sqlite>>(
+        executor: E,
+        standing_order_entity: &StandingOrderEntity,
+    ) {
+        sqlx::query(
+            "
+            UPDATE standing_order_entity
+            SET executed_occurrences = ?, next_execution_date = ?, status = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(standing_order_entity.executed_occurrences)
+        .bind(standing_order_entity.next_execution_date)
+        .bind(&standing_order_entity.status)
+        .bind(standing_order_entity.id)
+        .execute(executor)
+        .await
+        .unwrap();
+    }
+}
+
+#[async_trait]
+impl StandingOrderRepository for StandingOrderRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_id(&self, id: i64) -> Option<StandingOrderEntity> {
+        sqlx::query_as::<_, StandingOrderEntity>(
+            "
+            SELECT * FROM standing_order_entity
+            WHERE id = ?
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_by_account(&self, account_id: i64) -> Vec<StandingOrderEntity> {
+        let rows = sqlx::query_as::<_, StandingOrderEntity>(
+            "
+            SELECT * FROM standing_order_entity
+            WHERE source_account_id = ?
+            OR target_account_id = ?
+            ORDER BY id
+            ",
+        )
+        .bind(account_id)
+        .bind(account_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_active_due_until(&self, until: NaiveDateTime) -> Vec<StandingOrderEntity> {
+        let rows = sqlx::query_as::<_, StandingOrderEntity>(
+            "
+            SELECT * FROM standing_order_entity
+            WHERE status = 'ACTIVE'
+            AND next_execution_date <= ?
+            ORDER BY next_execution_date, id
+            ",
+        )
+        .bind(until)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save(&self, standing_order_entity: StandingOrderEntity) -> i64 {
+        if let Some(id) = standing_order_entity.id {
+            Self::update(&self.db_pool, &standing_order_entity).await;
+            return id;
+        }
+        sqlx::query(
+            "
+            INSERT INTO standing_order_entity (source_account_id, target_account_id, amount, recurrence, start_date, end_date, max_occurrences, executed_occurrences, next_execution_date, status, created_by, created_by_role)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(standing_order_entity.source_account_id)
+        .bind(standing_order_entity.target_account_id)
+        .bind(standing_order_entity.amount)
+        .bind(standing_order_entity.recurrence)
+        .bind(standing_order_entity.start_date)
+        .bind(standing_order_entity.end_date)
+        .bind(standing_order_entity.max_occurrences)
+        .bind(standing_order_entity.executed_occurrences)
+        .bind(standing_order_entity.next_execution_date)
+        .bind(standing_order_entity.status)
+        .bind(standing_order_entity.created_by)
+        .bind(standing_order_entity.created_by_role)
+        .execute(&self.db_pool)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_executions(&self, standing_order_id: i64) -> Vec<StandingOrderExecutionEntity> {
+        let rows = sqlx::query_as::<_, StandingOrderExecutionEntity>(
+            "
+            SELECT * FROM standing_order_execution_entity
+            WHERE standing_order_id = ?
+            ORDER BY scheduled_for
+            ",
+        )
+        .bind(standing_order_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn claim_execution(
+        &self,
+        standing_order_entity: StandingOrderEntity,
+        execution_entity: StandingOrderExecutionEntity,
+    ) -> Option<i64> {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        // the unique (standing_order_id, scheduled_for) constraint turns a second claim into a no-op
+        let result = sqlx::query(
+            "
+            INSERT OR IGNORE INTO standing_order_execution_entity (standing_order_id, scheduled_for, started_at, finished_at, status, failure_reason)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(execution_entity.standing_order_id)
+        .bind(execution_entity.scheduled_for)
+        .bind(execution_entity.started_at)
+        .bind(execution_entity.finished_at)
+        .bind(execution_entity.status)
+        .bind(execution_entity.failure_reason)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        if result.rows_affected() == 0 {
+            tx.rollback().await.unwrap();
+            return None;
+        }
+        // advance the schedule only while the order is active, so that neither a pause nor a
+        // cancellation made in the meantime is reverted
+        let advanced = sqlx::query(
+            "
+            UPDATE standing_order_entity
+            SET executed_occurrences = ?, next_execution_date = ?, status = ?
+            WHERE id = ?
+            AND status = 'ACTIVE'
+            ",
+        )
+        .bind(standing_order_entity.executed_occurrences)
+        .bind(standing_order_entity.next_execution_date)
+        .bind(standing_order_entity.status)
+        .bind(standing_order_entity.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        if advanced.rows_affected() == 0 {
+            tx.rollback().await.unwrap();
+            return None;
+        }
+        tx.commit().await.unwrap();
+        Some(result.last_insert_rowid())
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_execution(&self, execution_entity: StandingOrderExecutionEntity) {
+        sqlx::query(
+            "
+            UPDATE standing_order_execution_entity
+            SET finished_at = ?, status = ?, failure_reason = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(execution_entity.finished_at)
+        .bind(execution_entity.status)
+        .bind(execution_entity.failure_reason)
+        .bind(execution_entity.id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct StandingOrderEntity {
+    pub id: Option<i64>,
+    pub source_account_id: i64,
+    pub target_account_id: i64,
+    pub amount: i64,
+    pub recurrence: String,
+    pub start_date: NaiveDateTime,
+    pub end_date: Option<NaiveDateTime>,
+    pub max_occurrences: Option<i64>,
+    pub executed_occurrences: i64,
+    pub next_execution_date: Option<NaiveDateTime>,
+    pub status: String,
+    pub created_by: String,
+    pub created_by_role: String,
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct StandingOrderExecutionEntity {
+    pub id: Option<i64>,
+    pub standing_order_id: i64,
+    pub scheduled_for: NaiveDateTime,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub failure_reason: Option<String>,
+}
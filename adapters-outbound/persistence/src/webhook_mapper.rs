@@ -0,0 +1,115 @@
+use crate::{
+    account_mapper::map_to_amount,
+    webhook_repository::{WebhookDeliveryEntity, WebhookSubscriptionEntity},
+};
+use domain::{
+    ar::{
+        account::AccountId,
+        domain_event::DomainEvent,
+        webhook_subscription::{
+            WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookSubscription,
+            WebhookSubscriptionId,
+        },
+    },
+    vo::money::Money,
+};
+
+pub fn map_to_webhook_subscription(entity: WebhookSubscriptionEntity) -> WebhookSubscription {
+    let mut webhook_subscription = WebhookSubscription::new(
+        AccountId(entity.account_id),
+        entity.url,
+        entity.secret,
+        entity.created_at,
+    );
+    webhook_subscription.id = entity.id.map(WebhookSubscriptionId);
+    webhook_subscription
+}
+
+pub fn map_to_webhook_subscription_entity(
+    webhook_subscription: WebhookSubscription,
+) -> WebhookSubscriptionEntity {
+    WebhookSubscriptionEntity {
+        id: webhook_subscription.id.map(|id| id.0),
+        account_id: webhook_subscription.account_id.0,
+        url: webhook_subscription.url,
+        secret: webhook_subscription.secret,
+        created_at: webhook_subscription.created_at,
+    }
+}
+
+pub fn map_to_webhook_delivery(entity: WebhookDeliveryEntity) -> WebhookDelivery {
+    let account_id = AccountId(entity.account_id);
+    let counterpart_account_id = AccountId(entity.counterpart_account_id);
+    let money = Money::of(entity.amount as i128);
+    let timestamp = entity.timestamp;
+    let domain_event = match entity.event_type.as_str() {
+        "MONEY_WITHDRAWN" => DomainEvent::MoneyWithdrawn {
+            account_id,
+            target_account_id: counterpart_account_id,
+            money,
+            timestamp,
+        },
+        "MONEY_DEPOSITED" => DomainEvent::MoneyDeposited {
+            account_id,
+            source_account_id: counterpart_account_id,
+            money,
+            timestamp,
+        },
+        event_type => panic!("unknown webhook event type: {}", event_type),
+    };
+    WebhookDelivery {
+        id: entity.id.map(WebhookDeliveryId),
+        subscription_id: WebhookSubscriptionId(entity.subscription_id),
+        domain_event,
+        status: match entity.status.as_str() {
+            "DELIVERED" => WebhookDeliveryStatus::Delivered,
+            "DEAD_LETTERED" => WebhookDeliveryStatus::DeadLettered,
+            _ => WebhookDeliveryStatus::Pending,
+        },
+        attempts: entity.attempts as u32,
+        next_attempt_at: entity.next_attempt_at,
+        last_error: entity.last_error,
+        finished_at: entity.finished_at,
+    }
+}
+
+/**
+ * Only withdrawals and deposits are notified by webhooks.
+ */
+pub fn map_to_webhook_delivery_entity(webhook_delivery: WebhookDelivery) -> WebhookDeliveryEntity {
+    let (event_type, account_id, counterpart_account_id, money) =
+        match &webhook_delivery.domain_event {
+            DomainEvent::MoneyWithdrawn {
+                account_id,
+                target_account_id,
+                money,
+                ..
+            } => ("MONEY_WITHDRAWN", account_id, target_account_id, money),
+            DomainEvent::MoneyDeposited {
+                account_id,
+                source_account_id,
+                money,
+                ..
+            } => ("MONEY_DEPOSITED", account_id, source_account_id, money),
+            domain_event => panic!("not notified by webhooks: {:?}", domain_event),
+        };
+    WebhookDeliveryEntity {
+        id: webhook_delivery.id.as_ref().map(|id| id.0),
+        subscription_id: webhook_delivery.subscription_id.0,
+        event_type: event_type.to_string(),
+        account_id: account_id.0,
+        counterpart_account_id: counterpart_account_id.0,
+        amount: map_to_amount(money),
+        timestamp: webhook_delivery.domain_event.timestamp(),
+        status: match webhook_delivery.status {
+            WebhookDeliveryStatus::Pending => "PENDING",
+            WebhookDeliveryStatus::Delivered => "DELIVERED",
+            WebhookDeliveryStatus::DeadLettered => "DEAD_LETTERED",
+        }
+        .to_string(),
+        attempts: webhook_delivery.attempts as i64,
+        next_attempt_at: webhook_delivery.next_attempt_at,
+        last_error: webhook_delivery.last_error,
+        finished_at: webhook_delivery.finished_at,
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{webhook_mapper, webhook_repository::WebhookRepository};
+use application::outbound_ports::{
+    LoadWebhookDeliveryPort, LoadWebhookSubscriptionPort, UpdateWebhookDeliveryStatePort,
+    UpdateWebhookSubscriptionStatePort,
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use domain::ar::{
+    account::AccountId,
+    webhook_subscription::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId},
+};
+use tracing::debug;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct WebhookPersistenceAdapter {
+    webhook_repository: Box<dyn WebhookRepository>,
+}
+
+impl WebhookPersistenceAdapter {
+    // #[inject]
+    pub fn new(webhook_repository: Box<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+}
+
+#[async_trait]
+impl LoadWebhookSubscriptionPort for WebhookPersistenceAdapter {
+    async fn load_webhook_subscription(
+        &self,
+        id: WebhookSubscriptionId,
+    ) -> Option<WebhookSubscription> {
+        let entity = self.webhook_repository.find_subscription_by_id(id.0).await;
+        debug!(?id, result = ?entity, "find_subscription_by_id");
+        entity.map(webhook_mapper::map_to_webhook_subscription)
+    }
+
+    async fn load_webhook_subscriptions_of_account(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<WebhookSubscription> {
+        let entities = self
+            .webhook_repository
+            .find_subscriptions_by_account(account_id.0)
+            .await;
+        debug!(?account_id, result = ?entities, "find_subscriptions_by_account");
+        entities
+            .into_iter()
+            .map(webhook_mapper::map_to_webhook_subscription)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateWebhookSubscriptionStatePort for WebhookPersistenceAdapter {
+    async fn save_webhook_subscription(
+        &self,
+        webhook_subscription: WebhookSubscription,
+    ) -> WebhookSubscriptionId {
+        let entity = webhook_mapper::map_to_webhook_subscription_entity(webhook_subscription);
+        debug!(webhook_subscription_entity = ?entity, "save_subscription");
+        WebhookSubscriptionId(self.webhook_repository.save_subscription(entity).await)
+    }
+
+    async fn delete_webhook_subscription(&self, id: WebhookSubscriptionId) -> bool {
+        debug!(?id, "delete_subscription");
+        self.webhook_repository.delete_subscription(id.0).await
+    }
+}
+
+#[async_trait]
+impl LoadWebhookDeliveryPort for WebhookPersistenceAdapter {
+    async fn load_due_webhook_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: usize,
+    ) -> Vec<WebhookDelivery> {
+        let entities = self
+            .webhook_repository
+            .find_pending_deliveries_due_until(now, limit as i64)
+            .await;
+        debug!(until = %now, %limit, result = ?entities, "find_pending_deliveries_due_until");
+        entities
+            .into_iter()
+            .map(webhook_mapper::map_to_webhook_delivery)
+            .collect()
+    }
+
+    async fn load_webhook_deliveries(
+        &self,
+        subscription_id: WebhookSubscriptionId,
+    ) -> Vec<WebhookDelivery> {
+        let entities = self
+            .webhook_repository
+            .find_deliveries_by_subscription(subscription_id.0)
+            .await;
+        debug!(?subscription_id, result = ?entities, "find_deliveries_by_subscription");
+        entities
+            .into_iter()
+            .map(webhook_mapper::map_to_webhook_delivery)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateWebhookDeliveryStatePort for WebhookPersistenceAdapter {
+    async fn schedule_webhook_deliveries(&self, webhook_deliveries: Vec<WebhookDelivery>) {
+        let entities: Vec<_> = webhook_deliveries
+            .into_iter()
+            .map(webhook_mapper::map_to_webhook_delivery_entity)
+            .collect();
+        debug!(webhook_delivery_entities = ?entities, "insert_deliveries");
+        self.webhook_repository.insert_deliveries(entities).await;
+    }
+
+    async fn save_webhook_delivery(&self, webhook_delivery: WebhookDelivery) {
+        let entity = webhook_mapper::map_to_webhook_delivery_entity(webhook_delivery);
+        debug!(webhook_delivery_entity = ?entity, "update_delivery_state");
+        self.webhook_repository.update_delivery_state(entity).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook_repository::{WebhookDeliveryEntity, WebhookSubscriptionEntity};
+    use chrono::NaiveDate;
+    use domain::{
+        ar::{
+            domain_event::DomainEvent,
+            webhook_subscription::{WebhookDeliveryId, WebhookDeliveryStatus},
+        },
+        vo::money::Money,
+    };
+    use mockall::{mock, predicate::eq};
+
+    mock! {
+        #[derive(Debug)]
+        WebhookRepositoryImpl {}
+        #[async_trait]
+        impl WebhookRepository for WebhookRepositoryImpl {
+            async fn find_subscription_by_id(&self, id: i64) -> Option<WebhookSubscriptionEntity>;
+            async fn find_subscriptions_by_account(
+                &self,
+                account_id: i64,
+            ) -> Vec<WebhookSubscriptionEntity>;
+            async fn save_subscription(
+                &self,
+                webhook_subscription_entity: WebhookSubscriptionEntity,
+            ) -> i64;
+            async fn delete_subscription(&self, id: i64) -> bool;
+            async fn find_pending_deliveries_due_until(
+                &self,
+                until: NaiveDateTime,
+                limit: i64,
+            ) -> Vec<WebhookDeliveryEntity>;
+            async fn find_deliveries_by_subscription(
+                &self,
+                subscription_id: i64,
+            ) -> Vec<WebhookDeliveryEntity>;
+            async fn insert_deliveries(&self, webhook_delivery_entities: Vec<WebhookDeliveryEntity>);
+            async fn update_delivery_state(&self, webhook_delivery_entity: WebhookDeliveryEntity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_loads_due_deliveries_and_records_their_outcome() {
+        // Given a withdrawal waiting to be delivered
+        let mut repository = Box::new(MockWebhookRepositoryImpl::new());
+        repository
+            .expect_find_pending_deliveries_due_until()
+            .with(eq(timestamp()), eq(10))
+            .returning(|_, _| vec![withdrawal_entity("PENDING", None)]);
+        // And the delivered state is stored
+        repository
+            .expect_update_delivery_state()
+            .times(1)
+            .with(eq(withdrawal_entity("DELIVERED", Some(timestamp()))))
+            .return_const(());
+
+        // When
+        let adapter_under_test = WebhookPersistenceAdapter::new(repository);
+        let mut webhook_deliveries = adapter_under_test
+            .load_due_webhook_deliveries(timestamp(), 10)
+            .await;
+
+        // Then
+        assert_eq!(1, webhook_deliveries.len());
+        assert_eq!(Some(WebhookDeliveryId(3)), webhook_deliveries[0].id);
+        assert_eq!(WebhookDeliveryStatus::Pending, webhook_deliveries[0].status);
+        assert_eq!(
+            DomainEvent::MoneyWithdrawn {
+                account_id: AccountId(41),
+                target_account_id: AccountId(42),
+                money: Money::of(500),
+                timestamp: timestamp(),
+            },
+            webhook_deliveries[0].domain_event
+        );
+
+        // When
+        let mut webhook_delivery = webhook_deliveries.remove(0);
+        webhook_delivery.mark_delivered(timestamp());
+        adapter_under_test
+            .save_webhook_delivery(webhook_delivery)
+            .await;
+    }
+
+    fn withdrawal_entity(
+        status: &str,
+        finished_at: Option<NaiveDateTime>,
+    ) -> WebhookDeliveryEntity {
+        WebhookDeliveryEntity {
+            id: Some(3),
+            subscription_id: 7,
+            event_type: "MONEY_WITHDRAWN".to_string(),
+            account_id: 41,
+            counterpart_account_id: 42,
+            amount: 500,
+            timestamp: timestamp(),
+            status: status.to_string(),
+            attempts: if finished_at.is_some() { 1 } else { 0 },
+            next_attempt_at: timestamp(),
+            last_error: None,
+            finished_at,
+        }
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
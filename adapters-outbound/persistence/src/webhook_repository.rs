@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, SqlitePool};
+use tracing::instrument;
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync + std::fmt::Debug {
+    async fn find_subscription_by_id(&self, id: i64) -> Option<WebhookSubscriptionEntity>;
+    async fn find_subscriptions_by_account(
+        &self,
+        account_id: i64,
+    ) -> Vec<WebhookSubscriptionEntity>;
+    async fn save_subscription(
+        &self,
+        webhook_subscription_entity: WebhookSubscriptionEntity,
+    ) -> i64;
+    async fn delete_subscription(&self, id: i64) -> bool;
+    async fn find_pending_deliveries_due_until(
+        &self,
+        until: NaiveDateTime,
+        limit: i64,
+    ) -> Vec<WebhookDeliveryEntity>;
+    async fn find_deliveries_by_subscription(
+        &self,
+        subscription_id: i64,
+    ) -> Vec<WebhookDeliveryEntity>;
+    /**
+     * Inserts all new deliveries in a single transaction, deliveries already stored are ignored.
+     */
+    async fn insert_deliveries(&self, webhook_delivery_entities: Vec<WebhookDeliveryEntity>);
+    async fn update_delivery_state(&self, webhook_delivery_entity: WebhookDeliveryEntity);
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct WebhookRepositoryImpl {
+    db_pool: SqlitePool,
+}
+
+impl WebhookRepositoryImpl {
+    // #[inject]
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for WebhookRepositoryImpl {
+    #[instrument(level = "debug", skip_all)]
+    async fn find_subscription_by_id(&self, id: i64) -> Option<WebhookSubscriptionEntity> {
+        sqlx::query_as::<_, WebhookSubscriptionEntity>(
+            "
+            SELECT * FROM webhook_subscription_entity
+            WHERE id = ?
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .unwrap_or(None)
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_subscriptions_by_account(
+        &self,
+        account_id: i64,
+    ) -> Vec<WebhookSubscriptionEntity> {
+        let rows = sqlx::query_as::<_, WebhookSubscriptionEntity>(
+            "
+            SELECT * FROM webhook_subscription_entity
+            WHERE account_id = ?
+            ORDER BY id
+            ",
+        )
+        .bind(account_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn save_subscription(
+        &self,
+        webhook_subscription_entity: WebhookSubscriptionEntity,
+    ) -> i64 {
+        sqlx::query(
+            "
+            INSERT INTO webhook_subscription_entity (account_id, url, secret, created_at)
+            VALUES (?, ?, ?, ?)
+            ",
+        )
+        .bind(webhook_subscription_entity.account_id)
+        .bind(webhook_subscription_entity.url)
+        .bind(webhook_subscription_entity.secret)
+        .bind(webhook_subscription_entity.created_at)
+        .execute(&self.db_pool)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn delete_subscription(&self, id: i64) -> bool {
+        sqlx::query(
+            "
+            DELETE FROM webhook_subscription_entity
+            WHERE id = ?
+            ",
+        )
+        .bind(id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap()
+        .rows_affected()
+            > 0
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_pending_deliveries_due_until(
+        &self,
+        until: NaiveDateTime,
+        limit: i64,
+    ) -> Vec<WebhookDeliveryEntity> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryEntity>(
+            "
+            SELECT * FROM webhook_delivery_entity
+            WHERE status = 'PENDING'
+            AND next_attempt_at <= ?
+            ORDER BY next_attempt_at, id
+            LIMIT ?
+            ",
+        )
+        .bind(until)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn find_deliveries_by_subscription(
+        &self,
+        subscription_id: i64,
+    ) -> Vec<WebhookDeliveryEntity> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryEntity>(
+            "
+            SELECT * FROM webhook_delivery_entity
+            WHERE subscription_id = ?
+            ORDER BY id DESC
+            ",
+        )
+        .bind(subscription_id)
+        .fetch_all(&self.db_pool)
+        .await;
+        if let Ok(rows) = rows {
+            return rows;
+        }
+        vec![]
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn insert_deliveries(&self, webhook_delivery_entities: Vec<WebhookDeliveryEntity>) {
+        let mut tx = self.db_pool.begin().await.unwrap();
+        for webhook_delivery_entity in webhook_delivery_entities {
+            sqlx::query(
+                "
+                INSERT OR IGNORE INTO webhook_delivery_entity (subscription_id, event_type, account_id,
+                    counterpart_account_id, amount, timestamp, status, attempts, next_attempt_at, last_error,
+                    finished_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ",
+            )
+            .bind(webhook_delivery_entity.subscription_id)
+            .bind(webhook_delivery_entity.event_type)
+            .bind(webhook_delivery_entity.account_id)
+            .bind(webhook_delivery_entity.counterpart_account_id)
+            .bind(webhook_delivery_entity.amount)
+            .bind(webhook_delivery_entity.timestamp)
+            .bind(webhook_delivery_entity.status)
+            .bind(webhook_delivery_entity.attempts)
+            .bind(webhook_delivery_entity.next_attempt_at)
+            .bind(webhook_delivery_entity.last_error)
+            .bind(webhook_delivery_entity.finished_at)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        }
+        tx.commit().await.unwrap();
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn update_delivery_state(&self, webhook_delivery_entity: WebhookDeliveryEntity) {
+        sqlx::query(
+            "
+            UPDATE webhook_delivery_entity
+            SET status = ?, attempts = ?, next_attempt_at = ?, last_error = ?, finished_at = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(webhook_delivery_entity.status)
+        .bind(webhook_delivery_entity.attempts)
+        .bind(webhook_delivery_entity.next_attempt_at)
+        .bind(webhook_delivery_entity.last_error)
+        .bind(webhook_delivery_entity.finished_at)
+        .bind(webhook_delivery_entity.id)
+        .execute(&self.db_pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct WebhookSubscriptionEntity {
+    pub id: Option<i64>,
+    pub account_id: i64,
+    pub url: String,
+    pub secret: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(FromRow, PartialEq, Hash, Debug)]
+pub struct WebhookDeliveryEntity {
+    pub id: Option<i64>,
+    pub subscription_id: i64,
+    pub event_type: String,
+    // the owner of the activity
+    pub account_id: i64,
+    pub counterpart_account_id: i64,
+    pub amount: i64,
+    pub timestamp: NaiveDateTime,
+    pub status: String,
+    pub attempts: i64,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub finished_at: Option<NaiveDateTime>,
+}
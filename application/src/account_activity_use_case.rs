@@ -0,0 +1,219 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{AccountActivity, AccountActivityError, AccountActivityUseCase, Principal},
+    outbound_ports::LoadAccountActivityPort,
+};
+use async_trait::async_trait;
+use domain::{
+    ar::{
+        account::AccountId,
+        activity::{Activity, ActivityId},
+    },
+    vo::money::Money,
+};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccountActivityUseCaseImpl {
+    load_account_activity_port: Arc<dyn LoadAccountActivityPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl AccountActivityUseCaseImpl {
+    // #[inject]
+    pub fn new(load_account_activity_port: Arc<dyn LoadAccountActivityPort>) -> Self {
+        Self {
+            load_account_activity_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl AccountActivityUseCase for AccountActivityUseCaseImpl {
+    async fn list_activities_after(
+        &self,
+        account_id: AccountId,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<AccountActivity> {
+        let activities = self
+            .load_account_activity_port
+            .load_activities_after(
+                account_id.clone(),
+                after_activity_id.as_ref().map(|id| ActivityId(id.0)),
+                limit,
+            )
+            .await;
+        if activities.is_empty() {
+            return vec![];
+        }
+        let balance = self
+            .load_account_activity_port
+            .load_balance_until(account_id.clone(), after_activity_id)
+            .await;
+        with_running_balance(&account_id, balance, activities)
+    }
+
+    async fn open_activity_stream(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Option<ActivityId>, AccountActivityError> {
+        self.authorization_policy
+            .authorize(&principal, Action::ViewAccount, &account_id)
+            .await?;
+        Ok(self
+            .load_account_activity_port
+            .load_last_activity_id(account_id)
+            .await)
+    }
+}
+
+/**
+ * Applies the activities in order to the balance of the account before the first of them.
+ */
+pub(crate) fn with_running_balance(
+    account_id: &AccountId,
+    mut balance: Money,
+    activities: Vec<Activity>,
+) -> Vec<AccountActivity> {
+    activities
+        .into_iter()
+        .map(|activity| {
+            if &activity.target_account_id == account_id {
+                balance = balance.plus(&activity.money);
+            }
+            if &activity.source_account_id == account_id {
+                balance = balance.minus(&activity.money);
+            }
+            AccountActivity::new(activity, balance.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockLoadAccountActivityPort, MockLoadAccountMandatePort, MockRecordAuthorizationDenialPort,
+    };
+    use chrono::NaiveDate;
+    use domain::vo::role::Role;
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_running_balance_continues_after_the_last_seen_activity() {
+        // Given the account had a balance of 1000 after activity 41
+        let mut load_account_activity_port = MockLoadAccountActivityPort::new();
+        load_account_activity_port
+            .expect_load_activities_after()
+            .times(1)
+            .with(eq(AccountId(42)), eq(Some(ActivityId(41))), eq(10))
+            .returning(|_, _, _| {
+                vec![
+                    activity(42, AccountId(42), AccountId(43), 300),
+                    activity(43, AccountId(44), AccountId(42), 50),
+                ]
+            });
+        load_account_activity_port
+            .expect_load_balance_until()
+            .times(1)
+            .with(eq(AccountId(42)), eq(Some(ActivityId(41))))
+            .returning(|_, _| Money::of(1000));
+
+        let account_activity_use_case =
+            AccountActivityUseCaseImpl::new(Arc::new(load_account_activity_port));
+
+        // When
+        let account_activities = account_activity_use_case
+            .list_activities_after(AccountId(42), Some(ActivityId(41)), 10)
+            .await;
+
+        // Then the withdrawal and the deposit are applied in order
+        assert_eq!(
+            vec![Money::of(700), Money::of(750)],
+            account_activities
+                .iter()
+                .map(|account_activity| account_activity.balance.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(ActivityId(43)), account_activities[1].activity.id);
+    }
+
+    #[async_std::test]
+    async fn test_stream_opens_at_the_last_activity_for_permitted_principals_only() {
+        // Given an account whose last activity is 43
+        let mut load_account_activity_port = MockLoadAccountActivityPort::new();
+        load_account_activity_port
+            .expect_load_last_activity_id()
+            .times(1)
+            .with(eq(AccountId(42)))
+            .returning(|_| Some(ActivityId(43)));
+
+        // And no customer holds a mandate on it
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .returning(|_| vec![]);
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        let account_activity_use_case =
+            AccountActivityUseCaseImpl::new(Arc::new(load_account_activity_port))
+                .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+                    Arc::new(load_account_mandate_port),
+                    Arc::new(record_authorization_denial_port),
+                )));
+
+        // When support and a customer open a stream
+        let support = account_activity_use_case
+            .open_activity_stream(
+                AccountId(42),
+                Principal::new("bob".to_string(), Role::Support),
+            )
+            .await;
+        let customer = account_activity_use_case
+            .open_activity_stream(
+                AccountId(42),
+                Principal::new("mallory".to_string(), Role::Customer),
+            )
+            .await;
+
+        // Then only support may follow the account from its last activity
+        assert_eq!(Ok(Some(ActivityId(43))), support);
+        assert_eq!(Err(AccountActivityError::Unauthorized), customer);
+    }
+
+    fn activity(
+        id: i64,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        amount: i128,
+    ) -> Activity {
+        Activity::with_id(
+            Some(ActivityId(id)),
+            AccountId(42),
+            source_account_id,
+            target_account_id,
+            NaiveDate::from_ymd_opt(2019, 8, 9)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            Money::of(amount),
+            None,
+        )
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    inbound_ports::{
+        AccountAdministrationUseCase, AccountSummary, CloseAccountError,
+        GrantAccountMandateCommand, GrantAccountMandateError, OpenAccountCommand, OpenAccountError,
+    },
+    outbound_ports::{
+        LoadAccountDetailsPort, LoadAccountPort, UpdateAccountDetailsPort, UpdateAccountMandatePort,
+    },
+};
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{account::AccountId, account_mandate::AccountMandate};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccountAdministrationUseCaseImpl {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    load_account_details_port: Arc<dyn LoadAccountDetailsPort>,
+    update_account_details_port: Arc<dyn UpdateAccountDetailsPort>,
+    update_account_mandate_port: Arc<dyn UpdateAccountMandatePort>,
+}
+
+impl AccountAdministrationUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_account_port: Arc<dyn LoadAccountPort>,
+        load_account_details_port: Arc<dyn LoadAccountDetailsPort>,
+        update_account_details_port: Arc<dyn UpdateAccountDetailsPort>,
+        update_account_mandate_port: Arc<dyn UpdateAccountMandatePort>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            load_account_details_port,
+            update_account_details_port,
+            update_account_mandate_port,
+        }
+    }
+}
+
+#[async_trait]
+impl AccountAdministrationUseCase for AccountAdministrationUseCaseImpl {
+    async fn open_account(
+        &self,
+        command: OpenAccountCommand,
+    ) -> Result<AccountId, OpenAccountError> {
+        if command.overdraft_limit.is_negative() {
+            return Err(OpenAccountError::NegativeOverdraftLimit);
+        }
+        Ok(self
+            .update_account_details_port
+            .open_account(
+                command.currency,
+                command.overdraft_limit,
+                Local::now().naive_local(),
+            )
+            .await)
+    }
+
+    async fn close_account(&self, account_id: AccountId) -> Result<(), CloseAccountError> {
+        let Some(details) = self
+            .load_account_details_port
+            .load_account_details(account_id.clone())
+            .await
+        else {
+            return Err(CloseAccountError::AccountNotFound);
+        };
+        if details.closed_at.is_some() {
+            return Err(CloseAccountError::AlreadyClosed);
+        }
+
+        let now = Local::now().naive_local();
+        // all activities up to now are within the baseline balance
+        let mut account = self
+            .load_account_port
+            .load_account(account_id.clone(), now)
+            .await;
+        if !account.close() {
+            return Err(CloseAccountError::BalanceNotZero);
+        }
+
+        self.update_account_details_port
+            .close_account(account_id, now)
+            .await;
+        Ok(())
+    }
+
+    async fn get_account_summary(&self, account_id: AccountId) -> Option<AccountSummary> {
+        let details = self
+            .load_account_details_port
+            .load_account_details(account_id.clone())
+            .await?;
+        let account = self
+            .load_account_port
+            .load_account(account_id, Local::now().naive_local())
+            .await;
+        Some(AccountSummary {
+            details,
+            balance: account.calculate_balance(),
+        })
+    }
+
+    async fn grant_account_mandate(
+        &self,
+        command: GrantAccountMandateCommand,
+    ) -> Result<(), GrantAccountMandateError> {
+        let Some(details) = self
+            .load_account_details_port
+            .load_account_details(command.account_id.clone())
+            .await
+        else {
+            return Err(GrantAccountMandateError::AccountNotFound);
+        };
+        if details.closed_at.is_some() {
+            return Err(GrantAccountMandateError::AccountClosed);
+        }
+
+        self.update_account_mandate_port
+            .grant_account_mandate(AccountMandate::new(
+                command.account_id,
+                command.customer,
+                command.kind,
+                Local::now().naive_local(),
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inbound_ports::AccountDetails,
+        outbound_ports::{
+            MockLoadAccountDetailsPort, MockLoadAccountPort, MockUpdateAccountDetailsPort,
+            MockUpdateAccountMandatePort,
+        },
+    };
+    use domain::{
+        ar::account_mandate::MandateKind,
+        vo::{currency::Currency, money::Money},
+    };
+    use mockall::predicate::{always, eq, function};
+    use mockall_double::double;
+
+    #[double]
+    use domain::ar::account::Account;
+
+    #[async_std::test]
+    async fn test_only_an_empty_open_account_is_closed() {
+        // Given account 41 is open and empty, account 42 holds money and account 43 is closed
+        let mut load_account_details_port = MockLoadAccountDetailsPort::new();
+        load_account_details_port
+            .expect_load_account_details()
+            .returning(|account_id| {
+                Some(AccountDetails {
+                    closed_at: (account_id == AccountId(43)).then(|| Local::now().naive_local()),
+                    account_id,
+                    currency: Currency::of("EUR"),
+                    overdraft_limit: Money::of(0),
+                })
+            });
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .returning(|account_id, _| {
+                let mut account = Account::new();
+                account
+                    .expect_close()
+                    .return_const(account_id == AccountId(41));
+                account
+            });
+        let mut update_account_details_port = MockUpdateAccountDetailsPort::new();
+        update_account_details_port
+            .expect_close_account()
+            .times(1)
+            .with(eq(AccountId(41)), always())
+            .return_const(());
+
+        let use_case = AccountAdministrationUseCaseImpl::new(
+            Arc::new(load_account_port),
+            Arc::new(load_account_details_port),
+            Arc::new(update_account_details_port),
+            Arc::new(MockUpdateAccountMandatePort::new()),
+        );
+
+        // When
+        let results = vec![
+            use_case.close_account(AccountId(41)).await,
+            use_case.close_account(AccountId(42)).await,
+            use_case.close_account(AccountId(43)).await,
+        ];
+
+        // Then
+        assert_eq!(
+            vec![
+                Ok(()),
+                Err(CloseAccountError::BalanceNotZero),
+                Err(CloseAccountError::AlreadyClosed),
+            ],
+            results
+        );
+    }
+
+    #[async_std::test]
+    async fn test_account_with_negative_overdraft_limit_is_not_opened() {
+        // Given
+        let mut update_account_details_port = MockUpdateAccountDetailsPort::new();
+        update_account_details_port
+            .expect_open_account()
+            .times(1)
+            .with(eq(Currency::of("USD")), eq(Money::of(500)), always())
+            .return_const(AccountId(7));
+
+        let use_case = AccountAdministrationUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Arc::new(MockLoadAccountDetailsPort::new()),
+            Arc::new(update_account_details_port),
+            Arc::new(MockUpdateAccountMandatePort::new()),
+        );
+
+        // When
+        let rejected = use_case
+            .open_account(OpenAccountCommand::new(Currency::of("USD"), Money::of(-1)))
+            .await;
+        let opened = use_case
+            .open_account(OpenAccountCommand::new(Currency::of("USD"), Money::of(500)))
+            .await;
+
+        // Then
+        assert_eq!(Err(OpenAccountError::NegativeOverdraftLimit), rejected);
+        assert_eq!(Ok(AccountId(7)), opened);
+    }
+
+    #[async_std::test]
+    async fn test_mandate_is_granted_on_open_account_only() {
+        // Given account 41 is open and account 43 is closed
+        let mut load_account_details_port = MockLoadAccountDetailsPort::new();
+        load_account_details_port
+            .expect_load_account_details()
+            .returning(|account_id| {
+                (account_id != AccountId(42)).then(|| AccountDetails {
+                    closed_at: (account_id == AccountId(43)).then(|| Local::now().naive_local()),
+                    account_id,
+                    currency: Currency::of("EUR"),
+                    overdraft_limit: Money::of(0),
+                })
+            });
+
+        // Then only the mandate on account 41 is stored
+        let mut update_account_mandate_port = MockUpdateAccountMandatePort::new();
+        update_account_mandate_port
+            .expect_grant_account_mandate()
+            .times(1)
+            .with(function(|mandate: &AccountMandate| {
+                mandate.account_id == AccountId(41)
+                    && mandate.customer == "alice"
+                    && mandate.kind == MandateKind::Signatory
+            }))
+            .return_const(());
+
+        let use_case = AccountAdministrationUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Arc::new(load_account_details_port),
+            Arc::new(MockUpdateAccountDetailsPort::new()),
+            Arc::new(update_account_mandate_port),
+        );
+
+        // When
+        let mut results = vec![];
+        for account_id in [41, 42, 43] {
+            results.push(
+                use_case
+                    .grant_account_mandate(GrantAccountMandateCommand::new(
+                        AccountId(account_id),
+                        "alice".to_string(),
+                        MandateKind::Signatory,
+                    ))
+                    .await,
+            );
+        }
+
+        // Then
+        assert_eq!(
+            vec![
+                Ok(()),
+                Err(GrantAccountMandateError::AccountNotFound),
+                Err(GrantAccountMandateError::AccountClosed),
+            ],
+            results
+        );
+    }
+}
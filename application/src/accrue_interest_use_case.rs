@@ -0,0 +1,585 @@
+use crate::{
+    inbound_ports::{AccrueInterestUseCase, InterestAccrualReport, UnpaidInterest},
+    interest_policy::InterestProperties,
+    outbound_ports::{
+        AccountLock, LoadAccountPort, LoadInterestAccrualPort, UpdateInterestAccrualPort,
+    },
+};
+
+use async_trait::async_trait;
+use chrono::{Datelike, Days, NaiveDate, NaiveTime};
+use domain::{
+    ar::{account::AccountId, interest_accrual::InterestAccrual},
+    vo::money::Money,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct AccrueInterestUseCaseImpl {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    account_lock: Box<dyn AccountLock>,
+    load_interest_accrual_port: Arc<dyn LoadInterestAccrualPort>,
+    update_interest_accrual_port: Arc<dyn UpdateInterestAccrualPort>,
+    interest_properties: InterestProperties,
+}
+
+impl AccrueInterestUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_account_port: Arc<dyn LoadAccountPort>,
+        account_lock: Box<dyn AccountLock>,
+        load_interest_accrual_port: Arc<dyn LoadInterestAccrualPort>,
+        update_interest_accrual_port: Arc<dyn UpdateInterestAccrualPort>,
+        interest_properties: InterestProperties,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_lock,
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+            interest_properties,
+        }
+    }
+
+    /**
+     * Accrues the interest of a single account.
+     */
+    async fn accrue_interest_of_account(
+        &self,
+        account_id: AccountId,
+        accrual_date: NaiveDate,
+    ) -> Accrual {
+        let start_of_month = accrual_date.with_day(1).unwrap();
+        let accruals = self
+            .load_interest_accrual_port
+            .load_interest_accruals(account_id.clone(), start_of_month, accrual_date)
+            .await;
+        if accruals.iter().any(|a| a.accrual_date == accrual_date) {
+            return Accrual::AlreadyAccrued;
+        }
+
+        // the window starts with the day, so the baseline is the balance of the previous day
+        let start_of_day = accrual_date.and_time(NaiveTime::MIN);
+        let end_of_day = start_of_day + Days::new(1);
+        let mut account = self
+            .load_account_port
+            .load_account(account_id.clone(), start_of_day)
+            .await;
+        let mut interest_accrual = InterestAccrual::new(
+            account_id.clone(),
+            accrual_date,
+            account.calculate_balance_at(end_of_day),
+        );
+
+        if !is_last_day_of_month(accrual_date) {
+            self.update_interest_accrual_port
+                .save_interest_accrual(interest_accrual, vec![], vec![])
+                .await;
+            return Accrual::Accrued;
+        }
+
+        let balance_sum = accruals
+            .iter()
+            .chain([&interest_accrual])
+            .map(InterestAccrual::interest_bearing_balance)
+            .fold(Money::of(0), |acc, balance| acc.plus(&balance));
+        let interest = self
+            .interest_properties
+            .policy
+            .interest_for(&balance_sum, accrual_date.day());
+        if !interest.is_positive() {
+            self.update_interest_accrual_port
+                .save_interest_accrual(interest_accrual, vec![], vec![])
+                .await;
+            return Accrual::Accrued;
+        }
+
+        let interest_account_id = self.interest_properties.interest_account_id.clone();
+        let mut interest_account = self
+            .load_account_port
+            .load_account(interest_account_id.clone(), start_of_day)
+            .await;
+        self.account_lock.lock_account(interest_account_id.clone());
+        self.account_lock.lock_account(account_id.clone());
+        let accrual = if interest_account.withdraw(interest.clone(), account_id.clone())
+            && account.deposit(interest.clone(), interest_account_id.clone())
+        {
+            interest_accrual.capitalise(interest);
+            let mut domain_events = interest_account.take_domain_events();
+            domain_events.append(&mut account.take_domain_events());
+            self.update_interest_accrual_port
+                .save_interest_accrual(
+                    interest_accrual,
+                    vec![interest_account, account],
+                    domain_events,
+                )
+                .await;
+            Accrual::Accrued
+        } else {
+            // skipped rather than retried, which would hold the account back on this day
+            warn!(
+                account_id = account_id.0,
+                interest_account_id = interest_account_id.0,
+                ?interest,
+                "interest could not be paid, the day is accrued without it"
+            );
+            self.update_interest_accrual_port
+                .save_interest_accrual(interest_accrual, vec![], vec![])
+                .await;
+            Accrual::Unpaid(interest)
+        };
+        self.account_lock.release_account(interest_account_id);
+        self.account_lock.release_account(account_id);
+        accrual
+    }
+}
+
+enum Accrual {
+    AlreadyAccrued,
+    Accrued,
+    /// The day has been accrued without the interest, which the funding account could not pay.
+    Unpaid(Money),
+}
+
+unsafe impl Send for AccrueInterestUseCaseImpl {}
+unsafe impl Sync for AccrueInterestUseCaseImpl {}
+
+#[async_trait]
+impl AccrueInterestUseCase for AccrueInterestUseCaseImpl {
+    async fn accrue_interest(&self, until: NaiveDate) -> InterestAccrualReport {
+        let account_ids = self
+            .load_interest_accrual_port
+            .load_interest_bearing_account_ids()
+            .await;
+
+        let mut report = InterestAccrualReport::default();
+        for account_id in account_ids {
+            // catch up day by day on what was missed, e.g. while the service was down, as the
+            // interest of a month is paid from the balances of all of its days
+            let mut accrual_date = self
+                .load_interest_accrual_port
+                .load_last_accrual_date(account_id.clone())
+                .await
+                .and_then(|last_accrual_date| last_accrual_date.succ_opt())
+                .unwrap_or(until);
+            while accrual_date <= until {
+                match self
+                    .accrue_interest_of_account(account_id.clone(), accrual_date)
+                    .await
+                {
+                    Accrual::AlreadyAccrued => break,
+                    Accrual::Accrued => {}
+                    Accrual::Unpaid(interest) => report.unpaid_interest.push(UnpaidInterest::new(
+                        account_id.clone(),
+                        accrual_date,
+                        interest,
+                    )),
+                }
+                report.accrued_days += 1;
+                accrual_date = accrual_date.succ_opt().unwrap();
+            }
+        }
+        report
+    }
+}
+
+fn is_last_day_of_month(date: NaiveDate) -> bool {
+    date.succ_opt()
+        .is_none_or(|next_day| next_day.month() != date.month())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interest_policy::{DayCountConvention, InterestPolicy},
+        outbound_ports::{
+            MockAccountLock, MockLoadAccountPort, MockLoadInterestAccrualPort,
+            MockUpdateInterestAccrualPort,
+        },
+    };
+    use domain::{ar::domain_event::DomainEvent, vo::rounding::Rounding};
+    use mockall::predicate::{always, eq};
+    use mockall_double::double;
+
+    #[double]
+    use domain::ar::account::Account;
+
+    #[async_std::test]
+    async fn test_accounts_already_accrued_for_the_day_are_skipped() {
+        // Given an account which has already been accrued for the day
+        let mut load_interest_accrual_port = MockLoadInterestAccrualPort::new();
+        load_interest_accrual_port
+            .expect_load_interest_bearing_account_ids()
+            .return_const(vec![AccountId(41)]);
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .with(eq(AccountId(41)))
+            .return_const(Some(date(8, 9)));
+
+        // Then the account is neither loaded nor accrued again
+        let mut update_interest_accrual_port = MockUpdateInterestAccrualPort::new();
+        update_interest_accrual_port
+            .expect_save_interest_accrual()
+            .never();
+
+        // When
+        let report = use_case(
+            MockLoadAccountPort::new(),
+            Box::new(MockAccountLock::new()),
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+        )
+        .accrue_interest(date(8, 9))
+        .await;
+
+        // Then
+        assert_eq!(
+            InterestAccrualReport {
+                accrued_days: 0,
+                unpaid_interest: vec![],
+            },
+            report
+        );
+    }
+
+    #[async_std::test]
+    async fn test_balance_at_end_of_day_is_accrued() {
+        // Given an account with a balance of 1_000 at the end of the day
+        let mut load_interest_accrual_port = MockLoadInterestAccrualPort::new();
+        load_interest_accrual_port
+            .expect_load_interest_bearing_account_ids()
+            .return_const(vec![AccountId(41)]);
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .return_const(Some(date(8, 8)));
+        load_interest_accrual_port
+            .expect_load_interest_accruals()
+            .return_const(vec![]);
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), eq(date(8, 9).and_time(NaiveTime::MIN)))
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_calculate_balance_at()
+                    .with(eq(date(8, 10).and_time(NaiveTime::MIN)))
+                    .return_const(Money::of(1_000));
+                account
+            });
+
+        // Then the balance is accrued without paying interest
+        let mut update_interest_accrual_port = MockUpdateInterestAccrualPort::new();
+        update_interest_accrual_port
+            .expect_save_interest_accrual()
+            .times(1)
+            .withf(|interest_accrual, accounts, domain_events| {
+                *interest_accrual
+                    == InterestAccrual::new(AccountId(41), date(8, 9), Money::of(1_000))
+                    && accounts.is_empty()
+                    && domain_events.is_empty()
+            })
+            .return_const(());
+
+        // When
+        let report = use_case(
+            load_account_port,
+            Box::new(MockAccountLock::new()),
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+        )
+        .accrue_interest(date(8, 9))
+        .await;
+
+        // Then
+        assert_eq!(
+            InterestAccrualReport {
+                accrued_days: 1,
+                unpaid_interest: vec![],
+            },
+            report
+        );
+    }
+
+    #[async_std::test]
+    async fn test_interest_of_the_month_is_capitalised_on_its_last_day() {
+        // Given an account with 30 days of accrued balances, one of them overdrawn
+        let mut load_interest_accrual_port = MockLoadInterestAccrualPort::new();
+        load_interest_accrual_port
+            .expect_load_interest_bearing_account_ids()
+            .return_const(vec![AccountId(41)]);
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .return_const(Some(date(8, 30)));
+        load_interest_accrual_port
+            .expect_load_interest_accruals()
+            .with(eq(AccountId(41)), eq(date(8, 1)), eq(date(8, 31)))
+            .returning(|account_id, _, _| {
+                (1..=30)
+                    .map(|day| {
+                        let balance = if day == 1 { -500 } else { 12_000 };
+                        InterestAccrual::new(account_id.clone(), date(8, day), Money::of(balance))
+                    })
+                    .collect()
+            });
+
+        // And a balance of 12_000 at the end of the last day
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_calculate_balance_at()
+                    .return_const(Money::of(12_000));
+                account
+                    .expect_deposit()
+                    .times(1)
+                    .with(eq(Money::of(12)), eq(AccountId(1)))
+                    .return_const(true);
+                account
+                    .expect_take_domain_events()
+                    .returning(move || vec![money_deposited()]);
+                account
+            });
+        // And the interest account of the bank
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(1)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_withdraw()
+                    .times(1)
+                    .with(eq(Money::of(12)), eq(AccountId(41)))
+                    .return_const(true);
+                account.expect_take_domain_events().returning(Vec::new);
+                account
+            });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(2).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(2)
+            .return_const(());
+
+        // Then the interest of 30 days of 12_000 at 1.2 % of a 360 day year is paid
+        let mut update_interest_accrual_port = MockUpdateInterestAccrualPort::new();
+        update_interest_accrual_port
+            .expect_save_interest_accrual()
+            .times(1)
+            .withf(|interest_accrual, accounts, domain_events| {
+                interest_accrual.capitalised_interest == Some(Money::of(12))
+                    && accounts.len() == 2
+                    && *domain_events == [money_deposited()]
+            })
+            .return_const(());
+
+        // When
+        let report = use_case(
+            load_account_port,
+            account_lock,
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+        )
+        .accrue_interest(date(8, 31))
+        .await;
+
+        // Then
+        assert_eq!(
+            InterestAccrualReport {
+                accrued_days: 1,
+                unpaid_interest: vec![],
+            },
+            report
+        );
+    }
+
+    #[async_std::test]
+    async fn test_interest_the_funding_account_cannot_pay_is_skipped_and_reported() {
+        // Given an account with 30 days of accrued balances
+        let mut load_interest_accrual_port = MockLoadInterestAccrualPort::new();
+        load_interest_accrual_port
+            .expect_load_interest_bearing_account_ids()
+            .return_const(vec![AccountId(41)]);
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .return_const(Some(date(8, 30)));
+        load_interest_accrual_port
+            .expect_load_interest_accruals()
+            .with(eq(AccountId(41)), eq(date(8, 1)), eq(date(8, 31)))
+            .returning(|account_id, _, _| {
+                (1..=30)
+                    .map(|day| {
+                        let balance = if day == 1 { -500 } else { 12_000 };
+                        InterestAccrual::new(account_id.clone(), date(8, day), Money::of(balance))
+                    })
+                    .collect()
+            });
+        load_interest_accrual_port
+            .expect_load_interest_accruals()
+            .with(eq(AccountId(41)), eq(date(9, 1)), eq(date(9, 1)))
+            .return_const(vec![]);
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_calculate_balance_at()
+                    .return_const(Money::of(12_000));
+                account.expect_deposit().never();
+                account
+            });
+
+        // And a funding account which cannot pay the interest
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(1)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account.expect_withdraw().times(1).return_const(false);
+                account
+            });
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(2).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(2)
+            .return_const(());
+
+        // Then the last day of the month is accrued without interest, and the next day as usual
+        let mut update_interest_accrual_port = MockUpdateInterestAccrualPort::new();
+        for day in [date(8, 31), date(9, 1)] {
+            update_interest_accrual_port
+                .expect_save_interest_accrual()
+                .times(1)
+                .withf(move |interest_accrual, accounts, domain_events| {
+                    *interest_accrual == InterestAccrual::new(AccountId(41), day, Money::of(12_000))
+                        && accounts.is_empty()
+                        && domain_events.is_empty()
+                })
+                .return_const(());
+        }
+
+        // When
+        let report = use_case(
+            load_account_port,
+            account_lock,
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+        )
+        .accrue_interest(date(9, 1))
+        .await;
+
+        // Then the interest of 12 is reported as unpaid
+        assert_eq!(
+            InterestAccrualReport {
+                accrued_days: 2,
+                unpaid_interest: vec![UnpaidInterest::new(
+                    AccountId(41),
+                    date(8, 31),
+                    Money::of(12)
+                )],
+            },
+            report
+        );
+    }
+
+    #[async_std::test]
+    async fn test_missed_days_are_caught_up_on() {
+        // Given an account last accrued three days ago and a new one never accrued
+        let mut load_interest_accrual_port = MockLoadInterestAccrualPort::new();
+        load_interest_accrual_port
+            .expect_load_interest_bearing_account_ids()
+            .return_const(vec![AccountId(41), AccountId(43)]);
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .with(eq(AccountId(41)))
+            .return_const(Some(date(8, 6)));
+        load_interest_accrual_port
+            .expect_load_last_accrual_date()
+            .with(eq(AccountId(43)))
+            .return_const(None);
+        load_interest_accrual_port
+            .expect_load_interest_accruals()
+            .return_const(vec![]);
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = Account::new();
+            account
+                .expect_calculate_balance_at()
+                .return_const(Money::of(1_000));
+            account
+        });
+
+        // Then the first account is accrued for each missed day and the new one for the last
+        let mut update_interest_accrual_port = MockUpdateInterestAccrualPort::new();
+        for (account_id, day) in [(41, 7), (41, 8), (41, 9), (43, 9)] {
+            update_interest_accrual_port
+                .expect_save_interest_accrual()
+                .times(1)
+                .withf(move |interest_accrual, _, _| {
+                    interest_accrual.account_id == AccountId(account_id)
+                        && interest_accrual.accrual_date == date(8, day)
+                })
+                .return_const(());
+        }
+
+        // When
+        let report = use_case(
+            load_account_port,
+            Box::new(MockAccountLock::new()),
+            load_interest_accrual_port,
+            update_interest_accrual_port,
+        )
+        .accrue_interest(date(8, 9))
+        .await;
+
+        // Then
+        assert_eq!(
+            InterestAccrualReport {
+                accrued_days: 4,
+                unpaid_interest: vec![],
+            },
+            report
+        );
+    }
+
+    fn money_deposited() -> DomainEvent {
+        DomainEvent::MoneyDeposited {
+            account_id: AccountId(41),
+            source_account_id: AccountId(1),
+            money: Money::of(12),
+            timestamp: date(8, 31).and_time(NaiveTime::MIN),
+        }
+    }
+
+    fn use_case(
+        load_account_port: MockLoadAccountPort,
+        account_lock: Box<MockAccountLock>,
+        load_interest_accrual_port: MockLoadInterestAccrualPort,
+        update_interest_accrual_port: MockUpdateInterestAccrualPort,
+    ) -> AccrueInterestUseCaseImpl {
+        AccrueInterestUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(load_interest_accrual_port),
+            Arc::new(update_interest_accrual_port),
+            InterestProperties::new(
+                InterestPolicy::new(120, DayCountConvention::Actual360, Rounding::HalfEven),
+                AccountId(1),
+            ),
+        )
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2019, month, day).unwrap()
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    inbound_ports::{
+        AccountActivityError, ChangeOverdraftLimitError, ChangeTransferLimitsError,
+        GenerateStatementError, Principal, ScheduleTransferError, SendMoneyError,
+        StandingOrderError, SubscribeWebhookError,
+    },
+    outbound_ports::{LoadAccountMandatePort, RecordAuthorizationDenialPort},
+};
+use chrono::Local;
+use domain::{
+    ar::{account::AccountId, authorization_denial::AuthorizationDenial},
+    vo::role::Role,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+/**
+ * What a principal may attempt on an account.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    ViewAccount,
+    DebitAccount,
+    /// Changes the overdraft or transfer limits of the account.
+    ChangeAccountLimits,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::ViewAccount => write!(f, "view_account"),
+            Action::DebitAccount => write!(f, "debit_account"),
+            Action::ChangeAccountLimits => write!(f, "change_account_limits"),
+        }
+    }
+}
+
+impl Action {
+    // Methods
+
+    /**
+     * Customers view and debit the accounts they hold a mandate on, support views every account
+     * but moves no money, and only admins change the limits of an account, as they may do
+     * anything.
+     *
+     * # Arguments
+     *
+     * * `mandated` - Whether the principal holds a mandate on the account.
+     */
+    pub fn is_permitted(&self, role: Role, mandated: bool) -> bool {
+        match role {
+            Role::Customer => {
+                mandated && matches!(self, Action::ViewAccount | Action::DebitAccount)
+            }
+            Role::Support => *self == Action::ViewAccount,
+            Role::Admin => true,
+        }
+    }
+}
+
+/**
+ * The principal may not attempt the action on the account, which has been recorded for the audit.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Denied;
+
+impl From<Denied> for SendMoneyError {
+    fn from(_: Denied) -> Self {
+        SendMoneyError::Unauthorized
+    }
+}
+
+impl From<Denied> for ScheduleTransferError {
+    fn from(_: Denied) -> Self {
+        ScheduleTransferError::Unauthorized
+    }
+}
+
+impl From<Denied> for StandingOrderError {
+    fn from(_: Denied) -> Self {
+        StandingOrderError::Unauthorized
+    }
+}
+
+impl From<Denied> for ChangeOverdraftLimitError {
+    fn from(_: Denied) -> Self {
+        ChangeOverdraftLimitError::Unauthorized
+    }
+}
+
+impl From<Denied> for ChangeTransferLimitsError {
+    fn from(_: Denied) -> Self {
+        ChangeTransferLimitsError::Unauthorized
+    }
+}
+
+impl From<Denied> for SubscribeWebhookError {
+    fn from(_: Denied) -> Self {
+        SubscribeWebhookError::Unauthorized
+    }
+}
+
+impl From<Denied> for GenerateStatementError {
+    fn from(_: Denied) -> Self {
+        GenerateStatementError::Unauthorized
+    }
+}
+
+impl From<Denied> for AccountActivityError {
+    fn from(_: Denied) -> Self {
+        AccountActivityError::Unauthorized
+    }
+}
+
+/**
+ * Decides whether a principal may act on an account, by its role and, for customers, by the
+ * mandates on the account. Every denial is recorded for the audit.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct AuthorizationPolicy {
+    // none on the command line, where whoever runs it may do anything anyway
+    ports: Option<(
+        Arc<dyn LoadAccountMandatePort>,
+        Arc<dyn RecordAuthorizationDenialPort>,
+    )>,
+}
+
+impl AuthorizationPolicy {
+    // #[inject]
+    pub fn new(
+        load_account_mandate_port: Arc<dyn LoadAccountMandatePort>,
+        record_authorization_denial_port: Arc<dyn RecordAuthorizationDenialPort>,
+    ) -> Self {
+        Self {
+            ports: Some((load_account_mandate_port, record_authorization_denial_port)),
+        }
+    }
+
+    /**
+     * Permits every principal any action on any account, the default of the use cases.
+     */
+    pub fn permit_all() -> Self {
+        Self { ports: None }
+    }
+
+    pub async fn authorize(
+        &self,
+        principal: &Principal,
+        action: Action,
+        account_id: &AccountId,
+    ) -> Result<(), Denied> {
+        let Some((load_account_mandate_port, record_authorization_denial_port)) = &self.ports
+        else {
+            return Ok(());
+        };
+        // only the customers are bound to the mandates
+        let mandated = principal.role == Role::Customer
+            && load_account_mandate_port
+                .load_account_mandates(account_id.clone())
+                .await
+                .iter()
+                .any(|mandate| mandate.customer == principal.subject);
+        if action.is_permitted(principal.role, mandated) {
+            return Ok(());
+        }
+
+        warn!(
+            principal = %principal.subject,
+            role = %principal.role,
+            %action,
+            account_id = account_id.0,
+            "authorization denied"
+        );
+        record_authorization_denial_port
+            .record_authorization_denial(AuthorizationDenial::new(
+                principal.subject.clone(),
+                principal.role,
+                action.to_string(),
+                account_id.clone(),
+                Local::now().naive_local(),
+            ))
+            .await;
+        Err(Denied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{MockLoadAccountMandatePort, MockRecordAuthorizationDenialPort};
+    use chrono::NaiveDate;
+    use domain::ar::account_mandate::{AccountMandate, MandateKind};
+    use mockall::predicate::{eq, function};
+
+    #[test]
+    fn test_permissions_by_role() {
+        use Action::*;
+
+        let permitted = |role, mandated| {
+            [ViewAccount, DebitAccount, ChangeAccountLimits]
+                .into_iter()
+                .filter(|action| action.is_permitted(role, mandated))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(permitted(Role::Customer, true), [ViewAccount, DebitAccount]);
+        assert_eq!(permitted(Role::Customer, false), []);
+        assert_eq!(permitted(Role::Support, false), [ViewAccount]);
+        assert_eq!(
+            permitted(Role::Admin, false),
+            [ViewAccount, DebitAccount, ChangeAccountLimits]
+        );
+    }
+
+    #[async_std::test]
+    async fn test_customers_need_a_mandate_and_denials_are_audited() {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 18)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+
+        // Given an account owned by alice
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .with(eq(AccountId(41)))
+            .returning(move |account_id| {
+                vec![AccountMandate::new(
+                    account_id,
+                    "alice".to_string(),
+                    MandateKind::Owner,
+                    now,
+                )]
+            });
+
+        // Then only the attempt of mallory is recorded
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .with(function(move |denial: &AuthorizationDenial| {
+                denial.subject == "mallory"
+                    && denial.role == Role::Customer
+                    && denial.action == "debit_account"
+                    && denial.account_id == AccountId(41)
+                    && denial.denied_at > now
+            }))
+            .return_const(());
+
+        // When alice and mallory attempt to debit the account
+        let policy = AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        );
+        let authorize = |subject: &str| {
+            let principal = Principal::new(subject.to_string(), Role::Customer);
+            let policy = &policy;
+            async move {
+                policy
+                    .authorize(&principal, Action::DebitAccount, &AccountId(41))
+                    .await
+            }
+        };
+
+        // Then
+        assert_eq!(Ok(()), authorize("alice").await);
+        assert_eq!(Err(Denied), authorize("mallory").await);
+    }
+
+    #[async_std::test]
+    async fn test_permit_all_neither_loads_mandates_nor_audits() {
+        // When a customer without any mandate debits an account
+        let result = AuthorizationPolicy::permit_all()
+            .authorize(
+                &Principal::new("mallory".to_string(), Role::Customer),
+                Action::DebitAccount,
+                &AccountId(41),
+            )
+            .await;
+
+        // Then
+        assert_eq!(Ok(()), result);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy, Denied},
+    inbound_ports::{
+        ChangeOverdraftLimitCommand, ChangeOverdraftLimitError, ChangeOverdraftLimitUseCase,
+        Principal,
+    },
+    outbound_ports::{LoadAccountPort, LoadOverdraftLimitChangePort, UpdateOverdraftLimitPort},
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{account::AccountId, overdraft_limit_change::OverdraftLimitChange};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ChangeOverdraftLimitUseCaseImpl {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    load_overdraft_limit_change_port: Arc<dyn LoadOverdraftLimitChangePort>,
+    update_overdraft_limit_port: Arc<dyn UpdateOverdraftLimitPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl ChangeOverdraftLimitUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_account_port: Arc<dyn LoadAccountPort>,
+        load_overdraft_limit_change_port: Arc<dyn LoadOverdraftLimitChangePort>,
+        update_overdraft_limit_port: Arc<dyn UpdateOverdraftLimitPort>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            load_overdraft_limit_change_port,
+            update_overdraft_limit_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl ChangeOverdraftLimitUseCase for ChangeOverdraftLimitUseCaseImpl {
+    async fn change_overdraft_limit(
+        &self,
+        command: ChangeOverdraftLimitCommand,
+    ) -> Result<(), ChangeOverdraftLimitError> {
+        if command.principal.subject.trim().is_empty() {
+            return Err(ChangeOverdraftLimitError::MissingChangedBy);
+        }
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::ChangeAccountLimits,
+                &command.account_id,
+            )
+            .await?;
+
+        let now = Local::now().naive_local();
+        // only the limit is of interest, not the activities
+        let mut account = self
+            .load_account_port
+            .load_account(command.account_id.clone(), now)
+            .await;
+
+        let previous_limit = account.get_overdraft_limit();
+        if !account.change_overdraft_limit(command.overdraft_limit.clone()) {
+            return Err(ChangeOverdraftLimitError::NegativeLimit);
+        }
+
+        self.update_overdraft_limit_port
+            .update_overdraft_limit(OverdraftLimitChange::new(
+                command.account_id,
+                previous_limit,
+                command.overdraft_limit,
+                command.principal.subject,
+                now,
+            ))
+            .await;
+        Ok(())
+    }
+
+    async fn list_overdraft_limit_changes(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Vec<OverdraftLimitChange>, Denied> {
+        self.authorization_policy
+            .authorize(&principal, Action::ViewAccount, &account_id)
+            .await?;
+        Ok(self
+            .load_overdraft_limit_change_port
+            .load_overdraft_limit_changes(account_id)
+            .await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockLoadAccountMandatePort, MockLoadAccountPort, MockLoadOverdraftLimitChangePort,
+        MockRecordAuthorizationDenialPort, MockUpdateOverdraftLimitPort,
+    };
+    use domain::{
+        ar::account_mandate::{AccountMandate, MandateKind},
+        vo::{money::Money, role::Role},
+    };
+    use mockall::predicate::{always, eq, function};
+    use mockall_double::double;
+
+    #[double]
+    use domain::ar::account::Account;
+
+    #[async_std::test]
+    async fn test_change_overdraft_limit_is_audited() {
+        // Given an account without overdraft
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_get_overdraft_limit()
+                    .return_const(Money::of(0));
+                account
+                    .expect_change_overdraft_limit()
+                    .with(eq(Money::of(5_000)))
+                    .return_const(true);
+                account
+            });
+
+        // Then the new limit is stored together with who changed it
+        let mut update_port = MockUpdateOverdraftLimitPort::new();
+        update_port
+            .expect_update_overdraft_limit()
+            .times(1)
+            .with(function(|change: &OverdraftLimitChange| {
+                change.account_id == AccountId(41)
+                    && change.previous_limit == Money::of(0)
+                    && change.new_limit == Money::of(5_000)
+                    && change.changed_by == "alice"
+            }))
+            .return_const(());
+
+        // When the overdraft limit is raised
+        let use_case = ChangeOverdraftLimitUseCaseImpl::new(
+            Arc::new(load_account_port),
+            Arc::new(MockLoadOverdraftLimitChangePort::new()),
+            Arc::new(update_port),
+        );
+        let result = use_case
+            .change_overdraft_limit(ChangeOverdraftLimitCommand::new(
+                AccountId(41),
+                Money::of(5_000),
+                Principal::new("alice".to_string(), Role::Admin),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Ok(()), result);
+    }
+
+    #[async_std::test]
+    async fn test_negative_overdraft_limit_is_rejected() {
+        // Given an account which rejects the limit
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = Account::new();
+            account
+                .expect_get_overdraft_limit()
+                .return_const(Money::of(0));
+            account.expect_change_overdraft_limit().return_const(false);
+            account
+        });
+
+        // Then nothing is stored
+        let mut update_port = MockUpdateOverdraftLimitPort::new();
+        update_port.expect_update_overdraft_limit().never();
+
+        // When
+        let use_case = ChangeOverdraftLimitUseCaseImpl::new(
+            Arc::new(load_account_port),
+            Arc::new(MockLoadOverdraftLimitChangePort::new()),
+            Arc::new(update_port),
+        );
+        let result = use_case
+            .change_overdraft_limit(ChangeOverdraftLimitCommand::new(
+                AccountId(41),
+                Money::of(-1),
+                Principal::new("alice".to_string(), Role::Admin),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Err(ChangeOverdraftLimitError::NegativeLimit), result);
+    }
+
+    #[async_std::test]
+    async fn test_only_admins_change_the_overdraft_limit() {
+        // Given an account owned by alice
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .returning(|account_id| {
+                vec![AccountMandate::new(
+                    account_id,
+                    "alice".to_string(),
+                    MandateKind::Owner,
+                    Local::now().naive_local(),
+                )]
+            });
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // Then neither the account is loaded nor anything stored
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port.expect_load_account().never();
+        let mut update_port = MockUpdateOverdraftLimitPort::new();
+        update_port.expect_update_overdraft_limit().never();
+
+        // When alice raises her own overdraft limit
+        let use_case = ChangeOverdraftLimitUseCaseImpl::new(
+            Arc::new(load_account_port),
+            Arc::new(MockLoadOverdraftLimitChangePort::new()),
+            Arc::new(update_port),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )));
+        let result = use_case
+            .change_overdraft_limit(ChangeOverdraftLimitCommand::new(
+                AccountId(41),
+                Money::of(5_000),
+                Principal::new("alice".to_string(), Role::Customer),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Err(ChangeOverdraftLimitError::Unauthorized), result);
+    }
+}
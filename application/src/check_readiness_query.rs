@@ -0,0 +1,227 @@
+use crate::{
+    inbound_ports::{
+        CheckReadinessQuery, ComponentHealth, HealthStatus, ReadinessReport, WorkerStatus,
+    },
+    outbound_ports::{CheckDatabasePort, LoadWorkerStatusPort},
+};
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime};
+use std::{sync::Arc, time::Instant};
+
+/// A worker is down once it has missed this many of its runs.
+const MISSED_RUNS: i32 = 3;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct CheckReadinessQueryImpl {
+    check_database_port: Arc<dyn CheckDatabasePort>,
+    load_worker_status_port: Arc<dyn LoadWorkerStatusPort>,
+}
+
+impl CheckReadinessQueryImpl {
+    // #[inject]
+    pub fn new(
+        check_database_port: Arc<dyn CheckDatabasePort>,
+        load_worker_status_port: Arc<dyn LoadWorkerStatusPort>,
+    ) -> Self {
+        Self {
+            check_database_port,
+            load_worker_status_port,
+        }
+    }
+
+    async fn check_database(&self) -> ComponentHealth {
+        let started_at = Instant::now();
+        let result = self.check_database_port.ping_database().await;
+        component_health("database", started_at.elapsed(), result.err())
+    }
+
+    async fn check_migrations(&self) -> ComponentHealth {
+        let started_at = Instant::now();
+        let detail = match self.check_database_port.load_pending_migrations().await {
+            Ok(pending_migrations) if pending_migrations.is_empty() => None,
+            Ok(pending_migrations) => Some(format!(
+                "migrations {:?} have not been applied",
+                pending_migrations
+            )),
+            Err(error) => Some(error),
+        };
+        component_health("migrations", started_at.elapsed(), detail)
+    }
+}
+
+fn component_health(
+    name: &str,
+    latency: std::time::Duration,
+    detail: Option<String>,
+) -> ComponentHealth {
+    ComponentHealth {
+        name: name.to_string(),
+        status: match detail {
+            None => HealthStatus::Up,
+            Some(_) => HealthStatus::Down,
+        },
+        latency,
+        detail,
+    }
+}
+
+/**
+ * A worker is down once it has been stopped or has not started a run for a few intervals, e.g.
+ * because its last run hangs. Its latency is the duration of its last finished run.
+ */
+fn check_worker(worker: &WorkerStatus, now: NaiveDateTime) -> ComponentHealth {
+    let last_started_at = worker.last_run_started_at.unwrap_or(worker.registered_at);
+    let overdue = now - last_started_at > worker.interval * MISSED_RUNS;
+    let running = worker.last_run_started_at.is_some()
+        && worker.last_run_finished_at < worker.last_run_started_at;
+    let detail = if worker.stopped {
+        Some("stopped".to_string())
+    } else if overdue && running {
+        Some(format!(
+            "run started at {} has not finished",
+            last_started_at
+        ))
+    } else if overdue {
+        Some(format!("no run started since {}", last_started_at))
+    } else {
+        None
+    };
+    let latency = worker
+        .last_run_started_at
+        .zip(worker.last_run_finished_at)
+        .and_then(|(started_at, finished_at)| (finished_at - started_at).to_std().ok())
+        .unwrap_or_default();
+    component_health(&format!("worker:{}", worker.name), latency, detail)
+}
+
+#[async_trait]
+impl CheckReadinessQuery for CheckReadinessQueryImpl {
+    async fn check_readiness(&self) -> ReadinessReport {
+        let mut components = vec![self.check_database().await, self.check_migrations().await];
+        let now = Local::now().naive_local();
+        components.extend(
+            self.load_worker_status_port
+                .load_worker_statuses()
+                .iter()
+                .map(|worker| check_worker(worker, now)),
+        );
+        let status = if components
+            .iter()
+            .all(|component| component.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+        ReadinessReport { status, components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{MockCheckDatabasePort, MockLoadWorkerStatusPort};
+    use chrono::Duration;
+
+    fn worker(name: &str, last_run_started_at: NaiveDateTime) -> WorkerStatus {
+        WorkerStatus {
+            name: name.to_string(),
+            interval: Duration::seconds(60),
+            registered_at: last_run_started_at - Duration::hours(1),
+            last_run_started_at: Some(last_run_started_at),
+            last_run_finished_at: Some(last_run_started_at + Duration::milliseconds(20)),
+            stopped: false,
+        }
+    }
+
+    #[async_std::test]
+    async fn test_ready_if_all_components_are_up() {
+        // Given a database with all migrations applied
+        let mut check_database_port = MockCheckDatabasePort::new();
+        check_database_port
+            .expect_ping_database()
+            .times(1)
+            .returning(|| Ok(()));
+        check_database_port
+            .expect_load_pending_migrations()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
+        // And a worker which has just run
+        let mut load_worker_status_port = MockLoadWorkerStatusPort::new();
+        load_worker_status_port
+            .expect_load_worker_statuses()
+            .returning(|| vec![worker("outbox-relay", Local::now().naive_local())]);
+
+        let check_readiness_query = CheckReadinessQueryImpl::new(
+            Arc::new(check_database_port),
+            Arc::new(load_worker_status_port),
+        );
+
+        // When
+        let readiness_report = check_readiness_query.check_readiness().await;
+
+        // Then
+        assert_eq!(readiness_report.status, HealthStatus::Up);
+        let names: Vec<&str> = readiness_report
+            .components
+            .iter()
+            .map(|component| component.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["database", "migrations", "worker:outbox-relay"]);
+        assert_eq!(
+            readiness_report.components[2].latency,
+            std::time::Duration::from_millis(20)
+        );
+    }
+
+    #[async_std::test]
+    async fn test_not_ready_if_a_component_is_down() {
+        // Given a database with a pending migration
+        let mut check_database_port = MockCheckDatabasePort::new();
+        check_database_port
+            .expect_ping_database()
+            .returning(|| Ok(()));
+        check_database_port
+            .expect_load_pending_migrations()
+            .returning(|| Ok(vec![20261018210000]));
+
+        // And a worker whose last run hangs and a stopped one
+        let mut load_worker_status_port = MockLoadWorkerStatusPort::new();
+        load_worker_status_port
+            .expect_load_worker_statuses()
+            .returning(|| {
+                let now = Local::now().naive_local();
+                let mut hanging = worker("outbox-relay", now - Duration::minutes(10));
+                hanging.last_run_finished_at = Some(now - Duration::minutes(11));
+                let mut stopped = worker("webhook-delivery", now);
+                stopped.stopped = true;
+                vec![hanging, stopped]
+            });
+
+        let check_readiness_query = CheckReadinessQueryImpl::new(
+            Arc::new(check_database_port),
+            Arc::new(load_worker_status_port),
+        );
+
+        // When
+        let readiness_report = check_readiness_query.check_readiness().await;
+
+        // Then the service is not ready and every component tells why
+        assert_eq!(readiness_report.status, HealthStatus::Down);
+        let details: Vec<Option<&str>> = readiness_report
+            .components
+            .iter()
+            .map(|component| component.detail.as_deref())
+            .collect();
+        assert_eq!(details[0], None);
+        assert_eq!(
+            details[1],
+            Some("migrations [20261018210000] have not been applied")
+        );
+        assert!(details[2].unwrap().ends_with("has not finished"));
+        assert_eq!(details[3], Some("stopped"));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{
+    inbound_ports::DeliverWebhooksUseCase,
+    outbound_ports::{
+        LoadWebhookDeliveryPort, LoadWebhookSubscriptionPort, UpdateWebhookDeliveryStatePort,
+        WebhookPort,
+    },
+};
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct DeliverWebhooksUseCaseImpl {
+    load_webhook_delivery_port: Arc<dyn LoadWebhookDeliveryPort>,
+    update_webhook_delivery_state_port: Arc<dyn UpdateWebhookDeliveryStatePort>,
+    load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+    webhook_port: Arc<dyn WebhookPort>,
+    webhook_delivery_properties: WebhookDeliveryProperties,
+}
+
+impl DeliverWebhooksUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_webhook_delivery_port: Arc<dyn LoadWebhookDeliveryPort>,
+        update_webhook_delivery_state_port: Arc<dyn UpdateWebhookDeliveryStatePort>,
+        load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+        webhook_port: Arc<dyn WebhookPort>,
+        webhook_delivery_properties: WebhookDeliveryProperties,
+    ) -> Self {
+        Self {
+            load_webhook_delivery_port,
+            update_webhook_delivery_state_port,
+            load_webhook_subscription_port,
+            webhook_port,
+            webhook_delivery_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl DeliverWebhooksUseCase for DeliverWebhooksUseCaseImpl {
+    async fn deliver_webhooks(&self, now: NaiveDateTime) -> usize {
+        let due_deliveries = self
+            .load_webhook_delivery_port
+            .load_due_webhook_deliveries(now, self.webhook_delivery_properties.batch_size)
+            .await;
+
+        let mut delivered = 0;
+        // unlike the outbox, the deliveries are independent of each other and a failed
+        // delivery does not hold back the following ones
+        for mut webhook_delivery in due_deliveries {
+            let Some(webhook_subscription) = self
+                .load_webhook_subscription_port
+                .load_webhook_subscription(webhook_delivery.subscription_id.clone())
+                .await
+            else {
+                webhook_delivery.dead_letter(now, "subscription removed".to_string());
+                self.update_webhook_delivery_state_port
+                    .save_webhook_delivery(webhook_delivery)
+                    .await;
+                continue;
+            };
+
+            match self
+                .webhook_port
+                .post_webhook(&webhook_subscription, &webhook_delivery)
+                .await
+            {
+                Ok(_) => {
+                    webhook_delivery.mark_delivered(now);
+                    delivered += 1;
+                }
+                Err(error)
+                    if webhook_delivery.attempts + 1
+                        >= self.webhook_delivery_properties.maximum_attempts =>
+                {
+                    webhook_delivery.dead_letter(now, error);
+                }
+                Err(error) => {
+                    let backoff = self
+                        .webhook_delivery_properties
+                        .backoff_after(webhook_delivery.attempts + 1);
+                    webhook_delivery.schedule_retry(now, backoff, error);
+                }
+            }
+            self.update_webhook_delivery_state_port
+                .save_webhook_delivery(webhook_delivery)
+                .await;
+        }
+        delivered
+    }
+}
+
+// #[singleton]
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WebhookDeliveryProperties {
+    batch_size: usize,
+    initial_backoff: Duration,
+    maximum_backoff: Duration,
+    maximum_attempts: u32,
+}
+
+impl WebhookDeliveryProperties {
+    // Functions
+
+    /// # Arguments
+    ///
+    /// * `batch_size` - The maximum number of deliveries posted per run.
+    /// * `initial_backoff` - The delay after the first failed attempt, doubled after each further failure.
+    /// * `maximum_backoff` - The longest delay between two attempts of the same delivery.
+    /// * `maximum_attempts` - The number of failed attempts after which a delivery is dead-lettered.
+    pub fn new(
+        batch_size: usize,
+        initial_backoff: Duration,
+        maximum_backoff: Duration,
+        maximum_attempts: u32,
+    ) -> Self {
+        Self {
+            batch_size,
+            initial_backoff,
+            maximum_backoff,
+            maximum_attempts,
+        }
+    }
+
+    // Methods
+
+    pub(crate) fn backoff_after(&self, attempts: u32) -> Duration {
+        let factor = 2_i32.saturating_pow(attempts.saturating_sub(1).min(30));
+        (self.initial_backoff * factor).min(self.maximum_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockLoadWebhookDeliveryPort, MockLoadWebhookSubscriptionPort,
+        MockUpdateWebhookDeliveryStatePort, MockWebhookPort,
+    };
+    use chrono::NaiveDate;
+    use domain::{
+        ar::{
+            account::AccountId,
+            domain_event::DomainEvent,
+            webhook_subscription::{
+                WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookSubscription,
+                WebhookSubscriptionId,
+            },
+        },
+        vo::money::Money,
+    };
+    use mockall::predicate::{eq, function};
+
+    #[async_std::test]
+    async fn test_failed_deliveries_are_retried_until_dead_lettered() {
+        // Given a new delivery, a delivery in its last attempt and one of a removed subscription
+        let mut load_webhook_delivery_port = MockLoadWebhookDeliveryPort::new();
+        load_webhook_delivery_port
+            .expect_load_due_webhook_deliveries()
+            .with(eq(now()), eq(10))
+            .returning(|_, _| {
+                vec![
+                    webhook_delivery(1, 1, 0),
+                    webhook_delivery(2, 1, 2),
+                    webhook_delivery(3, 2, 0),
+                ]
+            });
+        let mut load_webhook_subscription_port = MockLoadWebhookSubscriptionPort::new();
+        load_webhook_subscription_port
+            .expect_load_webhook_subscription()
+            .with(eq(WebhookSubscriptionId(1)))
+            .returning(|id| {
+                let mut webhook_subscription = WebhookSubscription::new(
+                    AccountId(42),
+                    "http://127.0.0.1:9000/hook".to_string(),
+                    "secret".to_string(),
+                    now(),
+                );
+                webhook_subscription.id = Some(id);
+                Some(webhook_subscription)
+            });
+        load_webhook_subscription_port
+            .expect_load_webhook_subscription()
+            .with(eq(WebhookSubscriptionId(2)))
+            .returning(|_| None);
+
+        // And the endpoint is unavailable
+        let mut webhook_port = MockWebhookPort::new();
+        webhook_port
+            .expect_post_webhook()
+            .times(2)
+            .returning(|_, _| Err("503 Service Unavailable".to_string()));
+
+        // Then the new delivery is retried after the initial backoff
+        let mut update_webhook_delivery_state_port = MockUpdateWebhookDeliveryStatePort::new();
+        update_webhook_delivery_state_port
+            .expect_save_webhook_delivery()
+            .times(1)
+            .with(function(|d: &WebhookDelivery| {
+                d.id == Some(WebhookDeliveryId(1))
+                    && d.status == WebhookDeliveryStatus::Pending
+                    && d.attempts == 1
+                    && d.next_attempt_at == now() + Duration::seconds(1)
+                    && d.last_error == Some("503 Service Unavailable".to_string())
+            }))
+            .return_const(());
+        // And the delivery in its last attempt is dead-lettered
+        update_webhook_delivery_state_port
+            .expect_save_webhook_delivery()
+            .times(1)
+            .with(function(|d: &WebhookDelivery| {
+                d.id == Some(WebhookDeliveryId(2))
+                    && d.status == WebhookDeliveryStatus::DeadLettered
+                    && d.attempts == 3
+                    && d.finished_at == Some(now())
+            }))
+            .return_const(());
+        // And the delivery of the removed subscription is dead-lettered without an attempt
+        update_webhook_delivery_state_port
+            .expect_save_webhook_delivery()
+            .times(1)
+            .with(function(|d: &WebhookDelivery| {
+                d.id == Some(WebhookDeliveryId(3))
+                    && d.status == WebhookDeliveryStatus::DeadLettered
+                    && d.last_error == Some("subscription removed".to_string())
+            }))
+            .return_const(());
+
+        // When
+        let deliver_webhooks_use_case = DeliverWebhooksUseCaseImpl::new(
+            Arc::new(load_webhook_delivery_port),
+            Arc::new(update_webhook_delivery_state_port),
+            Arc::new(load_webhook_subscription_port),
+            Arc::new(webhook_port),
+            WebhookDeliveryProperties::new(10, Duration::seconds(1), Duration::minutes(5), 3),
+        );
+        let delivered = deliver_webhooks_use_case.deliver_webhooks(now()).await;
+
+        // Then
+        assert_eq!(0, delivered);
+    }
+
+    #[async_std::test]
+    async fn test_successful_delivery_is_marked_delivered() {
+        // Given
+        let mut load_webhook_delivery_port = MockLoadWebhookDeliveryPort::new();
+        load_webhook_delivery_port
+            .expect_load_due_webhook_deliveries()
+            .returning(|_, _| vec![webhook_delivery(1, 1, 1)]);
+        let mut load_webhook_subscription_port = MockLoadWebhookSubscriptionPort::new();
+        load_webhook_subscription_port
+            .expect_load_webhook_subscription()
+            .returning(|_| {
+                Some(WebhookSubscription::new(
+                    AccountId(42),
+                    "http://127.0.0.1:9000/hook".to_string(),
+                    "secret".to_string(),
+                    now(),
+                ))
+            });
+        let mut webhook_port = MockWebhookPort::new();
+        webhook_port
+            .expect_post_webhook()
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        // Then
+        let mut update_webhook_delivery_state_port = MockUpdateWebhookDeliveryStatePort::new();
+        update_webhook_delivery_state_port
+            .expect_save_webhook_delivery()
+            .times(1)
+            .with(function(|d: &WebhookDelivery| {
+                d.status == WebhookDeliveryStatus::Delivered
+                    && d.attempts == 2
+                    && d.last_error.is_none()
+                    && d.finished_at == Some(now())
+            }))
+            .return_const(());
+
+        // When
+        let deliver_webhooks_use_case = DeliverWebhooksUseCaseImpl::new(
+            Arc::new(load_webhook_delivery_port),
+            Arc::new(update_webhook_delivery_state_port),
+            Arc::new(load_webhook_subscription_port),
+            Arc::new(webhook_port),
+            WebhookDeliveryProperties::new(10, Duration::seconds(1), Duration::minutes(5), 3),
+        );
+        let delivered = deliver_webhooks_use_case.deliver_webhooks(now()).await;
+
+        // Then
+        assert_eq!(1, delivered);
+    }
+
+    fn webhook_delivery(id: i64, subscription_id: i64, attempts: u32) -> WebhookDelivery {
+        let mut webhook_delivery = WebhookDelivery::new(
+            WebhookSubscriptionId(subscription_id),
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(42),
+                source_account_id: AccountId(41),
+                money: Money::of(500),
+                timestamp: now(),
+            },
+        );
+        webhook_delivery.id = Some(WebhookDeliveryId(id));
+        webhook_delivery.attempts = attempts;
+        if attempts > 0 {
+            webhook_delivery.last_error = Some("timeout".to_string());
+        }
+        webhook_delivery
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,294 @@
+use crate::inbound_ports::{
+    FxQuote, FxQuoteId, FxTransferUseCase, Principal, SendMoneyBatchCommand, SendMoneyBatchUseCase,
+    SendMoneyCommand, SendMoneyError, SendMoneyUseCase, TransferReceipt,
+};
+
+use async_trait::async_trait;
+use domain::ar::account::AccountId;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/**
+ * Counts the transfers in flight of all the draining use cases, so that a shutdown can wait for
+ * them to be booked instead of interrupting them.
+ */
+// #[singleton]
+#[derive(Debug, Default)]
+pub struct TransfersInFlight {
+    count: AtomicUsize,
+}
+
+impl TransfersInFlight {
+    // #[inject]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    fn enter(&self) -> InFlight<'_> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        InFlight(&self.count)
+    }
+}
+
+/**
+ * Leaves the count when the transfer completes or is dropped before.
+ */
+struct InFlight<'a>(&'a AtomicUsize);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct DrainingSendMoneyUseCase {
+    send_money_use_case: Arc<dyn SendMoneyUseCase>,
+    transfers_in_flight: Arc<TransfersInFlight>,
+}
+
+impl DrainingSendMoneyUseCase {
+    // #[inject]
+    pub fn new(
+        send_money_use_case: Arc<dyn SendMoneyUseCase>,
+        transfers_in_flight: Arc<TransfersInFlight>,
+    ) -> Self {
+        Self {
+            send_money_use_case,
+            transfers_in_flight,
+        }
+    }
+}
+
+#[async_trait]
+impl SendMoneyUseCase for DrainingSendMoneyUseCase {
+    async fn send_money(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<TransferReceipt, SendMoneyError> {
+        let _in_flight = self.transfers_in_flight.enter();
+        self.send_money_use_case.send_money(command).await
+    }
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct DrainingSendMoneyBatchUseCase {
+    send_money_batch_use_case: Arc<dyn SendMoneyBatchUseCase>,
+    transfers_in_flight: Arc<TransfersInFlight>,
+}
+
+impl DrainingSendMoneyBatchUseCase {
+    // #[inject]
+    pub fn new(
+        send_money_batch_use_case: Arc<dyn SendMoneyBatchUseCase>,
+        transfers_in_flight: Arc<TransfersInFlight>,
+    ) -> Self {
+        Self {
+            send_money_batch_use_case,
+            transfers_in_flight,
+        }
+    }
+}
+
+#[async_trait]
+impl SendMoneyBatchUseCase for DrainingSendMoneyBatchUseCase {
+    async fn send_money_batch(
+        &self,
+        command: SendMoneyBatchCommand,
+    ) -> Vec<Result<TransferReceipt, SendMoneyError>> {
+        let _in_flight = self.transfers_in_flight.enter();
+        self.send_money_batch_use_case
+            .send_money_batch(command)
+            .await
+    }
+}
+
+/**
+ * Drains the FX transfers, the quotes are not booked and therefore not counted.
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct DrainingFxTransferUseCase {
+    fx_transfer_use_case: Arc<dyn FxTransferUseCase>,
+    transfers_in_flight: Arc<TransfersInFlight>,
+}
+
+impl DrainingFxTransferUseCase {
+    // #[inject]
+    pub fn new(
+        fx_transfer_use_case: Arc<dyn FxTransferUseCase>,
+        transfers_in_flight: Arc<TransfersInFlight>,
+    ) -> Self {
+        Self {
+            fx_transfer_use_case,
+            transfers_in_flight,
+        }
+    }
+}
+
+#[async_trait]
+impl FxTransferUseCase for DrainingFxTransferUseCase {
+    async fn quote_fx_transfer(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<FxQuote, SendMoneyError> {
+        self.fx_transfer_use_case.quote_fx_transfer(command).await
+    }
+
+    async fn send_money_fx(
+        &self,
+        source_account_id: AccountId,
+        quote_id: FxQuoteId,
+        principal: Principal,
+    ) -> Result<FxQuote, SendMoneyError> {
+        let _in_flight = self.transfers_in_flight.enter();
+        self.fx_transfer_use_case
+            .send_money_fx(source_account_id, quote_id, principal)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbound_ports::BatchMode;
+    use async_std::channel::{Receiver, bounded};
+    use domain::vo::money::Money;
+
+    /**
+     * Completes each transfer when told so.
+     */
+    #[derive(Debug)]
+    struct PendingTransferUseCase {
+        completed: Receiver<()>,
+    }
+
+    #[async_trait]
+    impl SendMoneyUseCase for PendingTransferUseCase {
+        async fn send_money(
+            &self,
+            _command: SendMoneyCommand,
+        ) -> Result<TransferReceipt, SendMoneyError> {
+            self.completed.recv().await.unwrap();
+            Err(SendMoneyError::InsufficientFunds)
+        }
+    }
+
+    #[async_trait]
+    impl SendMoneyBatchUseCase for PendingTransferUseCase {
+        async fn send_money_batch(
+            &self,
+            _command: SendMoneyBatchCommand,
+        ) -> Vec<Result<TransferReceipt, SendMoneyError>> {
+            self.completed.recv().await.unwrap();
+            vec![Err(SendMoneyError::InsufficientFunds)]
+        }
+    }
+
+    #[async_trait]
+    impl FxTransferUseCase for PendingTransferUseCase {
+        async fn quote_fx_transfer(
+            &self,
+            _command: SendMoneyCommand,
+        ) -> Result<FxQuote, SendMoneyError> {
+            Err(SendMoneyError::InsufficientFunds)
+        }
+
+        async fn send_money_fx(
+            &self,
+            _source_account_id: AccountId,
+            _quote_id: FxQuoteId,
+            _principal: Principal,
+        ) -> Result<FxQuote, SendMoneyError> {
+            self.completed.recv().await.unwrap();
+            Err(SendMoneyError::InsufficientFunds)
+        }
+    }
+
+    #[async_std::test]
+    async fn test_counts_transfers_in_flight() {
+        // Given a single transfer, a batch and an FX transfer which complete when told so
+        let (complete, completed) = bounded(3);
+        let pending_transfer_use_case = Arc::new(PendingTransferUseCase { completed });
+        let transfers_in_flight = Arc::new(TransfersInFlight::new());
+        let draining_send_money_use_case = Arc::new(DrainingSendMoneyUseCase::new(
+            pending_transfer_use_case.clone(),
+            transfers_in_flight.clone(),
+        ));
+        let draining_send_money_batch_use_case = Arc::new(DrainingSendMoneyBatchUseCase::new(
+            pending_transfer_use_case.clone(),
+            transfers_in_flight.clone(),
+        ));
+        let draining_fx_transfer_use_case = Arc::new(DrainingFxTransferUseCase::new(
+            pending_transfer_use_case,
+            transfers_in_flight.clone(),
+        ));
+
+        // When they are sent
+        let transfer = async_std::task::spawn({
+            let draining_send_money_use_case = draining_send_money_use_case.clone();
+            async move { draining_send_money_use_case.send_money(command()).await }
+        });
+        let batch = async_std::task::spawn({
+            let draining_send_money_batch_use_case = draining_send_money_batch_use_case.clone();
+            async move {
+                draining_send_money_batch_use_case
+                    .send_money_batch(SendMoneyBatchCommand::new(
+                        vec![command()],
+                        BatchMode::BestEffort,
+                    ))
+                    .await
+            }
+        });
+        let fx_transfer = async_std::task::spawn({
+            let draining_fx_transfer_use_case = draining_fx_transfer_use_case.clone();
+            async move {
+                draining_fx_transfer_use_case
+                    .send_money_fx(
+                        AccountId(41),
+                        FxQuoteId("quote".to_string()),
+                        Principal::system(),
+                    )
+                    .await
+            }
+        });
+        while transfers_in_flight.count() < 3 {
+            async_std::task::yield_now().await;
+        }
+
+        // Then they are in flight until they complete
+        assert_eq!(transfers_in_flight.count(), 3);
+        for _ in 0..3 {
+            complete.send(()).await.unwrap();
+        }
+        assert_eq!(transfer.await, Err(SendMoneyError::InsufficientFunds));
+        assert_eq!(batch.await, vec![Err(SendMoneyError::InsufficientFunds)]);
+        assert_eq!(fx_transfer.await, Err(SendMoneyError::InsufficientFunds));
+        assert_eq!(transfers_in_flight.count(), 0);
+        // And quotes are not counted
+        assert!(
+            draining_fx_transfer_use_case
+                .quote_fx_transfer(command())
+                .await
+                .is_err()
+        );
+        assert_eq!(transfers_in_flight.count(), 0);
+    }
+
+    fn command() -> SendMoneyCommand {
+        SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        )
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{
+    inbound_ports::{
+        ExecuteScheduledTransfersUseCase, Principal, SendMoneyCommand, SendMoneyUseCase,
+    },
+    outbound_ports::{LoadScheduledTransferPort, UpdateScheduledTransferStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+use domain::ar::scheduled_transfer::ScheduledTransferStatus;
+use std::sync::Arc;
+use tracing::warn;
+
+const INTERRUPTED_EXECUTION_REASON: &str =
+    "the execution was interrupted, check whether the money has been sent";
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ExecuteScheduledTransfersUseCaseImpl {
+    load_scheduled_transfer_port: Arc<dyn LoadScheduledTransferPort>,
+    update_scheduled_transfer_state_port: Arc<dyn UpdateScheduledTransferStatePort>,
+    send_money_use_case: Arc<dyn SendMoneyUseCase>,
+    claim_lease: Duration,
+}
+
+impl ExecuteScheduledTransfersUseCaseImpl {
+    /// # Arguments
+    ///
+    /// * `claim_lease` - How long an executor may take to record the outcome of a transfer it
+    ///   claimed, well above the time a transfer takes.
+    // #[inject]
+    pub fn new(
+        load_scheduled_transfer_port: Arc<dyn LoadScheduledTransferPort>,
+        update_scheduled_transfer_state_port: Arc<dyn UpdateScheduledTransferStatePort>,
+        send_money_use_case: Arc<dyn SendMoneyUseCase>,
+        claim_lease: Duration,
+    ) -> Self {
+        Self {
+            load_scheduled_transfer_port,
+            update_scheduled_transfer_state_port,
+            send_money_use_case,
+            claim_lease,
+        }
+    }
+}
+
+#[async_trait]
+impl ExecuteScheduledTransfersUseCase for ExecuteScheduledTransfersUseCaseImpl {
+    async fn execute_due_transfers(&self, now: NaiveDateTime) -> usize {
+        let mut processed = 0;
+
+        // a claim which outlived its lease was interrupted, e.g. by a crash, possibly after the
+        // money moved, so the transfer is flagged as failed for a person to check instead of
+        // being sent again
+        let interrupted_transfers = self
+            .load_scheduled_transfer_port
+            .load_scheduled_transfers_claimed_before(now - self.claim_lease)
+            .await;
+        for mut scheduled_transfer in interrupted_transfers {
+            if !scheduled_transfer.is_claim_expired(now, self.claim_lease) {
+                continue;
+            }
+            warn!(
+                id = ?scheduled_transfer.id,
+                claimed_at = ?scheduled_transfer.claimed_at,
+                "scheduled transfer interrupted during its execution"
+            );
+            scheduled_transfer.mark_failed(now, INTERRUPTED_EXECUTION_REASON.to_string());
+            if self
+                .update_scheduled_transfer_state_port
+                .update_scheduled_transfer_state(
+                    scheduled_transfer,
+                    ScheduledTransferStatus::Executing,
+                )
+                .await
+            {
+                processed += 1;
+            }
+        }
+
+        let due_transfers = self
+            .load_scheduled_transfer_port
+            .load_due_scheduled_transfers(now)
+            .await;
+        for mut scheduled_transfer in due_transfers {
+            if !scheduled_transfer.is_due(now) {
+                continue;
+            }
+
+            // claim the transfer before the money moves, so that a transfer cancelled or picked up
+            // by another executor meanwhile is skipped, and one interrupted by a crash is not
+            // sent again
+            scheduled_transfer.start_execution(now);
+            if !self
+                .update_scheduled_transfer_state_port
+                .update_scheduled_transfer_state(
+                    scheduled_transfer.clone(),
+                    ScheduledTransferStatus::Pending,
+                )
+                .await
+            {
+                continue;
+            }
+
+            // as whoever scheduled it, so that it fails once they may no longer debit the account
+            let command = SendMoneyCommand::new(
+                scheduled_transfer.source_account_id.clone(),
+                scheduled_transfer.target_account_id.clone(),
+                scheduled_transfer.money.clone(),
+                Principal::new(
+                    scheduled_transfer.created_by.clone(),
+                    scheduled_transfer.created_by_role,
+                ),
+            );
+            match self.send_money_use_case.send_money(command).await {
+                Ok(_) => scheduled_transfer.mark_executed(now),
+                Err(error) => scheduled_transfer.mark_failed(now, error.to_string()),
+            }
+
+            self.update_scheduled_transfer_state_port
+                .update_scheduled_transfer_state(
+                    scheduled_transfer,
+                    ScheduledTransferStatus::Executing,
+                )
+                .await;
+            processed += 1;
+        }
+        processed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbound_ports::{SendMoneyError, TransferReceipt};
+    use crate::outbound_ports::{
+        MockLoadScheduledTransferPort, MockUpdateScheduledTransferStatePort,
+    };
+    use chrono::{NaiveDate, NaiveTime};
+    use domain::{
+        ar::{
+            account::AccountId,
+            scheduled_transfer::{ScheduledTransfer, ScheduledTransferId},
+        },
+        vo::{money::Money, role::Role},
+    };
+    use mockall::{
+        mock,
+        predicate::{eq, function},
+    };
+
+    mock! {
+        #[derive(Debug)]
+        SendMoneyUseCaseImpl {}
+        #[async_trait]
+        impl SendMoneyUseCase for SendMoneyUseCaseImpl {
+            async fn send_money(
+                &self,
+                command: SendMoneyCommand,
+            ) -> Result<TransferReceipt, SendMoneyError>;
+        }
+    }
+
+    #[async_std::test]
+    async fn test_due_transfers_are_executed_and_outcome_is_recorded() {
+        // Given two due scheduled transfers
+        let mut load_port = MockLoadScheduledTransferPort::new();
+        load_port
+            .expect_load_scheduled_transfers_claimed_before()
+            .return_const(vec![]);
+        load_port
+            .expect_load_due_scheduled_transfers()
+            .with(eq(now()))
+            .returning(|_| {
+                vec![
+                    scheduled_transfer(1, AccountId(41), Money::of(500)),
+                    scheduled_transfer(2, AccountId(43), Money::of(9_999)),
+                    scheduled_transfer(3, AccountId(44), Money::of(700)),
+                ]
+            });
+
+        // And the first transfer succeeds and the second one is rejected
+        let mut send_money_use_case = MockSendMoneyUseCaseImpl::new();
+        send_money_use_case
+            .expect_send_money()
+            .with(eq(SendMoneyCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                alice(),
+            )))
+            .returning(|command| {
+                Ok(TransferReceipt::new(
+                    command.source_account_id,
+                    command.target_account_id,
+                    command.money,
+                    Money::of(0),
+                ))
+            });
+        send_money_use_case
+            .expect_send_money()
+            .with(eq(SendMoneyCommand::new(
+                AccountId(43),
+                AccountId(42),
+                Money::of(9_999),
+                alice(),
+            )))
+            .return_const(Err(SendMoneyError::InsufficientFunds));
+
+        // And the third transfer was claimed by another executor meanwhile
+        let mut update_port = MockUpdateScheduledTransferStatePort::new();
+        update_port
+            .expect_update_scheduled_transfer_state()
+            .times(3)
+            .with(
+                function(|st: &ScheduledTransfer| {
+                    st.status == ScheduledTransferStatus::Executing && st.claimed_at == Some(now())
+                }),
+                eq(ScheduledTransferStatus::Pending),
+            )
+            .returning(|st, _| st.id != Some(ScheduledTransferId(3)));
+
+        // Then the outcome of each claimed transfer is recorded
+        update_port
+            .expect_update_scheduled_transfer_state()
+            .times(1)
+            .with(
+                function(|st: &ScheduledTransfer| {
+                    st.id == Some(ScheduledTransferId(1))
+                        && st.status == ScheduledTransferStatus::Executed
+                }),
+                eq(ScheduledTransferStatus::Executing),
+            )
+            .return_const(true);
+        update_port
+            .expect_update_scheduled_transfer_state()
+            .times(1)
+            .with(
+                function(|st: &ScheduledTransfer| {
+                    st.id == Some(ScheduledTransferId(2))
+                        && st.status == ScheduledTransferStatus::Failed
+                        && st.failure_reason.is_some()
+                }),
+                eq(ScheduledTransferStatus::Executing),
+            )
+            .return_const(true);
+
+        // When due transfers are executed
+        let use_case = ExecuteScheduledTransfersUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(update_port),
+            Arc::new(send_money_use_case),
+            claim_lease(),
+        );
+        let processed = use_case.execute_due_transfers(now()).await;
+
+        assert_eq!(2, processed);
+    }
+
+    #[async_std::test]
+    async fn test_interrupted_executions_are_failed_instead_of_sent_again() {
+        // Given a transfer claimed longer than the lease ago
+        let mut load_port = MockLoadScheduledTransferPort::new();
+        load_port
+            .expect_load_scheduled_transfers_claimed_before()
+            .times(1)
+            .with(eq(now() - claim_lease()))
+            .returning(|_| {
+                let mut transfer = scheduled_transfer(1, AccountId(41), Money::of(500));
+                transfer.start_execution(now() - claim_lease() - Duration::minutes(1));
+                vec![transfer]
+            });
+        load_port
+            .expect_load_due_scheduled_transfers()
+            .return_const(vec![]);
+
+        // Then it is failed for a person to check whether the money has been sent
+        let mut update_port = MockUpdateScheduledTransferStatePort::new();
+        update_port
+            .expect_update_scheduled_transfer_state()
+            .times(1)
+            .with(
+                function(|st: &ScheduledTransfer| {
+                    st.id == Some(ScheduledTransferId(1))
+                        && st.status == ScheduledTransferStatus::Failed
+                        && st.executed_at == Some(now())
+                        && st.failure_reason == Some(INTERRUPTED_EXECUTION_REASON.to_string())
+                }),
+                eq(ScheduledTransferStatus::Executing),
+            )
+            .return_const(true);
+
+        // And not sent again
+        let mut send_money_use_case = MockSendMoneyUseCaseImpl::new();
+        send_money_use_case.expect_send_money().never();
+
+        // When
+        let use_case = ExecuteScheduledTransfersUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(update_port),
+            Arc::new(send_money_use_case),
+            claim_lease(),
+        );
+        let processed = use_case.execute_due_transfers(now()).await;
+
+        assert_eq!(1, processed);
+    }
+
+    fn scheduled_transfer(id: i64, source: AccountId, money: Money) -> ScheduledTransfer {
+        let mut transfer = ScheduledTransfer::new(
+            source,
+            AccountId(42),
+            money,
+            now(),
+            "alice".to_string(),
+            Role::Customer,
+        );
+        transfer.id = Some(ScheduledTransferId(id));
+        transfer
+    }
+
+    fn claim_lease() -> Duration {
+        Duration::minutes(10)
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 9, 1).unwrap(),
+            NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+        )
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    inbound_ports::{ExecuteStandingOrdersUseCase, Principal, SendMoneyCommand, SendMoneyUseCase},
+    outbound_ports::{LoadStandingOrderPort, UpdateStandingOrderStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime};
+use domain::ar::standing_order::{StandingOrderExecution, StandingOrderExecutionStatus};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ExecuteStandingOrdersUseCaseImpl {
+    load_standing_order_port: Arc<dyn LoadStandingOrderPort>,
+    update_standing_order_state_port: Arc<dyn UpdateStandingOrderStatePort>,
+    send_money_use_case: Arc<dyn SendMoneyUseCase>,
+}
+
+impl ExecuteStandingOrdersUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_standing_order_port: Arc<dyn LoadStandingOrderPort>,
+        update_standing_order_state_port: Arc<dyn UpdateStandingOrderStatePort>,
+        send_money_use_case: Arc<dyn SendMoneyUseCase>,
+    ) -> Self {
+        Self {
+            load_standing_order_port,
+            update_standing_order_state_port,
+            send_money_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl ExecuteStandingOrdersUseCase for ExecuteStandingOrdersUseCaseImpl {
+    async fn execute_due_standing_orders(&self, now: NaiveDateTime) -> usize {
+        let due_standing_orders = self
+            .load_standing_order_port
+            .load_due_standing_orders(now)
+            .await;
+
+        let mut executed = 0;
+        for mut standing_order in due_standing_orders {
+            let Some(standing_order_id) = standing_order.id.clone() else {
+                continue;
+            };
+            // catch up on every occurrence missed while the scheduler was not running
+            while standing_order.is_due(now) {
+                let scheduled_for = standing_order.advance().unwrap();
+
+                // claim the occurrence before moving any money, so it is never executed twice
+                let mut execution = StandingOrderExecution::started(
+                    standing_order_id.clone(),
+                    scheduled_for,
+                    Local::now().naive_local(),
+                );
+                let Some(execution_id) = self
+                    .update_standing_order_state_port
+                    .claim_standing_order_execution(standing_order.clone(), execution.clone())
+                    .await
+                else {
+                    break;
+                };
+                execution.id = Some(execution_id);
+
+                // as whoever created the order, so that it fails once they may no longer debit
+                // the account
+                let command = SendMoneyCommand::new(
+                    standing_order.source_account_id.clone(),
+                    standing_order.target_account_id.clone(),
+                    standing_order.money.clone(),
+                    Principal::new(
+                        standing_order.created_by.clone(),
+                        standing_order.created_by_role,
+                    ),
+                );
+                match self.send_money_use_case.send_money(command).await {
+                    Ok(_) => execution.status = StandingOrderExecutionStatus::Succeeded,
+                    Err(error) => {
+                        execution.status = StandingOrderExecutionStatus::Failed;
+                        execution.failure_reason = Some(error.to_string());
+                    }
+                }
+                execution.finished_at = Some(Local::now().naive_local());
+
+                self.update_standing_order_state_port
+                    .save_standing_order_execution(execution)
+                    .await;
+                executed += 1;
+            }
+        }
+        executed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbound_ports::{SendMoneyError, TransferReceipt};
+    use crate::outbound_ports::{MockLoadStandingOrderPort, MockUpdateStandingOrderStatePort};
+    use chrono::{Days, NaiveDate};
+    use domain::{
+        ar::{
+            account::AccountId,
+            standing_order::{
+                StandingOrder, StandingOrderExecutionId, StandingOrderId, StandingOrderStatus,
+            },
+        },
+        vo::{money::Money, recurrence::Recurrence, role::Role},
+    };
+    use mockall::{mock, predicate::function};
+
+    mock! {
+        #[derive(Debug)]
+        SendMoneyUseCaseImpl {}
+        #[async_trait]
+        impl SendMoneyUseCase for SendMoneyUseCaseImpl {
+            async fn send_money(
+                &self,
+                command: SendMoneyCommand,
+            ) -> Result<TransferReceipt, SendMoneyError>;
+        }
+    }
+
+    #[async_std::test]
+    async fn test_missed_occurrences_are_claimed_and_executed() {
+        // Given a daily standing order that missed two occurrences
+        let mut load_port = MockLoadStandingOrderPort::new();
+        load_port
+            .expect_load_due_standing_orders()
+            .returning(|_| vec![daily_standing_order()]);
+
+        // And each occurrence is claimed before money is sent
+        let mut update_port = MockUpdateStandingOrderStatePort::new();
+        update_port
+            .expect_claim_standing_order_execution()
+            .times(2)
+            .returning(|so, execution| {
+                assert_eq!(
+                    so.executed_occurrences,
+                    (execution.scheduled_for - start_date()).num_days() as u32 + 1
+                );
+                Some(StandingOrderExecutionId(so.executed_occurrences as i64))
+            });
+
+        // And the money is sent on behalf of the creator of the order
+        let mut send_money_use_case = MockSendMoneyUseCaseImpl::new();
+        send_money_use_case
+            .expect_send_money()
+            .times(2)
+            .with(function(|command: &SendMoneyCommand| {
+                command.principal == Principal::new("alice".to_string(), Role::Customer)
+            }))
+            .returning(|command| {
+                Ok(TransferReceipt::new(
+                    command.source_account_id,
+                    command.target_account_id,
+                    command.money,
+                    Money::of(0),
+                ))
+            });
+
+        // Then the outcome of each execution is recorded
+        update_port
+            .expect_save_standing_order_execution()
+            .times(2)
+            .with(function(|e: &StandingOrderExecution| {
+                e.id.is_some()
+                    && e.status == StandingOrderExecutionStatus::Succeeded
+                    && e.finished_at.is_some()
+            }))
+            .return_const(());
+
+        // When due standing orders are executed
+        let use_case = ExecuteStandingOrdersUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(update_port),
+            Arc::new(send_money_use_case),
+        );
+        let executed = use_case.execute_due_standing_orders(now()).await;
+
+        assert_eq!(2, executed);
+    }
+
+    #[async_std::test]
+    async fn test_already_claimed_occurrence_is_not_executed_again() {
+        // Given a due standing order whose occurrence was claimed before a restart
+        let mut load_port = MockLoadStandingOrderPort::new();
+        load_port
+            .expect_load_due_standing_orders()
+            .returning(|_| vec![daily_standing_order()]);
+
+        let mut update_port = MockUpdateStandingOrderStatePort::new();
+        update_port
+            .expect_claim_standing_order_execution()
+            .times(1)
+            .returning(|_, _| None);
+
+        // Then no money is sent
+        let use_case = ExecuteStandingOrdersUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(update_port),
+            Arc::new(MockSendMoneyUseCaseImpl::new()),
+        );
+        let executed = use_case.execute_due_standing_orders(now()).await;
+
+        assert_eq!(0, executed);
+    }
+
+    fn daily_standing_order() -> StandingOrder {
+        let mut so = StandingOrder::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(100),
+            Recurrence::Daily,
+            start_date(),
+            None,
+            None,
+            "alice".to_string(),
+            Role::Customer,
+        );
+        so.id = Some(StandingOrderId(3));
+        assert_eq!(StandingOrderStatus::Active, so.status);
+        so
+    }
+
+    fn start_date() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 1)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap()
+    }
+
+    fn now() -> NaiveDateTime {
+        start_date().checked_add_days(Days::new(1)).unwrap()
+    }
+}
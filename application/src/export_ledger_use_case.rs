@@ -0,0 +1,404 @@
+use crate::{
+    inbound_ports::{
+        ExportLedgerCommand, ExportLedgerError, ExportLedgerUseCase, Ledger, LedgerExport,
+        LedgerTransfer,
+    },
+    outbound_ports::{LedgerFilePort, LoadLedgerPort, UpdateLedgerExportStatePort},
+};
+use async_trait::async_trait;
+use chrono::{Local, NaiveTime};
+use domain::{
+    ar::{
+        account::AccountId,
+        activity::{Activity, ActivityId},
+    },
+    vo::{exchange_rate::ExchangeRate, money::Money, rounding::Rounding},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+const PAGE_SIZE: usize = 1000;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ExportLedgerUseCaseImpl {
+    load_ledger_port: Arc<dyn LoadLedgerPort>,
+    update_ledger_export_state_port: Arc<dyn UpdateLedgerExportStatePort>,
+    ledger_file_port: Arc<dyn LedgerFilePort>,
+    fx_rounding: Rounding,
+}
+
+impl ExportLedgerUseCaseImpl {
+    /// # Arguments
+    ///
+    /// * `fx_rounding` - How the FX transfers round the money they credit, see [TransferKey].
+    // #[inject]
+    pub fn new(
+        load_ledger_port: Arc<dyn LoadLedgerPort>,
+        update_ledger_export_state_port: Arc<dyn UpdateLedgerExportStatePort>,
+        ledger_file_port: Arc<dyn LedgerFilePort>,
+        fx_rounding: Rounding,
+    ) -> Self {
+        Self {
+            load_ledger_port,
+            update_ledger_export_state_port,
+            ledger_file_port,
+            fx_rounding,
+        }
+    }
+}
+
+#[async_trait]
+impl ExportLedgerUseCase for ExportLedgerUseCaseImpl {
+    async fn export_ledger(
+        &self,
+        command: ExportLedgerCommand,
+    ) -> Result<LedgerExport, ExportLedgerError> {
+        if command
+            .from
+            .zip(command.to)
+            .is_some_and(|(from, to)| from > to)
+        {
+            return Err(ExportLedgerError::InvalidPeriod);
+        }
+        let since = command.from.map(|from| from.and_time(NaiveTime::MIN));
+        let until = match command.to {
+            Some(to) => match to.succ_opt() {
+                Some(day_after) => Some(day_after.and_time(NaiveTime::MIN)),
+                None => return Err(ExportLedgerError::InvalidPeriod),
+            },
+            None => None,
+        };
+
+        let mut after_activity_id = if command.incremental {
+            self.load_ledger_port.load_last_exported_activity_id().await
+        } else {
+            None
+        };
+        let mut activities = vec![];
+        loop {
+            let page = self
+                .load_ledger_port
+                .load_ledger_activities(
+                    after_activity_id.as_ref().map(|id| ActivityId(id.0)),
+                    since,
+                    until,
+                    PAGE_SIZE,
+                )
+                .await;
+            let page_size = page.len();
+            if let Some(last) = page.last() {
+                after_activity_id = last.id.as_ref().map(|id| ActivityId(id.0));
+            }
+            activities.extend(page);
+            if page_size < PAGE_SIZE {
+                break;
+            }
+        }
+        let first_activity_id = activities
+            .first()
+            .and_then(|activity| activity.id.as_ref().map(|id| ActivityId(id.0)));
+        let last_activity_id = activities
+            .last()
+            .and_then(|activity| activity.id.as_ref().map(|id| ActivityId(id.0)));
+
+        let ledger = Ledger {
+            exported_at: Local::now().naive_local(),
+            from: command.from,
+            to: command.to,
+            transfers: group_by_transfer(activities, self.fx_rounding),
+        };
+        let file = self
+            .ledger_file_port
+            .write_ledger_file(&ledger)
+            .await
+            .map_err(ExportLedgerError::WriteFailed)?;
+
+        let ledger_export = LedgerExport {
+            exported_at: ledger.exported_at,
+            from: ledger.from,
+            to: ledger.to,
+            first_activity_id,
+            last_activity_id,
+            transfer_count: ledger.transfers.len(),
+            incomplete_transfer_count: ledger
+                .transfers
+                .iter()
+                .filter(|transfer| !transfer.is_complete())
+                .count(),
+            file,
+        };
+        self.update_ledger_export_state_port
+            .save_ledger_export(&ledger_export)
+            .await;
+        Ok(ledger_export)
+    }
+}
+
+/**
+ * What the withdrawal and the deposit of a transfer have in common: the accounts, the rate of an
+ * exchange and the money credited to the target account, which an exchanged withdrawal is
+ * converted into, rounded like the FX transfers round it.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct TransferKey {
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub credited_money: Money,
+    pub exchange_rate: Option<ExchangeRate>,
+}
+
+impl TransferKey {
+    // Functions
+
+    /**
+     * @return the key of the transfer and whether the activity is its withdrawal.
+     */
+    pub(crate) fn of(activity: &Activity, fx_rounding: Rounding) -> (Self, bool) {
+        let withdrawal = activity.owner_account_id == activity.source_account_id;
+        let credited_money = match &activity.exchange_rate {
+            Some(exchange_rate) if withdrawal => {
+                exchange_rate.convert(&activity.money, fx_rounding)
+            }
+            _ => activity.money.clone(),
+        };
+        let key = Self {
+            source_account_id: activity.source_account_id.clone(),
+            target_account_id: activity.target_account_id.clone(),
+            credited_money,
+            exchange_rate: activity.exchange_rate.clone(),
+        };
+        (key, withdrawal)
+    }
+}
+
+/**
+ * Pairs each withdrawal with the next deposit of the same [TransferKey] and vice versa, as the
+ * two activities of a transfer are stored one after the other but not at the same instant. An
+ * activity whose counterpart is missing is thus not paired with that of another transfer between
+ * the same accounts.
+ */
+pub(crate) fn group_by_transfer(
+    activities: Vec<Activity>,
+    fx_rounding: Rounding,
+) -> Vec<LedgerTransfer> {
+    let mut transfers: Vec<LedgerTransfer> = vec![];
+    // the indexes of the transfers still missing a withdrawal (true) or a deposit (false)
+    let mut incomplete: HashMap<(TransferKey, bool), VecDeque<usize>> = HashMap::new();
+    for activity in activities {
+        let (key, withdrawal) = TransferKey::of(&activity, fx_rounding);
+        if let Some(index) = incomplete
+            .get_mut(&(key.clone(), withdrawal))
+            .and_then(VecDeque::pop_front)
+        {
+            if withdrawal {
+                transfers[index].withdrawal = Some(activity);
+            } else {
+                transfers[index].deposit = Some(activity);
+            }
+            continue;
+        }
+        let transfer_id = ActivityId(activity.id.as_ref().unwrap().0);
+        let source_account_id = key.source_account_id.clone();
+        let target_account_id = key.target_account_id.clone();
+        incomplete
+            .entry((key, !withdrawal))
+            .or_default()
+            .push_back(transfers.len());
+        let (withdrawal, deposit) = if withdrawal {
+            (Some(activity), None)
+        } else {
+            (None, Some(activity))
+        };
+        transfers.push(LedgerTransfer {
+            transfer_id,
+            source_account_id,
+            target_account_id,
+            withdrawal,
+            deposit,
+        });
+    }
+    transfers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inbound_ports::LedgerFile,
+        outbound_ports::{MockLedgerFilePort, MockLoadLedgerPort, MockUpdateLedgerExportStatePort},
+    };
+    use chrono::NaiveDate;
+    use domain::vo::currency::Currency;
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_incremental_export_groups_the_activities_by_transfer() {
+        // Given activity 10 has been exported
+        let mut load_ledger_port = MockLoadLedgerPort::new();
+        load_ledger_port
+            .expect_load_last_exported_activity_id()
+            .times(1)
+            .returning(|| Some(ActivityId(10)));
+        // And a transfer from 1 to 2 with a fee, followed by the deposit of a transfer from 3 to
+        // 2 whose withdrawal has been exported before
+        load_ledger_port
+            .expect_load_ledger_activities()
+            .times(1)
+            .with(
+                eq(Some(ActivityId(10))),
+                eq(Some(date(1).and_time(NaiveTime::MIN))),
+                eq(Some(date(2).and_time(NaiveTime::MIN))),
+                eq(PAGE_SIZE),
+            )
+            .returning(|_, _, _, _| {
+                vec![
+                    activity(11, 1, 1, 2, 500),
+                    activity(12, 1, 1, 100, 1),
+                    activity(13, 2, 1, 2, 500),
+                    activity(14, 100, 1, 100, 1),
+                    activity(15, 2, 3, 2, 70),
+                ]
+            });
+        let mut ledger_file_port = MockLedgerFilePort::new();
+        ledger_file_port
+            .expect_write_ledger_file()
+            .times(1)
+            .withf(|ledger: &Ledger| {
+                ledger
+                    .transfers
+                    .iter()
+                    .map(|transfer| (transfer.transfer_id.0, transfer.is_complete()))
+                    .collect::<Vec<_>>()
+                    == vec![(11, true), (12, true), (15, false)]
+                    && ledger.transfers[0].deposit.as_ref().unwrap().id == Some(ActivityId(13))
+                    && ledger.transfers[2].withdrawal.is_none()
+            })
+            .returning(|_| Ok(ledger_file()));
+        let mut update_ledger_export_state_port = MockUpdateLedgerExportStatePort::new();
+        update_ledger_export_state_port
+            .expect_save_ledger_export()
+            .times(1)
+            .withf(|ledger_export: &LedgerExport| {
+                ledger_export.first_activity_id == Some(ActivityId(11))
+                    && ledger_export.last_activity_id == Some(ActivityId(15))
+            })
+            .return_const(());
+
+        let export_ledger_use_case = ExportLedgerUseCaseImpl::new(
+            Arc::new(load_ledger_port),
+            Arc::new(update_ledger_export_state_port),
+            Arc::new(ledger_file_port),
+            Rounding::HalfEven,
+        );
+
+        // When
+        let ledger_export = export_ledger_use_case
+            .export_ledger(ExportLedgerCommand::new(Some(date(1)), Some(date(1)), true))
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(3, ledger_export.transfer_count);
+        assert_eq!(1, ledger_export.incomplete_transfer_count);
+        assert_eq!(ledger_file(), ledger_export.file);
+
+        // When the period ends before it starts
+        let ledger_export = export_ledger_use_case
+            .export_ledger(ExportLedgerCommand::new(Some(date(2)), Some(date(1)), true))
+            .await;
+
+        // Then
+        assert_eq!(Err(ExportLedgerError::InvalidPeriod), ledger_export);
+    }
+
+    #[test]
+    fn test_activities_are_only_paired_with_those_of_the_same_transfer() {
+        // Given a withdrawal of 500 from 1 to 2 whose deposit is missing, followed by a transfer
+        // of 300 from 1 to 2 and a transfer from 1 to 3 exchanged from 100 Euro into 108 US Dollar
+        let eur_usd = ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500);
+        let exchanged = |id, owner_account_id, amount| {
+            let mut activity = activity(id, owner_account_id, 1, 3, amount);
+            activity.exchange_rate = Some(eur_usd.clone());
+            activity
+        };
+        let activities = vec![
+            activity(21, 1, 1, 2, 500),
+            activity(22, 1, 1, 2, 300),
+            activity(23, 2, 1, 2, 300),
+            exchanged(24, 1, 100),
+            exchanged(25, 3, 108),
+        ];
+
+        // When
+        let transfers = group_by_transfer(activities, Rounding::HalfEven);
+
+        // Then the orphan is not paired with the deposit of the later transfer
+        assert_eq!(
+            vec![(21, false), (22, true), (24, true)],
+            transfers
+                .iter()
+                .map(|transfer| (transfer.transfer_id.0, transfer.is_complete()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(ActivityId(23)),
+            transfers[1].deposit.as_ref().unwrap().id
+        );
+    }
+
+    #[test]
+    fn test_exchanged_activities_are_paired_by_the_rounding_of_the_fx_transfers() {
+        // Given 100 Euro exchanged into 108.45 US Dollar, which were rounded up when credited
+        let eur_usd = ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500);
+        let activities = || {
+            vec![activity(31, 1, 1, 3, 100), activity(32, 3, 1, 3, 109)]
+                .into_iter()
+                .map(|mut activity| {
+                    activity.exchange_rate = Some(eur_usd.clone());
+                    activity
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // When they are grouped by the same rounding
+        let transfers = group_by_transfer(activities(), Rounding::Up);
+
+        // Then they form a single transfer
+        assert_eq!(1, transfers.len());
+        assert!(transfers[0].is_complete());
+        // But not by another rounding
+        assert_eq!(2, group_by_transfer(activities(), Rounding::HalfEven).len());
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2019, 8, day).unwrap()
+    }
+
+    fn ledger_file() -> LedgerFile {
+        LedgerFile {
+            path: "ledger-20190802T000000000.csv".to_string(),
+            checksum: "00".to_string(),
+        }
+    }
+
+    fn activity(
+        id: i64,
+        owner_account_id: i64,
+        source_account_id: i64,
+        target_account_id: i64,
+        amount: i128,
+    ) -> Activity {
+        Activity::with_id(
+            Some(ActivityId(id)),
+            AccountId(owner_account_id),
+            AccountId(source_account_id),
+            AccountId(target_account_id),
+            date(1).and_hms_opt(9, 0, 0).unwrap(),
+            Money::of(amount),
+            None,
+        )
+    }
+}
@@ -0,0 +1,168 @@
+use domain::{
+    ar::account::AccountId,
+    vo::{money::Money, rounding::Rounding},
+};
+
+/**
+ * Calculates the fee charged on a transfer of a certain amount of money.
+ * Policies can be combined, e.g. a capped percentage within a tier.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub enum FeePolicy {
+    /// The same fee for every transfer.
+    Flat(Money),
+    /// A share of the transferred money in basis points (1/100 of a percent), rounded half up.
+    Percentage { basis_points: i128 },
+    /// The policy of the tier with the highest lower bound not above the transferred money applies,
+    /// transfers below the lowest tier are free.
+    Tiered(Vec<FeeTier>),
+    /// The fee of the policy, but at least `minimum` and at most `maximum`.
+    Capped {
+        policy: Box<FeePolicy>,
+        minimum: Option<Money>,
+        maximum: Option<Money>,
+    },
+}
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct FeeTier {
+    pub from: Money,
+    pub policy: FeePolicy,
+}
+
+/**
+ * The fee policy together with the account which collects the fees.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct TransferFees {
+    pub policy: FeePolicy,
+    pub fee_collection_account_id: AccountId,
+}
+
+impl FeePolicy {
+    // Methods
+
+    /**
+     * @return the fee of a transfer of `money`, never negative.
+     */
+    pub fn fee_for(&self, money: &Money) -> Money {
+        let fee = match self {
+            FeePolicy::Flat(fee) => fee.clone(),
+            FeePolicy::Percentage { basis_points } => {
+                money.multiply_ratio(*basis_points, 10_000, Rounding::HalfUp)
+            }
+            FeePolicy::Tiered(tiers) => tiers
+                .iter()
+                .filter(|tier| money.is_greater_than_or_equal_to(&tier.from))
+                .max_by(|a, b| a.from.amount.cmp(&b.from.amount))
+                .map(|tier| tier.policy.fee_for(money))
+                .unwrap_or(Money::of(0)),
+            FeePolicy::Capped {
+                policy,
+                minimum,
+                maximum,
+            } => {
+                let mut fee = policy.fee_for(money);
+                if let Some(minimum) = minimum.as_ref().filter(|m| m.is_greater_than(&fee)) {
+                    fee = minimum.clone();
+                }
+                if let Some(maximum) = maximum.as_ref().filter(|m| fee.is_greater_than(m)) {
+                    fee = maximum.clone();
+                }
+                fee
+            }
+        };
+        if fee.is_negative() { Money::of(0) } else { fee }
+    }
+}
+
+impl TransferFees {
+    // Functions
+
+    pub fn new(policy: FeePolicy, fee_collection_account_id: AccountId) -> Self {
+        Self {
+            policy,
+            fee_collection_account_id,
+        }
+    }
+
+    // Methods
+
+    /**
+     * Transfers from or to the fee collection account itself are free.
+     * @return the fee of a transfer of `money` between the accounts.
+     */
+    pub fn fee_for(
+        &self,
+        source_account_id: &AccountId,
+        target_account_id: &AccountId,
+        money: &Money,
+    ) -> Money {
+        if *source_account_id == self.fee_collection_account_id
+            || *target_account_id == self.fee_collection_account_id
+        {
+            return Money::of(0);
+        }
+        self.policy.fee_for(money)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_and_percentage_fees() {
+        assert_eq!(
+            Money::of(5),
+            FeePolicy::Flat(Money::of(5)).fee_for(&Money::of(1_000))
+        );
+        // 1.25 % of 1_000 is 12.5
+        assert_eq!(
+            Money::of(13),
+            FeePolicy::Percentage { basis_points: 125 }.fee_for(&Money::of(1_000))
+        );
+    }
+
+    #[test]
+    fn test_tiered_fees() {
+        let policy = FeePolicy::Tiered(vec![
+            FeeTier {
+                from: Money::of(100),
+                policy: FeePolicy::Flat(Money::of(1)),
+            },
+            FeeTier {
+                from: Money::of(10_000),
+                policy: FeePolicy::Percentage { basis_points: 10 },
+            },
+        ]);
+        assert_eq!(Money::of(0), policy.fee_for(&Money::of(99)));
+        assert_eq!(Money::of(1), policy.fee_for(&Money::of(9_999)));
+        assert_eq!(Money::of(20), policy.fee_for(&Money::of(20_000)));
+    }
+
+    #[test]
+    fn test_capped_fees() {
+        let policy = FeePolicy::Capped {
+            policy: Box::new(FeePolicy::Percentage { basis_points: 100 }),
+            minimum: Some(Money::of(2)),
+            maximum: Some(Money::of(50)),
+        };
+        assert_eq!(Money::of(2), policy.fee_for(&Money::of(10)));
+        assert_eq!(Money::of(30), policy.fee_for(&Money::of(3_000)));
+        assert_eq!(Money::of(50), policy.fee_for(&Money::of(1_000_000)));
+    }
+
+    #[test]
+    fn test_transfers_of_fee_collection_account_are_free() {
+        let fees = TransferFees::new(FeePolicy::Flat(Money::of(5)), AccountId(1));
+        assert_eq!(
+            Money::of(0),
+            fees.fee_for(&AccountId(41), &AccountId(1), &Money::of(100))
+        );
+        assert_eq!(
+            Money::of(5),
+            fees.fee_for(&AccountId(41), &AccountId(42), &Money::of(100))
+        );
+    }
+}
@@ -0,0 +1,856 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{
+        FxQuote, FxQuoteId, FxTransferUseCase, Principal, SendMoneyCommand, SendMoneyError,
+    },
+    no_op_metrics::NoOpMetrics,
+    outbound_ports::{
+        AccountLock, DomainEventPublisher, ExchangeRatePort, LoadAccountPort,
+        LoadTransferLimitsPort, MetricsPort, UpdateAccountStatePort,
+    },
+    send_money_use_case::MoneyTransferProperties,
+    transfer_limits_use_case::load_transfer_limits_and_usage,
+};
+
+use async_trait::async_trait;
+use chrono::{Local, NaiveDateTime};
+use domain::{
+    ar::{account::AccountId, domain_event::DomainEvent},
+    vo::{exchange_rate::ExchangeRate, rounding::Rounding},
+};
+use mockall_double::double;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tracing::info;
+use uuid::Uuid;
+
+#[double]
+use domain::ar::account::Account;
+
+/**
+ * A quote may only be used by whoever requested it, to debit the account it was requested for.
+ */
+#[derive(Debug)]
+struct IssuedQuote {
+    quote: FxQuote,
+    principal: Principal,
+    // the fee is exchanged into the currency of the fee collection account if that differs
+    fee_exchange_rate: Option<ExchangeRate>,
+}
+
+// #[singleton]
+#[derive(Debug)]
+pub struct FxTransferUseCaseImpl {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    account_lock: Box<dyn AccountLock>,
+    update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+    load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+    domain_event_publisher: Arc<dyn DomainEventPublisher>,
+    exchange_rate_port: Arc<dyn ExchangeRatePort>,
+    money_transfer_properties: MoneyTransferProperties,
+    // how the fraction of the smallest unit of the target currency is rounded away
+    rounding: Rounding,
+    // quotes are short lived, a quote lost by a restart only has to be requested again
+    quotes: Mutex<HashMap<FxQuoteId, IssuedQuote>>,
+    metrics_port: Arc<dyn MetricsPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl FxTransferUseCaseImpl {
+    // #[inject]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        load_account_port: Arc<dyn LoadAccountPort>,
+        account_lock: Box<dyn AccountLock>,
+        update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+        load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+        domain_event_publisher: Arc<dyn DomainEventPublisher>,
+        exchange_rate_port: Arc<dyn ExchangeRatePort>,
+        money_transfer_properties: MoneyTransferProperties,
+        rounding: Rounding,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            load_transfer_limits_port,
+            domain_event_publisher,
+            exchange_rate_port,
+            money_transfer_properties,
+            rounding,
+            quotes: Mutex::new(HashMap::new()),
+            metrics_port: Arc::new(NoOpMetrics {}),
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_metrics(mut self, metrics_port: Arc<dyn MetricsPort>) -> Self {
+        self.metrics_port = metrics_port;
+        self
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+
+    /**
+     * Takes the quote out, unless it has been issued to someone else or for another account, so
+     * that a quote id seen by anyone else is of no use.
+     */
+    fn take_quote(
+        &self,
+        source_account_id: &AccountId,
+        quote_id: &FxQuoteId,
+        principal: &Principal,
+    ) -> Result<IssuedQuote, SendMoneyError> {
+        let mut quotes = self.quotes.lock().unwrap();
+        match quotes.get(quote_id) {
+            Some(issued_quote)
+                if issued_quote.principal == *principal
+                    && issued_quote.quote.source_account_id == *source_account_id =>
+            {
+                Ok(quotes.remove(quote_id).unwrap())
+            }
+            _ => Err(SendMoneyError::QuoteNotFound),
+        }
+    }
+
+    /**
+     * Withdraws the money exchanged to the target account and the fee to the fee collection
+     * account, see [FxTransferUseCaseImpl::deposit_fee].
+     * @return false if the source account may not withdraw the money plus the fee.
+     */
+    fn withdraw(&self, source_account: &mut Account, issued_quote: &IssuedQuote) -> bool {
+        let quote = &issued_quote.quote;
+        let withdraw_money = |source_account: &mut Account| {
+            source_account.withdraw_exchanged(
+                quote.source_money.clone(),
+                quote.target_account_id.clone(),
+                quote.exchange_rate.clone(),
+            )
+        };
+        if !quote.fee.is_positive() {
+            return withdraw_money(source_account);
+        }
+        let fee_collection_account_id = self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .unwrap()
+            .clone();
+        source_account.may_withdraw(&quote.source_money.plus(&quote.fee))
+            && withdraw_money(source_account)
+            && match &issued_quote.fee_exchange_rate {
+                Some(fee_exchange_rate) => source_account.withdraw_exchanged(
+                    quote.fee.clone(),
+                    fee_collection_account_id,
+                    fee_exchange_rate.clone(),
+                ),
+                None => source_account.withdraw(quote.fee.clone(), fee_collection_account_id),
+            }
+    }
+
+    /**
+     * Books the quoted transfer, the quote has been taken out whether it is booked or not.
+     */
+    async fn transfer(
+        &self,
+        issued_quote: &IssuedQuote,
+        principal: &Principal,
+        now: NaiveDateTime,
+    ) -> Result<(), SendMoneyError> {
+        let quote = &issued_quote.quote;
+        // again, as the mandate may have been revoked since the quote was issued
+        self.authorization_policy
+            .authorize(principal, Action::DebitAccount, &quote.source_account_id)
+            .await?;
+        if now >= quote.expires_at {
+            return Err(SendMoneyError::QuoteExpired);
+        }
+
+        let (transfer_limits, usage) = load_transfer_limits_and_usage(
+            self.load_transfer_limits_port.as_ref(),
+            &self.money_transfer_properties,
+            &quote.source_account_id,
+            now,
+        )
+        .await;
+        transfer_limits
+            .check(&usage, &quote.source_money)
+            .map_err(SendMoneyError::LimitExceeded)?;
+
+        let baseline_date = self.money_transfer_properties.baseline_date(now);
+        let mut source_account = self
+            .load_account_port
+            .load_account(quote.source_account_id.clone(), baseline_date)
+            .await;
+        let mut target_account = self
+            .load_account_port
+            .load_account(quote.target_account_id.clone(), baseline_date)
+            .await;
+        let fee_collection_account = match self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .filter(|_| quote.fee.is_positive())
+        {
+            Some(fee_collection_account_id) => Some(
+                self.load_account_port
+                    .load_account(fee_collection_account_id.clone(), baseline_date)
+                    .await,
+            ),
+            None => None,
+        };
+
+        self.account_lock
+            .lock_account(quote.source_account_id.clone());
+        if !self.withdraw(&mut source_account, issued_quote) {
+            self.account_lock
+                .release_account(quote.source_account_id.clone());
+            return Err(SendMoneyError::InsufficientFunds);
+        }
+
+        self.account_lock
+            .lock_account(quote.target_account_id.clone());
+        if !target_account.deposit_exchanged(
+            quote.target_money.clone(),
+            quote.source_account_id.clone(),
+            quote.exchange_rate.clone(),
+        ) {
+            self.account_lock
+                .release_account(quote.source_account_id.clone());
+            self.account_lock
+                .release_account(quote.target_account_id.clone());
+            return Err(SendMoneyError::DepositRejected);
+        }
+
+        let mut accounts = vec![source_account, target_account];
+        let mut fee_collection_account_id = None;
+        if let Some(mut fee_collection_account) = fee_collection_account {
+            let account_id = fee_collection_account
+                .get_id()
+                .unwrap_or_else(|| panic!("expected fee collection account ID not to be empty"));
+            self.account_lock.lock_account(account_id.clone());
+            self.deposit_fee(&mut fee_collection_account, issued_quote);
+            accounts.push(fee_collection_account);
+            fee_collection_account_id = Some(account_id);
+        }
+
+        let mut domain_events = Vec::new();
+        for account in accounts.iter_mut() {
+            domain_events.append(&mut account.take_domain_events());
+        }
+        domain_events.push(DomainEvent::TransferCompleted {
+            source_account_id: quote.source_account_id.clone(),
+            target_account_id: quote.target_account_id.clone(),
+            money: quote.source_money.clone(),
+            fee: quote.fee.clone(),
+            timestamp: now,
+        });
+        self.update_account_state_port
+            .update_accounts_and_record_events(accounts, domain_events)
+            .await;
+
+        self.account_lock
+            .release_account(quote.source_account_id.clone());
+        self.account_lock
+            .release_account(quote.target_account_id.clone());
+        if let Some(fee_collection_account_id) = fee_collection_account_id {
+            self.account_lock.release_account(fee_collection_account_id);
+        }
+        Ok(())
+    }
+
+    fn record_rejection(&self, error: &SendMoneyError) {
+        self.metrics_port.record_transfer_rejected(error);
+        info!(reason = %error, "transfer rejected");
+    }
+
+    fn deposit_fee(&self, fee_collection_account: &mut Account, issued_quote: &IssuedQuote) {
+        let quote = &issued_quote.quote;
+        match &issued_quote.fee_exchange_rate {
+            Some(fee_exchange_rate) => fee_collection_account.deposit_exchanged(
+                fee_exchange_rate.convert(&quote.fee, self.rounding),
+                quote.source_account_id.clone(),
+                fee_exchange_rate.clone(),
+            ),
+            None => {
+                fee_collection_account.deposit(quote.fee.clone(), quote.source_account_id.clone())
+            }
+        };
+    }
+}
+
+unsafe impl Send for FxTransferUseCaseImpl {}
+unsafe impl Sync for FxTransferUseCaseImpl {}
+
+#[async_trait]
+impl FxTransferUseCase for FxTransferUseCaseImpl {
+    async fn quote_fx_transfer(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<FxQuote, SendMoneyError> {
+        let now = Local::now().naive_local();
+        // before anything else, so that neither the currency nor the fee of a foreign account is
+        // disclosed
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::DebitAccount,
+                &command.source_account_id,
+            )
+            .await?;
+        if self
+            .money_transfer_properties
+            .exceeds_threshold(&command.money)
+        {
+            return Err(SendMoneyError::ThresholdExceeded);
+        }
+
+        let baseline_date = self.money_transfer_properties.baseline_date(now);
+        let source_account = self
+            .load_account_port
+            .load_account(command.source_account_id.clone(), baseline_date)
+            .await;
+        let target_account = self
+            .load_account_port
+            .load_account(command.target_account_id.clone(), baseline_date)
+            .await;
+
+        let exchange_rate_quote = self
+            .exchange_rate_port
+            .quote_exchange_rate(
+                source_account.get_currency(),
+                target_account.get_currency(),
+                now,
+            )
+            .await
+            .ok_or(SendMoneyError::ExchangeRateUnavailable)?;
+        let mut expires_at = exchange_rate_quote.expires_at;
+
+        // the fee is charged in the currency of the source account
+        let fee = self.money_transfer_properties.fee_for(&command);
+        let mut fee_exchange_rate = None;
+        if let Some(fee_collection_account_id) = self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .filter(|_| fee.is_positive())
+        {
+            let fee_currency = self
+                .load_account_port
+                .load_account(fee_collection_account_id.clone(), baseline_date)
+                .await
+                .get_currency();
+            if fee_currency != source_account.get_currency() {
+                let fee_exchange_rate_quote = self
+                    .exchange_rate_port
+                    .quote_exchange_rate(source_account.get_currency(), fee_currency, now)
+                    .await
+                    .ok_or(SendMoneyError::ExchangeRateUnavailable)?;
+                expires_at = expires_at.min(fee_exchange_rate_quote.expires_at);
+                fee_exchange_rate = Some(fee_exchange_rate_quote.exchange_rate);
+            }
+        }
+
+        let quote = FxQuote {
+            id: FxQuoteId(Uuid::new_v4().to_string()),
+            source_account_id: command.source_account_id,
+            target_account_id: command.target_account_id,
+            target_money: exchange_rate_quote
+                .exchange_rate
+                .convert(&command.money, self.rounding),
+            source_money: command.money,
+            fee,
+            exchange_rate: exchange_rate_quote.exchange_rate,
+            expires_at,
+        };
+
+        let mut quotes = self.quotes.lock().unwrap();
+        quotes.retain(|_, issued_quote| issued_quote.quote.expires_at > now);
+        quotes.insert(
+            quote.id.clone(),
+            IssuedQuote {
+                quote: quote.clone(),
+                principal: command.principal,
+                fee_exchange_rate,
+            },
+        );
+        Ok(quote)
+    }
+
+    async fn send_money_fx(
+        &self,
+        source_account_id: AccountId,
+        quote_id: FxQuoteId,
+        principal: Principal,
+    ) -> Result<FxQuote, SendMoneyError> {
+        let now = Local::now().naive_local();
+        self.metrics_port.record_transfer_attempted();
+        // a quote which has not been issued tells nothing about the transfer to publish
+        let issued_quote = match self.take_quote(&source_account_id, &quote_id, &principal) {
+            Ok(issued_quote) => issued_quote,
+            Err(error) => {
+                self.record_rejection(&error);
+                return Err(error);
+            }
+        };
+        let quote = &issued_quote.quote;
+        let result = self.transfer(&issued_quote, &principal, now).await;
+        match &result {
+            Ok(()) => {
+                self.metrics_port
+                    .record_transfer_succeeded(&quote.source_money);
+                info!("transfer booked");
+            }
+            Err(error) => {
+                self.record_rejection(error);
+                // nothing has been booked, so there is no unit of work to record the rejection with
+                self.domain_event_publisher
+                    .publish(vec![DomainEvent::TransferRejected {
+                        source_account_id: quote.source_account_id.clone(),
+                        target_account_id: quote.target_account_id.clone(),
+                        money: quote.source_money.clone(),
+                        reason: error.to_string(),
+                        timestamp: now,
+                    }])
+                    .await;
+            }
+        }
+        result.map(|_| issued_quote.quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fee_policy::{FeePolicy, TransferFees};
+    use crate::outbound_ports::{
+        MockAccountLock, MockDomainEventPublisher, MockExchangeRatePort,
+        MockLoadAccountMandatePort, MockLoadAccountPort, MockLoadTransferLimitsPort,
+        MockMetricsPort, MockRecordAuthorizationDenialPort, MockUpdateAccountStatePort,
+    };
+    use chrono::{Duration, NaiveDateTime};
+    use domain::vo::{
+        currency::Currency, exchange_rate::ExchangeRateQuote, money::Money, role::Role,
+        transfer_limits::TransferLimits,
+    };
+    use mockall::predicate::{always, eq};
+
+    #[async_std::test]
+    async fn test_quoted_transfer_is_exchanged_at_the_quoted_rate() {
+        // Given a source account held in Euro
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account("EUR");
+                // And the source account is debited 100 Euro
+                account
+                    .expect_withdraw_exchanged()
+                    .with(eq(Money::of(100)), eq(AccountId(42)), eq(eur_usd()))
+                    .return_const(true);
+                account
+            });
+        // And a target account held in US Dollar
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(42)), always())
+            .returning(|_, _| {
+                let mut account = account("USD");
+                // And the target account is credited 108.45 US Dollar rounded half to even
+                account
+                    .expect_deposit_exchanged()
+                    .with(eq(Money::of(108)), eq(AccountId(41)), eq(eur_usd()))
+                    .return_const(true);
+                account
+            });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(2).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(2)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, domain_events| {
+                accounts.len() == 2
+                    && matches!(
+                        domain_events.as_slice(),
+                        [DomainEvent::TransferCompleted { money, fee, .. }]
+                            if *money == Money::of(100) && *fee == Money::of(0)
+                    )
+            })
+            .return_const(());
+
+        let use_case = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            exchange_rate_port(Duration::seconds(30)),
+            domain_event_publisher(),
+            None,
+        );
+
+        // When the transfer is quoted
+        let quote = use_case
+            .quote_fx_transfer(SendMoneyCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(100),
+                alice(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(Money::of(108), quote.target_money);
+
+        // Then the quote can be used neither by anyone else nor for another account
+        let mallory = Principal::new("mallory".to_string(), Role::Customer);
+        assert_eq!(
+            Err(SendMoneyError::QuoteNotFound),
+            use_case
+                .send_money_fx(AccountId(41), quote.id.clone(), mallory)
+                .await
+        );
+        assert_eq!(
+            Err(SendMoneyError::QuoteNotFound),
+            use_case
+                .send_money_fx(AccountId(43), quote.id.clone(), alice())
+                .await
+        );
+
+        // When the quote is used
+        let result = use_case
+            .send_money_fx(AccountId(41), quote.id.clone(), alice())
+            .await;
+
+        // Then the transfer is booked at the quoted rate, but only once
+        assert_eq!(Ok(quote.clone()), result);
+        assert_eq!(
+            Err(SendMoneyError::QuoteNotFound),
+            use_case
+                .send_money_fx(AccountId(41), quote.id, alice())
+                .await
+        );
+    }
+
+    #[async_std::test]
+    async fn test_fee_is_exchanged_into_the_currency_of_the_fee_collection_account() {
+        // Given a source account held in Euro
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account("EUR");
+                // And the source account is debited 100 Euro plus a fee of 2 Euro
+                account
+                    .expect_may_withdraw()
+                    .with(eq(Money::of(102)))
+                    .return_const(true);
+                account
+                    .expect_withdraw_exchanged()
+                    .with(eq(Money::of(100)), eq(AccountId(42)), eq(eur_usd()))
+                    .return_const(true);
+                account
+                    .expect_withdraw_exchanged()
+                    .with(eq(Money::of(2)), eq(AccountId(100)), eq(eur_usd()))
+                    .return_const(true);
+                account
+            });
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(42)), always())
+            .returning(|_, _| {
+                let mut account = account("USD");
+                account.expect_deposit_exchanged().return_const(true);
+                account
+            });
+        // And a fee collection account held in US Dollar
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(100)), always())
+            .returning(|_, _| {
+                let mut account = account("USD");
+                account.expect_get_id().return_const(Some(AccountId(100)));
+                // And the fee collection account is credited 2.169 US Dollar rounded half to even
+                account
+                    .expect_deposit_exchanged()
+                    .with(eq(Money::of(2)), eq(AccountId(41)), eq(eur_usd()))
+                    .return_const(true);
+                account
+            });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(3).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(3)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, domain_events| {
+                accounts.len() == 3
+                    && matches!(
+                        domain_events.as_slice(),
+                        [DomainEvent::TransferCompleted { fee, .. }] if *fee == Money::of(2)
+                    )
+            })
+            .return_const(());
+
+        let use_case = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            exchange_rate_port(Duration::seconds(30)),
+            domain_event_publisher(),
+            Some(TransferFees::new(
+                FeePolicy::Flat(Money::of(2)),
+                AccountId(100),
+            )),
+        );
+
+        // When the transfer is quoted
+        let quote = use_case
+            .quote_fx_transfer(SendMoneyCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(100),
+                alice(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(Money::of(2), quote.fee);
+
+        // And the quote is used
+        let result = use_case
+            .send_money_fx(AccountId(41), quote.id.clone(), alice())
+            .await;
+
+        // Then
+        assert_eq!(Ok(quote), result);
+    }
+
+    #[async_std::test]
+    async fn test_expired_quote_is_rejected() {
+        // Given accounts held in Euro and US Dollar
+        let mut load_account_port = MockLoadAccountPort::new();
+        for (account_id, currency) in [(41, "EUR"), (42, "USD")] {
+            load_account_port
+                .expect_load_account()
+                .times(1)
+                .with(eq(AccountId(account_id)), always())
+                .returning(move |_, _| account(currency));
+        }
+
+        // Then nothing is booked
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .never();
+
+        // But the rejection is published
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher
+            .expect_publish()
+            .times(1)
+            .withf(|domain_events| {
+                matches!(
+                    domain_events.as_slice(),
+                    [DomainEvent::TransferRejected { source_account_id, money, reason, .. }]
+                        if *source_account_id == AccountId(41)
+                            && *money == Money::of(100)
+                            && *reason == SendMoneyError::QuoteExpired.to_string()
+                )
+            })
+            .return_const(());
+        // And counted
+        let mut metrics_port = MockMetricsPort::new();
+        metrics_port
+            .expect_record_transfer_attempted()
+            .times(1)
+            .return_const(());
+        metrics_port
+            .expect_record_transfer_rejected()
+            .with(eq(SendMoneyError::QuoteExpired))
+            .times(1)
+            .return_const(());
+        metrics_port.expect_record_transfer_succeeded().never();
+
+        // When a quote which has already expired is used
+        let use_case = use_case(
+            load_account_port,
+            Box::new(MockAccountLock::new()),
+            update_account_state_port,
+            exchange_rate_port(Duration::seconds(-1)),
+            domain_event_publisher,
+            None,
+        )
+        .with_metrics(Arc::new(metrics_port));
+        let quote = use_case
+            .quote_fx_transfer(SendMoneyCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(100),
+                alice(),
+            ))
+            .await
+            .unwrap();
+        let result = use_case
+            .send_money_fx(AccountId(41), quote.id, alice())
+            .await;
+
+        // Then
+        assert_eq!(Err(SendMoneyError::QuoteExpired), result);
+    }
+
+    #[async_std::test]
+    async fn test_currency_pair_without_rate_cannot_be_quoted() {
+        // Given accounts held in Euro and Swiss Franc
+        let mut load_account_port = MockLoadAccountPort::new();
+        for (account_id, currency) in [(41, "EUR"), (42, "CHF")] {
+            load_account_port
+                .expect_load_account()
+                .with(eq(AccountId(account_id)), always())
+                .returning(move |_, _| account(currency));
+        }
+
+        // And no rate for Euro to Swiss Franc
+        let mut exchange_rate_port = MockExchangeRatePort::new();
+        exchange_rate_port
+            .expect_quote_exchange_rate()
+            .with(eq(Currency::of("EUR")), eq(Currency::of("CHF")), always())
+            .return_const(None);
+
+        // When
+        let result = use_case(
+            load_account_port,
+            Box::new(MockAccountLock::new()),
+            MockUpdateAccountStatePort::new(),
+            exchange_rate_port,
+            MockDomainEventPublisher::new(),
+            None,
+        )
+        .quote_fx_transfer(SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(100),
+            alice(),
+        ))
+        .await;
+
+        // Then
+        assert_eq!(Err(SendMoneyError::ExchangeRateUnavailable), result);
+    }
+
+    #[async_std::test]
+    async fn test_customer_without_mandate_cannot_quote() {
+        // Given a source account without any mandate
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .with(eq(AccountId(41)))
+            .return_const(vec![]);
+
+        // Then the attempt is audited
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // When a customer requests a quote to debit it
+        let result = use_case(
+            MockLoadAccountPort::new(),
+            Box::new(MockAccountLock::new()),
+            MockUpdateAccountStatePort::new(),
+            MockExchangeRatePort::new(),
+            MockDomainEventPublisher::new(),
+            None,
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )))
+        .quote_fx_transfer(SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(100),
+            Principal::new("mallory".to_string(), Role::Customer),
+        ))
+        .await;
+
+        // Then neither an account is loaded nor a rate quoted
+        assert_eq!(Err(SendMoneyError::Unauthorized), result);
+    }
+
+    fn use_case(
+        load_account_port: MockLoadAccountPort,
+        account_lock: Box<MockAccountLock>,
+        update_account_state_port: MockUpdateAccountStatePort,
+        exchange_rate_port: MockExchangeRatePort,
+        domain_event_publisher: MockDomainEventPublisher,
+        transfer_fees: Option<TransferFees>,
+    ) -> FxTransferUseCaseImpl {
+        let mut load_transfer_limits_port = MockLoadTransferLimitsPort::new();
+        load_transfer_limits_port
+            .expect_load_transfer_limits()
+            .return_const(TransferLimits::unlimited());
+        FxTransferUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(load_transfer_limits_port),
+            Arc::new(domain_event_publisher),
+            Arc::new(exchange_rate_port),
+            MoneyTransferProperties::new(None, None, transfer_fees),
+            Rounding::HalfEven,
+        )
+    }
+
+    fn domain_event_publisher() -> MockDomainEventPublisher {
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher.expect_publish().return_const(());
+        domain_event_publisher
+    }
+
+    /**
+     * Quotes Euro to US Dollar at 1.0845 for `time_to_live` from now.
+     */
+    fn exchange_rate_port(time_to_live: Duration) -> MockExchangeRatePort {
+        let mut exchange_rate_port = MockExchangeRatePort::new();
+        exchange_rate_port
+            .expect_quote_exchange_rate()
+            .with(eq(Currency::of("EUR")), eq(Currency::of("USD")), always())
+            .returning(move |_, _, now: NaiveDateTime| {
+                Some(ExchangeRateQuote::new(eur_usd(), now + time_to_live))
+            });
+        exchange_rate_port
+    }
+
+    fn eur_usd() -> ExchangeRate {
+        ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500)
+    }
+
+    fn alice() -> Principal {
+        Principal::new("alice".to_string(), Role::Customer)
+    }
+
+    fn account(currency: &str) -> Account {
+        let mut account = Account::new();
+        account
+            .expect_get_currency()
+            .return_const(Currency::of(currency));
+        account.expect_take_domain_events().returning(Vec::new);
+        account
+    }
+}
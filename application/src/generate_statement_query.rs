@@ -0,0 +1,169 @@
+use crate::{
+    account_activity_use_case::with_running_balance,
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{GenerateStatementError, GenerateStatementQuery, Principal, Statement},
+    outbound_ports::LoadAccountActivityPort,
+};
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveTime};
+use domain::ar::account::AccountId;
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct GenerateStatementQueryImpl {
+    load_account_activity_port: Arc<dyn LoadAccountActivityPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl GenerateStatementQueryImpl {
+    // #[inject]
+    pub fn new(load_account_activity_port: Arc<dyn LoadAccountActivityPort>) -> Self {
+        Self {
+            load_account_activity_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl GenerateStatementQuery for GenerateStatementQueryImpl {
+    async fn generate_statement(
+        &self,
+        account_id: AccountId,
+        from: NaiveDate,
+        to: NaiveDate,
+        principal: Principal,
+    ) -> Result<Statement, GenerateStatementError> {
+        self.authorization_policy
+            .authorize(&principal, Action::ViewAccount, &account_id)
+            .await?;
+        if from > to {
+            return Err(GenerateStatementError::InvalidPeriod);
+        }
+        let Some(day_after) = to.succ_opt() else {
+            return Err(GenerateStatementError::InvalidPeriod);
+        };
+        let since = from.and_time(NaiveTime::MIN);
+        let until = day_after.and_time(NaiveTime::MIN);
+
+        let opening_balance = self
+            .load_account_activity_port
+            .load_balance_before(account_id.clone(), since)
+            .await;
+        let activities = self
+            .load_account_activity_port
+            .load_activities_between(account_id.clone(), since, until)
+            .await;
+        let activities = with_running_balance(&account_id, opening_balance.clone(), activities);
+        let closing_balance = activities
+            .last()
+            .map_or(opening_balance.clone(), |account_activity| {
+                account_activity.balance.clone()
+            });
+
+        Ok(Statement {
+            account_id,
+            from,
+            to,
+            opening_balance,
+            activities,
+            closing_balance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::MockLoadAccountActivityPort;
+    use domain::{
+        ar::activity::{Activity, ActivityId},
+        vo::money::Money,
+    };
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_generate_statement() {
+        // Given the account had a balance of 1000 at the start of August
+        let since = date(1).and_time(NaiveTime::MIN);
+        let until = NaiveDate::from_ymd_opt(2019, 9, 1)
+            .unwrap()
+            .and_time(NaiveTime::MIN);
+        let mut load_account_activity_port = MockLoadAccountActivityPort::new();
+        load_account_activity_port
+            .expect_load_balance_before()
+            .times(1)
+            .with(eq(AccountId(42)), eq(since))
+            .returning(|_, _| Money::of(1000));
+        // And withdrew 300 and received 50 during the month
+        load_account_activity_port
+            .expect_load_activities_between()
+            .times(1)
+            .with(eq(AccountId(42)), eq(since), eq(until))
+            .returning(|_, _, _| {
+                vec![
+                    activity(1, AccountId(42), AccountId(43), 300),
+                    activity(2, AccountId(44), AccountId(42), 50),
+                ]
+            });
+
+        let generate_statement_query =
+            GenerateStatementQueryImpl::new(Arc::new(load_account_activity_port));
+
+        // When
+        let statement = generate_statement_query
+            .generate_statement(AccountId(42), date(1), date(31), Principal::system())
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(Money::of(1000), statement.opening_balance);
+        assert_eq!(
+            vec![Money::of(700), Money::of(750)],
+            statement
+                .activities
+                .iter()
+                .map(|account_activity| account_activity.balance.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Money::of(750), statement.closing_balance);
+
+        // When the period ends before it starts
+        let statement = generate_statement_query
+            .generate_statement(AccountId(42), date(31), date(1), Principal::system())
+            .await;
+
+        // Then
+        assert_eq!(Err(GenerateStatementError::InvalidPeriod), statement);
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2019, 8, day).unwrap()
+    }
+
+    fn activity(
+        id: i64,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        amount: i128,
+    ) -> Activity {
+        Activity::with_id(
+            Some(ActivityId(id)),
+            AccountId(42),
+            source_account_id,
+            target_account_id,
+            date(9).and_hms_opt(9, 0, 0).unwrap(),
+            Money::of(amount),
+            None,
+        )
+    }
+}
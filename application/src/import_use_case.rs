@@ -0,0 +1,344 @@
+use crate::{
+    inbound_ports::{
+        ImportCommand, ImportLine, ImportRecord, ImportReport, ImportUseCase, ImportedAccount,
+        RejectedLine,
+    },
+    outbound_ports::{LoadImportPort, UpdateImportStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{account::AccountId, activity::Activity};
+use std::{collections::HashSet, sync::Arc};
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ImportUseCaseImpl {
+    load_import_port: Arc<dyn LoadImportPort>,
+    update_import_state_port: Arc<dyn UpdateImportStatePort>,
+    batch_size: usize,
+}
+
+impl ImportUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_import_port: Arc<dyn LoadImportPort>,
+        update_import_state_port: Arc<dyn UpdateImportStatePort>,
+        batch_size: usize,
+    ) -> Self {
+        Self {
+            load_import_port,
+            update_import_state_port,
+            batch_size,
+        }
+    }
+
+    async fn import_batch(
+        &self,
+        command: &ImportCommand,
+        batch: Vec<ImportLine>,
+        accounts_of_run: &mut AccountsOfRun,
+        report: &mut ImportReport,
+    ) {
+        let last_line_number = batch.last().unwrap().line_number;
+        accounts_of_run
+            .look_up(&batch, &self.load_import_port)
+            .await;
+
+        let mut accounts = vec![];
+        let mut activities = vec![];
+        for line in batch {
+            let validated = match line.record {
+                Ok(ImportRecord::Account(account)) => accounts_of_run
+                    .validate_account(&account)
+                    .map(|_| accounts.push(account)),
+                Ok(ImportRecord::Activity(activity)) => accounts_of_run
+                    .validate_activity(&activity)
+                    .map(|_| activities.push(activity)),
+                Err(reason) => Err(reason),
+            };
+            if let Err(reason) = validated {
+                report.rejected_lines.push(RejectedLine {
+                    line_number: line.line_number,
+                    reason,
+                });
+            }
+        }
+        report.imported_accounts += accounts.len();
+        report.imported_activities += activities.len();
+
+        if !command.dry_run {
+            self.update_import_state_port
+                .save_import_batch(
+                    command.source.clone(),
+                    last_line_number,
+                    accounts,
+                    activities,
+                    Local::now().naive_local(),
+                )
+                .await;
+        }
+    }
+}
+
+#[async_trait]
+impl ImportUseCase for ImportUseCaseImpl {
+    async fn import(
+        &self,
+        command: ImportCommand,
+        lines: Box<dyn Iterator<Item = ImportLine> + Send>,
+    ) -> ImportReport {
+        let progress = self
+            .load_import_port
+            .load_import_progress(command.source.clone())
+            .await;
+
+        let mut report = ImportReport::default();
+        let mut accounts_of_run = AccountsOfRun::default();
+        let mut batch = vec![];
+        for line in lines {
+            if line.line_number <= progress {
+                report.skipped_lines += 1;
+                continue;
+            }
+            batch.push(line);
+            if batch.len() == self.batch_size {
+                self.import_batch(&command, batch, &mut accounts_of_run, &mut report)
+                    .await;
+                batch = vec![];
+            }
+        }
+        if !batch.is_empty() {
+            self.import_batch(&command, batch, &mut accounts_of_run, &mut report)
+                .await;
+        }
+        report
+    }
+}
+
+/**
+ * The accounts known during a run, which either existed before or were imported by the run.
+ * Accounts are looked up once per run, also in a dry run where nothing is stored.
+ */
+#[derive(Default, Debug)]
+struct AccountsOfRun {
+    looked_up: HashSet<AccountId>,
+    known: HashSet<AccountId>,
+}
+
+impl AccountsOfRun {
+    async fn look_up(&mut self, batch: &[ImportLine], load_import_port: &Arc<dyn LoadImportPort>) {
+        let mut account_ids = vec![];
+        for line in batch {
+            let referenced = match &line.record {
+                Ok(ImportRecord::Account(account)) => vec![&account.account_id],
+                Ok(ImportRecord::Activity(activity)) => {
+                    vec![&activity.source_account_id, &activity.target_account_id]
+                }
+                Err(_) => vec![],
+            };
+            for account_id in referenced {
+                if self.looked_up.insert(account_id.clone()) {
+                    account_ids.push(account_id.clone());
+                }
+            }
+        }
+        if !account_ids.is_empty() {
+            self.known.extend(
+                load_import_port
+                    .load_existing_account_ids(account_ids)
+                    .await,
+            );
+        }
+    }
+
+    fn validate_account(&mut self, account: &ImportedAccount) -> Result<(), String> {
+        if account.account_id.0 <= 0 {
+            return Err(format!("invalid account id {}", account.account_id.0));
+        }
+        if account.overdraft_limit.is_negative() {
+            return Err("the overdraft limit is negative".to_string());
+        }
+        if !self.known.insert(account.account_id.clone()) {
+            return Err(format!("account {} exists already", account.account_id.0));
+        }
+        Ok(())
+    }
+
+    fn validate_activity(&self, activity: &Activity) -> Result<(), String> {
+        if !activity.money.is_positive() {
+            return Err("the amount is not positive".to_string());
+        }
+        if activity.source_account_id == activity.target_account_id {
+            return Err("the source and the target account are the same".to_string());
+        }
+        if activity.owner_account_id != activity.source_account_id
+            && activity.owner_account_id != activity.target_account_id
+        {
+            return Err("the owner is neither the source nor the target account".to_string());
+        }
+        for account_id in [&activity.source_account_id, &activity.target_account_id] {
+            if !self.known.contains(account_id) {
+                return Err(format!("unknown account {}", account_id.0));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{MockLoadImportPort, MockUpdateImportStatePort};
+    use chrono::NaiveDate;
+    use domain::vo::{currency::Currency, money::Money};
+    use mockall::predicate::{always, eq};
+
+    #[async_std::test]
+    async fn test_imports_valid_lines_in_batches_and_reports_rejected_ones() {
+        // Given only account 1 exists
+        let mut load_import_port = MockLoadImportPort::new();
+        load_import_port
+            .expect_load_import_progress()
+            .with(eq("legacy.csv".to_string()))
+            .returning(|_| 0);
+        load_import_port
+            .expect_load_existing_account_ids()
+            .times(1)
+            .with(eq(vec![AccountId(5), AccountId(1)]))
+            .returning(|_| vec![AccountId(1)]);
+        // And every batch is stored with its last line
+        let mut update_import_state_port = MockUpdateImportStatePort::new();
+        update_import_state_port
+            .expect_save_import_batch()
+            .times(1)
+            .with(
+                eq("legacy.csv".to_string()),
+                eq(3),
+                eq(vec![account(5)]),
+                eq(vec![activity(5, 1)]),
+                always(),
+            )
+            .return_const(());
+        update_import_state_port
+            .expect_save_import_batch()
+            .times(1)
+            .with(
+                eq("legacy.csv".to_string()),
+                eq(5),
+                eq(vec![]),
+                eq(vec![]),
+                always(),
+            )
+            .return_const(());
+
+        let import_use_case = ImportUseCaseImpl::new(
+            Arc::new(load_import_port),
+            Arc::new(update_import_state_port),
+            2,
+        );
+
+        // When
+        let report = import_use_case
+            .import(
+                ImportCommand::new("legacy.csv".to_string(), false),
+                Box::new(lines().into_iter()),
+            )
+            .await;
+
+        // Then
+        assert_eq!(
+            ImportReport {
+                imported_accounts: 1,
+                imported_activities: 1,
+                skipped_lines: 0,
+                rejected_lines: vec![
+                    RejectedLine {
+                        line_number: 4,
+                        reason: "invalid amount: x".to_string(),
+                    },
+                    RejectedLine {
+                        line_number: 5,
+                        reason: "account 1 exists already".to_string(),
+                    },
+                ],
+            },
+            report
+        );
+    }
+
+    #[async_std::test]
+    async fn test_dry_run_resumes_after_the_stored_lines_without_storing() {
+        // Given the first batch has been stored
+        let mut load_import_port = MockLoadImportPort::new();
+        load_import_port
+            .expect_load_import_progress()
+            .returning(|_| 3);
+        load_import_port
+            .expect_load_existing_account_ids()
+            .times(1)
+            .with(eq(vec![AccountId(1)]))
+            .returning(|account_ids| account_ids);
+
+        let import_use_case = ImportUseCaseImpl::new(
+            Arc::new(load_import_port),
+            Arc::new(MockUpdateImportStatePort::new()),
+            2,
+        );
+
+        // When
+        let report = import_use_case
+            .import(
+                ImportCommand::new("legacy.csv".to_string(), true),
+                Box::new(lines().into_iter()),
+            )
+            .await;
+
+        // Then
+        assert_eq!(2, report.skipped_lines);
+        assert_eq!(0, report.imported_activities);
+        assert_eq!(2, report.rejected_lines.len());
+    }
+
+    fn lines() -> Vec<ImportLine> {
+        vec![
+            ImportLine {
+                line_number: 2,
+                record: Ok(ImportRecord::Account(account(5))),
+            },
+            ImportLine {
+                line_number: 3,
+                record: Ok(ImportRecord::Activity(activity(5, 1))),
+            },
+            ImportLine {
+                line_number: 4,
+                record: Err("invalid amount: x".to_string()),
+            },
+            ImportLine {
+                line_number: 5,
+                record: Ok(ImportRecord::Account(account(1))),
+            },
+        ]
+    }
+
+    fn account(id: i64) -> ImportedAccount {
+        ImportedAccount {
+            account_id: AccountId(id),
+            currency: Currency::default(),
+            overdraft_limit: Money::of(0),
+        }
+    }
+
+    fn activity(source_account_id: i64, target_account_id: i64) -> Activity {
+        Activity::new(
+            AccountId(source_account_id),
+            AccountId(source_account_id),
+            AccountId(target_account_id),
+            NaiveDate::from_ymd_opt(2018, 8, 8)
+                .unwrap()
+                .and_hms_opt(8, 0, 0)
+                .unwrap(),
+            Money::of(500),
+        )
+    }
+}
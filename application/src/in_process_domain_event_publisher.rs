@@ -0,0 +1,132 @@
+use crate::outbound_ports::{DomainEventPublisher, DomainEventSink};
+use async_trait::async_trait;
+use domain::ar::domain_event::DomainEvent;
+use std::sync::{Arc, RwLock};
+
+/**
+ * Reacts to the domain events published within this process.
+ */
+#[async_trait]
+pub trait DomainEventSubscriber: Send + Sync + std::fmt::Debug {
+    async fn on_domain_event(&self, domain_event: &DomainEvent);
+}
+
+/**
+ * Hands every published event to all subscribers in the order in which they subscribed,
+ * so other modules can react to transfers without touching the use cases.
+ * The events are delivered before `publish` returns and are lost if the process stops.
+ * As a sink it broadcasts the events relayed from the outbox, which are committed already.
+ */
+// #[singleton]
+#[derive(Default, Debug)]
+pub struct InProcessDomainEventPublisher {
+    subscribers: RwLock<Vec<Arc<dyn DomainEventSubscriber>>>,
+}
+
+impl InProcessDomainEventPublisher {
+    // Functions
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Methods
+
+    pub fn subscribe(&self, subscriber: Arc<dyn DomainEventSubscriber>) {
+        self.subscribers.write().unwrap().push(subscriber);
+    }
+}
+
+#[async_trait]
+impl DomainEventPublisher for InProcessDomainEventPublisher {
+    async fn publish(&self, domain_events: Vec<DomainEvent>) {
+        let subscribers = self.subscribers.read().unwrap().clone();
+        for domain_event in &domain_events {
+            for subscriber in &subscribers {
+                subscriber.on_domain_event(domain_event).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for InProcessDomainEventPublisher {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        let subscribers = self.subscribers.read().unwrap().clone();
+        for subscriber in &subscribers {
+            subscriber.on_domain_event(domain_event).await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::{ar::account::AccountId, vo::money::Money};
+    use std::sync::Mutex;
+
+    #[derive(Default, Debug)]
+    struct RecordingSubscriber {
+        domain_events: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl DomainEventSubscriber for RecordingSubscriber {
+        async fn on_domain_event(&self, domain_event: &DomainEvent) {
+            self.domain_events
+                .lock()
+                .unwrap()
+                .push(domain_event.clone());
+        }
+    }
+
+    #[async_std::test]
+    async fn test_every_subscriber_receives_all_events_in_order() {
+        // Given two subscribers
+        let publisher = InProcessDomainEventPublisher::new();
+        let first_subscriber = Arc::new(RecordingSubscriber::default());
+        let second_subscriber = Arc::new(RecordingSubscriber::default());
+        publisher.subscribe(first_subscriber.clone());
+        publisher.subscribe(second_subscriber.clone());
+
+        // When a transfer is published
+        let timestamp = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let domain_events = vec![
+            DomainEvent::MoneyWithdrawn {
+                account_id: AccountId(41),
+                target_account_id: AccountId(42),
+                money: Money::of(500),
+                timestamp,
+            },
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(42),
+                source_account_id: AccountId(41),
+                money: Money::of(500),
+                timestamp,
+            },
+            DomainEvent::TransferCompleted {
+                source_account_id: AccountId(41),
+                target_account_id: AccountId(42),
+                money: Money::of(500),
+                fee: Money::of(0),
+                timestamp,
+            },
+        ];
+        publisher.publish(domain_events.clone()).await;
+
+        // Then both subscribers received the events in the order they happened
+        assert_eq!(
+            domain_events,
+            *first_subscriber.domain_events.lock().unwrap()
+        );
+        assert_eq!(
+            domain_events,
+            *second_subscriber.domain_events.lock().unwrap()
+        );
+    }
+}
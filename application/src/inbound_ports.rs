@@ -1,27 +1,1025 @@
+use crate::authorization_policy::Denied;
 use async_trait::async_trait;
-use domain::{ar::account::AccountId, vo::money::Money};
+use chrono::{NaiveDate, NaiveDateTime};
+use domain::{
+    ar::{
+        account::AccountId,
+        account_mandate::MandateKind,
+        activity::{Activity, ActivityId},
+        overdraft_limit_change::OverdraftLimitChange,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId},
+        standing_order::{StandingOrder, StandingOrderExecution, StandingOrderId},
+        webhook_subscription::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId},
+    },
+    vo::{
+        currency::Currency,
+        exchange_rate::ExchangeRate,
+        money::Money,
+        recurrence::Recurrence,
+        role::Role,
+        transfer_limits::{TransferLimit, TransferLimits},
+    },
+};
+
+/**
+ * Who issues a command, as authenticated by the inbound adapter, e.g. the subject of a JWT or the
+ * owner of an API key, and in which role. The command line issues its commands as
+ * [Principal::system], the scheduled transfers and standing orders are executed as whoever created
+ * them.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Principal {
+    pub subject: String,
+    pub role: Role,
+}
+
+impl Principal {
+    // Functions
+
+    pub fn new(subject: String, role: Role) -> Self {
+        Self { subject, role }
+    }
+
+    /// Acts as an admin, as whoever may run the jobs or the command line may do anything anyway.
+    pub fn system() -> Self {
+        Self::new("system".to_string(), Role::Admin)
+    }
+}
 
 #[async_trait]
 pub trait SendMoneyUseCase: Send + Sync + std::fmt::Debug {
-    async fn send_money(&self, command: SendMoneyCommand) -> bool;
+    async fn send_money(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<TransferReceipt, SendMoneyError>;
 }
 
 // TODO implement validating
-#[derive(PartialEq, Hash, Debug)]
+#[derive(Clone, PartialEq, Hash, Debug)]
 pub struct SendMoneyCommand {
     pub source_account_id: AccountId,
     pub target_account_id: AccountId,
     pub money: Money,
+    pub principal: Principal,
 }
 
 impl SendMoneyCommand {
     // Functions
 
-    pub fn new(source_account_id: AccountId, target_account_id: AccountId, money: Money) -> Self {
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        principal: Principal,
+    ) -> Self {
+        Self {
+            source_account_id,
+            target_account_id,
+            money,
+            principal,
+        }
+    }
+}
+
+/**
+ * The outcome of a booked transfer. The source account has been charged the money plus the fee.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct TransferReceipt {
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub fee: Money,
+}
+
+impl TransferReceipt {
+    // Functions
+
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        fee: Money,
+    ) -> Self {
+        Self {
+            source_account_id,
+            target_account_id,
+            money,
+            fee,
+        }
+    }
+
+    // Methods
+
+    pub fn total(&self) -> Money {
+        self.money.plus(&self.fee)
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub enum SendMoneyError {
+    ThresholdExceeded,
+    LimitExceeded(TransferLimit),
+    InsufficientFunds,
+    DepositRejected,
+    /// Another transfer of an all-or-nothing batch was rejected.
+    BatchAborted,
+    /// The accounts are held in different currencies, which needs an exchange of the money.
+    CurrencyMismatch,
+    /// No exchange rate is quoted for the currencies of the accounts.
+    ExchangeRateUnavailable,
+    QuoteNotFound,
+    QuoteExpired,
+    /// The principal may not debit the source account, which has been audited.
+    Unauthorized,
+}
+
+impl std::fmt::Display for SendMoneyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendMoneyError::ThresholdExceeded => write!(f, "maximum transfer threshold exceeded"),
+            SendMoneyError::LimitExceeded(limit) => write!(f, "{} exceeded", limit),
+            SendMoneyError::InsufficientFunds => write!(f, "insufficient funds"),
+            SendMoneyError::DepositRejected => write!(f, "deposit rejected"),
+            SendMoneyError::BatchAborted => write!(f, "batch aborted"),
+            SendMoneyError::CurrencyMismatch => {
+                write!(f, "accounts are held in different currencies")
+            }
+            SendMoneyError::ExchangeRateUnavailable => write!(f, "exchange rate unavailable"),
+            SendMoneyError::QuoteNotFound => write!(f, "quote not found"),
+            SendMoneyError::QuoteExpired => write!(f, "quote expired"),
+            SendMoneyError::Unauthorized => write!(f, "not authorized to debit the source account"),
+        }
+    }
+}
+
+#[async_trait]
+pub trait FxTransferUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Quotes sending money of the currency of the source account to a target account held in
+     * another currency. The quote is honoured by [FxTransferUseCase::send_money_fx] until it expires.
+     */
+    async fn quote_fx_transfer(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<FxQuote, SendMoneyError>;
+
+    /**
+     * Debits the source account in its currency and credits the target account in its currency
+     * at the rate of the quote, which can only be used once, and only by the principal who
+     * requested it for the source account.
+     */
+    async fn send_money_fx(
+        &self,
+        source_account_id: AccountId,
+        quote_id: FxQuoteId,
+        principal: Principal,
+    ) -> Result<FxQuote, SendMoneyError>;
+}
+
+/// Opaque, so that a quote cannot be guessed from another one.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FxQuoteId(pub String);
+
+/**
+ * The money of the source currency which is debited and the money of the target currency which is
+ * credited at the quoted exchange rate. The fee is debited in the source currency in addition.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct FxQuote {
+    pub id: FxQuoteId,
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub source_money: Money,
+    pub target_money: Money,
+    pub fee: Money,
+    pub exchange_rate: ExchangeRate,
+    pub expires_at: NaiveDateTime,
+}
+
+#[async_trait]
+pub trait SendMoneyBatchUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Sends money for every command of the batch, loading and locking each distinct account only once.
+     * @return the result of each command, in the order of the commands.
+     */
+    async fn send_money_batch(
+        &self,
+        command: SendMoneyBatchCommand,
+    ) -> Vec<Result<TransferReceipt, SendMoneyError>>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BatchMode {
+    /// Either every transfer of the batch is booked or none.
+    AllOrNothing,
+    /// Transfers are booked in order, rejected ones are skipped.
+    BestEffort,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct SendMoneyBatchCommand {
+    pub commands: Vec<SendMoneyCommand>,
+    pub mode: BatchMode,
+}
+
+impl SendMoneyBatchCommand {
+    // Functions
+
+    pub fn new(commands: Vec<SendMoneyCommand>, mode: BatchMode) -> Self {
+        Self { commands, mode }
+    }
+}
+
+#[async_trait]
+pub trait ScheduleTransferUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Schedules a transfer which is executed as the principal who scheduled it.
+     */
+    async fn schedule_transfer(
+        &self,
+        command: ScheduleTransferCommand,
+    ) -> Result<ScheduledTransferId, ScheduleTransferError>;
+    async fn list_scheduled_transfers(&self, account_id: AccountId) -> Vec<ScheduledTransfer>;
+    async fn cancel_scheduled_transfer(&self, id: ScheduledTransferId) -> bool;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ScheduleTransferCommand {
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub execution_date: NaiveDateTime,
+    pub principal: Principal,
+}
+
+impl ScheduleTransferCommand {
+    // Functions
+
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        execution_date: NaiveDateTime,
+        principal: Principal,
+    ) -> Self {
         Self {
             source_account_id,
             target_account_id,
             money,
+            execution_date,
+            principal,
+        }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum ScheduleTransferError {
+    ExecutionDateNotInFuture,
+    NonPositiveAmount,
+    /// The principal may not debit the source account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait ExecuteScheduledTransfersUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Runs all scheduled transfers that are due at `now` and records the outcome of each, and
+     * fails the ones whose execution was interrupted before its outcome was recorded.
+     * @return the number of scheduled transfers that were processed.
+     */
+    async fn execute_due_transfers(&self, now: NaiveDateTime) -> usize;
+}
+
+#[async_trait]
+pub trait StandingOrderUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Creates a standing order whose occurrences are executed as the principal who created it.
+     */
+    async fn create_standing_order(
+        &self,
+        command: CreateStandingOrderCommand,
+    ) -> Result<StandingOrderId, StandingOrderError>;
+    async fn list_standing_orders(&self, account_id: AccountId) -> Vec<StandingOrder>;
+    async fn list_standing_order_executions(
+        &self,
+        id: StandingOrderId,
+    ) -> Vec<StandingOrderExecution>;
+    async fn pause_standing_order(&self, id: StandingOrderId) -> bool;
+    async fn resume_standing_order(&self, id: StandingOrderId) -> bool;
+    async fn cancel_standing_order(&self, id: StandingOrderId) -> bool;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct CreateStandingOrderCommand {
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub recurrence: Recurrence,
+    pub start_date: NaiveDateTime,
+    pub end_date: Option<NaiveDateTime>,
+    pub max_occurrences: Option<u32>,
+    pub principal: Principal,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum StandingOrderError {
+    StartDateNotInFuture,
+    EndDateBeforeStartDate,
+    NoOccurrences,
+    NonPositiveAmount,
+    /// The principal may not debit the source account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait ExecuteStandingOrdersUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Executes every occurrence of the active standing orders that is due at `now`.
+     * @return the number of occurrences that were executed.
+     */
+    async fn execute_due_standing_orders(&self, now: NaiveDateTime) -> usize;
+}
+
+#[async_trait]
+pub trait ChangeOverdraftLimitUseCase: Send + Sync + std::fmt::Debug {
+    async fn change_overdraft_limit(
+        &self,
+        command: ChangeOverdraftLimitCommand,
+    ) -> Result<(), ChangeOverdraftLimitError>;
+    async fn list_overdraft_limit_changes(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Vec<OverdraftLimitChange>, Denied>;
+}
+
+/// The subject of the principal is recorded as who changed the limit.
+#[derive(PartialEq, Hash, Debug)]
+pub struct ChangeOverdraftLimitCommand {
+    pub account_id: AccountId,
+    pub overdraft_limit: Money,
+    pub principal: Principal,
+}
+
+impl ChangeOverdraftLimitCommand {
+    // Functions
+
+    pub fn new(account_id: AccountId, overdraft_limit: Money, principal: Principal) -> Self {
+        Self {
+            account_id,
+            overdraft_limit,
+            principal,
+        }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum ChangeOverdraftLimitError {
+    NegativeLimit,
+    MissingChangedBy,
+    /// The principal may not change the limits of the account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait TransferLimitsUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Overrides the default transfer limits for the account, limits which are not set fall back to the defaults.
+     */
+    async fn change_transfer_limits(
+        &self,
+        command: ChangeTransferLimitsCommand,
+    ) -> Result<(), ChangeTransferLimitsError>;
+
+    /**
+     * @return the transfer limits in effect for the account.
+     */
+    async fn get_transfer_limits(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<TransferLimits, Denied>;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ChangeTransferLimitsCommand {
+    pub account_id: AccountId,
+    pub transfer_limits: TransferLimits,
+    pub principal: Principal,
+}
+
+impl ChangeTransferLimitsCommand {
+    // Functions
+
+    pub fn new(
+        account_id: AccountId,
+        transfer_limits: TransferLimits,
+        principal: Principal,
+    ) -> Self {
+        Self {
+            account_id,
+            transfer_limits,
+            principal,
         }
     }
 }
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum ChangeTransferLimitsError {
+    NegativeLimit,
+    /// The principal may not change the limits of the account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait AccrueInterestUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Accrues the interest of every interest bearing account for each day since its last
+     * accrual up to and including `until`, and capitalises the interest of a month on its last
+     * day. An account which has never been accrued starts with `until`. Interest the funding
+     * account cannot pay is skipped, so that the account catches up on the following days.
+     */
+    async fn accrue_interest(&self, until: NaiveDate) -> InterestAccrualReport;
+}
+
+#[derive(Clone, PartialEq, Hash, Debug, Default)]
+pub struct InterestAccrualReport {
+    /// The number of days accrued over all accounts.
+    pub accrued_days: usize,
+    pub unpaid_interest: Vec<UnpaidInterest>,
+}
+
+/**
+ * Interest of a month which the funding account could not pay, its last day has been accrued
+ * without it.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct UnpaidInterest {
+    pub account_id: AccountId,
+    pub accrual_date: NaiveDate,
+    pub interest: Money,
+}
+
+impl UnpaidInterest {
+    // Functions
+
+    pub fn new(account_id: AccountId, accrual_date: NaiveDate, interest: Money) -> Self {
+        Self {
+            account_id,
+            accrual_date,
+            interest,
+        }
+    }
+}
+
+#[async_trait]
+pub trait RelayDomainEventsUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Delivers the recorded events which are due at `now` in the order in which they were
+     * recorded. The relay stops at the first failed delivery, which is retried after a backoff.
+     * @return the number of events that were delivered.
+     */
+    async fn relay_domain_events(&self, now: NaiveDateTime) -> usize;
+}
+
+#[async_trait]
+pub trait WebhookSubscriptionUseCase: Send + Sync + std::fmt::Debug {
+    async fn subscribe_webhook(
+        &self,
+        command: SubscribeWebhookCommand,
+    ) -> Result<WebhookSubscriptionId, SubscribeWebhookError>;
+    async fn list_webhook_subscriptions(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Vec<WebhookSubscription>, Denied>;
+    /**
+     * Removes the subscription, its pending deliveries are dead-lettered when they become due.
+     * @return whether there was such a subscription.
+     */
+    async fn unsubscribe_webhook(
+        &self,
+        id: WebhookSubscriptionId,
+        principal: Principal,
+    ) -> Result<bool, Denied>;
+    /**
+     * @return the delivery log of the subscription, latest first.
+     */
+    async fn list_webhook_deliveries(
+        &self,
+        id: WebhookSubscriptionId,
+        principal: Principal,
+    ) -> Result<Vec<WebhookDelivery>, Denied>;
+}
+
+/// The principal has to be permitted to view the account, whose activities are posted to the url.
+#[derive(PartialEq, Hash, Debug)]
+pub struct SubscribeWebhookCommand {
+    pub account_id: AccountId,
+    pub url: String,
+    pub secret: String,
+    pub principal: Principal,
+}
+
+impl SubscribeWebhookCommand {
+    // Functions
+
+    pub fn new(account_id: AccountId, url: String, secret: String, principal: Principal) -> Self {
+        Self {
+            account_id,
+            url,
+            secret,
+            principal,
+        }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum SubscribeWebhookError {
+    InvalidUrl,
+    MissingSecret,
+    /// The principal may not view the account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait DeliverWebhooksUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Posts the webhook deliveries which are due at `now`. Failed deliveries are retried after
+     * a backoff and dead-lettered once the maximum number of attempts is reached.
+     * @return the number of deliveries that were delivered.
+     */
+    async fn deliver_webhooks(&self, now: NaiveDateTime) -> usize;
+}
+
+#[async_trait]
+pub trait AccountActivityUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Lists at most `limit` activities of the account stored after the given one, in the order
+     * in which they were stored, so a client can resume from the last activity it has seen.
+     */
+    async fn list_activities_after(
+        &self,
+        account_id: AccountId,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<AccountActivity>;
+
+    /**
+     * Opens a stream of the activities of the account, which the principal has to be permitted
+     * to view.
+     * @return the last activity stored so far, after which a client that has seen none follows.
+     */
+    async fn open_activity_stream(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Option<ActivityId>, AccountActivityError>;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum AccountActivityError {
+    Unauthorized,
+}
+
+/**
+ * An activity together with the balance of the account right after it.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct AccountActivity {
+    pub activity: Activity,
+    pub balance: Money,
+}
+
+impl AccountActivity {
+    // Functions
+
+    pub fn new(activity: Activity, balance: Money) -> Self {
+        Self { activity, balance }
+    }
+}
+
+#[async_trait]
+pub trait GenerateStatementQuery: Send + Sync + std::fmt::Debug {
+    /**
+     * Generates the statement of the account for the days from `from` to `to`, both inclusive,
+     * which the principal has to be permitted to view.
+     */
+    async fn generate_statement(
+        &self,
+        account_id: AccountId,
+        from: NaiveDate,
+        to: NaiveDate,
+        principal: Principal,
+    ) -> Result<Statement, GenerateStatementError>;
+}
+
+/**
+ * The activities of an account within a period, each with the running balance after it.
+ * The closing balance is the opening balance plus all activities of the period.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct Statement {
+    pub account_id: AccountId,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub opening_balance: Money,
+    pub activities: Vec<AccountActivity>,
+    pub closing_balance: Money,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum GenerateStatementError {
+    InvalidPeriod,
+    /// The principal may not view the account, which has been audited.
+    Unauthorized,
+}
+
+#[async_trait]
+pub trait ImportUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Validates the lines and stores the valid records in batches, each in a single transaction
+     * together with the number of its last line, so an interrupted import of the same source
+     * resumes after the last stored batch. A dry run only validates the lines.
+     */
+    async fn import(
+        &self,
+        command: ImportCommand,
+        lines: Box<dyn Iterator<Item = ImportLine> + Send>,
+    ) -> ImportReport;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ImportCommand {
+    /// Identifies the imported file across runs.
+    pub source: String,
+    pub dry_run: bool,
+}
+
+impl ImportCommand {
+    // Functions
+
+    pub fn new(source: String, dry_run: bool) -> Self {
+        Self { source, dry_run }
+    }
+}
+
+/**
+ * A line of the imported file with the record read from it, or why it could not be read.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct ImportLine {
+    pub line_number: usize,
+    pub record: Result<ImportRecord, String>,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum ImportRecord {
+    Account(ImportedAccount),
+    /// A historical activity, stored with a new id.
+    Activity(Activity),
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ImportedAccount {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    pub overdraft_limit: Money,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct RejectedLine {
+    pub line_number: usize,
+    pub reason: String,
+}
+
+#[derive(Default, PartialEq, Hash, Debug)]
+pub struct ImportReport {
+    pub imported_accounts: usize,
+    pub imported_activities: usize,
+    /// The lines stored by a previous run of the same source.
+    pub skipped_lines: usize,
+    pub rejected_lines: Vec<RejectedLine>,
+}
+
+#[async_trait]
+pub trait ExportLedgerUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Writes the activities of all accounts within the period as a general-ledger file, their
+     * withdrawals and deposits grouped by transfer. An incremental export only includes the
+     * activities stored after the last activity of the previous exports.
+     */
+    async fn export_ledger(
+        &self,
+        command: ExportLedgerCommand,
+    ) -> Result<LedgerExport, ExportLedgerError>;
+}
+
+/// The period is from `from` to `to`, both inclusive, and open ended if either is missing.
+#[derive(PartialEq, Hash, Debug)]
+pub struct ExportLedgerCommand {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub incremental: bool,
+}
+
+impl ExportLedgerCommand {
+    // Functions
+
+    pub fn new(from: Option<NaiveDate>, to: Option<NaiveDate>, incremental: bool) -> Self {
+        Self {
+            from,
+            to,
+            incremental,
+        }
+    }
+}
+
+/**
+ * The transfers of the exported activities, ordered by the id of their first activity.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct Ledger {
+    pub exported_at: NaiveDateTime,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub transfers: Vec<LedgerTransfer>,
+}
+
+/**
+ * The withdrawal from the source account and the deposit to the target account of a transfer.
+ * A transfer is incomplete if one of them has not been stored, or not within the export.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct LedgerTransfer {
+    /// The id of the first stored activity of the transfer.
+    pub transfer_id: ActivityId,
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub withdrawal: Option<Activity>,
+    pub deposit: Option<Activity>,
+}
+
+impl LedgerTransfer {
+    // Methods
+
+    pub fn is_complete(&self) -> bool {
+        self.withdrawal.is_some() && self.deposit.is_some()
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct LedgerFile {
+    pub path: String,
+    /// The hex encoded SHA-256 digest of the file.
+    pub checksum: String,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct LedgerExport {
+    pub exported_at: NaiveDateTime,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub first_activity_id: Option<ActivityId>,
+    pub last_activity_id: Option<ActivityId>,
+    pub transfer_count: usize,
+    pub incomplete_transfer_count: usize,
+    pub file: LedgerFile,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum ExportLedgerError {
+    InvalidPeriod,
+    WriteFailed(String),
+}
+
+#[async_trait]
+pub trait ReconcileUseCase: Send + Sync + std::fmt::Debug {
+    /**
+     * Scans the stored activities and account snapshots for inconsistencies. In repair mode the
+     * snapshots which do not match the replayed events are discarded, as they are rebuilt on the
+     * next load, the other inconsistencies need to be resolved manually.
+     */
+    async fn reconcile(&self, command: ReconcileCommand) -> ReconciliationReport;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ReconcileCommand {
+    pub repair: bool,
+}
+
+impl ReconcileCommand {
+    // Functions
+
+    pub fn new(repair: bool) -> Self {
+        Self { repair }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum Inconsistency {
+    /// A transfer whose withdrawal or whose deposit has not been stored.
+    MissingCounterpart {
+        activity_id: ActivityId,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        missing_withdrawal: bool,
+    },
+    /// An activity referencing an account which does not exist.
+    UnknownAccount {
+        activity_id: ActivityId,
+        account_id: AccountId,
+    },
+    SnapshotMismatch {
+        snapshot_balances: AccountSnapshotBalances,
+        repaired: bool,
+    },
+}
+
+impl Inconsistency {
+    // Methods
+
+    pub fn is_repaired(&self) -> bool {
+        matches!(self, Inconsistency::SnapshotMismatch { repaired: true, .. })
+    }
+}
+
+/**
+ * The balances of an account snapshot and those replayed from the events up to its last event.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct AccountSnapshotBalances {
+    pub account_id: AccountId,
+    pub withdrawal_balance: Money,
+    pub deposit_balance: Money,
+    pub replayed_withdrawal_balance: Money,
+    pub replayed_deposit_balance: Money,
+}
+
+impl AccountSnapshotBalances {
+    // Methods
+
+    pub fn is_consistent(&self) -> bool {
+        self.withdrawal_balance == self.replayed_withdrawal_balance
+            && self.deposit_balance == self.replayed_deposit_balance
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ReconciliationReport {
+    pub reconciled_at: NaiveDateTime,
+    pub repair: bool,
+    pub checked_activity_count: usize,
+    pub checked_snapshot_count: usize,
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+#[async_trait]
+pub trait AccountAdministrationUseCase: Send + Sync + std::fmt::Debug {
+    async fn open_account(
+        &self,
+        command: OpenAccountCommand,
+    ) -> Result<AccountId, OpenAccountError>;
+    /**
+     * Closes the account once all of its money has been withdrawn, a closed account neither
+     * takes withdrawals nor deposits.
+     */
+    async fn close_account(&self, account_id: AccountId) -> Result<(), CloseAccountError>;
+    /**
+     * @return the account with its current balance, none if there is no such account.
+     */
+    async fn get_account_summary(&self, account_id: AccountId) -> Option<AccountSummary>;
+    /**
+     * Entitles the customer to act on the account, granting a mandate again replaces its kind.
+     */
+    async fn grant_account_mandate(
+        &self,
+        command: GrantAccountMandateCommand,
+    ) -> Result<(), GrantAccountMandateError>;
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct OpenAccountCommand {
+    pub currency: Currency,
+    pub overdraft_limit: Money,
+}
+
+impl OpenAccountCommand {
+    // Functions
+
+    pub fn new(currency: Currency, overdraft_limit: Money) -> Self {
+        Self {
+            currency,
+            overdraft_limit,
+        }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum OpenAccountError {
+    NegativeOverdraftLimit,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum CloseAccountError {
+    AccountNotFound,
+    AlreadyClosed,
+    BalanceNotZero,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct GrantAccountMandateCommand {
+    pub account_id: AccountId,
+    pub customer: String,
+    pub kind: MandateKind,
+}
+
+impl GrantAccountMandateCommand {
+    // Functions
+
+    pub fn new(account_id: AccountId, customer: String, kind: MandateKind) -> Self {
+        Self {
+            account_id,
+            customer,
+            kind,
+        }
+    }
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub enum GrantAccountMandateError {
+    AccountNotFound,
+    AccountClosed,
+}
+
+/**
+ * The stored details of an account, independent of its activities.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct AccountDetails {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    pub overdraft_limit: Money,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct AccountSummary {
+    pub details: AccountDetails,
+    pub balance: Money,
+}
+
+#[async_trait]
+pub trait CheckReadinessQuery: Send + Sync + std::fmt::Debug {
+    /**
+     * Checks whether the service can take requests: the database answers, all migrations have
+     * been applied and the background workers run on schedule.
+     */
+    async fn check_readiness(&self) -> ReadinessReport;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(PartialEq, Hash, Debug)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    /// How long the check took, or the last run of a worker.
+    pub latency: std::time::Duration,
+    /// Why the component is down.
+    pub detail: Option<String>,
+}
+
+/**
+ * Up if all components are up.
+ */
+#[derive(PartialEq, Hash, Debug)]
+pub struct ReadinessReport {
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+}
+
+/**
+ * The runs of a background worker, which is due every `interval`.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WorkerStatus {
+    pub name: String,
+    pub interval: chrono::Duration,
+    pub registered_at: NaiveDateTime,
+    pub last_run_started_at: Option<NaiveDateTime>,
+    pub last_run_finished_at: Option<NaiveDateTime>,
+    pub stopped: bool,
+}
+
+pub trait RenderMetricsQuery: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the metrics recorded since the start in the Prometheus text exposition format.
+     */
+    fn render_metrics(&self) -> String;
+}
@@ -0,0 +1,135 @@
+use domain::{
+    ar::account::AccountId,
+    vo::{money::Money, rounding::Rounding},
+};
+
+/**
+ * How the days of an interest period are weighted against a year.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DayCountConvention {
+    /// Every actual day counts, a year has 360 days.
+    Actual360,
+    /// Every actual day counts, a year has 365 days.
+    Actual365,
+    /// Every month counts as 30 days, a year has 360 days.
+    Thirty360,
+}
+
+/**
+ * The annual interest rate paid on the balance of savings accounts.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct InterestPolicy {
+    pub annual_rate_basis_points: i128,
+    pub day_count_convention: DayCountConvention,
+    pub rounding: Rounding,
+}
+
+/**
+ * The interest policy together with the account of the bank which pays the interest.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct InterestProperties {
+    pub policy: InterestPolicy,
+    pub interest_account_id: AccountId,
+}
+
+impl InterestPolicy {
+    // Functions
+
+    /// # Arguments
+    ///
+    /// * `annual_rate_basis_points` - The annual interest rate in basis points (1/100 of a percent).
+    /// * `day_count_convention` - How the days of a month are weighted against a year.
+    /// * `rounding` - How the interest of a month is rounded to whole units.
+    pub fn new(
+        annual_rate_basis_points: i128,
+        day_count_convention: DayCountConvention,
+        rounding: Rounding,
+    ) -> Self {
+        Self {
+            annual_rate_basis_points,
+            day_count_convention,
+            rounding,
+        }
+    }
+
+    // Methods
+
+    /**
+     * The interest is calculated exactly from the sum of the daily balances and rounded only once.
+     * @return the interest of a month with `days_in_month` days whose daily balances sum up to `balance_sum`.
+     */
+    pub fn interest_for(&self, balance_sum: &Money, days_in_month: u32) -> Money {
+        let rate = self.annual_rate_basis_points;
+        let (numerator, denominator) = match self.day_count_convention {
+            DayCountConvention::Actual360 => (rate, 10_000 * 360),
+            DayCountConvention::Actual365 => (rate, 10_000 * 365),
+            // each day weighs 30 / days_in_month days
+            DayCountConvention::Thirty360 => (rate * 30, 10_000 * 360 * days_in_month as i128),
+        };
+        balance_sum.multiply_ratio(numerator, denominator, self.rounding)
+    }
+}
+
+impl InterestProperties {
+    // Functions
+
+    pub fn new(policy: InterestPolicy, interest_account_id: AccountId) -> Self {
+        Self {
+            policy,
+            interest_account_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interest_by_day_count_convention() {
+        // 10_000 on each of the 31 days of a month
+        let balance_sum = Money::of(310_000);
+
+        // 3.65 % is 0.01 % per day of a 365 day year
+        assert_eq!(
+            Money::of(31),
+            InterestPolicy::new(365, DayCountConvention::Actual365, Rounding::HalfEven)
+                .interest_for(&balance_sum, 31)
+        );
+        // 31.43 is rounded down
+        assert_eq!(
+            Money::of(31),
+            InterestPolicy::new(365, DayCountConvention::Actual360, Rounding::HalfEven)
+                .interest_for(&balance_sum, 31)
+        );
+        // a full month is 30 days
+        assert_eq!(
+            Money::of(30),
+            InterestPolicy::new(360, DayCountConvention::Thirty360, Rounding::HalfEven)
+                .interest_for(&balance_sum, 31)
+        );
+    }
+
+    #[test]
+    fn test_interest_is_rounded_by_policy() {
+        // 0.5 of interest
+        let balance_sum = Money::of(18_250);
+        let policy = |rounding| InterestPolicy::new(100, DayCountConvention::Actual365, rounding);
+
+        assert_eq!(
+            Money::of(0),
+            policy(Rounding::HalfEven).interest_for(&balance_sum, 30)
+        );
+        assert_eq!(
+            Money::of(1),
+            policy(Rounding::HalfUp).interest_for(&balance_sum, 30)
+        );
+        assert_eq!(
+            Money::of(0),
+            policy(Rounding::Down).interest_for(&balance_sum, 30)
+        );
+    }
+}
@@ -1,4 +1,31 @@
+pub mod account_activity_use_case;
+pub mod account_administration_use_case;
+pub mod accrue_interest_use_case;
+pub mod authorization_policy;
+pub mod change_overdraft_limit_use_case;
+pub mod check_readiness_query;
+pub mod deliver_webhooks_use_case;
+pub mod draining_transfer_use_cases;
+pub mod execute_scheduled_transfers_use_case;
+pub mod execute_standing_orders_use_case;
+pub mod export_ledger_use_case;
+pub mod fee_policy;
+pub mod fx_transfer_use_case;
+pub mod generate_statement_query;
+pub mod import_use_case;
+pub mod in_process_domain_event_publisher;
 pub mod inbound_ports;
+pub mod interest_policy;
 pub mod no_op_account_lock;
+pub mod no_op_metrics;
 pub mod outbound_ports;
+pub mod reconcile_use_case;
+pub mod relay_domain_events_use_case;
+pub mod schedule_transfer_use_case;
+pub mod send_money_batch_use_case;
 pub mod send_money_use_case;
+pub mod standing_order_use_case;
+pub mod transfer_limits_use_case;
+pub mod webhook_delivery_scheduler;
+pub mod webhook_subscription_use_case;
+pub mod worker_registry;
@@ -0,0 +1,32 @@
+use crate::{inbound_ports::SendMoneyError, outbound_ports::MetricsPort};
+use domain::vo::money::Money;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct NoOpMetrics {}
+
+impl MetricsPort for NoOpMetrics {
+    fn record_transfer_attempted(&self) {
+        // do nothing
+    }
+
+    fn record_transfer_succeeded(&self, _money: &Money) {
+        // do nothing
+    }
+
+    fn record_transfer_rejected(&self, _error: &SendMoneyError) {
+        // do nothing
+    }
+
+    fn record_lock_wait(&self, _duration: Duration) {
+        // do nothing
+    }
+
+    fn record_load_account(&self, _duration: Duration, _activity_count: usize) {
+        // do nothing
+    }
+
+    fn record_update_activities(&self, _duration: Duration) {
+        // do nothing
+    }
+}
@@ -1,6 +1,32 @@
+use crate::inbound_ports::{
+    AccountDetails, AccountSnapshotBalances, ImportedAccount, Ledger, LedgerExport, LedgerFile,
+    SendMoneyError, WorkerStatus,
+};
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
-use domain::ar::account::AccountId;
+use chrono::{NaiveDate, NaiveDateTime};
+use domain::{
+    ar::{
+        account::AccountId,
+        account_mandate::AccountMandate,
+        activity::{Activity, ActivityId},
+        authorization_denial::AuthorizationDenial,
+        domain_event::DomainEvent,
+        interest_accrual::InterestAccrual,
+        outbox_message::OutboxMessage,
+        overdraft_limit_change::OverdraftLimitChange,
+        scheduled_transfer::{ScheduledTransfer, ScheduledTransferId, ScheduledTransferStatus},
+        standing_order::{
+            StandingOrder, StandingOrderExecution, StandingOrderExecutionId, StandingOrderId,
+        },
+        webhook_subscription::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId},
+    },
+    vo::{
+        currency::Currency,
+        exchange_rate::ExchangeRateQuote,
+        money::Money,
+        transfer_limits::{TransferLimits, TransferVolume},
+    },
+};
 use mockall_double::double;
 
 #[cfg(test)]
@@ -29,4 +55,524 @@ pub trait AccountLock: std::fmt::Debug {
 #[async_trait]
 pub trait UpdateAccountStatePort: Send + Sync + std::fmt::Debug {
     async fn update_activities(&self, account: Account);
+    /**
+     * Persists the new activities of all accounts as a single unit of work.
+     */
+    async fn update_accounts(&self, accounts: Vec<Account>);
+    /**
+     * Persists the new activities of all accounts together with the events to publish as a
+     * single unit of work, so no event is lost and none is published for an aborted transfer.
+     */
+    async fn update_accounts_and_record_events(
+        &self,
+        accounts: Vec<Account>,
+        domain_events: Vec<DomainEvent>,
+    );
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadAccountDetailsPort: Send + Sync + std::fmt::Debug {
+    async fn load_account_details(&self, account_id: AccountId) -> Option<AccountDetails>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateAccountDetailsPort: Send + Sync + std::fmt::Debug {
+    /**
+     * Stores a new account together with the event opening it.
+     * @return the id of the new account.
+     */
+    async fn open_account(
+        &self,
+        currency: Currency,
+        overdraft_limit: Money,
+        opened_at: NaiveDateTime,
+    ) -> AccountId;
+    /**
+     * Marks the account as closed together with the event closing it.
+     */
+    async fn close_account(&self, account_id: AccountId, closed_at: NaiveDateTime);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadAccountActivityPort: Send + Sync + std::fmt::Debug {
+    /**
+     * Loads at most `limit` activities of the account stored after the given one, or from the
+     * first one, in the order in which they were stored.
+     */
+    async fn load_activities_after(
+        &self,
+        account_id: AccountId,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<Activity>;
+    /**
+     * @return the balance of the account up to and including the given activity, zero if none.
+     */
+    async fn load_balance_until(
+        &self,
+        account_id: AccountId,
+        activity_id: Option<ActivityId>,
+    ) -> Money;
+    /**
+     * @return the last activity stored for the account, if any.
+     */
+    async fn load_last_activity_id(&self, account_id: AccountId) -> Option<ActivityId>;
+    /**
+     * Loads the activities of the account from `since` (inclusive) to `until` (exclusive), in the
+     * order in which they happened.
+     */
+    async fn load_activities_between(
+        &self,
+        account_id: AccountId,
+        since: NaiveDateTime,
+        until: NaiveDateTime,
+    ) -> Vec<Activity>;
+    /**
+     * @return the deposits minus the withdrawals of the account before `until`, summed the same
+     * way as the baseline balance of a loaded account.
+     */
+    async fn load_balance_before(&self, account_id: AccountId, until: NaiveDateTime) -> Money;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadImportPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the number of the last line of the source that has been stored, zero if none.
+     */
+    async fn load_import_progress(&self, source: String) -> usize;
+    /**
+     * @return those of the accounts which exist already.
+     */
+    async fn load_existing_account_ids(&self, account_ids: Vec<AccountId>) -> Vec<AccountId>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateImportStatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Stores the accounts, which are opened at `opened_at`, the activities and the number of
+     * the last line of the batch as the progress of the source in a single transaction.
+     */
+    async fn save_import_batch(
+        &self,
+        source: String,
+        last_line_number: usize,
+        accounts: Vec<ImportedAccount>,
+        activities: Vec<Activity>,
+        opened_at: NaiveDateTime,
+    );
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadLedgerPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the id of the last activity of all previous ledger exports, none if none.
+     */
+    async fn load_last_exported_activity_id(&self) -> Option<ActivityId>;
+    /**
+     * @return at most `limit` activities of all accounts stored after the given one from `since`
+     * (inclusive) to `until` (exclusive), ordered by id.
+     */
+    async fn load_ledger_activities(
+        &self,
+        after_activity_id: Option<ActivityId>,
+        since: Option<NaiveDateTime>,
+        until: Option<NaiveDateTime>,
+        limit: usize,
+    ) -> Vec<Activity>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateLedgerExportStatePort: Send + Sync + std::fmt::Debug {
+    async fn save_ledger_export(&self, ledger_export: &LedgerExport);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LedgerFilePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Writes the ledger to a new file next to a file with its checksum.
+     */
+    async fn write_ledger_file(&self, ledger: &Ledger) -> Result<LedgerFile, String>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadReconciliationPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return at most `limit` activities of all accounts stored after the given one, ordered by id.
+     */
+    async fn load_activities_after(
+        &self,
+        after_activity_id: Option<ActivityId>,
+        limit: usize,
+    ) -> Vec<Activity>;
+    /**
+     * @return those of the accounts which exist.
+     */
+    async fn load_existing_account_ids(&self, account_ids: Vec<AccountId>) -> Vec<AccountId>;
+    /**
+     * @return at most `limit` snapshots of the accounts after the given one, ordered by account id.
+     */
+    async fn load_account_snapshot_balances(
+        &self,
+        after_account_id: Option<AccountId>,
+        limit: usize,
+    ) -> Vec<AccountSnapshotBalances>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RepairAccountSnapshotPort: Send + Sync + std::fmt::Debug {
+    async fn discard_account_snapshot(&self, account_id: AccountId);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadScheduledTransferPort: Send + Sync + std::fmt::Debug {
+    async fn load_scheduled_transfer(&self, id: ScheduledTransferId) -> Option<ScheduledTransfer>;
+    async fn load_scheduled_transfers_of_account(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<ScheduledTransfer>;
+    async fn load_due_scheduled_transfers(&self, now: NaiveDateTime) -> Vec<ScheduledTransfer>;
+    /**
+     * @return the transfers still being executed which were claimed before the given time or
+     * at an unknown time.
+     */
+    async fn load_scheduled_transfers_claimed_before(
+        &self,
+        claimed_before: NaiveDateTime,
+    ) -> Vec<ScheduledTransfer>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateScheduledTransferStatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Inserts a new scheduled transfer.
+     */
+    async fn save_scheduled_transfer(
+        &self,
+        scheduled_transfer: ScheduledTransfer,
+    ) -> ScheduledTransferId;
+
+    /**
+     * Updates the state of an existing scheduled transfer, provided it is still in the expected
+     * status.
+     * @return false if an executor or a cancellation changed the transfer in the meantime.
+     */
+    async fn update_scheduled_transfer_state(
+        &self,
+        scheduled_transfer: ScheduledTransfer,
+        expected_status: ScheduledTransferStatus,
+    ) -> bool;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadStandingOrderPort: Send + Sync + std::fmt::Debug {
+    async fn load_standing_order(&self, id: StandingOrderId) -> Option<StandingOrder>;
+    async fn load_standing_orders_of_account(&self, account_id: AccountId) -> Vec<StandingOrder>;
+    async fn load_due_standing_orders(&self, now: NaiveDateTime) -> Vec<StandingOrder>;
+    async fn load_standing_order_executions(
+        &self,
+        id: StandingOrderId,
+    ) -> Vec<StandingOrderExecution>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateStandingOrderStatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Inserts a new standing order or updates the state of an existing one.
+     */
+    async fn save_standing_order(&self, standing_order: StandingOrder) -> StandingOrderId;
+
+    /**
+     * Atomically stores the advanced standing order together with the started execution
+     * of the occurrence it consumed, before any money is moved.
+     * @return None if that occurrence has already been claimed, e.g. by a run before a restart,
+     * or if the standing order was paused or cancelled in the meantime.
+     */
+    async fn claim_standing_order_execution(
+        &self,
+        standing_order: StandingOrder,
+        execution: StandingOrderExecution,
+    ) -> Option<StandingOrderExecutionId>;
+
+    /**
+     * Records the outcome of a claimed execution.
+     */
+    async fn save_standing_order_execution(&self, execution: StandingOrderExecution);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadOverdraftLimitChangePort: Send + Sync + std::fmt::Debug {
+    async fn load_overdraft_limit_changes(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<OverdraftLimitChange>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateOverdraftLimitPort: Send + Sync + std::fmt::Debug {
+    /**
+     * Stores the new overdraft limit of the account together with the audit record of the change.
+     */
+    async fn update_overdraft_limit(&self, overdraft_limit_change: OverdraftLimitChange);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadTransferLimitsPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return the limits overridden for the account, unset limits fall back to the defaults.
+     */
+    async fn load_transfer_limits(&self, account_id: AccountId) -> TransferLimits;
+
+    /**
+     * @return the sum and the number of the withdrawals of the account since `since`, but for
+     * the fees withdrawn to the fee collection account, so each transfer counts once and without
+     * its fee.
+     */
+    async fn load_outgoing_transfer_volume(
+        &self,
+        account_id: AccountId,
+        since: NaiveDateTime,
+        fee_collection_account_id: Option<AccountId>,
+    ) -> TransferVolume;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateTransferLimitsPort: Send + Sync + std::fmt::Debug {
+    async fn update_transfer_limits(&self, account_id: AccountId, transfer_limits: TransferLimits);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadAccountMandatePort: Send + Sync + std::fmt::Debug {
+    async fn load_account_mandates(&self, account_id: AccountId) -> Vec<AccountMandate>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateAccountMandatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Stores the mandate, replacing the one of the customer on the account if there is any.
+     */
+    async fn grant_account_mandate(&self, account_mandate: AccountMandate);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RecordAuthorizationDenialPort: Send + Sync + std::fmt::Debug {
+    async fn record_authorization_denial(&self, authorization_denial: AuthorizationDenial);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadInterestAccrualPort: Send + Sync + std::fmt::Debug {
+    async fn load_interest_bearing_account_ids(&self) -> Vec<AccountId>;
+    /**
+     * @return the latest day the account has been accrued for, None if it never has been.
+     */
+    async fn load_last_accrual_date(&self, account_id: AccountId) -> Option<NaiveDate>;
+    /**
+     * @return the accruals of the account from `from` until `until`, both inclusive.
+     */
+    async fn load_interest_accruals(
+        &self,
+        account_id: AccountId,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Vec<InterestAccrual>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateInterestAccrualPort: Send + Sync + std::fmt::Debug {
+    /**
+     * Persists the accrual together with the new activities of the accounts and the events
+     * they recorded as a single unit of work, unless the account has already been accrued
+     * for the day.
+     */
+    async fn save_interest_accrual(
+        &self,
+        interest_accrual: InterestAccrual,
+        accounts: Vec<Account>,
+        domain_events: Vec<DomainEvent>,
+    );
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ExchangeRatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return a quote of the rate at which `source_currency` is exchanged into `target_currency`
+     * at `now`, None if the currency pair is not traded.
+     */
+    async fn quote_exchange_rate(
+        &self,
+        source_currency: Currency,
+        target_currency: Currency,
+        now: NaiveDateTime,
+    ) -> Option<ExchangeRateQuote>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DomainEventPublisher: Send + Sync + std::fmt::Debug {
+    /**
+     * Publishes the events in the order in which they happened.
+     */
+    async fn publish(&self, domain_events: Vec<DomainEvent>);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadOutboxPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return at most `limit` messages which have not been processed yet, oldest first.
+     */
+    async fn load_pending_outbox_messages(&self, limit: usize) -> Vec<OutboxMessage>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateOutboxStatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Records the outcome of a delivery attempt of a stored message.
+     */
+    async fn save_outbox_message(&self, outbox_message: OutboxMessage);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DomainEventSink: Send + Sync + std::fmt::Debug {
+    /**
+     * Hands the event to a consumer outside of the unit of work which recorded it.
+     * @return the reason if the consumer did not accept the event, it will be delivered again.
+     */
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadWebhookSubscriptionPort: Send + Sync + std::fmt::Debug {
+    async fn load_webhook_subscription(
+        &self,
+        id: WebhookSubscriptionId,
+    ) -> Option<WebhookSubscription>;
+    async fn load_webhook_subscriptions_of_account(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<WebhookSubscription>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateWebhookSubscriptionStatePort: Send + Sync + std::fmt::Debug {
+    async fn save_webhook_subscription(
+        &self,
+        webhook_subscription: WebhookSubscription,
+    ) -> WebhookSubscriptionId;
+    /**
+     * @return false if there is no such subscription.
+     */
+    async fn delete_webhook_subscription(&self, id: WebhookSubscriptionId) -> bool;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LoadWebhookDeliveryPort: Send + Sync + std::fmt::Debug {
+    /**
+     * @return at most `limit` pending deliveries which are due at `now`, oldest first.
+     */
+    async fn load_due_webhook_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: usize,
+    ) -> Vec<WebhookDelivery>;
+    /**
+     * @return all deliveries of the subscription, latest first.
+     */
+    async fn load_webhook_deliveries(
+        &self,
+        subscription_id: WebhookSubscriptionId,
+    ) -> Vec<WebhookDelivery>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait UpdateWebhookDeliveryStatePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Inserts new deliveries, a delivery of the same event to the same subscription is only
+     * stored once, so that events delivered again by the relay are not notified twice.
+     */
+    async fn schedule_webhook_deliveries(&self, webhook_deliveries: Vec<WebhookDelivery>);
+    /**
+     * Records the outcome of a delivery attempt of a stored delivery.
+     */
+    async fn save_webhook_delivery(&self, webhook_delivery: WebhookDelivery);
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WebhookPort: Send + Sync + std::fmt::Debug {
+    /**
+     * Posts the signed event of the delivery to the endpoint of the subscription.
+     * @return the reason if the endpoint did not accept the delivery.
+     */
+    async fn post_webhook(
+        &self,
+        webhook_subscription: &WebhookSubscription,
+        webhook_delivery: &WebhookDelivery,
+    ) -> Result<(), String>;
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CheckDatabasePort: Send + Sync + std::fmt::Debug {
+    /**
+     * Runs a trivial query.
+     * @return the reason if it failed.
+     */
+    async fn ping_database(&self) -> Result<(), String>;
+    /**
+     * @return the versions of the migrations this build knows which have not been applied.
+     */
+    async fn load_pending_migrations(&self) -> Result<Vec<i64>, String>;
+}
+
+#[cfg_attr(test, automock)]
+pub trait LoadWorkerStatusPort: Send + Sync + std::fmt::Debug {
+    fn load_worker_statuses(&self) -> Vec<WorkerStatus>;
+}
+
+/**
+ * Records what happens to transfers and accounts, for monitoring.
+ */
+#[cfg_attr(test, automock)]
+pub trait MetricsPort: Send + Sync + std::fmt::Debug {
+    fn record_transfer_attempted(&self);
+    fn record_transfer_succeeded(&self, money: &Money);
+    fn record_transfer_rejected(&self, error: &SendMoneyError);
+    fn record_lock_wait(&self, duration: std::time::Duration);
+    /**
+     * # Arguments
+     *
+     * * `activity_count` - The activities loaded with the account, i.e. those of its window.
+     */
+    fn record_load_account(&self, duration: std::time::Duration, activity_count: usize);
+    fn record_update_activities(&self, duration: std::time::Duration);
 }
@@ -0,0 +1,345 @@
+use crate::{
+    export_ledger_use_case::TransferKey,
+    inbound_ports::{Inconsistency, ReconcileCommand, ReconcileUseCase, ReconciliationReport},
+    outbound_ports::{LoadReconciliationPort, RepairAccountSnapshotPort},
+};
+use async_trait::async_trait;
+use chrono::Local;
+use domain::{
+    ar::{
+        account::AccountId,
+        activity::{Activity, ActivityId},
+    },
+    vo::rounding::Rounding,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+const PAGE_SIZE: usize = 1000;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ReconcileUseCaseImpl {
+    load_reconciliation_port: Arc<dyn LoadReconciliationPort>,
+    repair_account_snapshot_port: Arc<dyn RepairAccountSnapshotPort>,
+    fx_rounding: Rounding,
+}
+
+impl ReconcileUseCaseImpl {
+    /// # Arguments
+    ///
+    /// * `fx_rounding` - How the FX transfers round the money they credit, see [TransferKey].
+    // #[inject]
+    pub fn new(
+        load_reconciliation_port: Arc<dyn LoadReconciliationPort>,
+        repair_account_snapshot_port: Arc<dyn RepairAccountSnapshotPort>,
+        fx_rounding: Rounding,
+    ) -> Self {
+        Self {
+            load_reconciliation_port,
+            repair_account_snapshot_port,
+            fx_rounding,
+        }
+    }
+
+    // Methods
+
+    /**
+     * Checks the activities page by page for references to unknown accounts and pairs them by
+     * transfer, like the ledger export does. Only the activities still missing their counterpart
+     * are kept until all pages are checked.
+     * @return the number of checked activities.
+     */
+    async fn check_activities(&self, inconsistencies: &mut Vec<Inconsistency>) -> usize {
+        let mut existing_account_ids = HashSet::new();
+        let mut unknown_account_ids = HashSet::new();
+        let mut after_activity_id: Option<ActivityId> = None;
+        let mut checked_activity_count = 0;
+        // the activities missing a withdrawal (true) or a deposit (false) of their transfer
+        let mut unpaired: HashMap<(TransferKey, bool), VecDeque<Activity>> = HashMap::new();
+        loop {
+            let page = self
+                .load_reconciliation_port
+                .load_activities_after(
+                    after_activity_id.as_ref().map(|id| ActivityId(id.0)),
+                    PAGE_SIZE,
+                )
+                .await;
+            let page_size = page.len();
+            if let Some(last) = page.last() {
+                after_activity_id = last.id.as_ref().map(|id| ActivityId(id.0));
+            }
+
+            let unchecked_account_ids: Vec<AccountId> = page
+                .iter()
+                .flat_map(|activity| {
+                    [
+                        activity.owner_account_id.clone(),
+                        activity.source_account_id.clone(),
+                        activity.target_account_id.clone(),
+                    ]
+                })
+                .filter(|account_id| {
+                    !existing_account_ids.contains(account_id)
+                        && !unknown_account_ids.contains(account_id)
+                })
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            if !unchecked_account_ids.is_empty() {
+                let existing = self
+                    .load_reconciliation_port
+                    .load_existing_account_ids(unchecked_account_ids.clone())
+                    .await;
+                for account_id in unchecked_account_ids {
+                    if existing.contains(&account_id) {
+                        existing_account_ids.insert(account_id);
+                    } else {
+                        unknown_account_ids.insert(account_id);
+                    }
+                }
+            }
+            for activity in &page {
+                // an account is reported once per activity, the owner is usually a party as well
+                let mut account_ids =
+                    vec![&activity.source_account_id, &activity.target_account_id];
+                if !account_ids.contains(&&activity.owner_account_id) {
+                    account_ids.push(&activity.owner_account_id);
+                }
+                for account_id in account_ids {
+                    if unknown_account_ids.contains(account_id) {
+                        inconsistencies.push(Inconsistency::UnknownAccount {
+                            activity_id: ActivityId(activity.id.as_ref().unwrap().0),
+                            account_id: account_id.clone(),
+                        });
+                    }
+                }
+            }
+
+            checked_activity_count += page_size;
+            for activity in page {
+                let (key, withdrawal) = TransferKey::of(&activity, self.fx_rounding);
+                if unpaired
+                    .get_mut(&(key.clone(), withdrawal))
+                    .and_then(VecDeque::pop_front)
+                    .is_none()
+                {
+                    unpaired
+                        .entry((key, !withdrawal))
+                        .or_default()
+                        .push_back(activity);
+                }
+            }
+            if page_size < PAGE_SIZE {
+                break;
+            }
+        }
+
+        let mut missing_counterparts = unpaired
+            .into_iter()
+            .flat_map(|((_, missing_withdrawal), activities)| {
+                activities
+                    .into_iter()
+                    .map(move |activity| (activity, missing_withdrawal))
+            })
+            .collect::<Vec<_>>();
+        missing_counterparts.sort_by_key(|(activity, _)| activity.id.as_ref().map(|id| id.0));
+        for (activity, missing_withdrawal) in missing_counterparts {
+            inconsistencies.push(Inconsistency::MissingCounterpart {
+                activity_id: ActivityId(activity.id.as_ref().unwrap().0),
+                source_account_id: activity.source_account_id,
+                target_account_id: activity.target_account_id,
+                missing_withdrawal,
+            });
+        }
+        checked_activity_count
+    }
+
+    /**
+     * @return the number of checked snapshots.
+     */
+    async fn check_snapshots(
+        &self,
+        repair: bool,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> usize {
+        let mut checked_snapshot_count = 0;
+        let mut after_account_id = None;
+        loop {
+            let page = self
+                .load_reconciliation_port
+                .load_account_snapshot_balances(after_account_id.clone(), PAGE_SIZE)
+                .await;
+            checked_snapshot_count += page.len();
+            let page_size = page.len();
+            if let Some(last) = page.last() {
+                after_account_id = Some(last.account_id.clone());
+            }
+            for snapshot_balances in page {
+                if snapshot_balances.is_consistent() {
+                    continue;
+                }
+                if repair {
+                    self.repair_account_snapshot_port
+                        .discard_account_snapshot(snapshot_balances.account_id.clone())
+                        .await;
+                }
+                inconsistencies.push(Inconsistency::SnapshotMismatch {
+                    snapshot_balances,
+                    repaired: repair,
+                });
+            }
+            if page_size < PAGE_SIZE {
+                break;
+            }
+        }
+        checked_snapshot_count
+    }
+}
+
+#[async_trait]
+impl ReconcileUseCase for ReconcileUseCaseImpl {
+    async fn reconcile(&self, command: ReconcileCommand) -> ReconciliationReport {
+        let reconciled_at = Local::now().naive_local();
+        let mut inconsistencies = vec![];
+
+        let checked_activity_count = self.check_activities(&mut inconsistencies).await;
+
+        let checked_snapshot_count = self
+            .check_snapshots(command.repair, &mut inconsistencies)
+            .await;
+
+        ReconciliationReport {
+            reconciled_at,
+            repair: command.repair,
+            checked_activity_count,
+            checked_snapshot_count,
+            inconsistencies,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        inbound_ports::AccountSnapshotBalances,
+        outbound_ports::{MockLoadReconciliationPort, MockRepairAccountSnapshotPort},
+    };
+    use chrono::NaiveDate;
+    use domain::vo::money::Money;
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_reconcile_reports_and_repairs_inconsistencies() {
+        // Given a transfer from 1 to 2 and the withdrawal of a transfer from 1 to the unknown
+        // account 9, whose deposit is missing
+        let mut load_reconciliation_port = MockLoadReconciliationPort::new();
+        load_reconciliation_port
+            .expect_load_activities_after()
+            .times(1)
+            .with(eq(None), eq(PAGE_SIZE))
+            .returning(|_, _| {
+                vec![
+                    activity(11, 1, 1, 2, 500),
+                    activity(12, 2, 1, 2, 500),
+                    activity(13, 1, 1, 9, 70),
+                ]
+            });
+        load_reconciliation_port
+            .expect_load_existing_account_ids()
+            .times(1)
+            .withf(|account_ids: &Vec<AccountId>| account_ids.len() == 3)
+            .returning(|_| vec![AccountId(1), AccountId(2)]);
+        // And the snapshot of account 2 misses a deposit
+        load_reconciliation_port
+            .expect_load_account_snapshot_balances()
+            .times(1)
+            .with(eq(None), eq(PAGE_SIZE))
+            .returning(|_, _| {
+                vec![
+                    snapshot_balances(1, 570, 0, 570, 0),
+                    snapshot_balances(2, 0, 0, 0, 500),
+                ]
+            });
+        let mut repair_account_snapshot_port = MockRepairAccountSnapshotPort::new();
+        repair_account_snapshot_port
+            .expect_discard_account_snapshot()
+            .times(1)
+            .with(eq(AccountId(2)))
+            .return_const(());
+
+        let reconcile_use_case = ReconcileUseCaseImpl::new(
+            Arc::new(load_reconciliation_port),
+            Arc::new(repair_account_snapshot_port),
+            Rounding::HalfEven,
+        );
+
+        // When
+        let report = reconcile_use_case
+            .reconcile(ReconcileCommand::new(true))
+            .await;
+
+        // Then
+        assert_eq!(3, report.checked_activity_count);
+        assert_eq!(2, report.checked_snapshot_count);
+        assert_eq!(
+            vec![
+                Inconsistency::UnknownAccount {
+                    activity_id: ActivityId(13),
+                    account_id: AccountId(9),
+                },
+                Inconsistency::MissingCounterpart {
+                    activity_id: ActivityId(13),
+                    source_account_id: AccountId(1),
+                    target_account_id: AccountId(9),
+                    missing_withdrawal: false,
+                },
+                Inconsistency::SnapshotMismatch {
+                    snapshot_balances: snapshot_balances(2, 0, 0, 0, 500),
+                    repaired: true,
+                },
+            ],
+            report.inconsistencies
+        );
+    }
+
+    fn snapshot_balances(
+        account_id: i64,
+        withdrawal_balance: i128,
+        deposit_balance: i128,
+        replayed_withdrawal_balance: i128,
+        replayed_deposit_balance: i128,
+    ) -> AccountSnapshotBalances {
+        AccountSnapshotBalances {
+            account_id: AccountId(account_id),
+            withdrawal_balance: Money::of(withdrawal_balance),
+            deposit_balance: Money::of(deposit_balance),
+            replayed_withdrawal_balance: Money::of(replayed_withdrawal_balance),
+            replayed_deposit_balance: Money::of(replayed_deposit_balance),
+        }
+    }
+
+    fn activity(
+        id: i64,
+        owner_account_id: i64,
+        source_account_id: i64,
+        target_account_id: i64,
+        amount: i128,
+    ) -> Activity {
+        Activity::with_id(
+            Some(ActivityId(id)),
+            AccountId(owner_account_id),
+            AccountId(source_account_id),
+            AccountId(target_account_id),
+            NaiveDate::from_ymd_opt(2019, 8, 1)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap(),
+            Money::of(amount),
+            None,
+        )
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    inbound_ports::RelayDomainEventsUseCase,
+    outbound_ports::{DomainEventSink, LoadOutboxPort, UpdateOutboxStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct RelayDomainEventsUseCaseImpl {
+    load_outbox_port: Arc<dyn LoadOutboxPort>,
+    update_outbox_state_port: Arc<dyn UpdateOutboxStatePort>,
+    domain_event_sink: Arc<dyn DomainEventSink>,
+    outbox_relay_properties: OutboxRelayProperties,
+}
+
+impl RelayDomainEventsUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_outbox_port: Arc<dyn LoadOutboxPort>,
+        update_outbox_state_port: Arc<dyn UpdateOutboxStatePort>,
+        domain_event_sink: Arc<dyn DomainEventSink>,
+        outbox_relay_properties: OutboxRelayProperties,
+    ) -> Self {
+        Self {
+            load_outbox_port,
+            update_outbox_state_port,
+            domain_event_sink,
+            outbox_relay_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl RelayDomainEventsUseCase for RelayDomainEventsUseCaseImpl {
+    async fn relay_domain_events(&self, now: NaiveDateTime) -> usize {
+        let pending_messages = self
+            .load_outbox_port
+            .load_pending_outbox_messages(self.outbox_relay_properties.batch_size)
+            .await;
+
+        let mut delivered = 0;
+        for mut outbox_message in pending_messages {
+            // later messages wait for a message in backoff to keep the order of the events
+            if !outbox_message.is_due(now) {
+                break;
+            }
+
+            match self
+                .domain_event_sink
+                .deliver(&outbox_message.domain_event)
+                .await
+            {
+                Ok(_) => {
+                    outbox_message.mark_processed(now);
+                    self.update_outbox_state_port
+                        .save_outbox_message(outbox_message)
+                        .await;
+                    delivered += 1;
+                }
+                Err(error) => {
+                    let backoff = self
+                        .outbox_relay_properties
+                        .backoff_after(outbox_message.attempts + 1);
+                    outbox_message.schedule_retry(now, backoff, error);
+                    self.update_outbox_state_port
+                        .save_outbox_message(outbox_message)
+                        .await;
+                    break;
+                }
+            }
+        }
+        delivered
+    }
+}
+
+// #[singleton]
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct OutboxRelayProperties {
+    batch_size: usize,
+    initial_backoff: Duration,
+    maximum_backoff: Duration,
+}
+
+impl OutboxRelayProperties {
+    // Functions
+
+    /// # Arguments
+    ///
+    /// * `batch_size` - The maximum number of events delivered per run.
+    /// * `initial_backoff` - The delay after the first failed delivery, doubled after each further failure.
+    /// * `maximum_backoff` - The longest delay between two deliveries of the same event.
+    pub fn new(batch_size: usize, initial_backoff: Duration, maximum_backoff: Duration) -> Self {
+        Self {
+            batch_size,
+            initial_backoff,
+            maximum_backoff,
+        }
+    }
+
+    // Methods
+
+    pub(crate) fn backoff_after(&self, attempts: u32) -> Duration {
+        let factor = 2_i32.saturating_pow(attempts.saturating_sub(1).min(30));
+        (self.initial_backoff * factor).min(self.maximum_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockDomainEventSink, MockLoadOutboxPort, MockUpdateOutboxStatePort,
+    };
+    use chrono::NaiveDate;
+    use domain::{
+        ar::{
+            account::AccountId,
+            domain_event::DomainEvent,
+            outbox_message::{OutboxMessage, OutboxMessageId},
+        },
+        vo::money::Money,
+    };
+    use mockall::predicate::{eq, function};
+
+    #[async_std::test]
+    async fn test_events_are_delivered_in_order_until_a_delivery_fails() {
+        // Given three pending messages
+        let mut load_outbox_port = MockLoadOutboxPort::new();
+        load_outbox_port
+            .expect_load_pending_outbox_messages()
+            .with(eq(10))
+            .returning(|_| {
+                vec![
+                    outbox_message(1, Money::of(100)),
+                    outbox_message(2, Money::of(200)),
+                    outbox_message(3, Money::of(300)),
+                ]
+            });
+
+        // And the sink accepts the first event and rejects the second
+        let mut domain_event_sink = MockDomainEventSink::new();
+        domain_event_sink
+            .expect_deliver()
+            .times(1)
+            .with(eq(domain_event(Money::of(100))))
+            .returning(|_| Ok(()));
+        domain_event_sink
+            .expect_deliver()
+            .times(1)
+            .with(eq(domain_event(Money::of(200))))
+            .returning(|_| Err("503 Service Unavailable".to_string()));
+
+        // Then the first message is marked as processed
+        let mut update_outbox_state_port = MockUpdateOutboxStatePort::new();
+        update_outbox_state_port
+            .expect_save_outbox_message()
+            .times(1)
+            .with(function(|m: &OutboxMessage| {
+                m.id == Some(OutboxMessageId(1)) && m.processed_at == Some(now())
+            }))
+            .return_const(());
+        // And the second message is retried after the initial backoff
+        update_outbox_state_port
+            .expect_save_outbox_message()
+            .times(1)
+            .with(function(|m: &OutboxMessage| {
+                m.id == Some(OutboxMessageId(2))
+                    && m.processed_at.is_none()
+                    && m.attempts == 1
+                    && m.next_attempt_at == now() + Duration::seconds(1)
+                    && m.last_error == Some("503 Service Unavailable".to_string())
+            }))
+            .return_const(());
+
+        // When
+        let relay_domain_events_use_case = RelayDomainEventsUseCaseImpl::new(
+            Arc::new(load_outbox_port),
+            Arc::new(update_outbox_state_port),
+            Arc::new(domain_event_sink),
+            OutboxRelayProperties::new(10, Duration::seconds(1), Duration::minutes(5)),
+        );
+        let delivered = relay_domain_events_use_case
+            .relay_domain_events(now())
+            .await;
+
+        // Then the third message waits for the second one
+        assert_eq!(1, delivered);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_maximum() {
+        let properties = OutboxRelayProperties::new(10, Duration::seconds(1), Duration::minutes(1));
+        assert_eq!(Duration::seconds(1), properties.backoff_after(1));
+        assert_eq!(Duration::seconds(2), properties.backoff_after(2));
+        assert_eq!(Duration::seconds(32), properties.backoff_after(6));
+        assert_eq!(Duration::minutes(1), properties.backoff_after(7));
+        assert_eq!(Duration::minutes(1), properties.backoff_after(u32::MAX));
+    }
+
+    fn outbox_message(id: i64, money: Money) -> OutboxMessage {
+        OutboxMessage::with_id(
+            Some(OutboxMessageId(id)),
+            domain_event(money),
+            0,
+            now(),
+            None,
+            None,
+        )
+    }
+
+    fn domain_event(money: Money) -> DomainEvent {
+        DomainEvent::TransferCompleted {
+            source_account_id: AccountId(41),
+            target_account_id: AccountId(42),
+            money,
+            fee: Money::of(0),
+            timestamp: now(),
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{ScheduleTransferCommand, ScheduleTransferError, ScheduleTransferUseCase},
+    outbound_ports::{LoadScheduledTransferPort, UpdateScheduledTransferStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{
+    account::AccountId,
+    scheduled_transfer::{ScheduledTransfer, ScheduledTransferId, ScheduledTransferStatus},
+};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct ScheduleTransferUseCaseImpl {
+    load_scheduled_transfer_port: Arc<dyn LoadScheduledTransferPort>,
+    update_scheduled_transfer_state_port: Arc<dyn UpdateScheduledTransferStatePort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl ScheduleTransferUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_scheduled_transfer_port: Arc<dyn LoadScheduledTransferPort>,
+        update_scheduled_transfer_state_port: Arc<dyn UpdateScheduledTransferStatePort>,
+    ) -> Self {
+        Self {
+            load_scheduled_transfer_port,
+            update_scheduled_transfer_state_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl ScheduleTransferUseCase for ScheduleTransferUseCaseImpl {
+    async fn schedule_transfer(
+        &self,
+        command: ScheduleTransferCommand,
+    ) -> Result<ScheduledTransferId, ScheduleTransferError> {
+        let now = Local::now().naive_local();
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::DebitAccount,
+                &command.source_account_id,
+            )
+            .await?;
+        if !command.money.is_positive() {
+            return Err(ScheduleTransferError::NonPositiveAmount);
+        }
+        if command.execution_date <= now {
+            return Err(ScheduleTransferError::ExecutionDateNotInFuture);
+        }
+
+        let scheduled_transfer = ScheduledTransfer::new(
+            command.source_account_id,
+            command.target_account_id,
+            command.money,
+            command.execution_date,
+            command.principal.subject,
+            command.principal.role,
+        );
+        Ok(self
+            .update_scheduled_transfer_state_port
+            .save_scheduled_transfer(scheduled_transfer)
+            .await)
+    }
+
+    async fn list_scheduled_transfers(&self, account_id: AccountId) -> Vec<ScheduledTransfer> {
+        self.load_scheduled_transfer_port
+            .load_scheduled_transfers_of_account(account_id)
+            .await
+    }
+
+    async fn cancel_scheduled_transfer(&self, id: ScheduledTransferId) -> bool {
+        let Some(mut scheduled_transfer) = self
+            .load_scheduled_transfer_port
+            .load_scheduled_transfer(id)
+            .await
+        else {
+            return false;
+        };
+        if !scheduled_transfer.cancel() {
+            return false;
+        }
+        self.update_scheduled_transfer_state_port
+            .update_scheduled_transfer_state(scheduled_transfer, ScheduledTransferStatus::Pending)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbound_ports::Principal;
+    use crate::outbound_ports::{
+        MockLoadAccountMandatePort, MockLoadScheduledTransferPort,
+        MockRecordAuthorizationDenialPort, MockUpdateScheduledTransferStatePort,
+    };
+    use chrono::Days;
+    use domain::vo::{money::Money, role::Role};
+    use mockall::predicate::{eq, function};
+    use std::ops::Add;
+
+    #[async_std::test]
+    async fn test_schedule_transfer_succeeds() {
+        let execution_date = Local::now().naive_local().add(Days::new(3));
+
+        // Given the scheduled transfer will be saved as pending, on behalf of its creator
+        let mut update_port = MockUpdateScheduledTransferStatePort::new();
+        update_port
+            .expect_save_scheduled_transfer()
+            .times(1)
+            .with(function(|st: &ScheduledTransfer| {
+                st.id.is_none()
+                    && st.status == ScheduledTransferStatus::Pending
+                    && st.created_by == "system"
+                    && st.created_by_role == Role::Admin
+            }))
+            .return_const(ScheduledTransferId(7));
+
+        // When a transfer is scheduled
+        let use_case = ScheduleTransferUseCaseImpl::new(
+            Arc::new(MockLoadScheduledTransferPort::new()),
+            Arc::new(update_port),
+        );
+        let result = use_case
+            .schedule_transfer(ScheduleTransferCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                execution_date,
+                Principal::system(),
+            ))
+            .await;
+
+        // Then the id of the saved scheduled transfer is returned
+        assert_eq!(Ok(ScheduledTransferId(7)), result);
+    }
+
+    #[async_std::test]
+    async fn test_schedule_transfer_in_the_past_is_rejected() {
+        let execution_date = Local::now().naive_local() - Days::new(1);
+
+        let use_case = ScheduleTransferUseCaseImpl::new(
+            Arc::new(MockLoadScheduledTransferPort::new()),
+            Arc::new(MockUpdateScheduledTransferStatePort::new()),
+        );
+        let result = use_case
+            .schedule_transfer(ScheduleTransferCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                execution_date,
+                Principal::system(),
+            ))
+            .await;
+
+        assert_eq!(Err(ScheduleTransferError::ExecutionDateNotInFuture), result);
+    }
+
+    #[async_std::test]
+    async fn test_schedule_transfer_without_mandate_is_rejected_and_audited() {
+        // Given a source account without any mandate
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .with(eq(AccountId(41)))
+            .return_const(vec![]);
+
+        // Then the attempt is audited
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // When a customer schedules a transfer from it
+        let use_case = ScheduleTransferUseCaseImpl::new(
+            Arc::new(MockLoadScheduledTransferPort::new()),
+            Arc::new(MockUpdateScheduledTransferStatePort::new()),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )));
+        let result = use_case
+            .schedule_transfer(ScheduleTransferCommand::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                Local::now().naive_local().add(Days::new(3)),
+                Principal::new("mallory".to_string(), Role::Customer),
+            ))
+            .await;
+
+        // Then nothing is scheduled
+        assert_eq!(Err(ScheduleTransferError::Unauthorized), result);
+    }
+
+    #[async_std::test]
+    async fn test_cancel_pending_transfer() {
+        // Given a pending scheduled transfer
+        let mut load_port = MockLoadScheduledTransferPort::new();
+        load_port
+            .expect_load_scheduled_transfer()
+            .with(eq(ScheduledTransferId(7)))
+            .returning(|id| Some(pending_transfer(id)));
+
+        // And the cancellation is saved unless an executor claimed the transfer meanwhile
+        let mut update_port = MockUpdateScheduledTransferStatePort::new();
+        update_port
+            .expect_update_scheduled_transfer_state()
+            .times(1)
+            .with(
+                function(|st: &ScheduledTransfer| st.status == ScheduledTransferStatus::Cancelled),
+                eq(ScheduledTransferStatus::Pending),
+            )
+            .return_const(true);
+
+        let use_case = ScheduleTransferUseCaseImpl::new(Arc::new(load_port), Arc::new(update_port));
+        assert!(
+            use_case
+                .cancel_scheduled_transfer(ScheduledTransferId(7))
+                .await
+        );
+    }
+
+    #[async_std::test]
+    async fn test_cancel_unknown_transfer_fails() {
+        let mut load_port = MockLoadScheduledTransferPort::new();
+        load_port
+            .expect_load_scheduled_transfer()
+            .returning(|_| None);
+
+        let use_case = ScheduleTransferUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(MockUpdateScheduledTransferStatePort::new()),
+        );
+        assert!(
+            !use_case
+                .cancel_scheduled_transfer(ScheduledTransferId(7))
+                .await
+        );
+    }
+
+    fn pending_transfer(id: ScheduledTransferId) -> ScheduledTransfer {
+        let mut transfer = ScheduledTransfer::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Local::now().naive_local().add(Days::new(3)),
+            "alice".to_string(),
+            Role::Customer,
+        );
+        transfer.id = Some(id);
+        transfer
+    }
+}
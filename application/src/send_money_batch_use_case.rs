@@ -0,0 +1,980 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{
+        BatchMode, Principal, SendMoneyBatchCommand, SendMoneyBatchUseCase, SendMoneyCommand,
+        SendMoneyError, TransferReceipt,
+    },
+    no_op_metrics::NoOpMetrics,
+    outbound_ports::{
+        AccountLock, DomainEventPublisher, LoadAccountPort, LoadTransferLimitsPort, MetricsPort,
+        UpdateAccountStatePort,
+    },
+    send_money_use_case::MoneyTransferProperties,
+    transfer_limits_use_case::load_transfer_limits_and_usage,
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::{
+    ar::{account::AccountId, domain_event::DomainEvent},
+    vo::{
+        money::Money,
+        transfer_limits::{TransferLimits, TransferUsage},
+    },
+};
+use mockall_double::double;
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+#[double]
+use domain::ar::account::Account;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct SendMoneyBatchUseCaseImpl {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    account_lock: Box<dyn AccountLock>,
+    update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+    load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+    domain_event_publisher: Arc<dyn DomainEventPublisher>,
+    money_transfer_properties: MoneyTransferProperties,
+    metrics_port: Arc<dyn MetricsPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl SendMoneyBatchUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_account_port: Arc<dyn LoadAccountPort>,
+        account_lock: Box<dyn AccountLock>,
+        update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+        load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+        domain_event_publisher: Arc<dyn DomainEventPublisher>,
+        money_transfer_properties: MoneyTransferProperties,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            load_transfer_limits_port,
+            domain_event_publisher,
+            money_transfer_properties,
+            metrics_port: Arc::new(NoOpMetrics {}),
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_metrics(mut self, metrics_port: Arc<dyn MetricsPort>) -> Self {
+        self.metrics_port = metrics_port;
+        self
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+
+    /**
+     * Asks the policy once per principal and source account, so that a denial is audited only
+     * once no matter how many transfers of the batch it rejects.
+     *
+     * @return whether the principal may debit the source account, per transfer
+     */
+    async fn authorize(&self, commands: &[SendMoneyCommand]) -> Vec<bool> {
+        let mut decisions: HashMap<(&Principal, &AccountId), bool> = HashMap::new();
+        let mut authorized = Vec::with_capacity(commands.len());
+        for command in commands {
+            let key = (&command.principal, &command.source_account_id);
+            let decision = match decisions.get(&key) {
+                Some(decision) => *decision,
+                None => {
+                    let decision = self
+                        .authorization_policy
+                        .authorize(
+                            &command.principal,
+                            Action::DebitAccount,
+                            &command.source_account_id,
+                        )
+                        .await
+                        .is_ok();
+                    decisions.insert(key, decision);
+                    decision
+                }
+            };
+            authorized.push(decision);
+        }
+        authorized
+    }
+
+    /**
+     * Checks the threshold of every transfer and whether each source account
+     * may withdraw the total of all its transfers including their fees.
+     *
+     * @return the per transfer result of the checks
+     */
+    fn check_batch(
+        &self,
+        commands: &[SendMoneyCommand],
+        accounts: &HashMap<AccountId, Account>,
+    ) -> Vec<Result<(), SendMoneyError>> {
+        let mut totals: HashMap<AccountId, Money> = HashMap::new();
+        for command in commands {
+            let total = totals
+                .entry(command.source_account_id.clone())
+                .or_insert(Money::of(0));
+            *total = total
+                .plus(&command.money)
+                .plus(&self.money_transfer_properties.fee_for(command));
+        }
+
+        commands
+            .iter()
+            .map(|command| {
+                if self
+                    .money_transfer_properties
+                    .exceeds_threshold(&command.money)
+                {
+                    return Err(SendMoneyError::ThresholdExceeded);
+                }
+                check_currencies(command, accounts)?;
+                let total = &totals[&command.source_account_id];
+                if !accounts[&command.source_account_id].may_withdraw(total) {
+                    return Err(SendMoneyError::InsufficientFunds);
+                }
+                Ok(())
+            })
+            .collect()
+    }
+
+    /**
+     * Books a single transfer of the batch and its fee, counting it towards the velocity
+     * limits of the source account if it succeeds.
+     */
+    fn transfer(
+        &self,
+        command: &SendMoneyCommand,
+        accounts: &mut HashMap<AccountId, Account>,
+        usages: &mut HashMap<AccountId, (TransferLimits, TransferUsage)>,
+    ) -> Result<TransferReceipt, SendMoneyError> {
+        if self
+            .money_transfer_properties
+            .exceeds_threshold(&command.money)
+        {
+            return Err(SendMoneyError::ThresholdExceeded);
+        }
+        check_currencies(command, accounts)?;
+        let (transfer_limits, usage) = usages.get_mut(&command.source_account_id).unwrap();
+        transfer_limits
+            .check(usage, &command.money)
+            .map_err(SendMoneyError::LimitExceeded)?;
+
+        let fee = self.money_transfer_properties.fee_for(command);
+        let fee_collection_account_id = self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .filter(|_| fee.is_positive());
+        let source_account = accounts.get_mut(&command.source_account_id).unwrap();
+        if fee_collection_account_id.is_some()
+            && !source_account.may_withdraw(&command.money.plus(&fee))
+        {
+            return Err(SendMoneyError::InsufficientFunds);
+        }
+        if !source_account.withdraw(command.money.clone(), command.target_account_id.clone()) {
+            return Err(SendMoneyError::InsufficientFunds);
+        }
+        if let Some(fee_collection_account_id) = fee_collection_account_id {
+            source_account.withdraw(fee.clone(), fee_collection_account_id.clone());
+        }
+
+        let target_account = accounts.get_mut(&command.target_account_id).unwrap();
+        if !target_account.deposit(command.money.clone(), command.source_account_id.clone()) {
+            return Err(SendMoneyError::DepositRejected);
+        }
+        if let Some(fee_collection_account_id) = fee_collection_account_id {
+            let fee_collection_account = accounts.get_mut(fee_collection_account_id).unwrap();
+            fee_collection_account.deposit(fee.clone(), command.source_account_id.clone());
+        }
+        usage.record(&command.money);
+        Ok(TransferReceipt::new(
+            command.source_account_id.clone(),
+            command.target_account_id.clone(),
+            command.money.clone(),
+            fee,
+        ))
+    }
+}
+
+unsafe impl Send for SendMoneyBatchUseCaseImpl {}
+unsafe impl Sync for SendMoneyBatchUseCaseImpl {}
+
+#[async_trait]
+impl SendMoneyBatchUseCase for SendMoneyBatchUseCaseImpl {
+    async fn send_money_batch(
+        &self,
+        command: SendMoneyBatchCommand,
+    ) -> Vec<Result<TransferReceipt, SendMoneyError>> {
+        let now = Local::now().naive_local();
+        let baseline_date = self.money_transfer_properties.baseline_date(now);
+
+        // before anything else, so that nothing of a foreign account is disclosed, and an
+        // all-or-nothing batch with a transfer which may not be sent touches no account at all
+        let authorized = self.authorize(&command.commands).await;
+        let denied = authorized.contains(&false);
+        let authorized_commands: Vec<&SendMoneyCommand> =
+            if denied && command.mode == BatchMode::AllOrNothing {
+                vec![]
+            } else {
+                command
+                    .commands
+                    .iter()
+                    .zip(&authorized)
+                    .filter(|(_, authorized)| **authorized)
+                    .map(|(c, _)| c)
+                    .collect()
+            };
+
+        // every account is loaded and locked only once, no matter how often it takes part
+        let mut account_ids: Vec<AccountId> = authorized_commands
+            .iter()
+            .flat_map(|c| [c.source_account_id.clone(), c.target_account_id.clone()])
+            .collect();
+        if let Some(fee_collection_account_id) = self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .filter(|_| {
+                authorized_commands
+                    .iter()
+                    .any(|c| self.money_transfer_properties.fee_for(c).is_positive())
+            })
+        {
+            account_ids.push(fee_collection_account_id.clone());
+        }
+        account_ids.sort_by_key(|account_id| account_id.0);
+        account_ids.dedup();
+
+        let mut accounts = HashMap::new();
+        for account_id in &account_ids {
+            let account = self
+                .load_account_port
+                .load_account(account_id.clone(), baseline_date)
+                .await;
+            accounts.insert(account_id.clone(), account);
+        }
+        let mut usages = HashMap::new();
+        for command in &authorized_commands {
+            let source_account_id = &command.source_account_id;
+            if !usages.contains_key(source_account_id) {
+                let limits_and_usage = load_transfer_limits_and_usage(
+                    self.load_transfer_limits_port.as_ref(),
+                    &self.money_transfer_properties,
+                    source_account_id,
+                    now,
+                )
+                .await;
+                usages.insert(source_account_id.clone(), limits_and_usage);
+            }
+        }
+        for account_id in &account_ids {
+            let started_at = Instant::now();
+            self.account_lock.lock_account(account_id.clone());
+            self.metrics_port.record_lock_wait(started_at.elapsed());
+        }
+
+        let results = match command.mode {
+            BatchMode::AllOrNothing if denied => abort(
+                authorized
+                    .iter()
+                    .map(|authorized| {
+                        if *authorized {
+                            Ok(())
+                        } else {
+                            Err(SendMoneyError::Unauthorized)
+                        }
+                    })
+                    .collect(),
+            ),
+            BatchMode::AllOrNothing => {
+                let checks = self.check_batch(&command.commands, &accounts);
+                // nothing of an aborted batch is persisted
+                if checks.iter().any(Result::is_err) {
+                    accounts.clear();
+                    abort(checks)
+                } else {
+                    let results: Vec<_> = command
+                        .commands
+                        .iter()
+                        .map(|c| self.transfer(c, &mut accounts, &mut usages))
+                        .collect();
+                    if results.iter().any(Result::is_err) {
+                        accounts.clear();
+                        abort(results)
+                    } else {
+                        results
+                    }
+                }
+            }
+            BatchMode::BestEffort => command
+                .commands
+                .iter()
+                .zip(&authorized)
+                .map(|(c, authorized)| {
+                    if *authorized {
+                        self.transfer(c, &mut accounts, &mut usages)
+                    } else {
+                        Err(SendMoneyError::Unauthorized)
+                    }
+                })
+                .collect(),
+        };
+
+        let mut touched_accounts: Vec<_> = account_ids
+            .iter()
+            .filter_map(|account_id| accounts.remove(account_id))
+            .collect();
+        if !touched_accounts.is_empty() {
+            // the same events as for a single transfer, recorded within the unit of work
+            let mut domain_events = Vec::new();
+            for account in touched_accounts.iter_mut() {
+                domain_events.append(&mut account.take_domain_events());
+            }
+            domain_events.extend(results.iter().flatten().map(|receipt| {
+                DomainEvent::TransferCompleted {
+                    source_account_id: receipt.source_account_id.clone(),
+                    target_account_id: receipt.target_account_id.clone(),
+                    money: receipt.money.clone(),
+                    fee: receipt.fee.clone(),
+                    timestamp: now,
+                }
+            }));
+            self.update_account_state_port
+                .update_accounts_and_record_events(touched_accounts, domain_events)
+                .await;
+        }
+        for account_id in account_ids {
+            self.account_lock.release_account(account_id);
+        }
+        // like a rejected single transfer, as nothing has been booked for them there is no unit
+        // of work to record the rejections with
+        let rejections: Vec<DomainEvent> = command
+            .commands
+            .iter()
+            .zip(&results)
+            .filter_map(|(c, result)| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|error| DomainEvent::TransferRejected {
+                        source_account_id: c.source_account_id.clone(),
+                        target_account_id: c.target_account_id.clone(),
+                        money: c.money.clone(),
+                        reason: error.to_string(),
+                        timestamp: now,
+                    })
+            })
+            .collect();
+        if !rejections.is_empty() {
+            self.domain_event_publisher.publish(rejections).await;
+        }
+        for result in &results {
+            self.metrics_port.record_transfer_attempted();
+            match result {
+                Ok(receipt) => self.metrics_port.record_transfer_succeeded(&receipt.money),
+                Err(error) => self.metrics_port.record_transfer_rejected(error),
+            }
+        }
+        results
+    }
+}
+
+/**
+ * Money can only be sent between accounts held in the same currency.
+ */
+fn check_currencies(
+    command: &SendMoneyCommand,
+    accounts: &HashMap<AccountId, Account>,
+) -> Result<(), SendMoneyError> {
+    if accounts[&command.source_account_id].get_currency()
+        != accounts[&command.target_account_id].get_currency()
+    {
+        return Err(SendMoneyError::CurrencyMismatch);
+    }
+    Ok(())
+}
+
+/**
+ * Reports every transfer of an aborted batch which did not fail itself as aborted.
+ */
+fn abort<T>(
+    results: Vec<Result<T, SendMoneyError>>,
+) -> Vec<Result<TransferReceipt, SendMoneyError>> {
+    results
+        .into_iter()
+        .map(|result| result.and(Err(SendMoneyError::BatchAborted)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fee_policy::{FeePolicy, TransferFees};
+    use crate::inbound_ports::Principal;
+    use crate::outbound_ports::{
+        MockAccountLock, MockDomainEventPublisher, MockLoadAccountMandatePort, MockLoadAccountPort,
+        MockLoadTransferLimitsPort, MockMetricsPort, MockRecordAuthorizationDenialPort,
+        MockUpdateAccountStatePort,
+    };
+    use chrono::NaiveDate;
+    use domain::{
+        ar::account_mandate::{AccountMandate, MandateKind},
+        vo::{
+            currency::Currency,
+            role::Role,
+            transfer_limits::{TransferLimit, TransferVolume},
+        },
+    };
+    use mockall::predicate::{always, eq};
+
+    #[async_std::test]
+    async fn test_all_or_nothing_batch_succeeds() {
+        // Given a source account which may withdraw the total of the batch
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .times(1)
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account();
+                account
+                    .expect_may_withdraw()
+                    .with(eq(Money::of(800)))
+                    .return_const(true);
+                account.expect_withdraw().times(2).return_const(true);
+                account
+            });
+        // And two target accounts
+        for target_account_id in [42, 43] {
+            load_account_port
+                .expect_load_account()
+                .times(1)
+                .with(eq(AccountId(target_account_id)), always())
+                .returning(|_, _| {
+                    let mut account = account();
+                    account.expect_deposit().times(1).return_const(true);
+                    account
+                });
+        }
+
+        // Then every account is locked and released exactly once and all are updated together
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(3).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(3)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, domain_events| {
+                accounts.len() == 3
+                    && matches!(
+                        domain_events.as_slice(),
+                        [
+                            DomainEvent::TransferCompleted { .. },
+                            DomainEvent::TransferCompleted { .. }
+                        ]
+                    )
+            })
+            .return_const(());
+
+        // When
+        let results = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            TransferLimits::unlimited(),
+            domain_event_publisher(),
+        )
+        .send_money_batch(batch(BatchMode::AllOrNothing))
+        .await;
+
+        // Then
+        assert_eq!(vec![Ok(receipt(42, 500)), Ok(receipt(43, 300))], results);
+    }
+
+    #[async_std::test]
+    async fn test_all_or_nothing_batch_exceeding_the_balance_is_aborted() {
+        // Given a source account which may not withdraw the total of the batch
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account();
+                account.expect_may_withdraw().return_const(false);
+                account.expect_withdraw().never();
+                account
+            });
+        load_account_port
+            .expect_load_account()
+            .returning(|_, _| account());
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(3).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(3)
+            .return_const(());
+        // Then no account is updated
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .never();
+
+        // When
+        let results = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            TransferLimits::unlimited(),
+            domain_event_publisher(),
+        )
+        .send_money_batch(batch(BatchMode::AllOrNothing))
+        .await;
+
+        // Then
+        assert_eq!(
+            vec![
+                Err(SendMoneyError::InsufficientFunds),
+                Err(SendMoneyError::InsufficientFunds)
+            ],
+            results
+        );
+    }
+
+    #[async_std::test]
+    async fn test_best_effort_batch_reports_each_transfer() {
+        // Given a source account whose second withdrawal fails
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account();
+                account
+                    .expect_withdraw()
+                    .with(eq(Money::of(500)), always())
+                    .return_const(true);
+                account
+                    .expect_withdraw()
+                    .with(eq(Money::of(300)), always())
+                    .return_const(false);
+                account
+            });
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = account();
+            account.expect_deposit().return_const(true);
+            account
+        });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(3).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(3)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .return_const(());
+
+        // And each transfer is counted like a single one
+        let mut metrics_port = MockMetricsPort::new();
+        metrics_port
+            .expect_record_lock_wait()
+            .times(3)
+            .return_const(());
+        metrics_port
+            .expect_record_transfer_attempted()
+            .times(2)
+            .return_const(());
+        metrics_port
+            .expect_record_transfer_succeeded()
+            .times(1)
+            .with(eq(Money::of(500)))
+            .return_const(());
+        metrics_port
+            .expect_record_transfer_rejected()
+            .times(1)
+            .with(eq(SendMoneyError::InsufficientFunds))
+            .return_const(());
+
+        // And the rejected transfer is published like a single one
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher
+            .expect_publish()
+            .times(1)
+            .withf(|domain_events| {
+                matches!(
+                    domain_events.as_slice(),
+                    [DomainEvent::TransferRejected {
+                        target_account_id: AccountId(43),
+                        money,
+                        reason,
+                        ..
+                    }] if *money == Money::of(300)
+                        && *reason == SendMoneyError::InsufficientFunds.to_string()
+                )
+            })
+            .return_const(());
+
+        // When
+        let results = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            TransferLimits::unlimited(),
+            domain_event_publisher,
+        )
+        .with_metrics(Arc::new(metrics_port))
+        .send_money_batch(batch(BatchMode::BestEffort))
+        .await;
+
+        // Then
+        assert_eq!(
+            vec![Ok(receipt(42, 500)), Err(SendMoneyError::InsufficientFunds)],
+            results
+        );
+    }
+
+    #[async_std::test]
+    async fn test_transfers_of_a_batch_count_towards_the_limits() {
+        // Given accounts which allow every transfer
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = account();
+            account.expect_withdraw().return_const(true);
+            account.expect_deposit().return_const(true);
+            account
+        });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().return_const(());
+        account_lock.expect_release_account().return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .return_const(());
+
+        // When a source account with a daily count limit of one sends two transfers
+        let results = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            TransferLimits::new(None, None, Some(1)),
+            domain_event_publisher(),
+        )
+        .send_money_batch(batch(BatchMode::BestEffort))
+        .await;
+
+        // Then the second transfer exceeds the limit
+        assert_eq!(
+            vec![
+                Ok(receipt(42, 500)),
+                Err(SendMoneyError::LimitExceeded(TransferLimit::DailyCount))
+            ],
+            results
+        );
+    }
+
+    #[async_std::test]
+    async fn test_fees_of_a_batch_are_collected() {
+        // Given a source account which may withdraw the transfers plus their fees
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|_, _| {
+                let mut account = account();
+                // once per transfer for the batch total, then for each transfer itself
+                for (total, times) in [(810, 2), (505, 1), (305, 1)] {
+                    account
+                        .expect_may_withdraw()
+                        .times(times)
+                        .with(eq(Money::of(total)))
+                        .return_const(true);
+                }
+                account
+                    .expect_withdraw()
+                    .times(2)
+                    .with(eq(Money::of(5)), eq(AccountId(1)))
+                    .return_const(true);
+                for target_account_id in [42, 43] {
+                    account
+                        .expect_withdraw()
+                        .times(1)
+                        .with(always(), eq(AccountId(target_account_id)))
+                        .return_const(true);
+                }
+                account
+            });
+        // And a fee collection account which receives both fees
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(1)), always())
+            .returning(|_, _| {
+                let mut account = account();
+                account
+                    .expect_deposit()
+                    .times(2)
+                    .with(eq(Money::of(5)), eq(AccountId(41)))
+                    .return_const(true);
+                account
+            });
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = account();
+            account.expect_deposit().times(1).return_const(true);
+            account
+        });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(4).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(4)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, _| accounts.len() == 4)
+            .return_const(());
+        let mut transfer_limits_port = MockLoadTransferLimitsPort::new();
+        transfer_limits_port
+            .expect_load_transfer_limits()
+            .return_const(TransferLimits::unlimited());
+
+        // When
+        let results = SendMoneyBatchUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(transfer_limits_port),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(
+                None,
+                None,
+                Some(TransferFees::new(
+                    FeePolicy::Flat(Money::of(5)),
+                    AccountId(1),
+                )),
+            ),
+        )
+        .send_money_batch(batch(BatchMode::AllOrNothing))
+        .await;
+
+        // Then every receipt shows its fee
+        assert_eq!(
+            vec![
+                Ok(TransferReceipt::new(
+                    AccountId(41),
+                    AccountId(42),
+                    Money::of(500),
+                    Money::of(5)
+                )),
+                Ok(TransferReceipt::new(
+                    AccountId(41),
+                    AccountId(43),
+                    Money::of(300),
+                    Money::of(5)
+                )),
+            ],
+            results
+        );
+    }
+
+    #[async_std::test]
+    async fn test_all_or_nothing_batch_debiting_a_foreign_account_loads_no_account() {
+        // Given alice holds a mandate on the account 41 only
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .returning(|account_id| match account_id {
+                AccountId(41) => vec![AccountMandate::new(
+                    account_id,
+                    "alice".to_string(),
+                    MandateKind::Owner,
+                    NaiveDate::from_ymd_opt(2026, 10, 18)
+                        .unwrap()
+                        .and_hms_opt(12, 0, 0)
+                        .unwrap(),
+                )],
+                _ => vec![],
+            });
+
+        // Then her attempt to debit the account 44 is audited once
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // When she sends a batch which debits both accounts
+        let alice = Principal::new("alice".to_string(), Role::Customer);
+        let results = use_case(
+            MockLoadAccountPort::new(),
+            Box::new(MockAccountLock::new()),
+            MockUpdateAccountStatePort::new(),
+            TransferLimits::unlimited(),
+            domain_event_publisher(),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )))
+        .send_money_batch(SendMoneyBatchCommand::new(
+            vec![
+                SendMoneyCommand::new(AccountId(41), AccountId(42), Money::of(500), alice.clone()),
+                SendMoneyCommand::new(AccountId(44), AccountId(42), Money::of(300), alice.clone()),
+                SendMoneyCommand::new(AccountId(44), AccountId(43), Money::of(100), alice),
+            ],
+            BatchMode::AllOrNothing,
+        ))
+        .await;
+
+        // Then the whole batch is aborted without loading any account
+        assert_eq!(
+            vec![
+                Err(SendMoneyError::BatchAborted),
+                Err(SendMoneyError::Unauthorized),
+                Err(SendMoneyError::Unauthorized)
+            ],
+            results
+        );
+    }
+
+    fn use_case(
+        load_account_port: MockLoadAccountPort,
+        account_lock: Box<MockAccountLock>,
+        update_account_state_port: MockUpdateAccountStatePort,
+        transfer_limits: TransferLimits,
+        domain_event_publisher: MockDomainEventPublisher,
+    ) -> SendMoneyBatchUseCaseImpl {
+        // nothing has been sent before the batch
+        let mut transfer_limits_port = MockLoadTransferLimitsPort::new();
+        transfer_limits_port
+            .expect_load_transfer_limits()
+            .return_const(transfer_limits);
+        transfer_limits_port
+            .expect_load_outgoing_transfer_volume()
+            .return_const(TransferVolume::empty());
+        SendMoneyBatchUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(transfer_limits_port),
+            Arc::new(domain_event_publisher),
+            MoneyTransferProperties::new(Some(Money::of(1_000)), None, None),
+        )
+    }
+
+    fn domain_event_publisher() -> MockDomainEventPublisher {
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher.expect_publish().return_const(());
+        domain_event_publisher
+    }
+
+    #[async_std::test]
+    async fn test_transfers_between_currencies_are_rejected() {
+        // Given a target account held in another currency
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(43)), always())
+            .returning(|_, _| {
+                let mut account = Account::new();
+                account
+                    .expect_get_currency()
+                    .return_const(Currency::of("USD"));
+                account.expect_take_domain_events().returning(Vec::new);
+                account.expect_deposit().never();
+                account
+            });
+        load_account_port.expect_load_account().returning(|_, _| {
+            let mut account = account();
+            account.expect_may_withdraw().return_const(true);
+            account.expect_withdraw().return_const(true);
+            account.expect_deposit().return_const(true);
+            account
+        });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().return_const(());
+        account_lock.expect_release_account().return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .return_const(());
+
+        // When
+        let results = use_case(
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            TransferLimits::unlimited(),
+            domain_event_publisher(),
+        )
+        .send_money_batch(batch(BatchMode::BestEffort))
+        .await;
+
+        // Then only the transfer within the currency is booked
+        assert_eq!(
+            vec![Ok(receipt(42, 500)), Err(SendMoneyError::CurrencyMismatch)],
+            results
+        );
+    }
+
+    /**
+     * An account held in the default currency.
+     */
+    fn account() -> Account {
+        let mut account = Account::new();
+        account
+            .expect_get_currency()
+            .return_const(Currency::default());
+        account.expect_take_domain_events().returning(Vec::new);
+        account
+    }
+
+    fn receipt(target_account_id: i64, amount: i128) -> TransferReceipt {
+        TransferReceipt::new(
+            AccountId(41),
+            AccountId(target_account_id),
+            Money::of(amount),
+            Money::of(0),
+        )
+    }
+
+    fn batch(mode: BatchMode) -> SendMoneyBatchCommand {
+        SendMoneyBatchCommand::new(
+            vec![
+                SendMoneyCommand::new(
+                    AccountId(41),
+                    AccountId(42),
+                    Money::of(500),
+                    Principal::system(),
+                ),
+                SendMoneyCommand::new(
+                    AccountId(41),
+                    AccountId(43),
+                    Money::of(300),
+                    Principal::system(),
+                ),
+            ],
+            mode,
+        )
+    }
+}
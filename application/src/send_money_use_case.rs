@@ -1,12 +1,27 @@
 use crate::{
-    inbound_ports::{SendMoneyCommand, SendMoneyUseCase},
-    outbound_ports::{AccountLock, LoadAccountPort, UpdateAccountStatePort},
+    authorization_policy::{Action, AuthorizationPolicy},
+    fee_policy::TransferFees,
+    inbound_ports::{SendMoneyCommand, SendMoneyError, SendMoneyUseCase, TransferReceipt},
+    no_op_metrics::NoOpMetrics,
+    outbound_ports::{
+        AccountLock, DomainEventPublisher, LoadAccountPort, LoadTransferLimitsPort, MetricsPort,
+        UpdateAccountStatePort,
+    },
+    transfer_limits_use_case::load_transfer_limits_and_usage,
 };
 
 use async_trait::async_trait;
-use chrono::{Days, Local};
-use domain::vo::money::Money;
-use std::{ops::Sub, sync::Arc};
+use chrono::{Days, Local, NaiveDateTime};
+use domain::{
+    ar::{account::AccountId, domain_event::DomainEvent},
+    vo::{money::Money, transfer_limits::TransferLimits},
+};
+use mockall_double::double;
+use std::{ops::Sub, sync::Arc, time::Instant};
+use tracing::{info, instrument};
+
+#[double]
+use domain::ar::account::Account;
 
 // #[singleton]
 #[derive(Debug)]
@@ -14,7 +29,11 @@ pub struct SendMoneyUseCaseImpl {
     load_account_port: Arc<dyn LoadAccountPort>,
     account_lock: Box<dyn AccountLock>,
     update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+    load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+    domain_event_publisher: Arc<dyn DomainEventPublisher>,
     money_transfer_properties: MoneyTransferProperties,
+    metrics_port: Arc<dyn MetricsPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
 }
 
 impl SendMoneyUseCaseImpl {
@@ -23,44 +42,120 @@ impl SendMoneyUseCaseImpl {
         load_account_port: Arc<dyn LoadAccountPort>,
         account_lock: Box<dyn AccountLock>,
         update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+        load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+        domain_event_publisher: Arc<dyn DomainEventPublisher>,
         money_transfer_properties: MoneyTransferProperties,
     ) -> Self {
         Self {
             load_account_port,
             account_lock,
             update_account_state_port,
+            load_transfer_limits_port,
+            domain_event_publisher,
             money_transfer_properties,
+            metrics_port: Arc::new(NoOpMetrics {}),
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
         }
     }
 
-    fn check_threshold(&self, command: &SendMoneyCommand) {
-        if command
-            .money
-            .is_greater_than(&self.money_transfer_properties.maximum_transfer_threshold)
+    pub fn with_metrics(mut self, metrics_port: Arc<dyn MetricsPort>) -> Self {
+        self.metrics_port = metrics_port;
+        self
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+
+    fn lock_account(&self, account_id: AccountId) {
+        let started_at = Instant::now();
+        self.account_lock.lock_account(account_id);
+        self.metrics_port.record_lock_wait(started_at.elapsed());
+    }
+
+    fn check_threshold(&self, command: &SendMoneyCommand) -> Result<(), SendMoneyError> {
+        if self
+            .money_transfer_properties
+            .exceeds_threshold(&command.money)
         {
-            panic!("Threshold Exceeded Exception");
+            return Err(SendMoneyError::ThresholdExceeded);
         }
+        Ok(())
     }
-}
 
-unsafe impl Send for SendMoneyUseCaseImpl {}
-unsafe impl Sync for SendMoneyUseCaseImpl {}
+    async fn check_transfer_limits(
+        &self,
+        command: &SendMoneyCommand,
+        now: NaiveDateTime,
+    ) -> Result<(), SendMoneyError> {
+        let (transfer_limits, usage) = load_transfer_limits_and_usage(
+            self.load_transfer_limits_port.as_ref(),
+            &self.money_transfer_properties,
+            &command.source_account_id,
+            now,
+        )
+        .await;
+        transfer_limits
+            .check(&usage, &command.money)
+            .map_err(SendMoneyError::LimitExceeded)
+    }
 
-#[async_trait]
-impl SendMoneyUseCase for SendMoneyUseCaseImpl {
-    async fn send_money(&self, command: SendMoneyCommand) -> bool {
-        self.check_threshold(&command);
+    /**
+     * Withdraws the money to the target account and the fee to the fee collection account.
+     * @return false if the source account may not withdraw the money plus the fee.
+     */
+    fn withdraw(
+        &self,
+        source_account: &mut Account,
+        command: &SendMoneyCommand,
+        fee: &Money,
+    ) -> bool {
+        if !fee.is_positive() {
+            return source_account
+                .withdraw(command.money.clone(), command.target_account_id.clone());
+        }
+        let fee_collection_account_id = self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .unwrap();
+        source_account.may_withdraw(&command.money.plus(fee))
+            && source_account.withdraw(command.money.clone(), command.target_account_id.clone())
+            && source_account.withdraw(fee.clone(), fee_collection_account_id.clone())
+    }
+
+    /**
+     * Books the transfer together with the events recorded by the updated accounts.
+     */
+    async fn transfer(
+        &self,
+        command: &SendMoneyCommand,
+        now: NaiveDateTime,
+    ) -> Result<TransferReceipt, SendMoneyError> {
+        // before anything else, so that the limits of foreign accounts are not disclosed
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::DebitAccount,
+                &command.source_account_id,
+            )
+            .await?;
+        self.check_threshold(command)?;
+        self.check_transfer_limits(command, now).await?;
 
-        let baseline_date = Local::now().naive_local().sub(Days::new(10));
+        let baseline_date = self.money_transfer_properties.baseline_date(now);
 
         let mut source_account = self
             .load_account_port
-            .load_account(command.source_account_id, baseline_date)
+            .load_account(command.source_account_id.clone(), baseline_date)
             .await;
 
         let mut target_account = self
             .load_account_port
-            .load_account(command.target_account_id, baseline_date)
+            .load_account(command.target_account_id.clone(), baseline_date)
             .await;
 
         let source_account_id = source_account
@@ -69,63 +164,216 @@ impl SendMoneyUseCase for SendMoneyUseCaseImpl {
         let target_account_id = target_account
             .get_id()
             .unwrap_or_else(|| panic!("expected target account ID not to be empty"));
+        if source_account.get_currency() != target_account.get_currency() {
+            return Err(SendMoneyError::CurrencyMismatch);
+        }
+
+        // a transfer from or to the fee collection account itself is free, so that account is
+        // never loaded and locked a second time as the collector of the fee
+        let fee = self.money_transfer_properties.fee_for(command);
+        let fee_collection_account = match self
+            .money_transfer_properties
+            .fee_collection_account_id()
+            .filter(|_| fee.is_positive())
+        {
+            Some(fee_collection_account_id) => Some(
+                self.load_account_port
+                    .load_account(fee_collection_account_id.clone(), baseline_date)
+                    .await,
+            ),
+            None => None,
+        };
 
-        self.account_lock.lock_account(source_account_id.clone());
-        if !source_account.withdraw(command.money.clone(), target_account_id.clone()) {
+        self.lock_account(source_account_id.clone());
+        if !self.withdraw(&mut source_account, command, &fee) {
             self.account_lock.release_account(source_account_id);
-            return false;
+            return Err(SendMoneyError::InsufficientFunds);
         }
 
-        self.account_lock.lock_account(target_account_id.clone());
-        if !target_account.deposit(command.money, source_account_id.clone()) {
+        self.lock_account(target_account_id.clone());
+        if !target_account.deposit(command.money.clone(), source_account_id.clone()) {
             self.account_lock.release_account(source_account_id);
             self.account_lock.release_account(target_account_id);
-            return false;
+            return Err(SendMoneyError::DepositRejected);
         }
 
+        let mut accounts = vec![source_account, target_account];
+        let mut fee_collection_account_id = None;
+        if let Some(mut fee_collection_account) = fee_collection_account {
+            let account_id = fee_collection_account
+                .get_id()
+                .unwrap_or_else(|| panic!("expected fee collection account ID not to be empty"));
+            self.lock_account(account_id.clone());
+            fee_collection_account.deposit(fee.clone(), source_account_id.clone());
+            accounts.push(fee_collection_account);
+            fee_collection_account_id = Some(account_id);
+        }
+
+        let mut domain_events = Vec::new();
+        for account in accounts.iter_mut() {
+            domain_events.append(&mut account.take_domain_events());
+        }
+        domain_events.push(DomainEvent::TransferCompleted {
+            source_account_id: source_account_id.clone(),
+            target_account_id: target_account_id.clone(),
+            money: command.money.clone(),
+            fee: fee.clone(),
+            timestamp: now,
+        });
         self.update_account_state_port
-            .update_activities(source_account)
-            .await;
-        self.update_account_state_port
-            .update_activities(target_account)
+            .update_accounts_and_record_events(accounts, domain_events)
             .await;
 
         self.account_lock.release_account(source_account_id);
         self.account_lock.release_account(target_account_id);
-        true
+        if let Some(fee_collection_account_id) = fee_collection_account_id {
+            self.account_lock.release_account(fee_collection_account_id);
+        }
+        Ok(TransferReceipt::new(
+            command.source_account_id.clone(),
+            command.target_account_id.clone(),
+            command.money.clone(),
+            fee,
+        ))
+    }
+}
+
+unsafe impl Send for SendMoneyUseCaseImpl {}
+unsafe impl Sync for SendMoneyUseCaseImpl {}
+
+#[async_trait]
+impl SendMoneyUseCase for SendMoneyUseCaseImpl {
+    #[instrument(
+        skip_all,
+        fields(
+            source_account_id = command.source_account_id.0,
+            target_account_id = command.target_account_id.0,
+            amount = %command.money.amount,
+            principal = %command.principal.subject,
+        )
+    )]
+    async fn send_money(
+        &self,
+        command: SendMoneyCommand,
+    ) -> Result<TransferReceipt, SendMoneyError> {
+        let now = Local::now().naive_local();
+        self.metrics_port.record_transfer_attempted();
+        let result = self.transfer(&command, now).await;
+        match &result {
+            Ok(_) => {
+                self.metrics_port.record_transfer_succeeded(&command.money);
+                info!("transfer booked");
+            }
+            Err(error) => {
+                self.metrics_port.record_transfer_rejected(error);
+                info!(reason = %error, "transfer rejected");
+            }
+        }
+        if let Err(error) = &result {
+            // nothing has been booked, so there is no unit of work to record the rejection with
+            self.domain_event_publisher
+                .publish(vec![DomainEvent::TransferRejected {
+                    source_account_id: command.source_account_id,
+                    target_account_id: command.target_account_id,
+                    money: command.money,
+                    reason: error.to_string(),
+                    timestamp: now,
+                }])
+                .await;
+        }
+        result
     }
 }
 
 // #[singleton]
-#[derive(PartialEq, Hash, Debug)]
+#[derive(Clone, PartialEq, Hash, Debug)]
 pub struct MoneyTransferProperties {
     maximum_transfer_threshold: Money,
+    default_transfer_limits: TransferLimits,
+    transfer_fees: Option<TransferFees>,
+    baseline_window_days: u64,
 }
 
 impl MoneyTransferProperties {
     // Functions
 
-    pub fn new(maximum_transfer_threshold: Option<Money>) -> Self {
+    /// # Arguments
+    ///
+    /// * `maximum_transfer_threshold` - The maximum amount of a single transfer.
+    /// * `default_transfer_limits` - The velocity limits of accounts which do not override them, unlimited if not set.
+    /// * `transfer_fees` - The fee charged on transfers, transfers are free if not set.
+    pub fn new(
+        maximum_transfer_threshold: Option<Money>,
+        default_transfer_limits: Option<TransferLimits>,
+        transfer_fees: Option<TransferFees>,
+    ) -> Self {
         Self {
             maximum_transfer_threshold: maximum_transfer_threshold.unwrap_or(Money::of(1_000_000)),
+            default_transfer_limits: default_transfer_limits.unwrap_or_default(),
+            transfer_fees,
+            baseline_window_days: 10,
         }
     }
+
+    // Methods
+
+    /// # Arguments
+    ///
+    /// * `baseline_window_days` - How many days of activities are loaded with an account, 10 if not set.
+    pub fn with_baseline_window_days(mut self, baseline_window_days: u64) -> Self {
+        self.baseline_window_days = baseline_window_days;
+        self
+    }
+
+    pub(crate) fn baseline_date(&self, now: NaiveDateTime) -> NaiveDateTime {
+        now.sub(Days::new(self.baseline_window_days))
+    }
+
+    pub(crate) fn exceeds_threshold(&self, money: &Money) -> bool {
+        money.is_greater_than(&self.maximum_transfer_threshold)
+    }
+
+    pub(crate) fn default_transfer_limits(&self) -> &TransferLimits {
+        &self.default_transfer_limits
+    }
+
+    pub(crate) fn fee_for(&self, command: &SendMoneyCommand) -> Money {
+        self.transfer_fees
+            .as_ref()
+            .map(|transfer_fees| {
+                transfer_fees.fee_for(
+                    &command.source_account_id,
+                    &command.target_account_id,
+                    &command.money,
+                )
+            })
+            .unwrap_or(Money::of(0))
+    }
+
+    pub(crate) fn fee_collection_account_id(&self) -> Option<&AccountId> {
+        self.transfer_fees
+            .as_ref()
+            .map(|transfer_fees| &transfer_fees.fee_collection_account_id)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::outbound_ports::{MockAccountLock, MockLoadAccountPort, MockUpdateAccountStatePort};
+    use crate::outbound_ports::{
+        MockAccountLock, MockDomainEventPublisher, MockLoadAccountMandatePort, MockLoadAccountPort,
+        MockLoadTransferLimitsPort, MockMetricsPort, MockRecordAuthorizationDenialPort,
+        MockUpdateAccountStatePort,
+    };
 
     use super::*;
-    use chrono::NaiveDateTime;
-    use domain::ar::account::AccountId;
-    use mockall::{
-        predicate::{always, eq},
+    use crate::fee_policy::FeePolicy;
+    use crate::inbound_ports::Principal;
+    use domain::vo::{
+        currency::Currency,
+        role::Role,
+        transfer_limits::{TransferLimit, TransferVolume},
     };
-    use mockall_double::double;
-
-    #[double]
-    use domain::ar::account::Account;
+    use mockall::predicate::{always, eq};
 
     // TODO Add with() parameter expectations
     #[async_std::test]
@@ -134,7 +382,7 @@ mod tests {
         // Given a source account
         let source_account_closure =
             |account_id: AccountId, _baseline_date: NaiveDateTime| -> Account {
-                let mut account = Account::new();
+                let mut account = account();
                 account
                     .expect_get_id()
                     .returning(move || Some(account_id.clone()));
@@ -153,7 +401,7 @@ mod tests {
         // And a target account
         let target_account_closure =
             |account_id: AccountId, _baseline_date: NaiveDateTime| -> Account {
-                let mut account = Account::new();
+                let mut account = account();
                 account
                     .expect_get_id()
                     .returning(move || Some(account_id.clone()));
@@ -183,24 +431,40 @@ mod tests {
             .return_const(());
 
         let mut update_account_state_port = MockUpdateAccountStatePort::new();
-        // And accounts have been updated
+        // And accounts have been updated together
         update_account_state_port
-            .expect_update_activities()
-            .times(2)
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, _| accounts.len() == 2)
             .return_const(());
 
         // When money is send
-        let command = SendMoneyCommand::new(AccountId(41), AccountId(42), Money::of(500));
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
         let send_money_use_case = SendMoneyUseCaseImpl::new(
             Arc::new(load_account_port),
             account_lock,
             Arc::new(update_account_state_port),
-            MoneyTransferProperties::new(Some(Money::of(i128::MAX))),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(Some(Money::of(i128::MAX)), None, None),
         );
-        let success = send_money_use_case.send_money(command).await;
+        let result = send_money_use_case.send_money(command).await;
 
-        // Then send money succeeds
-        assert!(success);
+        // Then send money succeeds without a fee
+        assert_eq!(
+            Ok(TransferReceipt::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                Money::of(0)
+            )),
+            result
+        );
     }
 
     #[async_std::test]
@@ -209,7 +473,7 @@ mod tests {
         // Given a source account
         let source_account_closure =
             |account_id: AccountId, _baseline_date: NaiveDateTime| -> Account {
-                let mut account = Account::new();
+                let mut account = account();
                 account
                     .expect_get_id()
                     .returning(move || Some(account_id.clone()));
@@ -228,7 +492,7 @@ mod tests {
         // And a target account
         let target_account_closure =
             |account_id: AccountId, _baseline_date: NaiveDateTime| -> Account {
-                let mut account = Account::new();
+                let mut account = account();
                 account
                     .expect_get_id()
                     .returning(move || Some(account_id.clone()));
@@ -257,16 +521,571 @@ mod tests {
             .return_const(());
 
         // When money is send
-        let command = SendMoneyCommand::new(AccountId(41), AccountId(42), Money::of(300));
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(300),
+            Principal::system(),
+        );
         let send_money_use_case = SendMoneyUseCaseImpl::new(
             Arc::new(load_account_port),
             account_lock,
             Arc::new(MockUpdateAccountStatePort::new()),
-            MoneyTransferProperties::new(Some(Money::of(i128::MAX))),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(Some(Money::of(i128::MAX)), None, None),
         );
-        let success = send_money_use_case.send_money(command).await;
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails
+        assert_eq!(Err(SendMoneyError::InsufficientFunds), result);
+    }
+
+    #[async_std::test]
+    async fn test_given_threshold_is_exceeded_then_no_account_is_loaded() {
+        // Given the rejection is published with its reason
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher
+            .expect_publish()
+            .times(1)
+            .withf(|domain_events| {
+                matches!(
+                    domain_events.as_slice(),
+                    [DomainEvent::TransferRejected { reason, .. }]
+                        if reason == "maximum transfer threshold exceeded"
+                )
+            })
+            .return_const(());
+
+        // When more money than the threshold allows is send
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(1_001),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Box::new(MockAccountLock::new()),
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(MockLoadTransferLimitsPort::new()),
+            Arc::new(domain_event_publisher),
+            MoneyTransferProperties::new(Some(Money::of(1_000)), None, None),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails
+        assert_eq!(Err(SendMoneyError::ThresholdExceeded), result);
+    }
+
+    #[async_std::test]
+    async fn test_given_customer_without_mandate_then_no_account_is_loaded() {
+        // Given a source account without any mandate
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .with(eq(AccountId(41)))
+            .return_const(vec![]);
+
+        // Then the attempt is audited
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // When a customer sends money from it
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::new("mallory".to_string(), Role::Customer),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Box::new(MockAccountLock::new()),
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(MockLoadTransferLimitsPort::new()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(None, None, None),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )));
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails
+        assert_eq!(Err(SendMoneyError::Unauthorized), result);
+    }
+
+    #[async_std::test]
+    async fn test_attempts_and_rejections_are_counted() {
+        // Given the attempt and its rejection are recorded
+        let mut metrics_port = MockMetricsPort::new();
+        metrics_port
+            .expect_record_transfer_attempted()
+            .times(1)
+            .return_const(());
+        metrics_port
+            .expect_record_transfer_rejected()
+            .times(1)
+            .with(eq(SendMoneyError::ThresholdExceeded))
+            .return_const(());
+        metrics_port.expect_record_transfer_succeeded().never();
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher.expect_publish().return_const(());
+
+        // When more money than the threshold allows is send
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(1_001),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Box::new(MockAccountLock::new()),
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(MockLoadTransferLimitsPort::new()),
+            Arc::new(domain_event_publisher),
+            MoneyTransferProperties::new(Some(Money::of(1_000)), None, None),
+        )
+        .with_metrics(Arc::new(metrics_port));
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then
+        assert_eq!(Err(SendMoneyError::ThresholdExceeded), result);
+    }
+
+    #[async_std::test]
+    async fn test_account_events_are_recorded_together_with_the_activities() {
+        // Given a source account and a target account which record their activities
+        let timestamp = Local::now().naive_local();
+        let mut load_account_port = MockLoadAccountPort::new();
+        load_account_port
+            .expect_load_account()
+            .returning(move |account_id, _| {
+                let domain_event = match account_id.0 {
+                    41 => DomainEvent::MoneyWithdrawn {
+                        account_id: AccountId(41),
+                        target_account_id: AccountId(42),
+                        money: Money::of(500),
+                        timestamp,
+                    },
+                    _ => DomainEvent::MoneyDeposited {
+                        account_id: AccountId(42),
+                        source_account_id: AccountId(41),
+                        money: Money::of(500),
+                        timestamp,
+                    },
+                };
+                let mut account = Account::new();
+                account
+                    .expect_get_id()
+                    .returning(move || Some(account_id.clone()));
+                account
+                    .expect_get_currency()
+                    .return_const(Currency::default());
+                account.expect_withdraw().return_const(true);
+                account.expect_deposit().return_const(true);
+                account
+                    .expect_take_domain_events()
+                    .times(1)
+                    .return_const(vec![domain_event]);
+                account
+            });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().return_const(());
+        account_lock.expect_release_account().return_const(());
+
+        // And the events of both accounts are recorded followed by the completed transfer
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, domain_events| {
+                accounts.len() == 2
+                    && matches!(
+                        domain_events.as_slice(),
+                        [
+                            DomainEvent::MoneyWithdrawn { .. },
+                            DomainEvent::MoneyDeposited { .. },
+                            DomainEvent::TransferCompleted { money, .. },
+                        ] if *money == Money::of(500)
+                    )
+            })
+            .return_const(());
+
+        // And nothing is published before it has been recorded
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher.expect_publish().never();
+
+        // When money is send
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher),
+            MoneyTransferProperties::new(None, None, None),
+        );
+        let result = send_money_use_case.send_money(command).await;
 
         // Then send money succeeds
-        assert!(!success);
+        assert!(result.is_ok());
+    }
+
+    #[async_std::test]
+    async fn test_given_daily_limit_is_exceeded_then_no_account_is_loaded() {
+        // Given a source account which already sent 800 today
+        let mut load_transfer_limits_port = MockLoadTransferLimitsPort::new();
+        load_transfer_limits_port
+            .expect_load_transfer_limits()
+            .with(eq(AccountId(41)))
+            .return_const(TransferLimits::new(Some(Money::of(1_000)), None, None));
+        load_transfer_limits_port
+            .expect_load_outgoing_transfer_volume()
+            .return_const(TransferVolume::new(Money::of(800), 2));
+
+        // When another 300 are send
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(300),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(MockLoadAccountPort::new()),
+            Box::new(MockAccountLock::new()),
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(load_transfer_limits_port),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(None, None, None),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails naming the daily amount limit
+        assert_eq!(
+            Err(SendMoneyError::LimitExceeded(TransferLimit::DailyAmount)),
+            result
+        );
+    }
+
+    #[async_std::test]
+    async fn test_fee_is_withdrawn_to_fee_collection_account() {
+        let mut load_account_port = MockLoadAccountPort::new();
+        // Given a source account which may withdraw the money plus the fee
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|account_id, _| {
+                let mut account = account();
+                account
+                    .expect_get_id()
+                    .returning(move || Some(account_id.clone()));
+                account
+                    .expect_may_withdraw()
+                    .with(eq(Money::of(505)))
+                    .return_const(true);
+                account
+                    .expect_withdraw()
+                    .times(1)
+                    .with(eq(Money::of(500)), eq(AccountId(42)))
+                    .return_const(true);
+                account
+                    .expect_withdraw()
+                    .times(1)
+                    .with(eq(Money::of(5)), eq(AccountId(1)))
+                    .return_const(true);
+                account
+            });
+        // And a target account and a fee collection account
+        for (account_id, money) in [(42, 500), (1, 5)] {
+            load_account_port
+                .expect_load_account()
+                .with(eq(AccountId(account_id)), always())
+                .returning(move |account_id, _| {
+                    let mut account = account();
+                    account
+                        .expect_get_id()
+                        .returning(move || Some(account_id.clone()));
+                    account
+                        .expect_deposit()
+                        .times(1)
+                        .with(eq(Money::of(money)), eq(AccountId(41)))
+                        .return_const(true);
+                    account
+                });
+        }
+
+        // And all three accounts are locked and released
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(3).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(3)
+            .return_const(());
+
+        // And all three accounts are updated together
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, _| accounts.len() == 3)
+            .return_const(());
+
+        // When money is send with a flat fee
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(
+                None,
+                None,
+                Some(TransferFees::new(
+                    FeePolicy::Flat(Money::of(5)),
+                    AccountId(1),
+                )),
+            ),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then the receipt shows the fee
+        assert_eq!(
+            Ok(TransferReceipt::new(
+                AccountId(41),
+                AccountId(42),
+                Money::of(500),
+                Money::of(5)
+            )),
+            result
+        );
+    }
+
+    #[async_std::test]
+    async fn test_given_fee_collection_account_sends_money_then_it_is_loaded_once() {
+        // Given the fee collection account and a target account, each loaded exactly once
+        let mut load_account_port = MockLoadAccountPort::new();
+        for account_id in [1, 42] {
+            load_account_port
+                .expect_load_account()
+                .times(1)
+                .with(eq(AccountId(account_id)), always())
+                .returning(|account_id, _| {
+                    let mut account = account();
+                    account
+                        .expect_get_id()
+                        .returning(move || Some(account_id.clone()));
+                    account.expect_may_withdraw().never();
+                    account
+                        .expect_withdraw()
+                        .times(0..=1)
+                        .with(eq(Money::of(500)), eq(AccountId(42)))
+                        .return_const(true);
+                    account
+                        .expect_deposit()
+                        .times(0..=1)
+                        .with(eq(Money::of(500)), eq(AccountId(1)))
+                        .return_const(true);
+                    account
+                });
+        }
+
+        // And each of them is locked once and both are updated together
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(2).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(2)
+            .return_const(());
+        let mut update_account_state_port = MockUpdateAccountStatePort::new();
+        update_account_state_port
+            .expect_update_accounts_and_record_events()
+            .times(1)
+            .withf(|accounts, _| accounts.len() == 2)
+            .return_const(());
+
+        // When the fee collection account sends money
+        let command = SendMoneyCommand::new(
+            AccountId(1),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(update_account_state_port),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(
+                None,
+                None,
+                Some(TransferFees::new(
+                    FeePolicy::Flat(Money::of(5)),
+                    AccountId(1),
+                )),
+            ),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then no fee is charged
+        assert_eq!(
+            Ok(TransferReceipt::new(
+                AccountId(1),
+                AccountId(42),
+                Money::of(500),
+                Money::of(0)
+            )),
+            result
+        );
+    }
+
+    #[async_std::test]
+    async fn test_given_fee_exceeds_balance_then_nothing_is_withdrawn() {
+        let mut load_account_port = MockLoadAccountPort::new();
+        // Given a source account which may withdraw the money but not the fee
+        load_account_port
+            .expect_load_account()
+            .with(eq(AccountId(41)), always())
+            .returning(|account_id, _| {
+                let mut account = account();
+                account
+                    .expect_get_id()
+                    .returning(move || Some(account_id.clone()));
+                account
+                    .expect_may_withdraw()
+                    .with(eq(Money::of(505)))
+                    .return_const(false);
+                account.expect_withdraw().never();
+                account
+            });
+        load_account_port
+            .expect_load_account()
+            .returning(|account_id, _| {
+                let mut account = account();
+                account
+                    .expect_get_id()
+                    .returning(move || Some(account_id.clone()));
+                account
+            });
+
+        let mut account_lock = Box::new(MockAccountLock::new());
+        account_lock.expect_lock_account().times(1).return_const(());
+        account_lock
+            .expect_release_account()
+            .times(1)
+            .return_const(());
+
+        // When
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(load_account_port),
+            account_lock,
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(
+                None,
+                None,
+                Some(TransferFees::new(
+                    FeePolicy::Flat(Money::of(5)),
+                    AccountId(1),
+                )),
+            ),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails
+        assert_eq!(Err(SendMoneyError::InsufficientFunds), result);
+    }
+
+    #[async_std::test]
+    async fn test_given_accounts_in_different_currencies_then_no_account_is_locked() {
+        // Given a source account held in Euro and a target account held in US Dollar
+        let mut load_account_port = MockLoadAccountPort::new();
+        for (account_id, currency) in [(41, "EUR"), (42, "USD")] {
+            load_account_port
+                .expect_load_account()
+                .with(eq(AccountId(account_id)), always())
+                .returning(move |account_id, _| {
+                    let mut account = Account::new();
+                    account
+                        .expect_get_id()
+                        .returning(move || Some(account_id.clone()));
+                    account
+                        .expect_get_currency()
+                        .return_const(Currency::of(currency));
+                    account.expect_withdraw().never();
+                    account
+                });
+        }
+
+        // When
+        let command = SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(42),
+            Money::of(500),
+            Principal::system(),
+        );
+        let send_money_use_case = SendMoneyUseCaseImpl::new(
+            Arc::new(load_account_port),
+            Box::new(MockAccountLock::new()),
+            Arc::new(MockUpdateAccountStatePort::new()),
+            Arc::new(unlimited_transfer_limits_port()),
+            Arc::new(domain_event_publisher()),
+            MoneyTransferProperties::new(None, None, None),
+        );
+        let result = send_money_use_case.send_money(command).await;
+
+        // Then send money fails, the money has to be exchanged
+        assert_eq!(Err(SendMoneyError::CurrencyMismatch), result);
+    }
+
+    /**
+     * An account held in the default currency.
+     */
+    fn account() -> Account {
+        let mut account = Account::new();
+        account
+            .expect_get_currency()
+            .return_const(Currency::default());
+        account.expect_take_domain_events().returning(Vec::new);
+        account
+    }
+
+    fn domain_event_publisher() -> MockDomainEventPublisher {
+        let mut domain_event_publisher = MockDomainEventPublisher::new();
+        domain_event_publisher.expect_publish().return_const(());
+        domain_event_publisher
+    }
+
+    fn unlimited_transfer_limits_port() -> MockLoadTransferLimitsPort {
+        let mut load_transfer_limits_port = MockLoadTransferLimitsPort::new();
+        load_transfer_limits_port
+            .expect_load_transfer_limits()
+            .return_const(TransferLimits::unlimited());
+        load_transfer_limits_port
     }
 }
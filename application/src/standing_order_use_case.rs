@@ -0,0 +1,287 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy},
+    inbound_ports::{CreateStandingOrderCommand, StandingOrderError, StandingOrderUseCase},
+    outbound_ports::{LoadStandingOrderPort, UpdateStandingOrderStatePort},
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{
+    account::AccountId,
+    standing_order::{StandingOrder, StandingOrderExecution, StandingOrderId},
+};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct StandingOrderUseCaseImpl {
+    load_standing_order_port: Arc<dyn LoadStandingOrderPort>,
+    update_standing_order_state_port: Arc<dyn UpdateStandingOrderStatePort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl StandingOrderUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_standing_order_port: Arc<dyn LoadStandingOrderPort>,
+        update_standing_order_state_port: Arc<dyn UpdateStandingOrderStatePort>,
+    ) -> Self {
+        Self {
+            load_standing_order_port,
+            update_standing_order_state_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+
+    async fn change_standing_order(
+        &self,
+        id: StandingOrderId,
+        change: impl FnOnce(&mut StandingOrder) -> bool + Send,
+    ) -> bool {
+        let Some(mut standing_order) = self.load_standing_order_port.load_standing_order(id).await
+        else {
+            return false;
+        };
+        if !change(&mut standing_order) {
+            return false;
+        }
+        self.update_standing_order_state_port
+            .save_standing_order(standing_order)
+            .await;
+        true
+    }
+}
+
+#[async_trait]
+impl StandingOrderUseCase for StandingOrderUseCaseImpl {
+    async fn create_standing_order(
+        &self,
+        command: CreateStandingOrderCommand,
+    ) -> Result<StandingOrderId, StandingOrderError> {
+        let now = Local::now().naive_local();
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::DebitAccount,
+                &command.source_account_id,
+            )
+            .await?;
+        if !command.money.is_positive() {
+            return Err(StandingOrderError::NonPositiveAmount);
+        }
+        if command.start_date <= now {
+            return Err(StandingOrderError::StartDateNotInFuture);
+        }
+        if command.end_date.is_some_and(|end| end < command.start_date) {
+            return Err(StandingOrderError::EndDateBeforeStartDate);
+        }
+        if command.max_occurrences == Some(0) {
+            return Err(StandingOrderError::NoOccurrences);
+        }
+
+        let standing_order = StandingOrder::new(
+            command.source_account_id,
+            command.target_account_id,
+            command.money,
+            command.recurrence,
+            command.start_date,
+            command.end_date,
+            command.max_occurrences,
+            command.principal.subject,
+            command.principal.role,
+        );
+        Ok(self
+            .update_standing_order_state_port
+            .save_standing_order(standing_order)
+            .await)
+    }
+
+    async fn list_standing_orders(&self, account_id: AccountId) -> Vec<StandingOrder> {
+        self.load_standing_order_port
+            .load_standing_orders_of_account(account_id)
+            .await
+    }
+
+    async fn list_standing_order_executions(
+        &self,
+        id: StandingOrderId,
+    ) -> Vec<StandingOrderExecution> {
+        self.load_standing_order_port
+            .load_standing_order_executions(id)
+            .await
+    }
+
+    async fn pause_standing_order(&self, id: StandingOrderId) -> bool {
+        self.change_standing_order(id, |so| so.pause()).await
+    }
+
+    async fn resume_standing_order(&self, id: StandingOrderId) -> bool {
+        let now = Local::now().naive_local();
+        self.change_standing_order(id, |so| so.resume(now)).await
+    }
+
+    async fn cancel_standing_order(&self, id: StandingOrderId) -> bool {
+        self.change_standing_order(id, |so| so.cancel()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inbound_ports::Principal;
+    use crate::outbound_ports::{
+        MockLoadAccountMandatePort, MockLoadStandingOrderPort, MockRecordAuthorizationDenialPort,
+        MockUpdateStandingOrderStatePort,
+    };
+    use chrono::Days;
+    use domain::{
+        ar::standing_order::StandingOrderStatus,
+        vo::{money::Money, recurrence::Recurrence, role::Role},
+    };
+    use mockall::predicate::{eq, function};
+    use std::ops::Add;
+
+    #[async_std::test]
+    async fn test_create_standing_order_succeeds() {
+        // Given the standing order will be saved as active, on behalf of its creator
+        let mut update_port = MockUpdateStandingOrderStatePort::new();
+        update_port
+            .expect_save_standing_order()
+            .times(1)
+            .with(function(|so: &StandingOrder| {
+                so.id.is_none()
+                    && so.status == StandingOrderStatus::Active
+                    && so.next_execution_date == Some(so.start_date)
+                    && so.created_by == "alice"
+                    && so.created_by_role == Role::Customer
+            }))
+            .return_const(StandingOrderId(3));
+
+        // When a monthly standing order is created
+        let use_case = StandingOrderUseCaseImpl::new(
+            Arc::new(MockLoadStandingOrderPort::new()),
+            Arc::new(update_port),
+        );
+        let result = use_case.create_standing_order(command(Some(12))).await;
+
+        // Then
+        assert_eq!(Ok(StandingOrderId(3)), result);
+    }
+
+    #[async_std::test]
+    async fn test_create_standing_order_without_occurrences_is_rejected() {
+        let use_case = StandingOrderUseCaseImpl::new(
+            Arc::new(MockLoadStandingOrderPort::new()),
+            Arc::new(MockUpdateStandingOrderStatePort::new()),
+        );
+        let result = use_case.create_standing_order(command(Some(0))).await;
+        assert_eq!(Err(StandingOrderError::NoOccurrences), result);
+    }
+
+    #[async_std::test]
+    async fn test_create_standing_order_without_mandate_is_rejected_and_audited() {
+        // Given a source account without any mandate
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .with(eq(AccountId(41)))
+            .return_const(vec![]);
+
+        // Then the attempt is audited
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(1)
+            .return_const(());
+
+        // When a customer creates a standing order from it
+        let use_case = StandingOrderUseCaseImpl::new(
+            Arc::new(MockLoadStandingOrderPort::new()),
+            Arc::new(MockUpdateStandingOrderStatePort::new()),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )));
+        let result = use_case
+            .create_standing_order(CreateStandingOrderCommand {
+                principal: Principal::new("mallory".to_string(), Role::Customer),
+                ..command(Some(12))
+            })
+            .await;
+
+        // Then nothing is saved
+        assert_eq!(Err(StandingOrderError::Unauthorized), result);
+    }
+
+    #[async_std::test]
+    async fn test_pause_and_cancel() {
+        // Given an active standing order
+        let mut load_port = MockLoadStandingOrderPort::new();
+        load_port
+            .expect_load_standing_order()
+            .with(eq(StandingOrderId(3)))
+            .returning(|id| {
+                let c = command(None);
+                let mut so = StandingOrder::new(
+                    c.source_account_id,
+                    c.target_account_id,
+                    c.money,
+                    c.recurrence,
+                    c.start_date,
+                    c.end_date,
+                    c.max_occurrences,
+                    c.principal.subject,
+                    c.principal.role,
+                );
+                so.id = Some(id);
+                Some(so)
+            });
+
+        // Then the paused and the cancelled standing order are saved
+        let mut update_port = MockUpdateStandingOrderStatePort::new();
+        update_port
+            .expect_save_standing_order()
+            .times(1)
+            .with(function(|so: &StandingOrder| {
+                so.status == StandingOrderStatus::Paused
+            }))
+            .return_const(StandingOrderId(3));
+        update_port
+            .expect_save_standing_order()
+            .times(1)
+            .with(function(|so: &StandingOrder| {
+                so.status == StandingOrderStatus::Cancelled
+            }))
+            .return_const(StandingOrderId(3));
+
+        let use_case = StandingOrderUseCaseImpl::new(Arc::new(load_port), Arc::new(update_port));
+
+        // When
+        assert!(use_case.pause_standing_order(StandingOrderId(3)).await);
+        assert!(use_case.cancel_standing_order(StandingOrderId(3)).await);
+        // And an active standing order cannot be resumed
+        assert!(!use_case.resume_standing_order(StandingOrderId(3)).await);
+    }
+
+    fn command(max_occurrences: Option<u32>) -> CreateStandingOrderCommand {
+        CreateStandingOrderCommand {
+            source_account_id: AccountId(41),
+            target_account_id: AccountId(42),
+            money: Money::of(500),
+            recurrence: Recurrence::Monthly,
+            start_date: Local::now().naive_local().add(Days::new(1)),
+            end_date: None,
+            max_occurrences,
+            principal: Principal::new("alice".to_string(), Role::Customer),
+        }
+    }
+}
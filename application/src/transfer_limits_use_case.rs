@@ -0,0 +1,251 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy, Denied},
+    inbound_ports::{
+        ChangeTransferLimitsCommand, ChangeTransferLimitsError, Principal, TransferLimitsUseCase,
+    },
+    outbound_ports::{LoadTransferLimitsPort, UpdateTransferLimitsPort},
+    send_money_use_case::MoneyTransferProperties,
+};
+
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+use domain::{
+    ar::account::AccountId,
+    vo::transfer_limits::{TransferLimits, TransferUsage},
+};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct TransferLimitsUseCaseImpl {
+    load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+    update_transfer_limits_port: Arc<dyn UpdateTransferLimitsPort>,
+    money_transfer_properties: MoneyTransferProperties,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl TransferLimitsUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+        update_transfer_limits_port: Arc<dyn UpdateTransferLimitsPort>,
+        money_transfer_properties: MoneyTransferProperties,
+    ) -> Self {
+        Self {
+            load_transfer_limits_port,
+            update_transfer_limits_port,
+            money_transfer_properties,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+}
+
+#[async_trait]
+impl TransferLimitsUseCase for TransferLimitsUseCaseImpl {
+    async fn change_transfer_limits(
+        &self,
+        command: ChangeTransferLimitsCommand,
+    ) -> Result<(), ChangeTransferLimitsError> {
+        self.authorization_policy
+            .authorize(
+                &command.principal,
+                Action::ChangeAccountLimits,
+                &command.account_id,
+            )
+            .await?;
+        if command.transfer_limits.has_negative_limit() {
+            return Err(ChangeTransferLimitsError::NegativeLimit);
+        }
+        self.update_transfer_limits_port
+            .update_transfer_limits(command.account_id, command.transfer_limits)
+            .await;
+        Ok(())
+    }
+
+    async fn get_transfer_limits(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<TransferLimits, Denied> {
+        self.authorization_policy
+            .authorize(&principal, Action::ViewAccount, &account_id)
+            .await?;
+        let overrides = self
+            .load_transfer_limits_port
+            .load_transfer_limits(account_id)
+            .await;
+        Ok(self
+            .money_transfer_properties
+            .default_transfer_limits()
+            .overridden_by(&overrides))
+    }
+}
+
+/**
+ * Loads the transfer limits in effect for the account and, unless it is unlimited,
+ * its outgoing transfers of the current day and month.
+ */
+pub(crate) async fn load_transfer_limits_and_usage(
+    load_transfer_limits_port: &dyn LoadTransferLimitsPort,
+    money_transfer_properties: &MoneyTransferProperties,
+    account_id: &AccountId,
+    now: NaiveDateTime,
+) -> (TransferLimits, TransferUsage) {
+    let overrides = load_transfer_limits_port
+        .load_transfer_limits(account_id.clone())
+        .await;
+    let transfer_limits = money_transfer_properties
+        .default_transfer_limits()
+        .overridden_by(&overrides);
+    if transfer_limits.is_unlimited() {
+        return (transfer_limits, TransferUsage::empty());
+    }
+
+    let start_of_day = now.date().and_time(NaiveTime::MIN);
+    let start_of_month = now.date().with_day(1).unwrap().and_time(NaiveTime::MIN);
+    let fee_collection_account_id = money_transfer_properties.fee_collection_account_id();
+    let today = load_transfer_limits_port
+        .load_outgoing_transfer_volume(
+            account_id.clone(),
+            start_of_day,
+            fee_collection_account_id.cloned(),
+        )
+        .await;
+    let this_month = load_transfer_limits_port
+        .load_outgoing_transfer_volume(
+            account_id.clone(),
+            start_of_month,
+            fee_collection_account_id.cloned(),
+        )
+        .await;
+    (transfer_limits, TransferUsage::new(today, this_month))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{MockLoadTransferLimitsPort, MockUpdateTransferLimitsPort};
+    use chrono::NaiveDate;
+    use domain::vo::{money::Money, transfer_limits::TransferVolume};
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_account_limits_override_defaults() {
+        // Given an account with an overridden daily amount
+        let mut load_port = MockLoadTransferLimitsPort::new();
+        load_port
+            .expect_load_transfer_limits()
+            .with(eq(AccountId(41)))
+            .return_const(TransferLimits::new(Some(Money::of(5_000)), None, None));
+
+        // When
+        let use_case = TransferLimitsUseCaseImpl::new(
+            Arc::new(load_port),
+            Arc::new(MockUpdateTransferLimitsPort::new()),
+            MoneyTransferProperties::new(
+                None,
+                Some(TransferLimits::new(
+                    Some(Money::of(1_000)),
+                    Some(Money::of(10_000)),
+                    Some(10),
+                )),
+                None,
+            ),
+        );
+        let limits = use_case
+            .get_transfer_limits(AccountId(41), Principal::system())
+            .await;
+
+        // Then the other limits are the defaults
+        assert_eq!(
+            Ok(TransferLimits::new(
+                Some(Money::of(5_000)),
+                Some(Money::of(10_000)),
+                Some(10)
+            )),
+            limits
+        );
+    }
+
+    #[async_std::test]
+    async fn test_negative_limits_are_rejected() {
+        let mut update_port = MockUpdateTransferLimitsPort::new();
+        update_port.expect_update_transfer_limits().never();
+
+        let use_case = TransferLimitsUseCaseImpl::new(
+            Arc::new(MockLoadTransferLimitsPort::new()),
+            Arc::new(update_port),
+            MoneyTransferProperties::new(None, None, None),
+        );
+        let result = use_case
+            .change_transfer_limits(ChangeTransferLimitsCommand::new(
+                AccountId(41),
+                TransferLimits::new(None, Some(Money::of(-1)), None),
+                Principal::system(),
+            ))
+            .await;
+
+        assert_eq!(Err(ChangeTransferLimitsError::NegativeLimit), result);
+    }
+
+    #[async_std::test]
+    async fn test_usage_is_loaded_since_start_of_day_and_month() {
+        // Given
+        let now = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(14, 30, 0)
+            .unwrap();
+        let mut load_port = MockLoadTransferLimitsPort::new();
+        load_port
+            .expect_load_transfer_limits()
+            .return_const(TransferLimits::unlimited());
+        load_port
+            .expect_load_outgoing_transfer_volume()
+            .with(
+                eq(AccountId(41)),
+                eq(NaiveDate::from_ymd_opt(2019, 8, 9)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+                eq(None),
+            )
+            .return_const(TransferVolume::new(Money::of(100), 1));
+        load_port
+            .expect_load_outgoing_transfer_volume()
+            .with(
+                eq(AccountId(41)),
+                eq(NaiveDate::from_ymd_opt(2019, 8, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()),
+                eq(None),
+            )
+            .return_const(TransferVolume::new(Money::of(700), 5));
+
+        // When
+        let (limits, usage) = load_transfer_limits_and_usage(
+            &load_port,
+            &MoneyTransferProperties::new(
+                None,
+                Some(TransferLimits::new(None, None, Some(3))),
+                None,
+            ),
+            &AccountId(41),
+            now,
+        )
+        .await;
+
+        // Then
+        assert_eq!(Some(3), limits.daily_count);
+        assert_eq!(TransferVolume::new(Money::of(100), 1), usage.today);
+        assert_eq!(TransferVolume::new(Money::of(700), 5), usage.this_month);
+    }
+}
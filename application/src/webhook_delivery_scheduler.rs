@@ -0,0 +1,138 @@
+use crate::outbound_ports::{
+    DomainEventSink, LoadWebhookSubscriptionPort, UpdateWebhookDeliveryStatePort,
+};
+
+use async_trait::async_trait;
+use domain::ar::{domain_event::DomainEvent, webhook_subscription::WebhookDelivery};
+use std::sync::Arc;
+
+/**
+ * Schedules a webhook delivery for each subscription notified of a relayed event.
+ * The deliveries are posted by the [crate::inbound_ports::DeliverWebhooksUseCase].
+ */
+// #[singleton]
+#[derive(Debug)]
+pub struct WebhookDeliveryScheduler {
+    load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+    update_webhook_delivery_state_port: Arc<dyn UpdateWebhookDeliveryStatePort>,
+}
+
+impl WebhookDeliveryScheduler {
+    // #[inject]
+    pub fn new(
+        load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+        update_webhook_delivery_state_port: Arc<dyn UpdateWebhookDeliveryStatePort>,
+    ) -> Self {
+        Self {
+            load_webhook_subscription_port,
+            update_webhook_delivery_state_port,
+        }
+    }
+}
+
+#[async_trait]
+impl DomainEventSink for WebhookDeliveryScheduler {
+    async fn deliver(&self, domain_event: &DomainEvent) -> Result<(), String> {
+        let account_id = match domain_event {
+            DomainEvent::MoneyWithdrawn { account_id, .. }
+            | DomainEvent::MoneyDeposited { account_id, .. } => account_id.clone(),
+            DomainEvent::TransferCompleted { .. } | DomainEvent::TransferRejected { .. } => {
+                return Ok(());
+            }
+        };
+
+        let webhook_deliveries: Vec<_> = self
+            .load_webhook_subscription_port
+            .load_webhook_subscriptions_of_account(account_id)
+            .await
+            .into_iter()
+            .filter(|webhook_subscription| webhook_subscription.is_notified_of(domain_event))
+            .filter_map(|webhook_subscription| webhook_subscription.id)
+            .map(|subscription_id| WebhookDelivery::new(subscription_id, domain_event.clone()))
+            .collect();
+        if !webhook_deliveries.is_empty() {
+            self.update_webhook_delivery_state_port
+                .schedule_webhook_deliveries(webhook_deliveries)
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockLoadWebhookSubscriptionPort, MockUpdateWebhookDeliveryStatePort,
+    };
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::{
+        ar::{
+            account::AccountId,
+            webhook_subscription::{WebhookSubscription, WebhookSubscriptionId},
+        },
+        vo::money::Money,
+    };
+    use mockall::predicate::eq;
+
+    #[async_std::test]
+    async fn test_deposit_is_scheduled_for_each_subscription_of_the_account() {
+        // Given two subscriptions of the account
+        let mut load_webhook_subscription_port = MockLoadWebhookSubscriptionPort::new();
+        load_webhook_subscription_port
+            .expect_load_webhook_subscriptions_of_account()
+            .with(eq(AccountId(42)))
+            .returning(|account_id| {
+                [1, 2]
+                    .into_iter()
+                    .map(|id| {
+                        let mut webhook_subscription = WebhookSubscription::new(
+                            account_id.clone(),
+                            format!("http://127.0.0.1:9000/hook/{}", id),
+                            "secret".to_string(),
+                            timestamp(),
+                        );
+                        webhook_subscription.id = Some(WebhookSubscriptionId(id));
+                        webhook_subscription
+                    })
+                    .collect()
+            });
+
+        // Then a delivery is scheduled for each of them
+        let mut update_webhook_delivery_state_port = MockUpdateWebhookDeliveryStatePort::new();
+        update_webhook_delivery_state_port
+            .expect_schedule_webhook_deliveries()
+            .times(1)
+            .with(eq(vec![
+                WebhookDelivery::new(WebhookSubscriptionId(1), deposit()),
+                WebhookDelivery::new(WebhookSubscriptionId(2), deposit()),
+            ]))
+            .return_const(());
+
+        // When
+        let webhook_delivery_scheduler = WebhookDeliveryScheduler::new(
+            Arc::new(load_webhook_subscription_port),
+            Arc::new(update_webhook_delivery_state_port),
+        );
+        let scheduled = webhook_delivery_scheduler.deliver(&deposit()).await;
+
+        // Then
+        assert_eq!(Ok(()), scheduled);
+    }
+
+    fn deposit() -> DomainEvent {
+        DomainEvent::MoneyDeposited {
+            account_id: AccountId(42),
+            source_account_id: AccountId(41),
+            money: Money::of(500),
+            timestamp: timestamp(),
+        }
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{
+    authorization_policy::{Action, AuthorizationPolicy, Denied},
+    inbound_ports::{
+        Principal, SubscribeWebhookCommand, SubscribeWebhookError, WebhookSubscriptionUseCase,
+    },
+    outbound_ports::{
+        LoadWebhookDeliveryPort, LoadWebhookSubscriptionPort, UpdateWebhookSubscriptionStatePort,
+    },
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use domain::ar::{
+    account::AccountId,
+    webhook_subscription::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId},
+};
+use std::sync::Arc;
+
+// #[singleton]
+#[derive(Debug)]
+pub struct WebhookSubscriptionUseCaseImpl {
+    load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+    update_webhook_subscription_state_port: Arc<dyn UpdateWebhookSubscriptionStatePort>,
+    load_webhook_delivery_port: Arc<dyn LoadWebhookDeliveryPort>,
+    authorization_policy: Arc<AuthorizationPolicy>,
+}
+
+impl WebhookSubscriptionUseCaseImpl {
+    // #[inject]
+    pub fn new(
+        load_webhook_subscription_port: Arc<dyn LoadWebhookSubscriptionPort>,
+        update_webhook_subscription_state_port: Arc<dyn UpdateWebhookSubscriptionStatePort>,
+        load_webhook_delivery_port: Arc<dyn LoadWebhookDeliveryPort>,
+    ) -> Self {
+        Self {
+            load_webhook_subscription_port,
+            update_webhook_subscription_state_port,
+            load_webhook_delivery_port,
+            authorization_policy: Arc::new(AuthorizationPolicy::permit_all()),
+        }
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        authorization_policy: Arc<AuthorizationPolicy>,
+    ) -> Self {
+        self.authorization_policy = authorization_policy;
+        self
+    }
+
+    /**
+     * Authorizes the principal to view the account of the subscription.
+     * @return whether there is such a subscription.
+     */
+    async fn authorize(
+        &self,
+        id: &WebhookSubscriptionId,
+        principal: &Principal,
+    ) -> Result<bool, Denied> {
+        let Some(webhook_subscription) = self
+            .load_webhook_subscription_port
+            .load_webhook_subscription(id.clone())
+            .await
+        else {
+            return Ok(false);
+        };
+        self.authorization_policy
+            .authorize(
+                principal,
+                Action::ViewAccount,
+                &webhook_subscription.account_id,
+            )
+            .await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl WebhookSubscriptionUseCase for WebhookSubscriptionUseCaseImpl {
+    async fn subscribe_webhook(
+        &self,
+        command: SubscribeWebhookCommand,
+    ) -> Result<WebhookSubscriptionId, SubscribeWebhookError> {
+        self.authorization_policy
+            .authorize(&command.principal, Action::ViewAccount, &command.account_id)
+            .await?;
+        let is_http_url = ["http://", "https://"].iter().any(|scheme| {
+            command
+                .url
+                .strip_prefix(scheme)
+                .is_some_and(|rest| !rest.is_empty())
+        });
+        if !is_http_url {
+            return Err(SubscribeWebhookError::InvalidUrl);
+        }
+        if command.secret.trim().is_empty() {
+            return Err(SubscribeWebhookError::MissingSecret);
+        }
+
+        let webhook_subscription = WebhookSubscription::new(
+            command.account_id,
+            command.url,
+            command.secret,
+            Local::now().naive_local(),
+        );
+        Ok(self
+            .update_webhook_subscription_state_port
+            .save_webhook_subscription(webhook_subscription)
+            .await)
+    }
+
+    async fn list_webhook_subscriptions(
+        &self,
+        account_id: AccountId,
+        principal: Principal,
+    ) -> Result<Vec<WebhookSubscription>, Denied> {
+        self.authorization_policy
+            .authorize(&principal, Action::ViewAccount, &account_id)
+            .await?;
+        Ok(self
+            .load_webhook_subscription_port
+            .load_webhook_subscriptions_of_account(account_id)
+            .await)
+    }
+
+    async fn unsubscribe_webhook(
+        &self,
+        id: WebhookSubscriptionId,
+        principal: Principal,
+    ) -> Result<bool, Denied> {
+        if !self.authorize(&id, &principal).await? {
+            return Ok(false);
+        }
+        Ok(self
+            .update_webhook_subscription_state_port
+            .delete_webhook_subscription(id)
+            .await)
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        id: WebhookSubscriptionId,
+        principal: Principal,
+    ) -> Result<Vec<WebhookDelivery>, Denied> {
+        if !self.authorize(&id, &principal).await? {
+            return Ok(vec![]);
+        }
+        Ok(self
+            .load_webhook_delivery_port
+            .load_webhook_deliveries(id)
+            .await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_ports::{
+        MockLoadAccountMandatePort, MockLoadWebhookDeliveryPort, MockLoadWebhookSubscriptionPort,
+        MockRecordAuthorizationDenialPort, MockUpdateWebhookSubscriptionStatePort,
+    };
+    use domain::vo::role::Role;
+    use mockall::predicate::{eq, function};
+
+    #[async_std::test]
+    async fn test_subscribe_webhook() {
+        // Given
+        let mut update_webhook_subscription_state_port =
+            MockUpdateWebhookSubscriptionStatePort::new();
+        update_webhook_subscription_state_port
+            .expect_save_webhook_subscription()
+            .times(1)
+            .with(function(|s: &WebhookSubscription| {
+                s.id.is_none()
+                    && s.account_id == AccountId(42)
+                    && s.url == "https://partner.example/hook"
+                    && s.secret == "secret"
+            }))
+            .returning(|_| WebhookSubscriptionId(7));
+
+        let webhook_subscription_use_case = WebhookSubscriptionUseCaseImpl::new(
+            Arc::new(MockLoadWebhookSubscriptionPort::new()),
+            Arc::new(update_webhook_subscription_state_port),
+            Arc::new(MockLoadWebhookDeliveryPort::new()),
+        );
+
+        // When
+        let subscribed = webhook_subscription_use_case
+            .subscribe_webhook(SubscribeWebhookCommand::new(
+                AccountId(42),
+                "https://partner.example/hook".to_string(),
+                "secret".to_string(),
+                Principal::system(),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Ok(WebhookSubscriptionId(7)), subscribed);
+
+        // When the url is not an http url
+        let subscribed = webhook_subscription_use_case
+            .subscribe_webhook(SubscribeWebhookCommand::new(
+                AccountId(42),
+                "ftp://partner.example/hook".to_string(),
+                "secret".to_string(),
+                Principal::system(),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Err(SubscribeWebhookError::InvalidUrl), subscribed);
+
+        // When there is no secret to sign the payloads with
+        let subscribed = webhook_subscription_use_case
+            .subscribe_webhook(SubscribeWebhookCommand::new(
+                AccountId(42),
+                "http://127.0.0.1:9000".to_string(),
+                " ".to_string(),
+                Principal::system(),
+            ))
+            .await;
+
+        // Then
+        assert_eq!(Err(SubscribeWebhookError::MissingSecret), subscribed);
+    }
+
+    #[async_std::test]
+    async fn test_only_viewers_of_the_account_manage_its_webhooks() {
+        // Given a subscription to an account on which mallory holds no mandate
+        let mut load_webhook_subscription_port = MockLoadWebhookSubscriptionPort::new();
+        load_webhook_subscription_port
+            .expect_load_webhook_subscription()
+            .with(eq(WebhookSubscriptionId(7)))
+            .returning(|id| {
+                let mut webhook_subscription = WebhookSubscription::new(
+                    AccountId(42),
+                    "https://partner.example/hook".to_string(),
+                    "secret".to_string(),
+                    Local::now().naive_local(),
+                );
+                webhook_subscription.id = Some(id);
+                Some(webhook_subscription)
+            });
+        let mut load_account_mandate_port = MockLoadAccountMandatePort::new();
+        load_account_mandate_port
+            .expect_load_account_mandates()
+            .returning(|_| vec![]);
+        let mut record_authorization_denial_port = MockRecordAuthorizationDenialPort::new();
+        record_authorization_denial_port
+            .expect_record_authorization_denial()
+            .times(2)
+            .return_const(());
+
+        // Then neither the subscription is removed nor its deliveries loaded
+        let mut update_webhook_subscription_state_port =
+            MockUpdateWebhookSubscriptionStatePort::new();
+        update_webhook_subscription_state_port
+            .expect_delete_webhook_subscription()
+            .never();
+        let mut load_webhook_delivery_port = MockLoadWebhookDeliveryPort::new();
+        load_webhook_delivery_port
+            .expect_load_webhook_deliveries()
+            .never();
+
+        let webhook_subscription_use_case = WebhookSubscriptionUseCaseImpl::new(
+            Arc::new(load_webhook_subscription_port),
+            Arc::new(update_webhook_subscription_state_port),
+            Arc::new(load_webhook_delivery_port),
+        )
+        .with_authorization_policy(Arc::new(AuthorizationPolicy::new(
+            Arc::new(load_account_mandate_port),
+            Arc::new(record_authorization_denial_port),
+        )));
+        let mallory = Principal::new("mallory".to_string(), Role::Customer);
+
+        // When mallory lists the deliveries of the subscription and removes it
+        let webhook_deliveries = webhook_subscription_use_case
+            .list_webhook_deliveries(WebhookSubscriptionId(7), mallory.clone())
+            .await;
+        let unsubscribed = webhook_subscription_use_case
+            .unsubscribe_webhook(WebhookSubscriptionId(7), mallory)
+            .await;
+
+        // Then
+        assert_eq!(Err(Denied), webhook_deliveries);
+        assert_eq!(Err(Denied), unsubscribed);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{inbound_ports::WorkerStatus, outbound_ports::LoadWorkerStatusPort};
+use chrono::{Duration, NaiveDateTime};
+use std::sync::Mutex;
+
+/**
+ * Keeps track of the runs of the background workers of this process, which report to it.
+ */
+// #[singleton]
+#[derive(Default, Debug)]
+pub struct WorkerRegistry {
+    workers: Mutex<Vec<WorkerStatus>>,
+}
+
+impl WorkerRegistry {
+    // Functions
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Methods
+
+    pub fn register(&self, name: &str, interval: Duration, now: NaiveDateTime) {
+        self.workers.lock().unwrap().push(WorkerStatus {
+            name: name.to_string(),
+            interval,
+            registered_at: now,
+            last_run_started_at: None,
+            last_run_finished_at: None,
+            stopped: false,
+        });
+    }
+
+    pub fn record_run_started(&self, name: &str, now: NaiveDateTime) {
+        self.update(name, |worker| worker.last_run_started_at = Some(now));
+    }
+
+    pub fn record_run_finished(&self, name: &str, now: NaiveDateTime) {
+        self.update(name, |worker| worker.last_run_finished_at = Some(now));
+    }
+
+    pub fn record_stopped(&self, name: &str) {
+        self.update(name, |worker| worker.stopped = true);
+    }
+
+    fn update(&self, name: &str, update: impl FnOnce(&mut WorkerStatus)) {
+        if let Some(worker) = self
+            .workers
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|worker| worker.name == name)
+        {
+            update(worker);
+        }
+    }
+}
+
+impl LoadWorkerStatusPort for WorkerRegistry {
+    fn load_worker_statuses(&self) -> Vec<WorkerStatus> {
+        self.workers.lock().unwrap().clone()
+    }
+}
@@ -1,24 +1,33 @@
-use super::activity::Activity;
-use crate::vo::{activity_window::ActivityWindow, money::Money};
-use chrono::Local;
+use super::{activity::Activity, domain_event::DomainEvent};
+use crate::vo::{
+    activity_window::ActivityWindow, currency::Currency, exchange_rate::ExchangeRate, money::Money,
+};
+use chrono::{Local, NaiveDateTime};
 
 #[cfg(feature = "mockall")]
 use mockall::automock;
 
-#[derive(Clone, PartialEq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct AccountId(pub i64);
 
 /**
  * An account that holds a certain amount of money. An [Account] object only
  * contains a window of the latest account activities. The total balance of the account is
  * the sum of a baseline balance that was valid before the first activity in the
- * window and the sum of the activity values.
+ * window and the sum of the activity values. The balance may drop below zero
+ * by at most the agreed overdraft limit. All money of an account is held in its currency.
+ * Withdrawals and deposits are recorded as [DomainEvent]s until they are taken.
+ * A closed account neither takes withdrawals nor deposits.
  */
 #[derive(Debug)]
 pub struct Account {
     id: Option<AccountId>,
+    currency: Currency,
     baseline_balance: Money,
+    overdraft_limit: Money,
     pub activity_window: ActivityWindow,
+    domain_events: Vec<DomainEvent>,
+    closed: bool,
 }
 
 // Associated Functions
@@ -26,45 +35,120 @@ impl Account {
     /// # Arguments
     ///
     /// * `id` - The unique ID of the account.
+    /// * `currency` - The currency the account is held in.
     /// * `baseline_balance` - The baseline balance of the account. This was the balance of the account before the first activity in the activityWindow.
+    /// * `overdraft_limit` - The amount by which the balance may drop below zero.
     /// * `activity_window` - The window of latest activities on this account.
     fn new(
         id: Option<AccountId>,
+        currency: Currency,
         baseline_balance: Money,
+        overdraft_limit: Money,
         activity_window: ActivityWindow,
     ) -> Account {
         Self {
             id,
+            currency,
             baseline_balance,
+            overdraft_limit,
             activity_window,
+            domain_events: Vec::new(),
+            closed: false,
         }
     }
 
-    pub fn without_id(baseline_balance: Money, activity_window: ActivityWindow) -> Account {
-        Account::new(None, baseline_balance, activity_window)
+    pub fn without_id(
+        currency: Currency,
+        baseline_balance: Money,
+        overdraft_limit: Money,
+        activity_window: ActivityWindow,
+    ) -> Account {
+        Account::new(
+            None,
+            currency,
+            baseline_balance,
+            overdraft_limit,
+            activity_window,
+        )
     }
 
     pub fn with_id(
         account_id: AccountId,
+        currency: Currency,
         baseline_balance: Money,
+        overdraft_limit: Money,
         activity_window: ActivityWindow,
     ) -> Account {
-        Account::new(Some(account_id), baseline_balance, activity_window)
+        Account::new(
+            Some(account_id),
+            currency,
+            baseline_balance,
+            overdraft_limit,
+            activity_window,
+        )
+    }
+
+    /**
+     * Restores an account which has been closed.
+     */
+    pub fn closed(mut self) -> Account {
+        self.closed = true;
+        self
     }
 }
 
 // Methods
 #[cfg_attr(feature = "mockall", automock)]
 impl Account {
-    #[allow(unused)]
-    fn may_withdraw(&self, money: &Money) -> bool {
-        Money::add(&self.calculate_balance(), &money.negate()).is_positive_or_zero()
+    pub fn may_withdraw(&self, money: &Money) -> bool {
+        Money::add(&self.calculate_balance(), &money.negate())
+            .plus(&self.overdraft_limit)
+            .is_positive_or_zero()
     }
 
     pub fn get_id(&self) -> Option<AccountId> {
         self.id.clone()
     }
 
+    pub fn get_currency(&self) -> Currency {
+        self.currency.clone()
+    }
+
+    pub fn get_overdraft_limit(&self) -> Money {
+        self.overdraft_limit.clone()
+    }
+
+    /**
+     * Changes the amount by which the balance may drop below zero.
+     * An overdraft limit below the current overdraft only prevents further withdrawals.
+     * @return true if the limit was changed, false if the limit is negative.
+     */
+    pub fn change_overdraft_limit(&mut self, overdraft_limit: Money) -> bool {
+        if overdraft_limit.is_negative() {
+            return false;
+        }
+        self.overdraft_limit = overdraft_limit;
+        true
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /**
+     * Closes the account, which is only possible once all of its money has been withdrawn.
+     * @return true if the account was closed, false if it is closed already or the balance is
+     * not zero.
+     */
+    pub fn close(&mut self) -> bool {
+        let balance = self.calculate_balance();
+        if self.closed || balance.is_positive() || balance.is_negative() {
+            return false;
+        }
+        self.closed = true;
+        true
+    }
+
     /**
      * Calculates the total balance of the account by adding the activity values to the baseline balance.
      */
@@ -77,13 +161,26 @@ impl Account {
         )
     }
 
+    /**
+     * Calculates the balance of the account at a point in time within the activity window,
+     * i.e. the baseline balance plus the activities before `until`.
+     */
+    pub fn calculate_balance_at(&self, until: NaiveDateTime) -> Money {
+        Money::add(
+            &self.baseline_balance,
+            &self
+                .activity_window
+                .calculate_balance_until(&self.id.clone().unwrap(), until),
+        )
+    }
+
     /**
      * Tries to withdraw a certain amount of money from this account.
      * If successful, creates a new activity with a negative value.
      * @return true if the withdrawal was successful, false if not.
      */
     pub fn withdraw(&mut self, money: Money, target_account_id: AccountId) -> bool {
-        if !self.may_withdraw(&money) {
+        if self.closed || !self.may_withdraw(&money) {
             return false;
         }
         let withdrawal = Activity::new(
@@ -93,6 +190,12 @@ impl Account {
             Local::now().naive_local(),
             money,
         );
+        self.domain_events.push(DomainEvent::MoneyWithdrawn {
+            account_id: withdrawal.owner_account_id.clone(),
+            target_account_id: withdrawal.target_account_id.clone(),
+            money: withdrawal.money.clone(),
+            timestamp: withdrawal.timestamp,
+        });
         self.activity_window.add_activity(withdrawal);
         true
     }
@@ -103,6 +206,9 @@ impl Account {
      * @return true if the deposit was successful, false if not.
      */
     pub fn deposit(&mut self, money: Money, source_account_id: AccountId) -> bool {
+        if self.closed {
+            return false;
+        }
         let deposit = Activity::new(
             self.id.clone().unwrap(),
             source_account_id,
@@ -110,10 +216,90 @@ impl Account {
             Local::now().naive_local(),
             money,
         );
+        self.domain_events.push(DomainEvent::MoneyDeposited {
+            account_id: deposit.owner_account_id.clone(),
+            source_account_id: deposit.source_account_id.clone(),
+            money: deposit.money.clone(),
+            timestamp: deposit.timestamp,
+        });
         self.activity_window.add_activity(deposit);
         true
     }
 
+    /**
+     * Tries to withdraw money of the currency of this account which is exchanged into the currency
+     * of the target account at `exchange_rate`.
+     * If successful, creates a new activity recording the exchange rate.
+     * @return true if the withdrawal was successful, false if not.
+     */
+    pub fn withdraw_exchanged(
+        &mut self,
+        money: Money,
+        target_account_id: AccountId,
+        exchange_rate: ExchangeRate,
+    ) -> bool {
+        if self.closed || !self.may_withdraw(&money) {
+            return false;
+        }
+        let withdrawal = Activity::new(
+            self.id.clone().unwrap(),
+            self.id.clone().unwrap(),
+            target_account_id,
+            Local::now().naive_local(),
+            money,
+        )
+        .exchanged_at(exchange_rate);
+        self.domain_events.push(DomainEvent::MoneyWithdrawn {
+            account_id: withdrawal.owner_account_id.clone(),
+            target_account_id: withdrawal.target_account_id.clone(),
+            money: withdrawal.money.clone(),
+            timestamp: withdrawal.timestamp,
+        });
+        self.activity_window.add_activity(withdrawal);
+        true
+    }
+
+    /**
+     * Deposits money which has been exchanged from the currency of the source account into the
+     * currency of this account at `exchange_rate`.
+     * Creates a new activity recording the exchange rate.
+     * @return true if the deposit was successful, false if not.
+     */
+    pub fn deposit_exchanged(
+        &mut self,
+        money: Money,
+        source_account_id: AccountId,
+        exchange_rate: ExchangeRate,
+    ) -> bool {
+        if self.closed {
+            return false;
+        }
+        let deposit = Activity::new(
+            self.id.clone().unwrap(),
+            source_account_id,
+            self.id.clone().unwrap(),
+            Local::now().naive_local(),
+            money,
+        )
+        .exchanged_at(exchange_rate);
+        self.domain_events.push(DomainEvent::MoneyDeposited {
+            account_id: deposit.owner_account_id.clone(),
+            source_account_id: deposit.source_account_id.clone(),
+            money: deposit.money.clone(),
+            timestamp: deposit.timestamp,
+        });
+        self.activity_window.add_activity(deposit);
+        true
+    }
+
+    /**
+     * Takes the events recorded since they were last taken, e.g. to publish them once the
+     * new activities have been persisted.
+     */
+    pub fn take_domain_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.domain_events)
+    }
+
     // #[allow(unused)]
     // pub fn get_activity_window(&self) -> &ActivityWindow {
     //     &self.activity_window
@@ -146,6 +332,29 @@ mod tests {
         assert_eq!(Money::of(1555), balance);
     }
 
+    #[test]
+    fn test_calculates_balance_at_point_in_time() {
+        let account_id = AccountId(1);
+        let yesterday = Local::now().naive_local() - chrono::Days::new(1);
+        let account = default_account()
+            .with_account_id(account_id.clone())
+            .with_baseline_balance(Money::of(555))
+            .with_activity_window(ActivityWindow::new(vec![
+                default_activity()
+                    .with_target_account(account_id.clone())
+                    .with_timestamp(yesterday - chrono::Days::new(1))
+                    .with_money(Money::of(45))
+                    .build(),
+                default_activity()
+                    .with_target_account(account_id)
+                    .with_money(Money::of(1))
+                    .build(),
+            ]))
+            .build();
+        assert_eq!(Money::of(600), account.calculate_balance_at(yesterday));
+        assert_eq!(Money::of(601), account.calculate_balance());
+    }
+
     #[test]
     fn test_withdrawal_succeeds() {
         let account_id = AccountId(1);
@@ -214,4 +423,89 @@ mod tests {
         // assert_eq!(3, account.activity_window.activities.len());
         assert_eq!(Money::of(2000), account.calculate_balance());
     }
+
+    #[test]
+    fn test_withdrawal_within_overdraft_limit_succeeds() {
+        let mut account = default_account()
+            .with_account_id(AccountId(1))
+            .with_baseline_balance(Money::of(555))
+            .with_overdraft_limit(Money::of(1000))
+            .build();
+        assert!(account.withdraw(Money::of(1555), AccountId(99)));
+        assert_eq!(Money::of(-1000), account.calculate_balance());
+        assert!(!account.withdraw(Money::of(1), AccountId(99)));
+    }
+
+    #[test]
+    fn test_exchanged_withdrawal_records_exchange_rate() {
+        let exchange_rate = ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500);
+        let mut account = default_account()
+            .with_account_id(AccountId(1))
+            .with_baseline_balance(Money::of(555))
+            .build();
+        assert!(!account.withdraw_exchanged(Money::of(556), AccountId(99), exchange_rate.clone()));
+        assert!(account.withdraw_exchanged(Money::of(555), AccountId(99), exchange_rate.clone()));
+        assert_eq!(Money::of(0), account.calculate_balance());
+        assert_eq!(
+            Some(exchange_rate),
+            account.activity_window.activities[0].exchange_rate
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_and_deposit_are_recorded_as_domain_events() {
+        let mut account = default_account()
+            .with_account_id(AccountId(1))
+            .with_baseline_balance(Money::of(555))
+            .build();
+        assert!(!account.withdraw(Money::of(556), AccountId(99)));
+        assert!(account.withdraw(Money::of(500), AccountId(99)));
+        assert!(account.deposit(Money::of(20), AccountId(98)));
+
+        let activities = &account.activity_window.activities;
+        let (withdrawn_at, deposited_at) = (activities[0].timestamp, activities[1].timestamp);
+        assert_eq!(
+            vec![
+                DomainEvent::MoneyWithdrawn {
+                    account_id: AccountId(1),
+                    target_account_id: AccountId(99),
+                    money: Money::of(500),
+                    timestamp: withdrawn_at,
+                },
+                DomainEvent::MoneyDeposited {
+                    account_id: AccountId(1),
+                    source_account_id: AccountId(98),
+                    money: Money::of(20),
+                    timestamp: deposited_at,
+                },
+            ],
+            account.take_domain_events()
+        );
+        assert!(account.take_domain_events().is_empty());
+    }
+
+    #[test]
+    fn test_negative_overdraft_limit_is_rejected() {
+        let mut account = default_account().build();
+        assert!(!account.change_overdraft_limit(Money::of(-1)));
+        assert!(account.change_overdraft_limit(Money::of(500)));
+        assert_eq!(Money::of(500), account.get_overdraft_limit());
+    }
+
+    #[test]
+    fn test_closed_account_neither_withdraws_nor_deposits() {
+        let mut account = default_account()
+            .with_account_id(AccountId(1))
+            .with_baseline_balance(Money::of(555))
+            .with_overdraft_limit(Money::of(1000))
+            .build();
+        assert!(!account.close());
+        assert!(account.withdraw(Money::of(555), AccountId(99)));
+        assert!(account.close());
+        assert!(account.is_closed());
+        assert!(!account.close());
+        assert!(!account.withdraw(Money::of(1), AccountId(99)));
+        assert!(!account.deposit(Money::of(1), AccountId(98)));
+        assert_eq!(1, account.take_domain_events().len());
+    }
 }
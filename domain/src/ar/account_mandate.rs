@@ -0,0 +1,45 @@
+use super::account::AccountId;
+use chrono::NaiveDateTime;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MandateKind {
+    /// The customer holds the account.
+    Owner,
+    /// The customer acts on behalf of an owner, e.g. an accountant or a spouse.
+    Signatory,
+}
+
+/**
+ * Entitles a customer to view and debit an [Account], which is how customers come to act on
+ * accounts at all. An account may be held by several owners, e.g. a joint account.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AccountMandate {
+    pub account_id: AccountId,
+    pub customer: String,
+    pub kind: MandateKind,
+    pub granted_at: NaiveDateTime,
+}
+
+// Associated Functions
+impl AccountMandate {
+    /// # Arguments
+    ///
+    /// * `account_id` - The account the mandate is granted on.
+    /// * `customer` - The subject the customer is authenticated as.
+    /// * `kind` - Whether the customer holds the account or acts on behalf of an owner.
+    /// * `granted_at` - The point in time the mandate was granted.
+    pub fn new(
+        account_id: AccountId,
+        customer: String,
+        kind: MandateKind,
+        granted_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            account_id,
+            customer,
+            kind,
+            granted_at,
+        }
+    }
+}
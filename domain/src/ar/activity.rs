@@ -1,6 +1,6 @@
 // use crate::{account::AccountId, money::Money};
 use super::account::AccountId;
-use crate::vo::money::Money;
+use crate::vo::{exchange_rate::ExchangeRate, money::Money};
 use chrono::NaiveDateTime;
 
 #[derive(PartialEq, Hash, Debug)]
@@ -17,6 +17,8 @@ pub struct Activity {
     pub target_account_id: AccountId,
     pub timestamp: NaiveDateTime,
     pub money: Money,
+    /// The rate at which the money was exchanged, if the accounts are held in different currencies.
+    pub exchange_rate: Option<ExchangeRate>,
 }
 
 impl Activity {
@@ -26,7 +28,7 @@ impl Activity {
     /// * `source_account_id` - The debited account.
     /// * `target_account_id` - The credited account.
     /// * `timestamp` - The timestamp of the activity.
-    /// * `money` - The money that was transferred between the accounts.
+    /// * `money` - The money that was transferred between the accounts, in the currency of the owner.
     pub fn new(
         owner_account_id: AccountId,
         source_account_id: AccountId,
@@ -41,6 +43,7 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            None,
         )
     }
 
@@ -51,6 +54,7 @@ impl Activity {
         target_account_id: AccountId,
         timestamp: NaiveDateTime,
         money: Money,
+        exchange_rate: Option<ExchangeRate>,
     ) -> Self {
         Self {
             id,
@@ -59,6 +63,15 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            exchange_rate,
         }
     }
+
+    /**
+     * Records the rate at which the money was exchanged between the currencies of the accounts.
+     */
+    pub fn exchanged_at(mut self, exchange_rate: ExchangeRate) -> Self {
+        self.exchange_rate = Some(exchange_rate);
+        self
+    }
 }
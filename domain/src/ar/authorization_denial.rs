@@ -0,0 +1,42 @@
+use super::account::AccountId;
+use crate::vo::role::Role;
+use chrono::NaiveDateTime;
+
+/**
+ * Audit record of an attempt to act on an [Account] without being entitled to, keeping who
+ * attempted what and when.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct AuthorizationDenial {
+    pub subject: String,
+    pub role: Role,
+    pub action: String,
+    pub account_id: AccountId,
+    pub denied_at: NaiveDateTime,
+}
+
+// Associated Functions
+impl AuthorizationDenial {
+    /// # Arguments
+    ///
+    /// * `subject` - Who attempted the action.
+    /// * `role` - The role they attempted it in.
+    /// * `action` - What they attempted, e.g. `debit_account`.
+    /// * `account_id` - The account they attempted it on.
+    /// * `denied_at` - The point in time of the attempt.
+    pub fn new(
+        subject: String,
+        role: Role,
+        action: String,
+        account_id: AccountId,
+        denied_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            subject,
+            role,
+            action,
+            account_id,
+            denied_at,
+        }
+    }
+}
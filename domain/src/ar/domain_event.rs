@@ -0,0 +1,57 @@
+use super::account::AccountId;
+use crate::vo::money::Money;
+use chrono::NaiveDateTime;
+
+/**
+ * Something that happened to [Account]s which other parts of the system may react to.
+ * Money events are recorded by the [Account] aggregate, transfer events by the use case
+ * coordinating the transfer between two accounts.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub enum DomainEvent {
+    /// Money of the account owner was withdrawn to the target account.
+    MoneyWithdrawn {
+        account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        timestamp: NaiveDateTime,
+    },
+    /// Money from the source account was deposited to the account owner.
+    MoneyDeposited {
+        account_id: AccountId,
+        source_account_id: AccountId,
+        money: Money,
+        timestamp: NaiveDateTime,
+    },
+    /// Both sides of a transfer have been booked.
+    TransferCompleted {
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        fee: Money,
+        timestamp: NaiveDateTime,
+    },
+    /// A transfer was refused, nothing has been booked.
+    TransferRejected {
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        reason: String,
+        timestamp: NaiveDateTime,
+    },
+}
+
+// Methods
+impl DomainEvent {
+    /**
+     * @return the point in time the event happened.
+     */
+    pub fn timestamp(&self) -> NaiveDateTime {
+        match self {
+            DomainEvent::MoneyWithdrawn { timestamp, .. }
+            | DomainEvent::MoneyDeposited { timestamp, .. }
+            | DomainEvent::TransferCompleted { timestamp, .. }
+            | DomainEvent::TransferRejected { timestamp, .. } => *timestamp,
+        }
+    }
+}
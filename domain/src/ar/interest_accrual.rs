@@ -0,0 +1,52 @@
+use super::account::AccountId;
+use crate::vo::money::Money;
+use chrono::NaiveDate;
+
+/**
+ * The daily interest accrual of an [Account]. It records the balance at the end of
+ * the day, the interest of a month is calculated from the balances of all its days
+ * and capitalised with the accrual of the last day of the month.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct InterestAccrual {
+    pub account_id: AccountId,
+    pub accrual_date: NaiveDate,
+    pub balance: Money,
+    pub capitalised_interest: Option<Money>,
+}
+
+// Associated Functions
+impl InterestAccrual {
+    /// # Arguments
+    ///
+    /// * `account_id` - The account whose interest is accrued.
+    /// * `accrual_date` - The day the interest is accrued for.
+    /// * `balance` - The balance of the account at the end of the day.
+    pub fn new(account_id: AccountId, accrual_date: NaiveDate, balance: Money) -> Self {
+        Self {
+            account_id,
+            accrual_date,
+            balance,
+            capitalised_interest: None,
+        }
+    }
+}
+
+// Methods
+impl InterestAccrual {
+    /**
+     * Overdrawn days do not bear interest.
+     * @return the balance interest is paid on.
+     */
+    pub fn interest_bearing_balance(&self) -> Money {
+        if self.balance.is_negative() {
+            Money::of(0)
+        } else {
+            self.balance.clone()
+        }
+    }
+
+    pub fn capitalise(&mut self, interest: Money) {
+        self.capitalised_interest = Some(interest);
+    }
+}
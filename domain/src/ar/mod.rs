@@ -1,2 +1,11 @@
 pub mod account;
+pub mod account_mandate;
 pub mod activity;
+pub mod authorization_denial;
+pub mod domain_event;
+pub mod interest_accrual;
+pub mod outbox_message;
+pub mod overdraft_limit_change;
+pub mod scheduled_transfer;
+pub mod standing_order;
+pub mod webhook_subscription;
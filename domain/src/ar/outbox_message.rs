@@ -0,0 +1,108 @@
+use super::domain_event::DomainEvent;
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct OutboxMessageId(pub i64);
+
+/**
+ * A [DomainEvent] that has been stored together with the state change it describes and
+ * is waiting to be delivered. A message is delivered at least once, failed deliveries are
+ * retried after a backoff until it is marked as processed.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct OutboxMessage {
+    pub id: Option<OutboxMessageId>,
+    pub domain_event: DomainEvent,
+    pub attempts: u32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub processed_at: Option<NaiveDateTime>,
+}
+
+// Associated Functions
+impl OutboxMessage {
+    /**
+     * A new message is due as soon as the event has happened.
+     */
+    pub fn new(domain_event: DomainEvent) -> Self {
+        let next_attempt_at = domain_event.timestamp();
+        Self::with_id(None, domain_event, 0, next_attempt_at, None, None)
+    }
+
+    /// # Arguments
+    ///
+    /// * `id` - The position of the message in the outbox.
+    /// * `domain_event` - The event to deliver.
+    /// * `attempts` - The number of failed deliveries.
+    /// * `next_attempt_at` - The earliest point in time of the next delivery.
+    /// * `last_error` - The reason of the last failed delivery.
+    /// * `processed_at` - The point in time the message was delivered.
+    pub fn with_id(
+        id: Option<OutboxMessageId>,
+        domain_event: DomainEvent,
+        attempts: u32,
+        next_attempt_at: NaiveDateTime,
+        last_error: Option<String>,
+        processed_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            domain_event,
+            attempts,
+            next_attempt_at,
+            last_error,
+            processed_at,
+        }
+    }
+}
+
+// Methods
+impl OutboxMessage {
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.processed_at.is_none() && self.next_attempt_at <= now
+    }
+
+    pub fn mark_processed(&mut self, now: NaiveDateTime) {
+        self.processed_at = Some(now);
+    }
+
+    /**
+     * Records a failed delivery, the message is due again after `backoff`.
+     */
+    pub fn schedule_retry(&mut self, now: NaiveDateTime, backoff: Duration, error: String) {
+        self.attempts += 1;
+        self.next_attempt_at = now + backoff;
+        self.last_error = Some(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ar::account::AccountId, vo::money::Money};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_failed_delivery_is_retried_after_backoff() {
+        let timestamp = NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut outbox_message = OutboxMessage::new(DomainEvent::TransferCompleted {
+            source_account_id: AccountId(41),
+            target_account_id: AccountId(42),
+            money: Money::of(500),
+            fee: Money::of(0),
+            timestamp,
+        });
+        assert!(outbox_message.is_due(timestamp));
+
+        outbox_message.schedule_retry(timestamp, Duration::seconds(2), "timeout".to_string());
+        assert_eq!(1, outbox_message.attempts);
+        assert!(!outbox_message.is_due(timestamp + Duration::seconds(1)));
+        assert!(outbox_message.is_due(timestamp + Duration::seconds(2)));
+
+        outbox_message.mark_processed(timestamp + Duration::seconds(2));
+        assert!(!outbox_message.is_due(timestamp + Duration::seconds(3)));
+    }
+}
@@ -0,0 +1,42 @@
+use super::account::AccountId;
+use crate::vo::money::Money;
+use chrono::NaiveDateTime;
+
+/**
+ * Audit record of a change of the overdraft limit of an [Account], keeping
+ * the previous and the new limit together with who changed it and when.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct OverdraftLimitChange {
+    pub account_id: AccountId,
+    pub previous_limit: Money,
+    pub new_limit: Money,
+    pub changed_by: String,
+    pub changed_at: NaiveDateTime,
+}
+
+// Associated Functions
+impl OverdraftLimitChange {
+    /// # Arguments
+    ///
+    /// * `account_id` - The account whose overdraft limit was changed.
+    /// * `previous_limit` - The overdraft limit before the change.
+    /// * `new_limit` - The overdraft limit after the change.
+    /// * `changed_by` - The user who changed the overdraft limit.
+    /// * `changed_at` - The point in time of the change.
+    pub fn new(
+        account_id: AccountId,
+        previous_limit: Money,
+        new_limit: Money,
+        changed_by: String,
+        changed_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            account_id,
+            previous_limit,
+            new_limit,
+            changed_by,
+            changed_at,
+        }
+    }
+}
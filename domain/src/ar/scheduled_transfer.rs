@@ -0,0 +1,245 @@
+use super::account::AccountId;
+use crate::vo::{money::Money, role::Role};
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct ScheduledTransferId(pub i64);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ScheduledTransferStatus {
+    Pending,
+    /// Claimed by an executor; the money is being sent.
+    Executing,
+    Executed,
+    Failed,
+    Cancelled,
+}
+
+/**
+ * A money transfer between [Account]s that is booked now but executed at a
+ * future date, e.g. a rent payment, on behalf of whoever scheduled it.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct ScheduledTransfer {
+    pub id: Option<ScheduledTransferId>,
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub execution_date: NaiveDateTime,
+    pub status: ScheduledTransferStatus,
+    /// When an executor claimed the transfer, see [ScheduledTransfer::start_execution].
+    pub claimed_at: Option<NaiveDateTime>,
+    pub executed_at: Option<NaiveDateTime>,
+    pub failure_reason: Option<String>,
+    pub created_by: String,
+    pub created_by_role: Role,
+}
+
+// Associated Functions
+impl ScheduledTransfer {
+    /// # Arguments
+    ///
+    /// * `source_account_id` - The account to debit.
+    /// * `target_account_id` - The account to credit.
+    /// * `money` - The money to transfer.
+    /// * `execution_date` - The earliest point in time the transfer is executed.
+    /// * `created_by` - The subject who scheduled the transfer and whom it is executed as.
+    /// * `created_by_role` - The role they scheduled it in.
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        execution_date: NaiveDateTime,
+        created_by: String,
+        created_by_role: Role,
+    ) -> Self {
+        Self::with_id(
+            None,
+            source_account_id,
+            target_account_id,
+            money,
+            execution_date,
+            ScheduledTransferStatus::Pending,
+            None,
+            None,
+            None,
+            created_by,
+            created_by_role,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_id(
+        id: Option<ScheduledTransferId>,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        execution_date: NaiveDateTime,
+        status: ScheduledTransferStatus,
+        claimed_at: Option<NaiveDateTime>,
+        executed_at: Option<NaiveDateTime>,
+        failure_reason: Option<String>,
+        created_by: String,
+        created_by_role: Role,
+    ) -> Self {
+        Self {
+            id,
+            source_account_id,
+            target_account_id,
+            money,
+            execution_date,
+            status,
+            claimed_at,
+            executed_at,
+            failure_reason,
+            created_by,
+            created_by_role,
+        }
+    }
+}
+
+// Methods
+impl ScheduledTransfer {
+    /**
+     * A scheduled transfer is due if it is still pending and its execution date has been reached.
+     */
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.status == ScheduledTransferStatus::Pending && self.execution_date <= now
+    }
+
+    /**
+     * Claims this scheduled transfer for its execution, so that neither a second executor nor a
+     * cancellation touches it any more.
+     * @return true if the transfer was still pending.
+     */
+    pub fn start_execution(&mut self, now: NaiveDateTime) -> bool {
+        if self.status != ScheduledTransferStatus::Pending {
+            return false;
+        }
+        self.status = ScheduledTransferStatus::Executing;
+        self.claimed_at = Some(now);
+        true
+    }
+
+    /**
+     * A claim has expired once it is held longer than the lease, as its executor has been
+     * interrupted before it recorded the outcome, e.g. by a crash. Claims of unknown age have
+     * expired as well.
+     */
+    pub fn is_claim_expired(&self, now: NaiveDateTime, lease: Duration) -> bool {
+        self.status == ScheduledTransferStatus::Executing
+            && self
+                .claimed_at
+                .is_none_or(|claimed_at| claimed_at + lease <= now)
+    }
+
+    pub fn mark_executed(&mut self, now: NaiveDateTime) {
+        self.status = ScheduledTransferStatus::Executed;
+        self.executed_at = Some(now);
+        self.failure_reason = None;
+    }
+
+    pub fn mark_failed(&mut self, now: NaiveDateTime, reason: String) {
+        self.status = ScheduledTransferStatus::Failed;
+        self.executed_at = Some(now);
+        self.failure_reason = Some(reason);
+    }
+
+    /**
+     * Cancels this scheduled transfer.
+     * @return true if the transfer was still pending, false if it was already executed, failed or cancelled.
+     */
+    pub fn cancel(&mut self) -> bool {
+        if self.status != ScheduledTransferStatus::Pending {
+            return false;
+        }
+        self.status = ScheduledTransferStatus::Cancelled;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_is_due() {
+        let transfer = scheduled_transfer();
+        assert!(!transfer.is_due(execution_date() - chrono::Duration::seconds(1)));
+        assert!(transfer.is_due(execution_date()));
+        assert!(transfer.is_due(execution_date() + chrono::Duration::days(1)));
+    }
+
+    #[test]
+    fn test_executing_transfer_is_neither_due_nor_cancellable() {
+        let mut transfer = scheduled_transfer();
+        assert!(transfer.start_execution(execution_date()));
+        assert_eq!(ScheduledTransferStatus::Executing, transfer.status);
+        assert_eq!(Some(execution_date()), transfer.claimed_at);
+        assert!(!transfer.start_execution(execution_date()));
+        assert!(!transfer.is_due(execution_date()));
+        assert!(!transfer.cancel());
+    }
+
+    #[test]
+    fn test_is_claim_expired() {
+        let lease = Duration::minutes(10);
+        let mut transfer = scheduled_transfer();
+        assert!(!transfer.is_claim_expired(execution_date() + Duration::days(1), lease));
+        transfer.start_execution(execution_date());
+        assert!(!transfer.is_claim_expired(execution_date() + Duration::minutes(9), lease));
+        assert!(transfer.is_claim_expired(execution_date() + Duration::minutes(10), lease));
+        transfer.claimed_at = None;
+        assert!(transfer.is_claim_expired(execution_date(), lease));
+        transfer.mark_executed(execution_date());
+        assert!(!transfer.is_claim_expired(execution_date() + Duration::days(1), lease));
+    }
+
+    #[test]
+    fn test_executed_transfer_is_not_due() {
+        let mut transfer = scheduled_transfer();
+        transfer.mark_executed(execution_date());
+        assert_eq!(ScheduledTransferStatus::Executed, transfer.status);
+        assert_eq!(Some(execution_date()), transfer.executed_at);
+        assert!(!transfer.is_due(execution_date()));
+    }
+
+    #[test]
+    fn test_mark_failed() {
+        let mut transfer = scheduled_transfer();
+        transfer.mark_failed(execution_date(), "insufficient funds".to_string());
+        assert_eq!(ScheduledTransferStatus::Failed, transfer.status);
+        assert_eq!(
+            Some("insufficient funds".to_string()),
+            transfer.failure_reason
+        );
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut transfer = scheduled_transfer();
+        assert!(transfer.cancel());
+        assert_eq!(ScheduledTransferStatus::Cancelled, transfer.status);
+        assert!(!transfer.cancel());
+        assert!(!transfer.is_due(execution_date()));
+    }
+
+    fn scheduled_transfer() -> ScheduledTransfer {
+        ScheduledTransfer::new(
+            AccountId(1),
+            AccountId(2),
+            Money::of(500),
+            execution_date(),
+            "alice".to_string(),
+            Role::Customer,
+        )
+    }
+
+    fn execution_date() -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2019, 9, 1).unwrap(),
+            NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        )
+    }
+}
@@ -0,0 +1,280 @@
+use super::account::AccountId;
+use crate::vo::{money::Money, recurrence::Recurrence, role::Role};
+use chrono::NaiveDateTime;
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct StandingOrderId(pub i64);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StandingOrderStatus {
+    Active,
+    Paused,
+    Cancelled,
+    Completed,
+}
+
+/**
+ * A recurring money transfer between [Account]s on behalf of whoever created it. It runs from its
+ * start date according to its [Recurrence] until it is cancelled, its end date has passed or the
+ * maximum number of occurrences has been executed.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct StandingOrder {
+    pub id: Option<StandingOrderId>,
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub recurrence: Recurrence,
+    pub start_date: NaiveDateTime,
+    pub end_date: Option<NaiveDateTime>,
+    pub max_occurrences: Option<u32>,
+    pub executed_occurrences: u32,
+    pub next_execution_date: Option<NaiveDateTime>,
+    pub status: StandingOrderStatus,
+    pub created_by: String,
+    pub created_by_role: Role,
+}
+
+// Associated Functions
+impl StandingOrder {
+    /// # Arguments
+    ///
+    /// * `source_account_id` - The account to debit.
+    /// * `target_account_id` - The account to credit.
+    /// * `money` - The money to transfer on each occurrence.
+    /// * `recurrence` - How often the order is executed.
+    /// * `start_date` - The first occurrence.
+    /// * `end_date` - No occurrence after this point in time is executed.
+    /// * `max_occurrences` - The order completes after this many occurrences.
+    /// * `created_by` - The subject who created the order and whom it is executed as.
+    /// * `created_by_role` - The role they created it in.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        recurrence: Recurrence,
+        start_date: NaiveDateTime,
+        end_date: Option<NaiveDateTime>,
+        max_occurrences: Option<u32>,
+        created_by: String,
+        created_by_role: Role,
+    ) -> Self {
+        Self {
+            id: None,
+            source_account_id,
+            target_account_id,
+            money,
+            recurrence,
+            start_date,
+            end_date,
+            max_occurrences,
+            executed_occurrences: 0,
+            next_execution_date: Some(start_date),
+            status: StandingOrderStatus::Active,
+            created_by,
+            created_by_role,
+        }
+    }
+}
+
+// Methods
+impl StandingOrder {
+    /**
+     * A standing order is due if it is active and its next occurrence has been reached.
+     */
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.status == StandingOrderStatus::Active
+            && self.next_execution_date.is_some_and(|next| next <= now)
+    }
+
+    /**
+     * Consumes the next occurrence and moves on to the following one,
+     * completing the order if there is none left.
+     * @return the point in time the consumed occurrence was scheduled for.
+     */
+    pub fn advance(&mut self) -> Option<NaiveDateTime> {
+        let scheduled_for = self.next_execution_date?;
+        self.executed_occurrences += 1;
+        self.next_execution_date = self.following_occurrence(scheduled_for);
+        if self.next_execution_date.is_none() {
+            self.status = StandingOrderStatus::Completed;
+        }
+        Some(scheduled_for)
+    }
+
+    pub fn pause(&mut self) -> bool {
+        if self.status != StandingOrderStatus::Active {
+            return false;
+        }
+        self.status = StandingOrderStatus::Paused;
+        true
+    }
+
+    /**
+     * Resumes a paused standing order. Occurrences missed while paused are skipped.
+     */
+    pub fn resume(&mut self, now: NaiveDateTime) -> bool {
+        if self.status != StandingOrderStatus::Paused {
+            return false;
+        }
+        self.status = StandingOrderStatus::Active;
+        while let Some(next) = self.next_execution_date {
+            if next >= now {
+                break;
+            }
+            self.next_execution_date = self.following_occurrence(next);
+        }
+        if self.next_execution_date.is_none() {
+            self.status = StandingOrderStatus::Completed;
+        }
+        true
+    }
+
+    pub fn cancel(&mut self) -> bool {
+        if !matches!(
+            self.status,
+            StandingOrderStatus::Active | StandingOrderStatus::Paused
+        ) {
+            return false;
+        }
+        self.status = StandingOrderStatus::Cancelled;
+        self.next_execution_date = None;
+        true
+    }
+
+    fn following_occurrence(&self, previous: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self
+            .max_occurrences
+            .is_some_and(|max| self.executed_occurrences >= max)
+        {
+            return None;
+        }
+        self.recurrence
+            .next_after(self.start_date, previous)
+            .filter(|next| self.end_date.is_none_or(|end| *next <= end))
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct StandingOrderExecutionId(pub i64);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum StandingOrderExecutionStatus {
+    Started,
+    Succeeded,
+    Failed,
+}
+
+/**
+ * The record of a single occurrence of a [StandingOrder].
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct StandingOrderExecution {
+    pub id: Option<StandingOrderExecutionId>,
+    pub standing_order_id: StandingOrderId,
+    pub scheduled_for: NaiveDateTime,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+    pub status: StandingOrderExecutionStatus,
+    pub failure_reason: Option<String>,
+}
+
+impl StandingOrderExecution {
+    pub fn started(
+        standing_order_id: StandingOrderId,
+        scheduled_for: NaiveDateTime,
+        started_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id: None,
+            standing_order_id,
+            scheduled_for,
+            started_at,
+            finished_at: None,
+            status: StandingOrderExecutionStatus::Started,
+            failure_reason: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Days, NaiveDate};
+
+    #[test]
+    fn test_advance_until_max_occurrences() {
+        let mut order = standing_order(Some(2), None);
+        assert!(order.is_due(start_date()));
+
+        assert_eq!(Some(start_date()), order.advance());
+        assert_eq!(Some(day(2)), order.next_execution_date);
+        assert!(!order.is_due(start_date()));
+
+        assert_eq!(Some(day(2)), order.advance());
+        assert_eq!(StandingOrderStatus::Completed, order.status);
+        assert_eq!(None, order.next_execution_date);
+        assert!(!order.is_due(day(3)));
+    }
+
+    #[test]
+    fn test_advance_until_end_date() {
+        let mut order = standing_order(None, Some(day(2)));
+        order.advance();
+        order.advance();
+        assert_eq!(StandingOrderStatus::Completed, order.status);
+        assert_eq!(2, order.executed_occurrences);
+    }
+
+    #[test]
+    fn test_paused_order_is_not_due_and_resume_skips_missed_occurrences() {
+        let mut order = standing_order(None, None);
+        assert!(order.pause());
+        assert!(!order.pause());
+        assert!(!order.is_due(day(5)));
+
+        assert!(order.resume(day(5)));
+        assert_eq!(Some(day(5)), order.next_execution_date);
+        assert_eq!(0, order.executed_occurrences);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut order = standing_order(None, None);
+        assert!(order.cancel());
+        assert!(!order.cancel());
+        assert!(!order.resume(day(2)));
+        assert!(!order.is_due(day(2)));
+    }
+
+    fn standing_order(
+        max_occurrences: Option<u32>,
+        end_date: Option<NaiveDateTime>,
+    ) -> StandingOrder {
+        StandingOrder::new(
+            AccountId(1),
+            AccountId(2),
+            Money::of(100),
+            Recurrence::Daily,
+            start_date(),
+            end_date,
+            max_occurrences,
+            "alice".to_string(),
+            Role::Customer,
+        )
+    }
+
+    fn start_date() -> NaiveDateTime {
+        day(1)
+    }
+
+    fn day(day: u64) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .checked_add_days(Days::new(day - 1))
+            .unwrap()
+    }
+}
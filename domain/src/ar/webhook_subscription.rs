@@ -0,0 +1,185 @@
+use super::{account::AccountId, domain_event::DomainEvent};
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WebhookSubscriptionId(pub i64);
+
+/**
+ * A partner endpoint which is notified of the money withdrawn from and deposited to an
+ * [Account]. The payloads are signed with the secret shared with the partner.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WebhookSubscription {
+    pub id: Option<WebhookSubscriptionId>,
+    pub account_id: AccountId,
+    pub url: String,
+    pub secret: String,
+    pub created_at: NaiveDateTime,
+}
+
+// Associated Functions
+impl WebhookSubscription {
+    /// # Arguments
+    ///
+    /// * `account_id` - The account whose activities are notified.
+    /// * `url` - The endpoint the notifications are posted to.
+    /// * `secret` - The key the payloads are signed with.
+    /// * `created_at` - The point in time the subscription was created.
+    pub fn new(
+        account_id: AccountId,
+        url: String,
+        secret: String,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id: None,
+            account_id,
+            url,
+            secret,
+            created_at,
+        }
+    }
+}
+
+// Methods
+impl WebhookSubscription {
+    /**
+     * @return true if the event is a withdrawal from or a deposit to the subscribed account.
+     */
+    pub fn is_notified_of(&self, domain_event: &DomainEvent) -> bool {
+        match domain_event {
+            DomainEvent::MoneyWithdrawn { account_id, .. }
+            | DomainEvent::MoneyDeposited { account_id, .. } => *account_id == self.account_id,
+            DomainEvent::TransferCompleted { .. } | DomainEvent::TransferRejected { .. } => false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WebhookDeliveryId(pub i64);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    DeadLettered,
+}
+
+/**
+ * The notification of a [WebhookSubscription] about a single [DomainEvent]. Failed deliveries
+ * are retried after a backoff until the delivery is dead-lettered.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct WebhookDelivery {
+    pub id: Option<WebhookDeliveryId>,
+    pub subscription_id: WebhookSubscriptionId,
+    pub domain_event: DomainEvent,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+// Associated Functions
+impl WebhookDelivery {
+    /**
+     * A new delivery is due as soon as the event has happened.
+     */
+    pub fn new(subscription_id: WebhookSubscriptionId, domain_event: DomainEvent) -> Self {
+        Self {
+            id: None,
+            subscription_id,
+            next_attempt_at: domain_event.timestamp(),
+            domain_event,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            finished_at: None,
+        }
+    }
+}
+
+// Methods
+impl WebhookDelivery {
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.status == WebhookDeliveryStatus::Pending && self.next_attempt_at <= now
+    }
+
+    pub fn mark_delivered(&mut self, now: NaiveDateTime) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Delivered;
+        self.last_error = None;
+        self.finished_at = Some(now);
+    }
+
+    /**
+     * Records a failed delivery, the delivery is due again after `backoff`.
+     */
+    pub fn schedule_retry(&mut self, now: NaiveDateTime, backoff: Duration, error: String) {
+        self.attempts += 1;
+        self.next_attempt_at = now + backoff;
+        self.last_error = Some(error);
+    }
+
+    /**
+     * Records a failed delivery which is not retried anymore.
+     */
+    pub fn dead_letter(&mut self, now: NaiveDateTime, error: String) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::DeadLettered;
+        self.last_error = Some(error);
+        self.finished_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vo::money::Money;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_failed_delivery_is_retried_until_dead_lettered() {
+        let subscription = WebhookSubscription::new(
+            AccountId(42),
+            "http://127.0.0.1/hook".to_string(),
+            "secret".to_string(),
+            timestamp(),
+        );
+        let deposit = DomainEvent::MoneyDeposited {
+            account_id: AccountId(42),
+            source_account_id: AccountId(41),
+            money: Money::of(500),
+            timestamp: timestamp(),
+        };
+        let withdrawal = DomainEvent::MoneyWithdrawn {
+            account_id: AccountId(41),
+            target_account_id: AccountId(42),
+            money: Money::of(500),
+            timestamp: timestamp(),
+        };
+        assert!(subscription.is_notified_of(&deposit));
+        assert!(!subscription.is_notified_of(&withdrawal));
+
+        let mut delivery = WebhookDelivery::new(WebhookSubscriptionId(1), deposit);
+        assert!(delivery.is_due(timestamp()));
+
+        delivery.schedule_retry(timestamp(), Duration::seconds(2), "timeout".to_string());
+        assert_eq!(1, delivery.attempts);
+        assert!(!delivery.is_due(timestamp() + Duration::seconds(1)));
+        assert!(delivery.is_due(timestamp() + Duration::seconds(2)));
+
+        delivery.dead_letter(timestamp() + Duration::seconds(2), "timeout".to_string());
+        assert_eq!(2, delivery.attempts);
+        assert_eq!(WebhookDeliveryStatus::DeadLettered, delivery.status);
+        assert!(!delivery.is_due(timestamp() + Duration::seconds(3)));
+    }
+
+    fn timestamp() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2019, 8, 9)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+}
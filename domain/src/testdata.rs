@@ -5,19 +5,23 @@ use crate::{
         account::{Account, AccountId},
         activity::{Activity, ActivityId},
     },
-    vo::{activity_window::ActivityWindow, money::Money},
+    vo::{activity_window::ActivityWindow, currency::Currency, money::Money},
 };
 
 pub fn default_account() -> AccountBuilder {
     AccountBuilder::new()
         .with_account_id(AccountId(42))
+        .with_currency(Currency::default())
         .with_baseline_balance(Money::of(999))
+        .with_overdraft_limit(Money::of(0))
         .with_activity_window(ActivityWindow::new(vec![]))
 }
 
 pub struct AccountBuilder {
     account_id: Option<AccountId>,
+    currency: Option<Currency>,
     baseline_balance: Option<Money>,
+    overdraft_limit: Option<Money>,
     activity_window: Option<ActivityWindow>,
 }
 
@@ -25,7 +29,9 @@ impl AccountBuilder {
     pub fn new() -> Self {
         Self {
             account_id: None,
+            currency: None,
             baseline_balance: None,
+            overdraft_limit: None,
             activity_window: None,
         }
     }
@@ -35,11 +41,21 @@ impl AccountBuilder {
         self
     }
 
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
     pub fn with_baseline_balance(mut self, baseline_balance: Money) -> Self {
         self.baseline_balance = Some(baseline_balance);
         self
     }
 
+    pub fn with_overdraft_limit(mut self, overdraft_limit: Money) -> Self {
+        self.overdraft_limit = Some(overdraft_limit);
+        self
+    }
+
     pub fn with_activity_window(mut self, activity_window: ActivityWindow) -> Self {
         self.activity_window = Some(activity_window);
         self
@@ -48,7 +64,9 @@ impl AccountBuilder {
     pub fn build(self) -> Account {
         Account::with_id(
             self.account_id.unwrap(),
+            self.currency.unwrap(),
             self.baseline_balance.unwrap(),
+            self.overdraft_limit.unwrap(),
             self.activity_window.unwrap(),
         )
     }
@@ -122,6 +140,7 @@ impl ActivityBuilder {
             self.target_account_id.unwrap(),
             self.timestamp.unwrap(),
             self.money.unwrap(),
+            None,
         )
     }
 }
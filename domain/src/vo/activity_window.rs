@@ -56,6 +56,25 @@ impl ActivityWindow {
         Money::add(&deposit_balance, &withdrawal_balance.negate())
     }
 
+    /**
+     * Calculates the balance by summing up the values of all activities within this window
+     * which happened before `until`.
+     */
+    pub fn calculate_balance_until(&self, account_id: &AccountId, until: NaiveDateTime) -> Money {
+        self.activities
+            .iter()
+            .filter(|a| a.timestamp < until)
+            .fold(Money::of(0), |acc, a| {
+                if &a.target_account_id == account_id {
+                    acc.plus(&a.money)
+                } else if &a.source_account_id == account_id {
+                    acc.minus(&a.money)
+                } else {
+                    acc
+                }
+            })
+    }
+
     pub fn add_activity(&mut self, activity: Activity) {
         self.activities.push(activity);
     }
@@ -113,6 +132,36 @@ mod tests {
         assert_eq!(Money::of(500), window.calculate_balance(&account2));
     }
 
+    #[test]
+    fn test_calculates_balance_until() {
+        let account1 = AccountId(1);
+        let window = ActivityWindow::new(vec![
+            default_activity()
+                .with_target_account(account1.clone())
+                .with_timestamp(start_date())
+                .with_money(Money::of(999))
+                .build(),
+            default_activity()
+                .with_source_account(account1.clone())
+                .with_timestamp(in_between_date())
+                .with_money(Money::of(99))
+                .build(),
+            default_activity()
+                .with_target_account(account1.clone())
+                .with_timestamp(end_date())
+                .with_money(Money::of(1))
+                .build(),
+        ]);
+        assert_eq!(
+            Money::of(0),
+            window.calculate_balance_until(&account1, start_date())
+        );
+        assert_eq!(
+            Money::of(900),
+            window.calculate_balance_until(&account1, end_date())
+        );
+    }
+
     fn start_date() -> NaiveDateTime {
         NaiveDateTime::new(
             NaiveDate::from_ymd_opt(2019, 8, 3).unwrap(),
@@ -0,0 +1,207 @@
+use chrono::{Datelike, Days, Duration, NaiveDateTime, NaiveTime, Timelike};
+use std::{fmt, str::FromStr};
+
+/// How many days `next_after` looks ahead before giving up, e.g. for `0 0 30 2 *`.
+const MAX_LOOKAHEAD_DAYS: u64 = 5 * 366;
+
+/**
+ * A cron-like schedule with the five classic fields
+ * `minute hour day-of-month month day-of-week`.
+ * Each field supports `*`, single values, ranges `a-b`, steps (`*` or a range followed by `/n`)
+ * and comma separated lists. Day-of-week is 0-7 with both 0 and 7 meaning Sunday.
+ * As in cron, if both day-of-month and day-of-week are restricted a day matches if either matches.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CronExpression {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    days_of_month_restricted: bool,
+    days_of_week_restricted: bool,
+}
+
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct InvalidCronExpression(pub String);
+
+impl FromStr for CronExpression {
+    type Err = InvalidCronExpression;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(InvalidCronExpression(expression.to_string()));
+        }
+        let invalid = || InvalidCronExpression(expression.to_string());
+        let mut days_of_week = parse_field(fields[4], 0, 7).ok_or_else(invalid)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(Self {
+            expression: fields.join(" "),
+            minutes: parse_field(fields[0], 0, 59).ok_or_else(invalid)?,
+            hours: parse_field(fields[1], 0, 23).ok_or_else(invalid)?,
+            days_of_month: parse_field(fields[2], 1, 31).ok_or_else(invalid)?,
+            months: parse_field(fields[3], 1, 12).ok_or_else(invalid)?,
+            days_of_week,
+            days_of_month_restricted: fields[2] != "*",
+            days_of_week_restricted: fields[4] != "*",
+        })
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+// Methods
+impl CronExpression {
+    /**
+     * The first point in time strictly after `after` that matches this expression.
+     * @return None if there is no match within the next five years.
+     */
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        for day_offset in 0..MAX_LOOKAHEAD_DAYS {
+            let date = start.date().checked_add_days(Days::new(day_offset))?;
+            if !self.matches_day(
+                date.day(),
+                date.month(),
+                date.weekday().num_days_from_sunday(),
+            ) {
+                continue;
+            }
+            let earliest = if day_offset == 0 {
+                start.time()
+            } else {
+                NaiveTime::MIN
+            };
+            for hour in earliest.hour()..24 {
+                if !is_set(self.hours, hour) {
+                    continue;
+                }
+                let first_minute = if hour == earliest.hour() {
+                    earliest.minute()
+                } else {
+                    0
+                };
+                if let Some(minute) = (first_minute..60).find(|m| is_set(self.minutes, *m)) {
+                    return date.and_hms_opt(hour, minute, 0);
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, day_of_month: u32, month: u32, day_of_week: u32) -> bool {
+        if !is_set(self.months, month) {
+            return false;
+        }
+        let dom = is_set(self.days_of_month, day_of_month);
+        let dow = is_set(self.days_of_week, day_of_week);
+        match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+fn is_set(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (from, to) = if range == "*" {
+            (min, max)
+        } else if let Some((from, to)) = range.split_once('-') {
+            (from.parse::<u32>().ok()?, to.parse::<u32>().ok()?)
+        } else {
+            let value = range.parse::<u32>().ok()?;
+            // `5/15` means "from 5 every 15"
+            (value, if step > 1 { max } else { value })
+        };
+        if from < min || to > max || from > to {
+            return None;
+        }
+        for value in (from..=to).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Some(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        assert!("* * * *".parse::<CronExpression>().is_err());
+        assert!("60 * * * *".parse::<CronExpression>().is_err());
+        assert!("* * 0 * *".parse::<CronExpression>().is_err());
+        assert!("*/0 * * * *".parse::<CronExpression>().is_err());
+        assert!("a * * * *".parse::<CronExpression>().is_err());
+    }
+
+    #[test]
+    fn test_next_after_first_of_month() {
+        let cron = "0 8 1 * *".parse::<CronExpression>().unwrap();
+        assert_eq!(
+            Some(date_time(2019, 9, 1, 8, 0)),
+            cron.next_after(date_time(2019, 8, 3, 12, 30))
+        );
+        assert_eq!(
+            Some(date_time(2019, 10, 1, 8, 0)),
+            cron.next_after(date_time(2019, 9, 1, 8, 0))
+        );
+    }
+
+    #[test]
+    fn test_next_after_on_weekdays() {
+        // 2019-08-03 is a Saturday
+        let cron = "30 9 * * 1-5".parse::<CronExpression>().unwrap();
+        assert_eq!(
+            Some(date_time(2019, 8, 5, 9, 30)),
+            cron.next_after(date_time(2019, 8, 3, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_next_after_with_steps_and_lists() {
+        let cron = "*/15 6,18 * * *".parse::<CronExpression>().unwrap();
+        assert_eq!(
+            Some(date_time(2019, 8, 3, 6, 45)),
+            cron.next_after(date_time(2019, 8, 3, 6, 31))
+        );
+        assert_eq!(
+            Some(date_time(2019, 8, 3, 18, 0)),
+            cron.next_after(date_time(2019, 8, 3, 6, 45))
+        );
+    }
+
+    #[test]
+    fn test_next_after_without_match() {
+        let cron = "0 0 30 2 *".parse::<CronExpression>().unwrap();
+        assert_eq!(None, cron.next_after(date_time(2019, 8, 3, 0, 0)));
+    }
+
+    fn date_time(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,47 @@
+/**
+ * The ISO 4217 code of the currency an [Account] is held in.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Currency(String);
+
+impl Currency {
+    // Functions
+
+    /**
+     * @return the currency with the given code, panics if it is not a three letter upper case code.
+     */
+    pub fn of(code: &str) -> Self {
+        code.parse()
+            .unwrap_or_else(|error| panic!("invalid currency: {}", error))
+    }
+
+    // Methods
+
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Accounts are held in Euro unless stated otherwise.
+impl Default for Currency {
+    fn default() -> Self {
+        Currency("EUR".to_string())
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 3 || !s.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(format!("not a currency code: {}", s));
+        }
+        Ok(Currency(s.to_string()))
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
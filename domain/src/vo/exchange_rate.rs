@@ -0,0 +1,160 @@
+use super::{currency::Currency, money::Money, rounding::Rounding};
+use chrono::NaiveDateTime;
+
+/// Exchange rates are held with six decimal places.
+pub const RATE_SCALE: i128 = 1_000_000;
+
+/**
+ * The price of one unit of the source currency in units of the target currency,
+ * e.g. `EUR/USD 1.084500`.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExchangeRate {
+    pub source_currency: Currency,
+    pub target_currency: Currency,
+    /// The rate in millionths, see [RATE_SCALE].
+    pub rate: i128,
+}
+
+/**
+ * An exchange rate which is only honoured until it expires.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExchangeRateQuote {
+    pub exchange_rate: ExchangeRate,
+    pub expires_at: NaiveDateTime,
+}
+
+impl ExchangeRate {
+    // Functions
+
+    /// # Arguments
+    ///
+    /// * `source_currency` - The currency which is sold.
+    /// * `target_currency` - The currency which is bought.
+    /// * `rate` - The units of the target currency per unit of the source currency in millionths.
+    pub fn new(source_currency: Currency, target_currency: Currency, rate: i128) -> Self {
+        Self {
+            source_currency,
+            target_currency,
+            rate,
+        }
+    }
+
+    // Methods
+
+    /**
+     * Converts money of the source currency into the target currency.
+     * The fraction of the smallest unit of the target currency is rounded away according to `rounding`.
+     */
+    pub fn convert(&self, money: &Money, rounding: Rounding) -> Money {
+        money.multiply_ratio(self.rate, RATE_SCALE, rounding)
+    }
+}
+
+impl ExchangeRateQuote {
+    // Functions
+
+    pub fn new(exchange_rate: ExchangeRate, expires_at: NaiveDateTime) -> Self {
+        Self {
+            exchange_rate,
+            expires_at,
+        }
+    }
+}
+
+/// Parses `EUR/USD 1.0845`, the rate must be positive and have at most six decimal places.
+impl std::str::FromStr for ExchangeRate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pair, rate) = s
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or(format!("not an exchange rate: {}", s))?;
+        let (source_currency, target_currency) = pair
+            .split_once('/')
+            .ok_or(format!("not a currency pair: {}", pair))?;
+        Ok(ExchangeRate::new(
+            source_currency.parse()?,
+            target_currency.parse()?,
+            parse_rate(rate.trim())?,
+        ))
+    }
+}
+
+impl std::fmt::Display for ExchangeRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{} {}.{:06}",
+            self.source_currency,
+            self.target_currency,
+            self.rate / RATE_SCALE,
+            self.rate % RATE_SCALE
+        )
+    }
+}
+
+fn parse_rate(rate: &str) -> Result<i128, String> {
+    let invalid = || {
+        format!(
+            "not a positive rate with at most six decimal places: {}",
+            rate
+        )
+    };
+    let (units, fraction) = rate.split_once('.').unwrap_or((rate, ""));
+    if units.is_empty()
+        || fraction.len() > 6
+        || !units
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let units: i128 = units.parse().map_err(|_| invalid())?;
+    let fraction: i128 = format!("{:0<6}", fraction).parse().map_err(|_| invalid())?;
+    let rate = units * RATE_SCALE + fraction;
+    if rate == 0 {
+        return Err(invalid());
+    }
+    Ok(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_and_displays_exchange_rate() {
+        let exchange_rate: ExchangeRate = "EUR/USD 1.0845".parse().unwrap();
+        assert_eq!(
+            ExchangeRate::new(Currency::of("EUR"), Currency::of("USD"), 1_084_500),
+            exchange_rate
+        );
+        assert_eq!("EUR/USD 1.084500", exchange_rate.to_string());
+
+        assert!("EUR/USD 1.0845001".parse::<ExchangeRate>().is_err());
+        assert!("EUR/USD 0".parse::<ExchangeRate>().is_err());
+        assert!("EUR/USD -1".parse::<ExchangeRate>().is_err());
+        assert!("EURUSD 1".parse::<ExchangeRate>().is_err());
+        assert!("eur/USD 1".parse::<ExchangeRate>().is_err());
+    }
+
+    #[test]
+    fn test_converts_with_explicit_rounding() {
+        let exchange_rate =
+            ExchangeRate::new(Currency::of("EUR"), Currency::of("JPY"), 162_345_000);
+
+        // 3 * 162.345 = 487.035
+        assert_eq!(
+            Money::of(487),
+            exchange_rate.convert(&Money::of(3), Rounding::HalfEven)
+        );
+        assert_eq!(
+            Money::of(488),
+            exchange_rate.convert(&Money::of(3), Rounding::Up)
+        );
+    }
+}
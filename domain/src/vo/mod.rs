@@ -1,2 +1,9 @@
 pub mod activity_window;
+pub mod cron_expression;
+pub mod currency;
+pub mod exchange_rate;
 pub mod money;
+pub mod recurrence;
+pub mod role;
+pub mod rounding;
+pub mod transfer_limits;
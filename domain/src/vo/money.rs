@@ -1,6 +1,7 @@
+use super::rounding::Rounding;
 use num_bigint::BigInt;
 
-#[derive(Clone, PartialEq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Money {
     pub amount: BigInt,
 }
@@ -69,6 +70,33 @@ impl Money {
             amount: -&self.amount,
         }
     }
+
+    /**
+     * Multiplies the amount by `numerator / denominator`, e.g. 125 / 10_000 for 1.25 %.
+     * The fraction of the smallest unit is rounded away according to `rounding`.
+     */
+    pub fn multiply_ratio(&self, numerator: i128, denominator: i128, rounding: Rounding) -> Self {
+        assert!(denominator > 0, "denominator must be positive");
+        let product = &self.amount * BigInt::from(numerator);
+        let negative = product < BigInt::from(0);
+        let magnitude = if negative { -product } else { product };
+        let denominator = BigInt::from(denominator);
+        let quotient = &magnitude / &denominator;
+        let twice_remainder = (&magnitude % &denominator) * 2;
+        let round_up = match rounding {
+            Rounding::Down => false,
+            Rounding::Up => twice_remainder > BigInt::from(0),
+            Rounding::HalfUp => twice_remainder >= denominator,
+            Rounding::HalfEven => {
+                twice_remainder > denominator
+                    || (twice_remainder == denominator && &quotient % 2 != BigInt::from(0))
+            }
+        };
+        let rounded = if round_up { quotient + 1 } else { quotient };
+        Self {
+            amount: if negative { -rounded } else { rounded },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -137,4 +165,25 @@ mod tests {
         let money_negated = Money::of(-1);
         assert_eq!(money_negated, money.negate());
     }
+
+    #[test]
+    fn test_multiply_ratio() {
+        // 2.5 units
+        let money = Money::of(250);
+        assert_eq!(Money::of(2), money.multiply_ratio(1, 100, Rounding::Down));
+        assert_eq!(Money::of(3), money.multiply_ratio(1, 100, Rounding::Up));
+        assert_eq!(Money::of(3), money.multiply_ratio(1, 100, Rounding::HalfUp));
+        assert_eq!(
+            Money::of(2),
+            money.multiply_ratio(1, 100, Rounding::HalfEven)
+        );
+        assert_eq!(
+            Money::of(-3),
+            money.negate().multiply_ratio(1, 100, Rounding::HalfUp)
+        );
+        assert_eq!(
+            Money::of(4),
+            Money::of(350).multiply_ratio(1, 100, Rounding::HalfEven)
+        );
+    }
 }
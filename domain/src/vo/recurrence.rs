@@ -0,0 +1,104 @@
+use super::cron_expression::{CronExpression, InvalidCronExpression};
+use chrono::{Datelike, Days, Months, NaiveDateTime};
+use std::{fmt, str::FromStr};
+
+/**
+ * How often a standing order is executed.
+ */
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    Cron(CronExpression),
+}
+
+impl FromStr for Recurrence {
+    type Err = InvalidCronExpression;
+
+    /// `DAILY`, `WEEKLY`, `MONTHLY` (case-insensitive) or a [CronExpression].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "DAILY" => Ok(Recurrence::Daily),
+            "WEEKLY" => Ok(Recurrence::Weekly),
+            "MONTHLY" => Ok(Recurrence::Monthly),
+            _ => Ok(Recurrence::Cron(s.parse()?)),
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "DAILY"),
+            Recurrence::Weekly => write!(f, "WEEKLY"),
+            Recurrence::Monthly => write!(f, "MONTHLY"),
+            Recurrence::Cron(cron) => write!(f, "{}", cron),
+        }
+    }
+}
+
+// Methods
+impl Recurrence {
+    /**
+     * The occurrence following `previous` for a schedule that started at `start`.
+     * Monthly occurrences are anchored on the day of month of `start`, so a schedule
+     * starting on Jan 31st runs on Feb 28th (or 29th) and then on Mar 31st again.
+     */
+    pub fn next_after(
+        &self,
+        start: NaiveDateTime,
+        previous: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        match self {
+            Recurrence::Daily => previous.checked_add_days(Days::new(1)),
+            Recurrence::Weekly => previous.checked_add_days(Days::new(7)),
+            Recurrence::Monthly => {
+                let elapsed_months = (previous.year() - start.year()) * 12
+                    + previous.month() as i32
+                    - start.month() as i32;
+                start.checked_add_months(Months::new((elapsed_months + 1) as u32))
+            }
+            Recurrence::Cron(cron) => cron.next_after(previous),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(Recurrence::Daily, "daily".parse().unwrap());
+        assert_eq!(Recurrence::Monthly, "MONTHLY".parse().unwrap());
+        let cron = "0 8 1 * *".parse::<Recurrence>().unwrap();
+        assert_eq!("0 8 1 * *", cron.to_string());
+        assert!("fortnightly".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn test_weekly() {
+        assert_eq!(
+            Some(date(2019, 8, 10)),
+            Recurrence::Weekly.next_after(date(2019, 8, 3), date(2019, 8, 3))
+        );
+    }
+
+    #[test]
+    fn test_monthly_is_anchored_on_start_day() {
+        let start = date(2019, 1, 31);
+        let february = Recurrence::Monthly.next_after(start, start).unwrap();
+        assert_eq!(date(2019, 2, 28), february);
+        let march = Recurrence::Monthly.next_after(start, february).unwrap();
+        assert_eq!(date(2019, 3, 31), march);
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+}
@@ -0,0 +1,51 @@
+/**
+ * What someone acting on the accounts is entitled to, independent of the accounts themselves.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Role {
+    /// Acts on the accounts held under a mandate.
+    Customer,
+    /// Looks into every account to help customers, but changes none.
+    Support,
+    /// May do anything, e.g. grant mandates.
+    Admin,
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    /// `customer`, `support` or `admin` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "customer" => Ok(Role::Customer),
+            "support" => Ok(Role::Support),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("not a role: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Customer => write!(f, "customer"),
+            Role::Support => write!(f, "support"),
+            Role::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_what_it_displays() {
+        for role in [Role::Customer, Role::Support, Role::Admin] {
+            assert_eq!(role.to_string().parse(), Ok(role));
+        }
+        assert_eq!("Support".parse(), Ok(Role::Support));
+        assert!("teller".parse::<Role>().is_err());
+        assert!("operator".parse::<Role>().is_err());
+    }
+}
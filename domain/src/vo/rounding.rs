@@ -0,0 +1,39 @@
+/**
+ * How a fraction of the smallest unit of [Money] is rounded away.
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Rounding {
+    /// Towards zero.
+    Down,
+    /// Away from zero.
+    Up,
+    /// To the nearest unit, halves away from zero.
+    HalfUp,
+    /// To the nearest unit, halves to the even unit (banker's rounding).
+    HalfEven,
+}
+
+impl std::str::FromStr for Rounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DOWN" => Ok(Rounding::Down),
+            "UP" => Ok(Rounding::Up),
+            "HALF_UP" => Ok(Rounding::HalfUp),
+            "HALF_EVEN" => Ok(Rounding::HalfEven),
+            _ => Err(format!("unknown rounding: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Rounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rounding::Down => write!(f, "DOWN"),
+            Rounding::Up => write!(f, "UP"),
+            Rounding::HalfUp => write!(f, "HALF_UP"),
+            Rounding::HalfEven => write!(f, "HALF_EVEN"),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use super::money::Money;
+
+/**
+ * The velocity limits of outgoing transfers of an [Account]. A limit which is
+ * not set does not restrict the transfers.
+ */
+#[derive(Clone, PartialEq, Hash, Debug, Default)]
+pub struct TransferLimits {
+    pub daily_amount: Option<Money>,
+    pub monthly_amount: Option<Money>,
+    pub daily_count: Option<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TransferLimit {
+    DailyAmount,
+    MonthlyAmount,
+    DailyCount,
+}
+
+impl std::fmt::Display for TransferLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferLimit::DailyAmount => write!(f, "daily amount limit"),
+            TransferLimit::MonthlyAmount => write!(f, "monthly amount limit"),
+            TransferLimit::DailyCount => write!(f, "daily count limit"),
+        }
+    }
+}
+
+/**
+ * The sum and the number of outgoing transfers within a period.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct TransferVolume {
+    pub amount: Money,
+    pub count: u32,
+}
+
+/**
+ * The outgoing transfers of an [Account] of the current day and month.
+ */
+#[derive(Clone, PartialEq, Hash, Debug)]
+pub struct TransferUsage {
+    pub today: TransferVolume,
+    pub this_month: TransferVolume,
+}
+
+// Associated Functions
+impl TransferLimits {
+    /// # Arguments
+    ///
+    /// * `daily_amount` - The maximum sum of outgoing transfers per day.
+    /// * `monthly_amount` - The maximum sum of outgoing transfers per month.
+    /// * `daily_count` - The maximum number of outgoing transfers per day.
+    pub fn new(
+        daily_amount: Option<Money>,
+        monthly_amount: Option<Money>,
+        daily_count: Option<u32>,
+    ) -> Self {
+        Self {
+            daily_amount,
+            monthly_amount,
+            daily_count,
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+}
+
+// Methods
+impl TransferLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.daily_amount.is_none() && self.monthly_amount.is_none() && self.daily_count.is_none()
+    }
+
+    pub fn has_negative_limit(&self) -> bool {
+        self.daily_amount.as_ref().is_some_and(Money::is_negative)
+            || self.monthly_amount.as_ref().is_some_and(Money::is_negative)
+    }
+
+    /**
+     * Every limit set in `overrides` replaces the corresponding limit of these limits.
+     */
+    pub fn overridden_by(&self, overrides: &TransferLimits) -> TransferLimits {
+        TransferLimits {
+            daily_amount: overrides
+                .daily_amount
+                .clone()
+                .or_else(|| self.daily_amount.clone()),
+            monthly_amount: overrides
+                .monthly_amount
+                .clone()
+                .or_else(|| self.monthly_amount.clone()),
+            daily_count: overrides.daily_count.or(self.daily_count),
+        }
+    }
+
+    /**
+     * Checks whether one more transfer of `money` stays within the limits.
+     * @return the first limit which would be exceeded.
+     */
+    pub fn check(&self, usage: &TransferUsage, money: &Money) -> Result<(), TransferLimit> {
+        if self
+            .daily_amount
+            .as_ref()
+            .is_some_and(|limit| usage.today.amount.plus(money).is_greater_than(limit))
+        {
+            return Err(TransferLimit::DailyAmount);
+        }
+        if self
+            .monthly_amount
+            .as_ref()
+            .is_some_and(|limit| usage.this_month.amount.plus(money).is_greater_than(limit))
+        {
+            return Err(TransferLimit::MonthlyAmount);
+        }
+        if self
+            .daily_count
+            .is_some_and(|limit| usage.today.count >= limit)
+        {
+            return Err(TransferLimit::DailyCount);
+        }
+        Ok(())
+    }
+}
+
+// Associated Functions
+impl TransferVolume {
+    pub fn new(amount: Money, count: u32) -> Self {
+        Self { amount, count }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Money::of(0), 0)
+    }
+}
+
+// Methods
+impl TransferVolume {
+    pub fn record(&mut self, money: &Money) {
+        self.amount = self.amount.plus(money);
+        self.count += 1;
+    }
+}
+
+// Associated Functions
+impl TransferUsage {
+    pub fn new(today: TransferVolume, this_month: TransferVolume) -> Self {
+        Self { today, this_month }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(TransferVolume::empty(), TransferVolume::empty())
+    }
+}
+
+// Methods
+impl TransferUsage {
+    /**
+     * Adds a transfer of `money` to the volume of the current day and month.
+     */
+    pub fn record(&mut self, money: &Money) {
+        self.today.record(money);
+        self.this_month.record(money);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_limits_override_default_limits() {
+        let default_limits =
+            TransferLimits::new(Some(Money::of(1_000)), Some(Money::of(10_000)), None);
+        let limits = default_limits.overridden_by(&TransferLimits::new(
+            Some(Money::of(5_000)),
+            None,
+            Some(3),
+        ));
+        assert_eq!(
+            TransferLimits::new(Some(Money::of(5_000)), Some(Money::of(10_000)), Some(3)),
+            limits
+        );
+    }
+
+    #[test]
+    fn test_check_names_exceeded_limit() {
+        let limits = TransferLimits::new(Some(Money::of(1_000)), Some(Money::of(2_000)), Some(2));
+        let mut usage = TransferUsage::new(
+            TransferVolume::new(Money::of(500), 1),
+            TransferVolume::new(Money::of(1_800), 4),
+        );
+
+        assert_eq!(Ok(()), limits.check(&usage, &Money::of(200)));
+        assert_eq!(
+            Err(TransferLimit::DailyAmount),
+            limits.check(&usage, &Money::of(501))
+        );
+        assert_eq!(
+            Err(TransferLimit::MonthlyAmount),
+            limits.check(&usage, &Money::of(201))
+        );
+
+        usage.record(&Money::of(100));
+        assert_eq!(
+            Err(TransferLimit::DailyCount),
+            limits.check(&usage, &Money::of(1))
+        );
+    }
+
+    #[test]
+    fn test_unlimited_allows_everything() {
+        let limits = TransferLimits::unlimited();
+        assert!(limits.is_unlimited());
+        assert_eq!(
+            Ok(()),
+            limits.check(&TransferUsage::empty(), &Money::of(i64::MAX as i128))
+        );
+    }
+}
@@ -0,0 +1,13 @@
+create table scheduled_transfer_entity(
+    id integer primary key autoincrement not null,
+    source_account_id integer not null,
+    target_account_id integer not null,
+    amount integer not null,
+    execution_date text not null,
+    status text not null,
+    executed_at text,
+    failure_reason text
+);
+
+create index scheduled_transfer_entity_status_execution_date
+    on scheduled_transfer_entity(status, execution_date);
@@ -0,0 +1,27 @@
+create table standing_order_entity(
+    id integer primary key autoincrement not null,
+    source_account_id integer not null,
+    target_account_id integer not null,
+    amount integer not null,
+    recurrence text not null,
+    start_date text not null,
+    end_date text,
+    max_occurrences integer,
+    executed_occurrences integer not null,
+    next_execution_date text,
+    status text not null
+);
+
+create index standing_order_entity_status_next_execution_date
+    on standing_order_entity(status, next_execution_date);
+
+create table standing_order_execution_entity(
+    id integer primary key autoincrement not null,
+    standing_order_id integer not null references standing_order_entity(id),
+    scheduled_for text not null,
+    started_at text not null,
+    finished_at text,
+    status text not null,
+    failure_reason text,
+    unique (standing_order_id, scheduled_for)
+);
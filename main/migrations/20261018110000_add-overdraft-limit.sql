@@ -0,0 +1,10 @@
+alter table account_entity add column overdraft_limit integer not null default 0;
+
+create table overdraft_limit_change_entity(
+    id integer primary key autoincrement not null,
+    account_id integer not null,
+    previous_limit integer not null,
+    new_limit integer not null,
+    changed_by text not null,
+    changed_at text not null
+);
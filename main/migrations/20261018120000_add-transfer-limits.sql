@@ -0,0 +1,3 @@
+alter table account_entity add column daily_amount_limit integer;
+alter table account_entity add column monthly_amount_limit integer;
+alter table account_entity add column daily_count_limit integer;
@@ -0,0 +1,2 @@
+-- collects the fees charged on transfers
+insert into account_entity (id) values (100);
@@ -0,0 +1,13 @@
+alter table account_entity add column interest_bearing integer not null default 0;
+
+create table interest_accrual_entity(
+    account_id integer not null,
+    accrual_date text not null,
+    balance integer not null,
+    capitalised_interest integer,
+    primary key (account_id, accrual_date)
+);
+
+-- pays the interest of the savings accounts, which has to hold the money or be granted an overdraft
+-- like any other account
+insert into account_entity (id) values (101);
@@ -0,0 +1,9 @@
+alter table account_entity add column currency text not null default 'EUR';
+
+alter table activity_entity add column exchange_rate_source_currency text;
+alter table activity_entity add column exchange_rate_target_currency text;
+-- in millionths
+alter table activity_entity add column exchange_rate integer;
+
+-- held in US Dollar, money is sent to it from Euro accounts at a quoted exchange rate
+insert into account_entity (id, currency) values (3, 'USD');
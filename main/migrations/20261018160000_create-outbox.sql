@@ -0,0 +1,18 @@
+create table outbox_message_entity(
+    id integer primary key autoincrement not null,
+    event_type text not null,
+    account_id integer,
+    source_account_id integer not null,
+    target_account_id integer not null,
+    amount integer not null,
+    fee integer,
+    reason text,
+    timestamp text not null,
+    attempts integer not null default 0,
+    next_attempt_at text not null,
+    last_error text,
+    processed_at text
+);
+
+create index outbox_message_entity_processed_at
+    on outbox_message_entity(processed_at, id);
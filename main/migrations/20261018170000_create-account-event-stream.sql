@@ -0,0 +1,50 @@
+-- the accounts as streams of events, written together with the activities and overdraft limit changes
+create table account_event_entity(
+    id integer primary key autoincrement not null,
+    account_id integer not null,
+    event_type text not null,
+    timestamp text not null,
+    -- ACCOUNT_OPENED
+    currency text,
+    -- ACCOUNT_OPENED and OVERDRAFT_LIMIT_CHANGED
+    overdraft_limit integer,
+    -- MONEY_WITHDRAWN and MONEY_DEPOSITED
+    activity_id integer,
+    counterpart_account_id integer,
+    amount integer,
+    exchange_rate_source_currency text,
+    exchange_rate_target_currency text,
+    exchange_rate integer
+);
+
+create index account_event_entity_account_id
+    on account_event_entity(account_id, id);
+
+-- the replayed state of all events of an account up to and including the last event
+create table account_snapshot_entity(
+    account_id integer primary key not null,
+    last_event_id integer not null,
+    last_timestamp text not null,
+    currency text not null,
+    overdraft_limit integer not null,
+    withdrawal_balance integer not null,
+    deposit_balance integer not null
+);
+
+-- the streams of the existing accounts start with their first activity
+insert into account_event_entity (account_id, event_type, timestamp, currency, overdraft_limit)
+select a.id, 'ACCOUNT_OPENED',
+    coalesce((select min(timestamp) from activity_entity where owner_account_id = a.id), '2018-01-01 00:00:00'),
+    a.currency, a.overdraft_limit
+from account_entity a
+order by a.id;
+
+insert into account_event_entity (account_id, event_type, timestamp, activity_id, counterpart_account_id, amount,
+    exchange_rate_source_currency, exchange_rate_target_currency, exchange_rate)
+select owner_account_id,
+    case when owner_account_id = source_account_id then 'MONEY_WITHDRAWN' else 'MONEY_DEPOSITED' end,
+    timestamp, id,
+    case when owner_account_id = source_account_id then target_account_id else source_account_id end,
+    amount, exchange_rate_source_currency, exchange_rate_target_currency, exchange_rate
+from activity_entity
+order by id;
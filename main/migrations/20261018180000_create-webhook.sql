@@ -0,0 +1,31 @@
+create table webhook_subscription_entity(
+    id integer primary key autoincrement not null,
+    account_id integer not null,
+    url text not null,
+    secret text not null,
+    created_at text not null
+);
+
+create index webhook_subscription_entity_account_id
+    on webhook_subscription_entity(account_id);
+
+-- the deliveries outlive their subscription as the delivery log
+create table webhook_delivery_entity(
+    id integer primary key autoincrement not null,
+    subscription_id integer not null,
+    event_type text not null,
+    account_id integer not null,
+    counterpart_account_id integer not null,
+    amount integer not null,
+    timestamp text not null,
+    status text not null,
+    attempts integer not null,
+    next_attempt_at text not null,
+    last_error text,
+    finished_at text,
+    -- an event relayed again is not notified twice
+    unique (subscription_id, event_type, account_id, counterpart_account_id, amount, timestamp)
+);
+
+create index webhook_delivery_entity_status_next_attempt_at
+    on webhook_delivery_entity(status, next_attempt_at);
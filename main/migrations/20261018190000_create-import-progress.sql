@@ -0,0 +1,6 @@
+-- the last line of each imported source stored so far, an interrupted import resumes after it
+create table import_progress_entity(
+    source text primary key not null,
+    last_line_number integer not null,
+    updated_at text not null
+);
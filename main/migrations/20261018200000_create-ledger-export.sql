@@ -0,0 +1,13 @@
+-- the general-ledger files written, an incremental export continues after the last activity of all of them
+create table ledger_export_entity(
+    id integer primary key autoincrement not null,
+    exported_at text not null,
+    period_from text,
+    period_to text,
+    first_activity_id integer,
+    last_activity_id integer,
+    transfer_count integer not null,
+    incomplete_transfer_count integer not null,
+    file_path text not null,
+    checksum text not null
+);
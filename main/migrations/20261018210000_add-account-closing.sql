@@ -0,0 +1,4 @@
+-- closed accounts neither take withdrawals nor deposits
+alter table account_entity add column closed_at text;
+
+alter table account_snapshot_entity add column closed_at text;
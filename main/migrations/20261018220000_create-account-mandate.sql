@@ -0,0 +1,18 @@
+-- the customers entitled to act on an account, by the subject they are authenticated as
+create table account_mandate_entity(
+    account_id integer not null,
+    customer text not null,
+    kind text not null,
+    granted_at text not null,
+    primary key (account_id, customer)
+);
+
+-- attempts to act on an account without being entitled to, for the audit
+create table authorization_denial_entity(
+    id integer primary key autoincrement not null,
+    subject text not null,
+    role text not null,
+    action text not null,
+    account_id integer not null,
+    denied_at text not null
+);
@@ -0,0 +1,7 @@
+-- scheduled transfers and standing orders are executed as whoever created them; those created
+-- before are kept running as the system
+alter table scheduled_transfer_entity add column created_by text not null default 'system';
+alter table scheduled_transfer_entity add column created_by_role text not null default 'admin';
+
+alter table standing_order_entity add column created_by text not null default 'system';
+alter table standing_order_entity add column created_by_role text not null default 'admin';
@@ -0,0 +1,3 @@
+-- when an executor claimed a transfer, so that a claim interrupted by a crash is detected; the
+-- transfers claimed before are taken as interrupted
+alter table scheduled_transfer_entity add column claimed_at text;
@@ -0,0 +1,938 @@
+use domain::vo::role::Role;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub const USAGE: &str = "usage: main [--config <file>] [--print-config] [<command> [<args>]]
+
+  --config <file>  reads the configuration from a TOML file, CONFIG_FILE if not given
+  --print-config   prints the effective configuration and exits
+
+Every setting can be overridden by an environment variable:
+  BIND_ADDRESS          server.bind_address
+  SHUTDOWN_TIMEOUT      server.shutdown_timeout_seconds
+  DATABASE_URL          database.url
+  DATABASE_POOL_SIZE    database.pool_size
+  TRANSFER_THRESHOLD    transfer.threshold
+  BASELINE_WINDOW_DAYS  transfer.baseline_window_days
+  DAILY_AMOUNT_LIMIT    transfer.default_limits.daily_amount
+  MONTHLY_AMOUNT_LIMIT  transfer.default_limits.monthly_amount
+  DAILY_COUNT_LIMIT     transfer.default_limits.daily_count
+  FEES_ENABLED          transfer.fees.enabled
+  FEE_ACCOUNT_ID        transfer.fees.collection_account_id
+  INTEREST_RATE         interest.annual_rate_basis_points
+  INTEREST_ACCOUNT_ID   interest.funding_account_id
+  EXCHANGE_RATES_FILE   exchange_rates.file
+  LOG_LEVEL             log.level
+  LOG_FORMAT            log.format
+  ACCOUNT_PERSISTENCE   features.account_persistence
+  DOMAIN_EVENT_SINK     features.domain_event_sink
+  BACKGROUND_JOBS       features.background_jobs
+  JWT_ALGORITHM         auth.jwt.algorithm
+  JWT_KEY_FILE          auth.jwt.key_file";
+
+const CONFIG_FILE: &str = "CONFIG_FILE";
+const IN_MEMORY_DATABASE_URL: &str = "sqlite::memory:";
+const LOG_LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+/**
+ * The options which precede the command.
+ */
+#[derive(Debug, Default, PartialEq)]
+pub struct ConfigArgs {
+    pub config_file: Option<PathBuf>,
+    pub print_config: bool,
+}
+
+impl ConfigArgs {
+    /**
+     * @return the options and the remaining arguments, i.e. the command and its arguments.
+     */
+    pub fn parse(args: &[String]) -> Result<(Self, &[String]), String> {
+        let mut config_args = ConfigArgs::default();
+        let mut index = 0;
+        while let Some(arg) = args.get(index) {
+            match arg.as_str() {
+                "--config" => {
+                    let config_file = args
+                        .get(index + 1)
+                        .ok_or("--config requires a file".to_string())?;
+                    config_args.config_file = Some(PathBuf::from(config_file));
+                    index += 2;
+                }
+                "--print-config" => {
+                    config_args.print_config = true;
+                    index += 1;
+                }
+                _ => break,
+            }
+        }
+        Ok((config_args, &args[index..]))
+    }
+}
+
+/**
+ * The configuration of the service. Missing sections and settings take the defaults, unknown
+ * ones are rejected so that a misspelt setting does not silently fall back to its default.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub transfer: TransferConfig,
+    pub interest: InterestConfig,
+    pub exchange_rates: ExchangeRatesConfig,
+    pub log: LogConfig,
+    pub features: FeaturesConfig,
+    pub auth: AuthConfig,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind_address: String,
+    /// How long a shutdown waits for the requests and transfers in flight.
+    pub shutdown_timeout_seconds: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: "127.0.0.1:8080".to_string(),
+            shutdown_timeout_seconds: 30,
+        }
+    }
+}
+
+/**
+ * An in-memory database unless stated otherwise, e.g. `sqlite://buckpal.db?mode=rwc` for a file
+ * that outlives the process.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub pool_size: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: IN_MEMORY_DATABASE_URL.to_string(),
+            pool_size: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransferConfig {
+    /// The maximum amount of a single transfer.
+    pub threshold: i64,
+    /// How many days of activities are loaded with an account to calculate its balance.
+    pub baseline_window_days: u64,
+    /// The velocity limits of the accounts which do not override them.
+    pub default_limits: TransferLimitsConfig,
+    pub fees: FeesConfig,
+}
+
+impl Default for TransferConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 1_000,
+            baseline_window_days: 10,
+            default_limits: TransferLimitsConfig::default(),
+            fees: FeesConfig::default(),
+        }
+    }
+}
+
+/**
+ * The limits on what an account sends per day and month, 0 for no limit, which is the default.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransferLimitsConfig {
+    pub daily_amount: i64,
+    pub monthly_amount: i64,
+    pub daily_count: u32,
+}
+
+/**
+ * The fee charged on transfers once enabled and the account collecting it, which itself sends and
+ * receives money free of charge.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeesConfig {
+    pub enabled: bool,
+    pub collection_account_id: i64,
+    pub policy: FeePolicyConfig,
+}
+
+impl Default for FeesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            collection_account_id: 100,
+            // 0.1 % of the transferred money, at least 1 and at most 50
+            policy: FeePolicyConfig::Capped {
+                policy: Box::new(FeePolicyConfig::Percentage { basis_points: 10 }),
+                minimum: Some(1),
+                maximum: Some(50),
+            },
+        }
+    }
+}
+
+/**
+ * A fee policy, selected by its `type`, e.g. `{ type = "flat", fee = 5 }`. A flat fee of 0 makes
+ * the transfers free.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum FeePolicyConfig {
+    Flat {
+        fee: i64,
+    },
+    /// In basis points, i.e. 1/100 of a percent.
+    Percentage {
+        basis_points: i64,
+    },
+    /// The policy of the highest tier not above the transferred money, below all tiers no fee.
+    Tiered {
+        tiers: Vec<FeeTierConfig>,
+    },
+    Capped {
+        policy: Box<FeePolicyConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        minimum: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        maximum: Option<i64>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeTierConfig {
+    pub from: i64,
+    pub policy: FeePolicyConfig,
+}
+
+/**
+ * The interest paid on the savings accounts. The funding account of the bank pays it like any
+ * other transfer, so it has to hold the money or be granted an overdraft.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InterestConfig {
+    /// In basis points, i.e. 1/100 of a percent.
+    pub annual_rate_basis_points: i64,
+    pub funding_account_id: i64,
+}
+
+impl Default for InterestConfig {
+    fn default() -> Self {
+        Self {
+            annual_rate_basis_points: 150,
+            funding_account_id: 101,
+        }
+    }
+}
+
+/**
+ * The rate table of the currency exchanges, which can be replaced without a rebuild. Each line
+ * holds a rate like `EUR/USD 1.0845`.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExchangeRatesConfig {
+    pub file: String,
+}
+
+impl Default for ExchangeRatesConfig {
+    fn default() -> Self {
+        Self {
+            file: "main/exchange-rates.txt".to_string(),
+        }
+    }
+}
+
+/**
+ * The level is either a plain level or a list of `tracing` filter directives, e.g.
+ * `info,adapters_outbound_persistence=debug`.
+ */
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            // sqlx logs every statement at the info level
+            level: "info,sqlx=warn".to_string(),
+            format: LogFormat::Json,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// One JSON object per event with the fields of its spans, for the log collectors.
+    Json,
+    /// Human-readable, multi-line events, for development.
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            _ => Err("expected `json` or `pretty`".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesConfig {
+    pub account_persistence: AccountPersistence,
+    /// Either `stdout`, `file:<path>` for a JSON-lines file or `webhook:<url>`.
+    pub domain_event_sink: String,
+    /// Whether the server runs the scheduled transfers, standing orders, interest accrual,
+    /// outbox relay and webhook delivery, which can be disabled on all but one instance.
+    pub background_jobs: bool,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        Self {
+            account_persistence: AccountPersistence::Crud,
+            domain_event_sink: "stdout".to_string(),
+            background_jobs: true,
+        }
+    }
+}
+
+/**
+ * Both are written in the same transactions, so either can be selected.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccountPersistence {
+    /// The account rows and their activities.
+    Crud,
+    /// The replayed event streams.
+    EventSourced,
+}
+
+impl FromStr for AccountPersistence {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "crud" => Ok(AccountPersistence::Crud),
+            "event-sourced" => Ok(AccountPersistence::EventSourced),
+            _ => Err("expected `crud` or `event-sourced`".to_string()),
+        }
+    }
+}
+
+/**
+ * How the REST API authenticates its clients. Without any API key or JWT key every request but
+ * the health checks and the metrics is rejected.
+ */
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    pub api_keys: Vec<ApiKeyConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<JwtConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// The principal the key authenticates.
+    pub subject: String,
+    /// One of `customer`, `support` or `admin`, `customer` if not given.
+    #[serde(default = "default_api_key_role")]
+    pub role: String,
+    /// The hex SHA-256 digest of the key, e.g. from `printf %s <key> | sha256sum`.
+    pub key_sha256: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JwtConfig {
+    pub algorithm: JwtAlgorithm,
+    /// The shared secret for HS256 or the public key in PEM format for RS256, read at startup.
+    pub key_file: String,
+    /// The `iss` claim the tokens must carry, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// The `aud` claim the tokens must carry, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audience: Option<String>,
+}
+
+fn default_api_key_role() -> String {
+    Role::Customer.to_string()
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: JwtAlgorithm::Rs256,
+            key_file: String::new(),
+            issuer: None,
+            audience: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum JwtAlgorithm {
+    #[serde(rename = "HS256")]
+    Hs256,
+    #[serde(rename = "RS256")]
+    Rs256,
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "HS256" => Ok(JwtAlgorithm::Hs256),
+            "RS256" => Ok(JwtAlgorithm::Rs256),
+            _ => Err("expected `HS256` or `RS256`".to_string()),
+        }
+    }
+}
+
+impl Config {
+    // Functions
+
+    /**
+     * Reads the configuration file, if any is given or set in `CONFIG_FILE`, and applies the
+     * environment variable overrides.
+     *
+     * # Arguments
+     *
+     * * `config_file` - The file given on the command line.
+     * * `env` - Looks up an environment variable.
+     */
+    pub fn load(
+        config_file: Option<&Path>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let config_file = config_file
+            .map(Path::to_path_buf)
+            .or(env(CONFIG_FILE).map(PathBuf::from));
+        let mut config = match config_file {
+            Some(config_file) => Self::read(&config_file)?,
+            None => Config::default(),
+        };
+        config.apply_env_overrides(env)?;
+        Ok(config)
+    }
+
+    fn read(config_file: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(config_file)
+            .map_err(|error| format!("{}: {}", config_file.display(), error))?;
+        toml::from_str(&content).map_err(|error| format!("{}: {}", config_file.display(), error))
+    }
+
+    // Methods
+
+    fn apply_env_overrides(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(value) = env("BIND_ADDRESS") {
+            self.server.bind_address = value;
+        }
+        if let Some(value) = env("SHUTDOWN_TIMEOUT") {
+            self.server.shutdown_timeout_seconds = parse_override("SHUTDOWN_TIMEOUT", &value)?;
+        }
+        if let Some(value) = env("DATABASE_URL") {
+            self.database.url = value;
+        }
+        if let Some(value) = env("DATABASE_POOL_SIZE") {
+            self.database.pool_size = parse_override("DATABASE_POOL_SIZE", &value)?;
+        }
+        if let Some(value) = env("TRANSFER_THRESHOLD") {
+            self.transfer.threshold = parse_override("TRANSFER_THRESHOLD", &value)?;
+        }
+        if let Some(value) = env("BASELINE_WINDOW_DAYS") {
+            self.transfer.baseline_window_days = parse_override("BASELINE_WINDOW_DAYS", &value)?;
+        }
+        if let Some(value) = env("DAILY_AMOUNT_LIMIT") {
+            self.transfer.default_limits.daily_amount =
+                parse_override("DAILY_AMOUNT_LIMIT", &value)?;
+        }
+        if let Some(value) = env("MONTHLY_AMOUNT_LIMIT") {
+            self.transfer.default_limits.monthly_amount =
+                parse_override("MONTHLY_AMOUNT_LIMIT", &value)?;
+        }
+        if let Some(value) = env("DAILY_COUNT_LIMIT") {
+            self.transfer.default_limits.daily_count = parse_override("DAILY_COUNT_LIMIT", &value)?;
+        }
+        if let Some(value) = env("FEES_ENABLED") {
+            self.transfer.fees.enabled = parse_override("FEES_ENABLED", &value)?;
+        }
+        if let Some(value) = env("FEE_ACCOUNT_ID") {
+            self.transfer.fees.collection_account_id = parse_override("FEE_ACCOUNT_ID", &value)?;
+        }
+        if let Some(value) = env("INTEREST_RATE") {
+            self.interest.annual_rate_basis_points = parse_override("INTEREST_RATE", &value)?;
+        }
+        if let Some(value) = env("INTEREST_ACCOUNT_ID") {
+            self.interest.funding_account_id = parse_override("INTEREST_ACCOUNT_ID", &value)?;
+        }
+        if let Some(value) = env("EXCHANGE_RATES_FILE") {
+            self.exchange_rates.file = value;
+        }
+        if let Some(value) = env("LOG_LEVEL") {
+            self.log.level = value;
+        }
+        if let Some(value) = env("LOG_FORMAT") {
+            self.log.format = parse_override("LOG_FORMAT", &value)?;
+        }
+        if let Some(value) = env("ACCOUNT_PERSISTENCE") {
+            self.features.account_persistence = parse_override("ACCOUNT_PERSISTENCE", &value)?;
+        }
+        if let Some(value) = env("DOMAIN_EVENT_SINK") {
+            self.features.domain_event_sink = value;
+        }
+        if let Some(value) = env("BACKGROUND_JOBS") {
+            self.features.background_jobs = parse_override("BACKGROUND_JOBS", &value)?;
+        }
+        if let Some(value) = env("JWT_ALGORITHM") {
+            let jwt = self.auth.jwt.get_or_insert_with(JwtConfig::default);
+            jwt.algorithm = parse_override("JWT_ALGORITHM", &value)?;
+        }
+        if let Some(value) = env("JWT_KEY_FILE") {
+            let jwt = self.auth.jwt.get_or_insert_with(JwtConfig::default);
+            jwt.key_file = value;
+        }
+        Ok(())
+    }
+
+    /**
+     * @return all invalid settings at once, one per line.
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if let Err(error) = SocketAddr::from_str(&self.server.bind_address) {
+            errors.push(format!(
+                "server.bind_address = {:?}: {}",
+                self.server.bind_address, error
+            ));
+        }
+        if !self.database.url.starts_with("sqlite:") {
+            errors.push(format!(
+                "database.url = {:?}: expected a `sqlite:` URL",
+                self.database.url
+            ));
+        }
+        if self.database.pool_size == 0 {
+            errors.push("database.pool_size = 0: expected at least 1".to_string());
+        } else if self.database.url == IN_MEMORY_DATABASE_URL && self.database.pool_size > 1 {
+            errors.push(format!(
+                "database.pool_size = {}: an in-memory database requires 1, as every connection \
+                 would open a database of its own",
+                self.database.pool_size
+            ));
+        }
+        if self.transfer.threshold <= 0 {
+            errors.push(format!(
+                "transfer.threshold = {}: expected a positive amount",
+                self.transfer.threshold
+            ));
+        }
+        if self.transfer.baseline_window_days == 0 {
+            errors.push("transfer.baseline_window_days = 0: expected at least 1".to_string());
+        }
+        let default_limits = &self.transfer.default_limits;
+        for (setting, amount) in [
+            ("daily_amount", default_limits.daily_amount),
+            ("monthly_amount", default_limits.monthly_amount),
+        ] {
+            if amount < 0 {
+                errors.push(format!(
+                    "transfer.default_limits.{} = {}: expected a positive amount or 0 for no limit",
+                    setting, amount
+                ));
+            }
+        }
+        if self.transfer.fees.collection_account_id <= 0 {
+            errors.push(format!(
+                "transfer.fees.collection_account_id = {}: expected an account",
+                self.transfer.fees.collection_account_id
+            ));
+        }
+        validate_fee_policy(
+            &self.transfer.fees.policy,
+            "transfer.fees.policy",
+            &mut errors,
+        );
+        if self.interest.annual_rate_basis_points < 0 {
+            errors.push(format!(
+                "interest.annual_rate_basis_points = {}: expected a positive rate or 0",
+                self.interest.annual_rate_basis_points
+            ));
+        }
+        if self.interest.funding_account_id <= 0 {
+            errors.push(format!(
+                "interest.funding_account_id = {}: expected an account",
+                self.interest.funding_account_id
+            ));
+        }
+        if self.exchange_rates.file.is_empty() {
+            errors.push("exchange_rates.file = \"\": expected a file".to_string());
+        }
+        if !is_valid_log_level(&self.log.level) {
+            errors.push(format!(
+                "log.level = {:?}: expected one of {} or a list of `<module>=<level>` directives",
+                self.log.level,
+                LOG_LEVELS.join(", ")
+            ));
+        }
+        if !is_valid_domain_event_sink(&self.features.domain_event_sink) {
+            errors.push(format!(
+                "features.domain_event_sink = {:?}: expected `stdout`, `file:<path>` or \
+                 `webhook:<url>`",
+                self.features.domain_event_sink
+            ));
+        }
+        for (index, api_key) in self.auth.api_keys.iter().enumerate() {
+            if api_key.subject.is_empty() {
+                errors.push(format!(
+                    "auth.api_keys[{}].subject = \"\": expected a principal",
+                    index
+                ));
+            }
+            if let Err(error) = api_key.role.parse::<Role>() {
+                errors.push(format!("auth.api_keys[{}].role: {}", index, error));
+            }
+            if !is_valid_sha256(&api_key.key_sha256) {
+                errors.push(format!(
+                    "auth.api_keys[{}].key_sha256 = {:?}: expected 64 hex digits",
+                    index, api_key.key_sha256
+                ));
+            }
+        }
+        if let Some(jwt) = &self.auth.jwt
+            && jwt.key_file.is_empty()
+        {
+            errors.push("auth.jwt.key_file = \"\": expected a file".to_string());
+        }
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(format!("invalid configuration:\n  {}", errors.join("\n  ")))
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap()
+    }
+}
+
+fn parse_override<T: FromStr>(variable: &str, value: &str) -> Result<T, String>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|error| format!("{}={:?}: {}", variable, value, error))
+}
+
+fn is_valid_log_level(level: &str) -> bool {
+    !level.is_empty()
+        && level.split(',').all(|directive| {
+            let level = directive.rsplit('=').next().unwrap().trim();
+            LOG_LEVELS.contains(&level.to_lowercase().as_str())
+        })
+}
+
+fn is_valid_domain_event_sink(domain_event_sink: &str) -> bool {
+    if let Some(path) = domain_event_sink.strip_prefix("file:") {
+        return !path.is_empty();
+    }
+    if let Some(url) = domain_event_sink.strip_prefix("webhook:") {
+        return url.starts_with("http://") || url.starts_with("https://");
+    }
+    domain_event_sink == "stdout"
+}
+
+/**
+ * Fees, their bounds and the lower bounds of the tiers must not be negative.
+ */
+fn validate_fee_policy(policy: &FeePolicyConfig, setting: &str, errors: &mut Vec<String>) {
+    let mut check = |name: &str, amount: i64| {
+        if amount < 0 {
+            errors.push(format!(
+                "{}.{} = {}: expected a positive amount or 0",
+                setting, name, amount
+            ));
+        }
+    };
+    match policy {
+        FeePolicyConfig::Flat { fee } => check("fee", *fee),
+        FeePolicyConfig::Percentage { basis_points } => check("basis_points", *basis_points),
+        FeePolicyConfig::Tiered { tiers } => {
+            for (index, tier) in tiers.iter().enumerate() {
+                let setting = format!("{}.tiers[{}]", setting, index);
+                if tier.from < 0 {
+                    errors.push(format!(
+                        "{}.from = {}: expected a positive amount or 0",
+                        setting, tier.from
+                    ));
+                }
+                validate_fee_policy(&tier.policy, &format!("{}.policy", setting), errors);
+            }
+        }
+        FeePolicyConfig::Capped {
+            policy,
+            minimum,
+            maximum,
+        } => {
+            if let Some(minimum) = minimum {
+                check("minimum", *minimum);
+            }
+            if let Some(maximum) = maximum {
+                check("maximum", *maximum);
+            }
+            validate_fee_policy(policy, &format!("{}.policy", setting), errors);
+        }
+    }
+}
+
+fn is_valid_sha256(digest: &str) -> bool {
+    digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_loads_file_with_env_overrides() {
+        // Given a configuration file
+        let config_file = std::env::temp_dir().join(format!("config-{}.toml", std::process::id()));
+        std::fs::write(
+            &config_file,
+            r#"
+[database]
+url = "sqlite://buckpal.db?mode=rwc"
+pool_size = 4
+
+[transfer]
+threshold = 5000
+
+[transfer.default_limits]
+daily_amount = 0
+
+[transfer.fees.policy]
+type = "tiered"
+
+[[transfer.fees.policy.tiers]]
+from = 0
+policy = { type = "flat", fee = 1 }
+
+[[transfer.fees.policy.tiers]]
+from = 1000
+policy = { type = "percentage", basis_points = 5 }
+
+[features]
+account_persistence = "event-sourced"
+
+[[auth.api_keys]]
+subject = "reporting"
+key_sha256 = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+"#,
+        )
+        .unwrap();
+
+        // And environment variables overriding some of its settings
+        let env = HashMap::from([
+            ("DATABASE_POOL_SIZE", "8"),
+            ("BASELINE_WINDOW_DAYS", "30"),
+            ("DAILY_COUNT_LIMIT", "50"),
+            ("FEES_ENABLED", "true"),
+            ("FEE_ACCOUNT_ID", "7"),
+            ("INTEREST_ACCOUNT_ID", "8"),
+            ("BACKGROUND_JOBS", "false"),
+            ("LOG_FORMAT", "pretty"),
+            ("JWT_KEY_FILE", "/etc/buckpal/jwt.pub.pem"),
+        ]);
+
+        // When the configuration is loaded
+        let config = Config::load(Some(config_file.as_path()), |variable| {
+            env.get(variable).map(|value| value.to_string())
+        })
+        .unwrap();
+        std::fs::remove_file(&config_file).unwrap();
+
+        // Then the overrides win over the file, which wins over the defaults
+        assert_eq!(config.server, ServerConfig::default());
+        assert_eq!(config.database.url, "sqlite://buckpal.db?mode=rwc");
+        assert_eq!(config.database.pool_size, 8);
+        assert_eq!(config.transfer.threshold, 5000);
+        assert_eq!(config.transfer.baseline_window_days, 30);
+        assert_eq!(
+            config.transfer.default_limits,
+            TransferLimitsConfig {
+                daily_amount: 0,
+                daily_count: 50,
+                ..TransferLimitsConfig::default()
+            }
+        );
+        assert_eq!(
+            config.transfer.fees,
+            FeesConfig {
+                enabled: true,
+                collection_account_id: 7,
+                policy: FeePolicyConfig::Tiered {
+                    tiers: vec![
+                        FeeTierConfig {
+                            from: 0,
+                            policy: FeePolicyConfig::Flat { fee: 1 },
+                        },
+                        FeeTierConfig {
+                            from: 1000,
+                            policy: FeePolicyConfig::Percentage { basis_points: 5 },
+                        },
+                    ],
+                },
+            }
+        );
+        assert_eq!(
+            config.features.account_persistence,
+            AccountPersistence::EventSourced
+        );
+        assert_eq!(
+            config.interest,
+            InterestConfig {
+                funding_account_id: 8,
+                ..InterestConfig::default()
+            }
+        );
+        assert!(!config.features.background_jobs);
+        assert_eq!(config.log.format, LogFormat::Pretty);
+        assert_eq!(config.auth.api_keys[0].subject, "reporting");
+        assert_eq!(config.auth.api_keys[0].role, "customer");
+        assert_eq!(
+            config.auth.jwt,
+            Some(JwtConfig {
+                key_file: "/etc/buckpal/jwt.pub.pem".to_string(),
+                ..JwtConfig::default()
+            })
+        );
+        assert_eq!(config.validate(), Ok(()));
+
+        // And the printed configuration reads back the same
+        assert_eq!(toml::from_str::<Config>(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn test_example_config_documents_the_defaults() {
+        let config_file = Path::new(env!("CARGO_MANIFEST_DIR")).join("config.example.toml");
+
+        let config = Config::load(Some(config_file.as_path()), |_| None).unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_reports_all_invalid_settings() {
+        // Given a configuration with several invalid settings
+        let mut config = Config::default();
+        config.server.bind_address = "localhost".to_string();
+        config.database.pool_size = 2;
+        config.transfer.threshold = 0;
+        config.transfer.default_limits.monthly_amount = -1;
+        config.transfer.fees.policy = FeePolicyConfig::Capped {
+            policy: Box::new(FeePolicyConfig::Flat { fee: -5 }),
+            minimum: None,
+            maximum: Some(50),
+        };
+        config.log.level = "verbose".to_string();
+        config.features.domain_event_sink = "webhook:example.com".to_string();
+        config.auth.api_keys.push(ApiKeyConfig {
+            subject: "reporting".to_string(),
+            role: "root".to_string(),
+            key_sha256: "secret".to_string(),
+        });
+
+        // When it is validated
+        let errors = config.validate().unwrap_err();
+
+        // Then every invalid setting is named
+        assert_eq!(errors.lines().count(), 10);
+        for setting in [
+            "server.bind_address",
+            "database.pool_size",
+            "transfer.threshold",
+            "transfer.default_limits.monthly_amount",
+            "transfer.fees.policy.policy.fee",
+            "log.level",
+            "features.domain_event_sink",
+            "auth.api_keys[0].role",
+            "auth.api_keys[0].key_sha256",
+        ] {
+            assert!(errors.contains(setting), "{} not in {}", setting, errors);
+        }
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings_and_malformed_overrides() {
+        // Given a misspelt setting
+        let unknown_setting = toml::from_str::<Config>("[transfer]\nthreshhold = 10\n");
+
+        // Then it is rejected
+        assert!(
+            unknown_setting
+                .unwrap_err()
+                .to_string()
+                .contains("unknown field `threshhold`")
+        );
+
+        // Given a malformed environment variable override
+        let malformed_override = Config::load(None, |variable| {
+            (variable == "ACCOUNT_PERSISTENCE").then(|| "sourced".to_string())
+        });
+
+        // Then the variable is named
+        assert_eq!(
+            malformed_override.unwrap_err(),
+            "ACCOUNT_PERSISTENCE=\"sourced\": expected `crud` or `event-sourced`"
+        );
+    }
+
+    #[test]
+    fn test_parses_options_preceding_the_command() {
+        let args: Vec<String> = ["--config", "buckpal.toml", "--print-config", "send", "1"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+
+        let (config_args, command) = ConfigArgs::parse(&args).unwrap();
+
+        assert_eq!(config_args.config_file, Some(PathBuf::from("buckpal.toml")));
+        assert!(config_args.print_config);
+        assert_eq!(command, &args[3..]);
+        assert!(ConfigArgs::parse(&args[..1]).is_err());
+    }
+}
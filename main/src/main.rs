@@ -1,58 +1,1216 @@
+mod config;
+
 use application::{
+    account_activity_use_case::AccountActivityUseCaseImpl,
+    account_administration_use_case::AccountAdministrationUseCaseImpl,
+    accrue_interest_use_case::AccrueInterestUseCaseImpl,
+    authorization_policy::AuthorizationPolicy,
+    change_overdraft_limit_use_case::ChangeOverdraftLimitUseCaseImpl,
+    check_readiness_query::CheckReadinessQueryImpl,
+    deliver_webhooks_use_case::{DeliverWebhooksUseCaseImpl, WebhookDeliveryProperties},
+    draining_transfer_use_cases::{
+        DrainingFxTransferUseCase, DrainingSendMoneyBatchUseCase, DrainingSendMoneyUseCase,
+        TransfersInFlight,
+    },
+    execute_scheduled_transfers_use_case::ExecuteScheduledTransfersUseCaseImpl,
+    execute_standing_orders_use_case::ExecuteStandingOrdersUseCaseImpl,
+    export_ledger_use_case::ExportLedgerUseCaseImpl,
+    fee_policy::{FeePolicy, FeeTier, TransferFees},
+    fx_transfer_use_case::FxTransferUseCaseImpl,
+    generate_statement_query::GenerateStatementQueryImpl,
+    import_use_case::ImportUseCaseImpl,
+    in_process_domain_event_publisher::InProcessDomainEventPublisher,
+    inbound_ports::{
+        AccrueInterestUseCase, DeliverWebhooksUseCase, ExecuteScheduledTransfersUseCase,
+        ExecuteStandingOrdersUseCase, RelayDomainEventsUseCase,
+    },
+    interest_policy::{DayCountConvention, InterestPolicy, InterestProperties},
     no_op_account_lock::NoOpAccountLock,
+    outbound_ports::{DomainEventSink, LoadAccountPort, UpdateAccountStatePort},
+    reconcile_use_case::ReconcileUseCaseImpl,
+    relay_domain_events_use_case::{OutboxRelayProperties, RelayDomainEventsUseCaseImpl},
+    schedule_transfer_use_case::ScheduleTransferUseCaseImpl,
+    send_money_batch_use_case::SendMoneyBatchUseCaseImpl,
     send_money_use_case::{MoneyTransferProperties, SendMoneyUseCaseImpl},
+    standing_order_use_case::StandingOrderUseCaseImpl,
+    transfer_limits_use_case::TransferLimitsUseCaseImpl,
+    webhook_delivery_scheduler::WebhookDeliveryScheduler,
+    webhook_subscription_use_case::WebhookSubscriptionUseCaseImpl,
+    worker_registry::WorkerRegistry,
+};
+use chrono::Local;
+use cli::{
+    account_command::{self, AccountArgs},
+    accrue_interest_command::{self, AccrueInterestArgs},
+    export_ledger_command::{self, ExportFormat, ExportLedgerArgs, LedgerExportDto},
+    import_command::{self, ImportArgs, ImportReportDto},
+    migrate_command::{self, MigrateArgs, MigrationDto},
+    reconcile_command::{self, ReconcileArgs},
+    send_money_command::{self, SendMoneyArgs},
+};
+use config::{
+    AccountPersistence, AuthConfig, Config, ConfigArgs, DatabaseConfig, ExchangeRatesConfig,
+    FeePolicyConfig, InterestConfig, JwtAlgorithm, LogConfig, LogFormat, TransferConfig,
+};
+use domain::{
+    ar::account::AccountId,
+    vo::{money::Money, rounding::Rounding, transfer_limits::TransferLimits},
 };
-use domain::vo::money::Money;
+use event_sinks::{
+    fan_out_sink::FanOutDomainEventSink, json_lines_sink::JsonLinesDomainEventSink,
+    signed_webhook_client::SignedWebhookClient, webhook_sink::WebhookDomainEventSink,
+};
+use exchange_rates::exchange_rate_table_adapter::ExchangeRateTableAdapter;
+use ledger_files::ledger_file_writer::{LedgerFileFormat, LedgerFileWriter};
+use metrics::prometheus_metrics::PrometheusMetrics;
 use persistence::{
     account_persistence_adapter::AccountPersistenceAdapter,
+    account_event_repository::AccountEventRepositoryImpl,
+    account_mandate_persistence_adapter::AccountMandatePersistenceAdapter,
+    account_mandate_repository::AccountMandateRepositoryImpl,
     account_repository::AccountRepositoryImpl, activity_repository::ActivityRepositoryImpl,
+    event_sourced_account_persistence_adapter::EventSourcedAccountPersistenceAdapter,
+    health_persistence_adapter::HealthPersistenceAdapter,
+    health_repository::{HealthRepository, HealthRepositoryImpl},
+    import_persistence_adapter::ImportPersistenceAdapter, import_repository::ImportRepositoryImpl,
+    interest_accrual_persistence_adapter::InterestAccrualPersistenceAdapter,
+    interest_accrual_repository::InterestAccrualRepositoryImpl,
+    ledger_export_persistence_adapter::LedgerExportPersistenceAdapter,
+    ledger_export_repository::LedgerExportRepositoryImpl,
+    outbox_persistence_adapter::OutboxPersistenceAdapter, outbox_repository::OutboxRepositoryImpl,
+    reconciliation_persistence_adapter::ReconciliationPersistenceAdapter,
+    reconciliation_repository::ReconciliationRepositoryImpl,
+    scheduled_transfer_persistence_adapter::ScheduledTransferPersistenceAdapter,
+    scheduled_transfer_repository::ScheduledTransferRepositoryImpl,
+    standing_order_persistence_adapter::StandingOrderPersistenceAdapter,
+    standing_order_repository::StandingOrderRepositoryImpl,
+    webhook_persistence_adapter::WebhookPersistenceAdapter,
+    webhook_repository::WebhookRepositoryImpl,
+};
+use rest::{
+    account_event_stream_handler::{self, AccountActivityBroadcast},
+    authentication::{self, Authenticator},
+    fx_transfer_handler, health_handler, metrics_handler, overdraft_limit_handler, request_tracing,
+    scheduled_transfer_handler, send_money_batch_handler, send_money_handler,
+    standing_order_handler, statement_handler, transfer_limits_handler, webhook_handler,
 };
-use rest::send_money_handler::{get_routes, set_dependencies};
 use salvo::prelude::*;
 use sqlx::{migrate, sqlite::SqlitePoolOptions, SqlitePool};
-use std::sync::Arc;
+use std::{path::Path, sync::Arc, time::Duration};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+    task::JoinHandle,
+    time::Instant,
+};
+use tracing::{info, warn};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+const SCHEDULED_TRANSFER_EXECUTOR_INTERVAL: Duration = Duration::from_secs(60);
+const SCHEDULED_TRANSFER_CLAIM_LEASE: Duration = Duration::from_secs(15 * 60);
+const STANDING_ORDER_SCHEDULER_INTERVAL: Duration = Duration::from_secs(60);
+const INTEREST_ACCRUAL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const EXCHANGE_RATE_QUOTE_TIME_TO_LIVE: Duration = Duration::from_secs(30);
+/// How the FX transfers round the money they credit, which the ledger export and the
+/// reconciliation pair the activities of exchanged transfers by.
+const FX_ROUNDING: Rounding = Rounding::HalfEven;
+const OUTBOX_RELAY_INTERVAL: Duration = Duration::from_secs(1);
+const OUTBOX_RELAY_BATCH_SIZE: usize = 100;
+const OUTBOX_RELAY_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const OUTBOX_RELAY_MAXIMUM_BACKOFF: Duration = Duration::from_secs(5 * 60);
+const ACCOUNT_SNAPSHOT_INTERVAL: usize = 100;
+const ACCOUNT_ACTIVITY_BROADCAST_CAPACITY: usize = 1024;
+const WEBHOOK_DELIVERY_INTERVAL: Duration = Duration::from_secs(1);
+const WEBHOOK_DELIVERY_BATCH_SIZE: usize = 100;
+const WEBHOOK_DELIVERY_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const WEBHOOK_DELIVERY_MAXIMUM_BACKOFF: Duration = Duration::from_secs(60 * 60);
+const WEBHOOK_DELIVERY_MAXIMUM_ATTEMPTS: u32 = 8;
+const IMPORT_BATCH_SIZE: usize = 1000;
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+struct BackgroundJobs {
+    execute_scheduled_transfers_use_case: Arc<dyn ExecuteScheduledTransfersUseCase>,
+    execute_standing_orders_use_case: Arc<dyn ExecuteStandingOrdersUseCase>,
+    accrue_interest_use_case: Arc<dyn AccrueInterestUseCase>,
+    relay_domain_events_use_case: Arc<dyn RelayDomainEventsUseCase>,
+    deliver_webhooks_use_case: Arc<dyn DeliverWebhooksUseCase>,
+}
 
 #[tokio::main]
 async fn main() {
-    let db_pool = create_db_pool().await;
-    migrate_database(db_pool.clone()).await;
+    let all_args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (config_args, args) = match ConfigArgs::parse(&all_args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{}\n{}", error, config::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let config = load_config(&config_args);
+    if config_args.print_config {
+        print!("{}", config.to_toml());
+        return;
+    }
+    init_tracing(&config.log);
+
+    let db_pool = create_db_pool(&config.database).await;
+    if args.first().map(String::as_str) == Some("migrate") {
+        return run_migrate(db_pool, &args[1..]).await;
+    }
+    let applied_migration_versions = migrate_database(db_pool.clone()).await;
+    if !applied_migration_versions.is_empty() {
+        // the commands apply the pending migrations like the service does on start, but say so
+        if args.is_empty() {
+            info!(versions = ?applied_migration_versions, "migrations applied");
+        } else {
+            eprintln!(
+                "applied {} pending migrations, main migrate lists them",
+                applied_migration_versions.len()
+            );
+        }
+    }
+
+    match args.first().map(String::as_str) {
+        Some("import") => return run_import(db_pool, &args[1..]).await,
+        Some("export-ledger") => return run_ledger_export(db_pool, &args[1..]).await,
+        Some("reconcile") => return run_reconcile(db_pool, &args[1..]).await,
+        Some("account") => return run_account(db_pool, &config, &args[1..]).await,
+        Some("send") => return run_send_money(db_pool, &config, &args[1..]).await,
+        Some("accrue-interest") => return run_accrue_interest(db_pool, &config, &args[1..]).await,
+        _ => {}
+    }
 
-    wire_dependencies(db_pool);
+    let worker_registry = Arc::new(WorkerRegistry::new());
+    let (background_jobs, transfers_in_flight) =
+        wire_dependencies(db_pool.clone(), &config, worker_registry.clone());
+    let (shutdown_sender, shutdown) = watch::channel(false);
+    let schedule = |name, period| {
+        WorkerSchedule::new(name, period, shutdown.clone(), worker_registry.clone())
+    };
+    let workers = if config.features.background_jobs {
+        vec![
+            spawn_scheduled_transfer_executor(
+                background_jobs.execute_scheduled_transfers_use_case.clone(),
+                schedule("scheduled-transfers", SCHEDULED_TRANSFER_EXECUTOR_INTERVAL),
+            ),
+            spawn_standing_order_scheduler(
+                background_jobs.execute_standing_orders_use_case.clone(),
+                schedule("standing-orders", STANDING_ORDER_SCHEDULER_INTERVAL),
+            ),
+            spawn_interest_accrual_job(
+                background_jobs.accrue_interest_use_case.clone(),
+                schedule("interest-accrual", INTEREST_ACCRUAL_INTERVAL),
+            ),
+            spawn_outbox_relay(
+                background_jobs.relay_domain_events_use_case.clone(),
+                schedule("outbox-relay", OUTBOX_RELAY_INTERVAL),
+            ),
+            spawn_webhook_delivery_worker(
+                background_jobs.deliver_webhooks_use_case.clone(),
+                schedule("webhook-delivery", WEBHOOK_DELIVERY_INTERVAL),
+            ),
+        ]
+    } else {
+        vec![]
+    };
 
-    println!("Server Running: http://127.0.0.1:8080");
-    let acceptor = TcpListener::new("127.0.0.1:8080").bind().await;
-    Server::new(acceptor).serve(get_routes()).await;
+    println!("Server Running: http://{}", config.server.bind_address);
+    let acceptor = TcpListener::new(config.server.bind_address.as_str())
+        .bind()
+        .await;
+    let server = Server::new(acceptor);
+    let server_handle = server.handle();
+    let shutdown_timeout = Duration::from_secs(config.server.shutdown_timeout_seconds);
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        info!(?shutdown_timeout, "shutting down");
+        // no request is accepted anymore, the ones in flight are answered until the timeout
+        server_handle.stop_graceful(shutdown_timeout);
+        shutdown_sender.send(true).ok();
+    });
+    server.serve(get_routes()).await;
+
+    shut_down(
+        db_pool,
+        background_jobs,
+        workers,
+        transfers_in_flight,
+        Instant::now() + shutdown_timeout,
+        config.features.background_jobs,
+    )
+    .await;
+}
+
+async fn wait_for_shutdown_signal() {
+    let mut terminate = signal(SignalKind::terminate()).unwrap();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate.recv() => {}
+    }
 }
 
-async fn create_db_pool() -> SqlitePool {
+/**
+ * Runs once the server has stopped: waits for the workers to finish their current run and for the
+ * transfers still in flight, relays the events recorded meanwhile and closes the database, so
+ * that no transfer is cut off between the activities of its accounts.
+ */
+async fn shut_down(
+    db_pool: SqlitePool,
+    background_jobs: BackgroundJobs,
+    workers: Vec<JoinHandle<()>>,
+    transfers_in_flight: Arc<TransfersInFlight>,
+    deadline: Instant,
+    flush_background_jobs: bool,
+) {
+    for worker in workers {
+        if tokio::time::timeout_at(deadline, worker).await.is_err() {
+            warn!("background worker still running at the shutdown timeout");
+        }
+    }
+    while transfers_in_flight.count() > 0 {
+        if Instant::now() >= deadline {
+            warn!(
+                in_flight_count = transfers_in_flight.count(),
+                "transfers still in flight at the shutdown timeout"
+            );
+            break;
+        }
+        tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+    }
+    if flush_background_jobs {
+        let now = Local::now().naive_local();
+        background_jobs
+            .relay_domain_events_use_case
+            .relay_domain_events(now)
+            .await;
+        background_jobs
+            .deliver_webhooks_use_case
+            .deliver_webhooks(now)
+            .await;
+    }
+    db_pool.close().await;
+    info!("shut down");
+}
+
+/**
+ * Loads and validates the configuration, see [config::USAGE], and exits with 2 if it is invalid,
+ * before anything is connected to.
+ */
+fn load_config(config_args: &ConfigArgs) -> Config {
+    let config = Config::load(config_args.config_file.as_deref(), |variable| {
+        std::env::var(variable).ok()
+    })
+    .and_then(|config| config.validate().map(|_| config));
+    match config {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    }
+}
+
+/**
+ * The health checks and the metrics are open to the orchestrator and the scraper, everything else
+ * requires an authenticated principal.
+ */
+fn get_routes() -> Router {
+    Router::new()
+        .hoop(request_tracing::trace_request)
+        .push(health_handler::get_routes())
+        .push(metrics_handler::get_routes())
+        .push(
+            Router::new()
+                .hoop(authentication::authenticate)
+                .push(send_money_handler::get_routes())
+                .push(send_money_batch_handler::get_routes())
+                .push(scheduled_transfer_handler::get_routes())
+                .push(standing_order_handler::get_routes())
+                .push(overdraft_limit_handler::get_routes())
+                .push(transfer_limits_handler::get_routes())
+                .push(fx_transfer_handler::get_routes())
+                .push(webhook_handler::get_routes())
+                .push(account_event_stream_handler::get_routes())
+                .push(statement_handler::get_routes()),
+        )
+}
+
+/**
+ * Reads the JWT key, if any, and exits with 2 if it cannot be, like an invalid configuration.
+ */
+fn create_authenticator(auth_config: &AuthConfig) -> Authenticator {
+    let mut authenticator = Authenticator::new();
+    for api_key in &auth_config.api_keys {
+        authenticator = authenticator.with_api_key(
+            api_key.subject.clone(),
+            // validated with the configuration
+            api_key.role.parse().unwrap(),
+            &api_key.key_sha256,
+        );
+    }
+    if let Some(jwt) = &auth_config.jwt {
+        let algorithm = match jwt.algorithm {
+            JwtAlgorithm::Hs256 => authentication::JwtAlgorithm::Hs256,
+            JwtAlgorithm::Rs256 => authentication::JwtAlgorithm::Rs256,
+        };
+        authenticator = std::fs::read(&jwt.key_file)
+            .map_err(|error| error.to_string())
+            .and_then(|key| {
+                authenticator.with_jwt(
+                    algorithm,
+                    &key,
+                    jwt.issuer.as_deref(),
+                    jwt.audience.as_deref(),
+                )
+            })
+            .unwrap_or_else(|error| {
+                eprintln!("auth.jwt.key_file = {:?}: {}", jwt.key_file, error);
+                std::process::exit(2);
+            });
+    }
+    if !authenticator.is_configured() {
+        warn!("no API keys and no JWT key configured, every request to the API is rejected");
+    }
+    authenticator
+}
+
+/**
+ * Reads the rate table and exits with 2 if it cannot be, like an invalid configuration.
+ */
+fn create_exchange_rate_adapter(
+    exchange_rates_config: &ExchangeRatesConfig,
+) -> ExchangeRateTableAdapter {
+    ExchangeRateTableAdapter::from_file(
+        Path::new(&exchange_rates_config.file),
+        chrono::Duration::from_std(EXCHANGE_RATE_QUOTE_TIME_TO_LIVE).unwrap(),
+    )
+    .unwrap_or_else(|error| {
+        eprintln!(
+            "exchange_rates.file = {:?}: {}",
+            exchange_rates_config.file, error
+        );
+        std::process::exit(2);
+    })
+}
+
+/**
+ * Writes the events to stderr, as stdout may be the domain event sink. Closing a span is an event
+ * too, which times e.g. the SQL queries of a request. The `log` records, e.g. those of sqlx, are
+ * turned into events as well.
+ */
+fn init_tracing(log_config: &LogConfig) {
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(&log_config.level))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr);
+    match log_config.format {
+        LogFormat::Json => subscriber.json().flatten_event(true).init(),
+        LogFormat::Pretty => subscriber.pretty().init(),
+    }
+}
+
+async fn create_db_pool(database_config: &DatabaseConfig) -> SqlitePool {
     SqlitePoolOptions::new()
-        .max_connections(1)
-        .connect("sqlite::memory:")
+        .max_connections(database_config.pool_size)
+        .connect(&database_config.url)
         .await
         .unwrap()
 }
 
-async fn migrate_database(db_pool: SqlitePool) {
+/**
+ * Applies the pending migrations.
+ * @return the versions of the migrations applied.
+ */
+async fn migrate_database(db_pool: SqlitePool) -> Vec<i64> {
+    // none have been applied to a new database, which lacks the table of the migrations
+    let previously_applied_versions = HealthRepositoryImpl::new(db_pool.clone())
+        .find_applied_migration_versions()
+        .await
+        .unwrap_or_default();
     migrate!("./migrations").run(&db_pool).await.unwrap();
+    migrate!("./migrations")
+        .iter()
+        .map(|migration| migration.version)
+        .filter(|version| !previously_applied_versions.contains(version))
+        .collect()
 }
 
-fn wire_dependencies(db_pool: SqlitePool) {
+fn wire_dependencies(
+    db_pool: SqlitePool,
+    config: &Config,
+    worker_registry: Arc<WorkerRegistry>,
+) -> (BackgroundJobs, Arc<TransfersInFlight>) {
+    let health_persistence_adapter = Arc::new(HealthPersistenceAdapter::new(
+        Box::new(HealthRepositoryImpl::new(db_pool.clone())),
+        migrate!("./migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .collect(),
+    ));
+    let check_readiness_query = Box::new(CheckReadinessQueryImpl::new(
+        health_persistence_adapter,
+        worker_registry,
+    ));
+    health_handler::set_dependencies(check_readiness_query);
+
+    authentication::set_dependencies(create_authenticator(&config.auth));
+
+    // the transfers and the account persistence are measured for the scrapes of /metrics
+    let prometheus_metrics = Arc::new(PrometheusMetrics::new());
+    metrics_handler::set_dependencies(prometheus_metrics.clone());
+
     let account_repository = Box::new(AccountRepositoryImpl::new(db_pool.clone()));
     let activity_repository = Box::new(ActivityRepositoryImpl::new(db_pool.clone()));
+    let account_persistence_adapter = Arc::new(
+        AccountPersistenceAdapter::new(account_repository, activity_repository)
+            .with_metrics(prometheus_metrics.clone()),
+    );
+    let (load_account_port, update_account_state_port) = create_account_state_ports(
+        db_pool.clone(),
+        account_persistence_adapter.clone(),
+        config.features.account_persistence,
+    );
+
+    let account_lock = Box::new(NoOpAccountLock {});
+
+    // money events are turned into deliveries for the webhooks subscribed to their account
+    let webhook_repository = Box::new(WebhookRepositoryImpl::new(db_pool.clone()));
+    let webhook_persistence_adapter = Arc::new(WebhookPersistenceAdapter::new(webhook_repository));
+    let webhook_delivery_scheduler = Arc::new(WebhookDeliveryScheduler::new(
+        webhook_persistence_adapter.clone(),
+        webhook_persistence_adapter.clone(),
+    ));
+
+    // the customers may only view and debit the accounts they hold a mandate on, only admins
+    // change their limits
+    let account_mandate_persistence_adapter = Arc::new(AccountMandatePersistenceAdapter::new(
+        Box::new(AccountMandateRepositoryImpl::new(db_pool.clone())),
+    ));
+    let authorization_policy = Arc::new(AuthorizationPolicy::new(
+        account_mandate_persistence_adapter.clone(),
+        account_mandate_persistence_adapter,
+    ));
+
+    // relayed events are broadcast within the process to the open account event streams
+    let account_activity_broadcast = Arc::new(AccountActivityBroadcast::new(
+        ACCOUNT_ACTIVITY_BROADCAST_CAPACITY,
+    ));
+    let in_process_domain_event_publisher = Arc::new(InProcessDomainEventPublisher::new());
+    in_process_domain_event_publisher.subscribe(account_activity_broadcast.clone());
+    let account_activity_use_case = Box::new(
+        AccountActivityUseCaseImpl::new(account_persistence_adapter.clone())
+            .with_authorization_policy(authorization_policy.clone()),
+    );
+    account_event_stream_handler::set_dependencies(
+        account_activity_use_case,
+        account_activity_broadcast,
+    );
+
+    let generate_statement_query = Box::new(
+        GenerateStatementQueryImpl::new(account_persistence_adapter.clone())
+            .with_authorization_policy(authorization_policy.clone()),
+    );
+    statement_handler::set_dependencies(generate_statement_query);
+
+    // events are stored with the activities and relayed from the outbox
+    let outbox_repository = Box::new(OutboxRepositoryImpl::new(db_pool.clone()));
+    let outbox_persistence_adapter = Arc::new(OutboxPersistenceAdapter::new(outbox_repository));
+    let relay_domain_events_use_case = Arc::new(RelayDomainEventsUseCaseImpl::new(
+        outbox_persistence_adapter.clone(),
+        outbox_persistence_adapter.clone(),
+        Arc::new(FanOutDomainEventSink::new(vec![
+            webhook_delivery_scheduler,
+            create_domain_event_sink(&config.features.domain_event_sink),
+            in_process_domain_event_publisher,
+        ])),
+        OutboxRelayProperties::new(
+            OUTBOX_RELAY_BATCH_SIZE,
+            chrono::Duration::from_std(OUTBOX_RELAY_INITIAL_BACKOFF).unwrap(),
+            chrono::Duration::from_std(OUTBOX_RELAY_MAXIMUM_BACKOFF).unwrap(),
+        ),
+    ));
+
+    let money_transfer_properties = create_money_transfer_properties(&config.transfer);
+
+    // the single, batch and FX transfers of the REST routes, the scheduled transfers and the
+    // standing orders are drained on shutdown
+    let transfers_in_flight = Arc::new(TransfersInFlight::new());
+    let send_money_use_case = Arc::new(DrainingSendMoneyUseCase::new(
+        Arc::new(
+            SendMoneyUseCaseImpl::new(
+                load_account_port.clone(),
+                account_lock,
+                update_account_state_port.clone(),
+                account_persistence_adapter.clone(),
+                outbox_persistence_adapter.clone(),
+                money_transfer_properties.clone(),
+            )
+            .with_metrics(prometheus_metrics.clone())
+            .with_authorization_policy(authorization_policy.clone()),
+        ),
+        transfers_in_flight.clone(),
+    ));
+    send_money_handler::set_dependencies(send_money_use_case.clone());
+
+    let send_money_batch_use_case = Box::new(DrainingSendMoneyBatchUseCase::new(
+        Arc::new(
+            SendMoneyBatchUseCaseImpl::new(
+                load_account_port.clone(),
+                Box::new(NoOpAccountLock {}),
+                update_account_state_port.clone(),
+                account_persistence_adapter.clone(),
+                outbox_persistence_adapter.clone(),
+                money_transfer_properties.clone(),
+            )
+            .with_metrics(prometheus_metrics.clone())
+            .with_authorization_policy(authorization_policy.clone()),
+        ),
+        transfers_in_flight.clone(),
+    ));
+    send_money_batch_handler::set_dependencies(send_money_batch_use_case);
+
+    let exchange_rate_adapter = Arc::new(create_exchange_rate_adapter(&config.exchange_rates));
+    let fx_transfer_use_case = Box::new(DrainingFxTransferUseCase::new(
+        Arc::new(
+            FxTransferUseCaseImpl::new(
+                load_account_port.clone(),
+                Box::new(NoOpAccountLock {}),
+                update_account_state_port,
+                account_persistence_adapter.clone(),
+                outbox_persistence_adapter,
+                exchange_rate_adapter,
+                money_transfer_properties.clone(),
+                FX_ROUNDING,
+            )
+            .with_metrics(prometheus_metrics.clone())
+            .with_authorization_policy(authorization_policy.clone()),
+        ),
+        transfers_in_flight.clone(),
+    ));
+    fx_transfer_handler::set_dependencies(fx_transfer_use_case);
+
+    let transfer_limits_use_case = Box::new(
+        TransferLimitsUseCaseImpl::new(
+            account_persistence_adapter.clone(),
+            account_persistence_adapter.clone(),
+            money_transfer_properties,
+        )
+        .with_authorization_policy(authorization_policy.clone()),
+    );
+    transfer_limits_handler::set_dependencies(transfer_limits_use_case);
+
+    let change_overdraft_limit_use_case = Box::new(
+        ChangeOverdraftLimitUseCaseImpl::new(
+            load_account_port.clone(),
+            account_persistence_adapter.clone(),
+            account_persistence_adapter,
+        )
+        .with_authorization_policy(authorization_policy.clone()),
+    );
+    overdraft_limit_handler::set_dependencies(change_overdraft_limit_use_case);
+
+    let accrue_interest_use_case = Arc::new(create_accrue_interest_use_case(
+        db_pool.clone(),
+        &config.interest,
+        load_account_port,
+    ));
+
+    let scheduled_transfer_repository =
+        Box::new(ScheduledTransferRepositoryImpl::new(db_pool.clone()));
+    let scheduled_transfer_persistence_adapter = Arc::new(
+        ScheduledTransferPersistenceAdapter::new(scheduled_transfer_repository),
+    );
+
+    // the scheduled transfers and standing orders are executed as whoever created them, so that
+    // both their creation and each execution are authorized
+    let schedule_transfer_use_case = Box::new(
+        ScheduleTransferUseCaseImpl::new(
+            scheduled_transfer_persistence_adapter.clone(),
+            scheduled_transfer_persistence_adapter.clone(),
+        )
+        .with_authorization_policy(authorization_policy.clone()),
+    );
+    scheduled_transfer_handler::set_dependencies(schedule_transfer_use_case);
+
+    let execute_scheduled_transfers_use_case = Arc::new(ExecuteScheduledTransfersUseCaseImpl::new(
+        scheduled_transfer_persistence_adapter.clone(),
+        scheduled_transfer_persistence_adapter,
+        send_money_use_case.clone(),
+        chrono::Duration::from_std(SCHEDULED_TRANSFER_CLAIM_LEASE).unwrap(),
+    ));
+
+    let standing_order_repository = Box::new(StandingOrderRepositoryImpl::new(db_pool));
+    let standing_order_persistence_adapter = Arc::new(StandingOrderPersistenceAdapter::new(
+        standing_order_repository,
+    ));
+
+    let standing_order_use_case = Box::new(
+        StandingOrderUseCaseImpl::new(
+            standing_order_persistence_adapter.clone(),
+            standing_order_persistence_adapter.clone(),
+        )
+        .with_authorization_policy(authorization_policy.clone()),
+    );
+    standing_order_handler::set_dependencies(standing_order_use_case);
+
+    let execute_standing_orders_use_case = Arc::new(ExecuteStandingOrdersUseCaseImpl::new(
+        standing_order_persistence_adapter.clone(),
+        standing_order_persistence_adapter,
+        send_money_use_case.clone(),
+    ));
+
+    let webhook_subscription_use_case = Box::new(
+        WebhookSubscriptionUseCaseImpl::new(
+            webhook_persistence_adapter.clone(),
+            webhook_persistence_adapter.clone(),
+            webhook_persistence_adapter.clone(),
+        )
+        .with_authorization_policy(authorization_policy),
+    );
+    webhook_handler::set_dependencies(webhook_subscription_use_case);
+
+    let deliver_webhooks_use_case = Arc::new(DeliverWebhooksUseCaseImpl::new(
+        webhook_persistence_adapter.clone(),
+        webhook_persistence_adapter.clone(),
+        webhook_persistence_adapter,
+        Arc::new(SignedWebhookClient::new()),
+        WebhookDeliveryProperties::new(
+            WEBHOOK_DELIVERY_BATCH_SIZE,
+            chrono::Duration::from_std(WEBHOOK_DELIVERY_INITIAL_BACKOFF).unwrap(),
+            chrono::Duration::from_std(WEBHOOK_DELIVERY_MAXIMUM_BACKOFF).unwrap(),
+            WEBHOOK_DELIVERY_MAXIMUM_ATTEMPTS,
+        ),
+    ));
+
+    (
+        BackgroundJobs {
+            execute_scheduled_transfers_use_case,
+            execute_standing_orders_use_case,
+            accrue_interest_use_case,
+            relay_domain_events_use_case,
+            deliver_webhooks_use_case,
+        },
+        transfers_in_flight,
+    )
+}
+
+fn create_money_transfer_properties(transfer_config: &TransferConfig) -> MoneyTransferProperties {
+    let default_limits = &transfer_config.default_limits;
+    let limit = |amount: i64| (amount > 0).then(|| Money::of(amount.into()));
+    let default_transfer_limits = TransferLimits::new(
+        limit(default_limits.daily_amount),
+        limit(default_limits.monthly_amount),
+        (default_limits.daily_count > 0).then_some(default_limits.daily_count),
+    );
+    let transfer_fees = transfer_config.fees.enabled.then(|| {
+        TransferFees::new(
+            create_fee_policy(&transfer_config.fees.policy),
+            AccountId(transfer_config.fees.collection_account_id),
+        )
+    });
+    MoneyTransferProperties::new(
+        Some(Money::of(transfer_config.threshold.into())),
+        Some(default_transfer_limits),
+        transfer_fees,
+    )
+    .with_baseline_window_days(transfer_config.baseline_window_days)
+}
+
+fn create_fee_policy(fee_policy_config: &FeePolicyConfig) -> FeePolicy {
+    let money = |amount: i64| Money::of(amount.into());
+    match fee_policy_config {
+        FeePolicyConfig::Flat { fee } => FeePolicy::Flat(money(*fee)),
+        FeePolicyConfig::Percentage { basis_points } => FeePolicy::Percentage {
+            basis_points: (*basis_points).into(),
+        },
+        FeePolicyConfig::Tiered { tiers } => FeePolicy::Tiered(
+            tiers
+                .iter()
+                .map(|tier| FeeTier {
+                    from: money(tier.from),
+                    policy: create_fee_policy(&tier.policy),
+                })
+                .collect(),
+        ),
+        FeePolicyConfig::Capped {
+            policy,
+            minimum,
+            maximum,
+        } => FeePolicy::Capped {
+            policy: Box::new(create_fee_policy(policy)),
+            minimum: minimum.map(money),
+            maximum: maximum.map(money),
+        },
+    }
+}
+
+/**
+ * Opens, closes and shows accounts and grants mandates on them, see [account_command::USAGE].
+ */
+async fn run_account(db_pool: SqlitePool, config: &Config, args: &[String]) {
+    let account_args = match AccountArgs::parse(args) {
+        Ok(account_args) => account_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, account_command::USAGE);
+            std::process::exit(2);
+        }
+    };
     let account_persistence_adapter = Arc::new(AccountPersistenceAdapter::new(
-        account_repository,
-        activity_repository,
+        Box::new(AccountRepositoryImpl::new(db_pool.clone())),
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
     ));
+    let (load_account_port, _) = create_account_state_ports(
+        db_pool.clone(),
+        account_persistence_adapter.clone(),
+        config.features.account_persistence,
+    );
+    let account_administration_use_case = AccountAdministrationUseCaseImpl::new(
+        load_account_port,
+        account_persistence_adapter.clone(),
+        account_persistence_adapter.clone(),
+        Arc::new(AccountMandatePersistenceAdapter::new(Box::new(
+            AccountMandateRepositoryImpl::new(db_pool.clone()),
+        ))),
+    );
+    let account_activity_use_case = AccountActivityUseCaseImpl::new(account_persistence_adapter);
+    match account_command::run(
+        &account_administration_use_case,
+        &account_activity_use_case,
+        &account_args,
+    )
+    .await
+    {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
 
-    let account_lock = Box::new(NoOpAccountLock {});
+/**
+ * Sends money the same way as the REST route does, see [send_money_command::USAGE]. The events
+ * are recorded in the outbox and relayed by the next running service.
+ */
+async fn run_send_money(db_pool: SqlitePool, config: &Config, args: &[String]) {
+    let send_money_args = match SendMoneyArgs::parse(args) {
+        Ok(send_money_args) => send_money_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, send_money_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let account_persistence_adapter = Arc::new(AccountPersistenceAdapter::new(
+        Box::new(AccountRepositoryImpl::new(db_pool.clone())),
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
+    ));
+    let (load_account_port, update_account_state_port) = create_account_state_ports(
+        db_pool.clone(),
+        account_persistence_adapter.clone(),
+        config.features.account_persistence,
+    );
+    let send_money_use_case = SendMoneyUseCaseImpl::new(
+        load_account_port,
+        Box::new(NoOpAccountLock {}),
+        update_account_state_port,
+        account_persistence_adapter,
+        Arc::new(OutboxPersistenceAdapter::new(Box::new(
+            OutboxRepositoryImpl::new(db_pool),
+        ))),
+        create_money_transfer_properties(&config.transfer),
+    );
+    match send_money_command::run(&send_money_use_case, &send_money_args).await {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            eprintln!("send money failed: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
 
-    let money_transfer_properties = MoneyTransferProperties::new(Some(Money::of(1_000)));
+/**
+ * Accrues the interest of the days missed by the background job, see
+ * [accrue_interest_command::USAGE].
+ */
+async fn run_accrue_interest(db_pool: SqlitePool, config: &Config, args: &[String]) {
+    let accrue_interest_args = match AccrueInterestArgs::parse(args) {
+        Ok(accrue_interest_args) => accrue_interest_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, accrue_interest_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let account_persistence_adapter = Arc::new(AccountPersistenceAdapter::new(
+        Box::new(AccountRepositoryImpl::new(db_pool.clone())),
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
+    ));
+    let (load_account_port, _) = create_account_state_ports(
+        db_pool.clone(),
+        account_persistence_adapter,
+        config.features.account_persistence,
+    );
+    let accrue_interest_use_case =
+        create_accrue_interest_use_case(db_pool, &config.interest, load_account_port);
+    match accrue_interest_command::run(
+        &accrue_interest_use_case,
+        &accrue_interest_args,
+        Local::now().date_naive(),
+    )
+    .await
+    {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            eprintln!("accruing interest failed: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
 
-    let send_money_use_case = Box::new(SendMoneyUseCaseImpl::new(
+/**
+ * Applies the pending migrations and lists all of them, see [migrate_command::USAGE].
+ */
+async fn run_migrate(db_pool: SqlitePool, args: &[String]) {
+    let migrate_args = match MigrateArgs::parse(args) {
+        Ok(migrate_args) => migrate_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, migrate_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let applied_migration_versions = migrate_database(db_pool).await;
+    let migrations: Vec<MigrationDto> = migrate!("./migrations")
+        .iter()
+        .map(|migration| MigrationDto {
+            version: migration.version,
+            description: migration.description.to_string(),
+            applied_now: applied_migration_versions.contains(&migration.version),
+        })
+        .collect();
+    print!(
+        "{}",
+        migrate_command::format_migrations(&migrations, migrate_args.json)
+    );
+}
+
+/**
+ * Imports a CSV or JSON-lines file of accounts and activities, see [import_command::USAGE].
+ */
+async fn run_import(db_pool: SqlitePool, args: &[String]) {
+    let import_args = match ImportArgs::parse(args) {
+        Ok(import_args) => import_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, import_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let import_repository = Box::new(ImportRepositoryImpl::new(db_pool));
+    let import_persistence_adapter = Arc::new(ImportPersistenceAdapter::new(import_repository));
+    let import_use_case = ImportUseCaseImpl::new(
+        import_persistence_adapter.clone(),
+        import_persistence_adapter,
+        IMPORT_BATCH_SIZE,
+    );
+    match import_command::run(&import_use_case, &import_args).await {
+        Ok(report) => print!(
+            "{}",
+            import_command::format_report(
+                &ImportReportDto::of(&report, &import_args),
+                import_args.json
+            )
+        ),
+        Err(error) => {
+            eprintln!("import failed: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/**
+ * Writes the activities as a general-ledger file, see [export_ledger_command::USAGE].
+ */
+async fn run_ledger_export(db_pool: SqlitePool, args: &[String]) {
+    let export_ledger_args = match ExportLedgerArgs::parse(args) {
+        Ok(export_ledger_args) => export_ledger_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, export_ledger_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let ledger_export_persistence_adapter = Arc::new(LedgerExportPersistenceAdapter::new(
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
+        Box::new(LedgerExportRepositoryImpl::new(db_pool)),
+    ));
+    let ledger_file_writer = Arc::new(LedgerFileWriter::new(
+        export_ledger_args.output_directory.clone(),
+        match export_ledger_args.format {
+            ExportFormat::Csv => LedgerFileFormat::Csv,
+            ExportFormat::Json => LedgerFileFormat::Json,
+        },
+    ));
+    let export_ledger_use_case = ExportLedgerUseCaseImpl::new(
+        ledger_export_persistence_adapter.clone(),
+        ledger_export_persistence_adapter,
+        ledger_file_writer,
+        FX_ROUNDING,
+    );
+    match export_ledger_command::run(&export_ledger_use_case, &export_ledger_args).await {
+        Ok(ledger_export) => print!(
+            "{}",
+            export_ledger_command::format_export(
+                &LedgerExportDto::from(&ledger_export),
+                export_ledger_args.json
+            )
+        ),
+        Err(error) => {
+            eprintln!("export failed: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/**
+ * Prints the report to the output and a summary to the standard error, and exits with 1 if
+ * inconsistencies remain unresolved, so that a scheduled job can alert on them.
+ */
+async fn run_reconcile(db_pool: SqlitePool, args: &[String]) {
+    let reconcile_args = match ReconcileArgs::parse(args) {
+        Ok(reconcile_args) => reconcile_args,
+        Err(error) => {
+            eprintln!("{}\n{}", error, reconcile_command::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let reconciliation_persistence_adapter = Arc::new(ReconciliationPersistenceAdapter::new(
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
+        Box::new(ReconciliationRepositoryImpl::new(db_pool)),
+    ));
+    let reconcile_use_case = ReconcileUseCaseImpl::new(
+        reconciliation_persistence_adapter.clone(),
+        reconciliation_persistence_adapter,
+        FX_ROUNDING,
+    );
+    let report = reconcile_command::run(&reconcile_use_case, &reconcile_args).await;
+    let formatted_report = reconcile_command::format_report(&report, reconcile_args.json);
+    match &reconcile_args.output {
+        Some(output) => {
+            if let Err(error) = std::fs::write(output, formatted_report) {
+                eprintln!("reconciliation failed: {}: {}", output.display(), error);
+                std::process::exit(1);
+            }
+        }
+        None => print!("{}", formatted_report),
+    }
+    eprintln!("{}", reconcile_command::format_summary(&report));
+    if report.unresolved_count > 0 {
+        std::process::exit(1);
+    }
+}
+
+/**
+ * Selects how accounts are loaded and updated, see [AccountPersistence].
+ */
+fn create_account_state_ports(
+    db_pool: SqlitePool,
+    account_persistence_adapter: Arc<AccountPersistenceAdapter>,
+    account_persistence: AccountPersistence,
+) -> (Arc<dyn LoadAccountPort>, Arc<dyn UpdateAccountStatePort>) {
+    if account_persistence == AccountPersistence::EventSourced {
+        let event_sourced_account_persistence_adapter =
+            Arc::new(EventSourcedAccountPersistenceAdapter::new(
+                Box::new(AccountEventRepositoryImpl::new(db_pool.clone())),
+                Box::new(ActivityRepositoryImpl::new(db_pool)),
+                ACCOUNT_SNAPSHOT_INTERVAL,
+            ));
+        return (
+            event_sourced_account_persistence_adapter.clone(),
+            event_sourced_account_persistence_adapter,
+        );
+    }
+    (
         account_persistence_adapter.clone(),
-        account_lock,
         account_persistence_adapter,
-        money_transfer_properties,
+    )
+}
+
+/**
+ * Selects where the recorded events are relayed to, which is either `stdout`, `file:<path>` for a
+ * JSON-lines file or `webhook:<url>`.
+ */
+fn create_accrue_interest_use_case(
+    db_pool: SqlitePool,
+    interest_config: &InterestConfig,
+    load_account_port: Arc<dyn LoadAccountPort>,
+) -> AccrueInterestUseCaseImpl {
+    let interest_accrual_persistence_adapter = Arc::new(InterestAccrualPersistenceAdapter::new(
+        Box::new(InterestAccrualRepositoryImpl::new(db_pool)),
     ));
-    set_dependencies(send_money_use_case);
+    let interest_properties = InterestProperties::new(
+        InterestPolicy::new(
+            interest_config.annual_rate_basis_points.into(),
+            DayCountConvention::Actual365,
+            Rounding::HalfEven,
+        ),
+        AccountId(interest_config.funding_account_id),
+    );
+    AccrueInterestUseCaseImpl::new(
+        load_account_port,
+        Box::new(NoOpAccountLock {}),
+        interest_accrual_persistence_adapter.clone(),
+        interest_accrual_persistence_adapter,
+        interest_properties,
+    )
+}
+
+fn create_domain_event_sink(domain_event_sink: &str) -> Arc<dyn DomainEventSink> {
+    if let Some(path) = domain_event_sink.strip_prefix("file:") {
+        return Arc::new(JsonLinesDomainEventSink::append_to_file(Path::new(path)).unwrap());
+    }
+    if let Some(url) = domain_event_sink.strip_prefix("webhook:") {
+        return Arc::new(WebhookDomainEventSink::new(url.to_string()));
+    }
+    Arc::new(JsonLinesDomainEventSink::stdout())
+}
+
+/**
+ * Periodically runs all scheduled transfers that have become due.
+ */
+fn spawn_scheduled_transfer_executor(
+    execute_scheduled_transfers_use_case: Arc<dyn ExecuteScheduledTransfersUseCase>,
+    mut schedule: WorkerSchedule,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while schedule.next_run().await {
+            execute_scheduled_transfers_use_case
+                .execute_due_transfers(Local::now().naive_local())
+                .await;
+            schedule.finish_run();
+        }
+    })
+}
+
+/**
+ * Periodically executes the due occurrences of all active standing orders.
+ */
+fn spawn_standing_order_scheduler(
+    execute_standing_orders_use_case: Arc<dyn ExecuteStandingOrdersUseCase>,
+    mut schedule: WorkerSchedule,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while schedule.next_run().await {
+            execute_standing_orders_use_case
+                .execute_due_standing_orders(Local::now().naive_local())
+                .await;
+            schedule.finish_run();
+        }
+    })
+}
+
+/**
+ * Periodically accrues the interest up to the previous day, catching up on the days missed while
+ * the job was not running. It is a no-op once the previous day has been accrued.
+ */
+fn spawn_interest_accrual_job(
+    accrue_interest_use_case: Arc<dyn AccrueInterestUseCase>,
+    mut schedule: WorkerSchedule,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while schedule.next_run().await {
+            let yesterday = Local::now().date_naive().pred_opt().unwrap();
+            accrue_interest_use_case.accrue_interest(yesterday).await;
+            schedule.finish_run();
+        }
+    })
+}
+
+/**
+ * Periodically delivers the events recorded in the outbox, at least once each.
+ */
+fn spawn_outbox_relay(
+    relay_domain_events_use_case: Arc<dyn RelayDomainEventsUseCase>,
+    mut schedule: WorkerSchedule,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while schedule.next_run().await {
+            relay_domain_events_use_case
+                .relay_domain_events(Local::now().naive_local())
+                .await;
+            schedule.finish_run();
+        }
+    })
+}
+
+/**
+ * Periodically posts the due webhook deliveries, retrying failed ones with backoff.
+ */
+fn spawn_webhook_delivery_worker(
+    deliver_webhooks_use_case: Arc<dyn DeliverWebhooksUseCase>,
+    mut schedule: WorkerSchedule,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while schedule.next_run().await {
+            deliver_webhooks_use_case
+                .deliver_webhooks(Local::now().naive_local())
+                .await;
+            schedule.finish_run();
+        }
+    })
+}
+
+/**
+ * Runs a worker every interval until the shutdown begins and reports its runs to the registry
+ * checked for readiness.
+ */
+struct WorkerSchedule {
+    name: &'static str,
+    interval: tokio::time::Interval,
+    shutdown: watch::Receiver<bool>,
+    worker_registry: Arc<WorkerRegistry>,
+}
+
+impl WorkerSchedule {
+    fn new(
+        name: &'static str,
+        period: Duration,
+        shutdown: watch::Receiver<bool>,
+        worker_registry: Arc<WorkerRegistry>,
+    ) -> Self {
+        worker_registry.register(
+            name,
+            chrono::Duration::from_std(period).unwrap(),
+            Local::now().naive_local(),
+        );
+        Self {
+            name,
+            interval: tokio::time::interval(period),
+            shutdown,
+            worker_registry,
+        }
+    }
+
+    /**
+     * Waits for the next run, false once the shutdown has begun. A run which has begun is
+     * completed, as the shutdown waits for the worker.
+     */
+    async fn next_run(&mut self) -> bool {
+        let run = tokio::select! {
+            _ = self.interval.tick() => !*self.shutdown.borrow(),
+            _ = self.shutdown.changed() => false,
+        };
+        if run {
+            self.worker_registry
+                .record_run_started(self.name, Local::now().naive_local());
+        }
+        run
+    }
+
+    fn finish_run(&self) {
+        self.worker_registry
+            .record_run_finished(self.name, Local::now().naive_local());
+    }
+}
+
+impl Drop for WorkerSchedule {
+    fn drop(&mut self) {
+        self.worker_registry.record_stopped(self.name);
+    }
 }
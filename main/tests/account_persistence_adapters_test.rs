@@ -0,0 +1,330 @@
+//! The same scenarios run against the account rows with their activities and against the
+//! replayed event streams, which have to be indistinguishable for the use cases.
+
+use application::outbound_ports::{
+    LoadAccountPort, LoadTransferLimitsPort, UpdateAccountStatePort, UpdateOverdraftLimitPort,
+};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use domain::{
+    ar::{
+        account::{Account, AccountId},
+        domain_event::DomainEvent,
+        overdraft_limit_change::OverdraftLimitChange,
+    },
+    vo::{currency::Currency, money::Money, transfer_limits::TransferVolume},
+};
+use persistence::{
+    account_event_repository::AccountEventRepositoryImpl,
+    account_persistence_adapter::AccountPersistenceAdapter,
+    account_repository::AccountRepositoryImpl, activity_repository::ActivityRepositoryImpl,
+    event_sourced_account_persistence_adapter::EventSourcedAccountPersistenceAdapter,
+};
+use sqlx::{SqlitePool, migrate, sqlite::SqlitePoolOptions};
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug)]
+enum AccountPersistence {
+    Crud,
+    EventSourced,
+}
+
+const ACCOUNT_PERSISTENCES: [AccountPersistence; 2] =
+    [AccountPersistence::Crud, AccountPersistence::EventSourced];
+
+struct AccountPorts {
+    load_account_port: Arc<dyn LoadAccountPort>,
+    update_account_state_port: Arc<dyn UpdateAccountStatePort>,
+    update_overdraft_limit_port: Arc<dyn UpdateOverdraftLimitPort>,
+    load_transfer_limits_port: Arc<dyn LoadTransferLimitsPort>,
+}
+
+#[derive(PartialEq, Debug)]
+struct LoadedAccount {
+    balance: Money,
+    currency: Currency,
+    overdraft_limit: Money,
+    // the IDs, counterparts and amounts, as new activities are timestamped when they are created
+    activities: Vec<(Option<i64>, i64, i64, Money)>,
+}
+
+#[tokio::test]
+async fn test_loads_accounts_identically() {
+    assert_identical(|account_ports| async move {
+        let mut loaded_accounts = Vec::new();
+        for account_id in [1, 2, 3, 100, 101] {
+            // snapshots taken at a later baseline date must not be used for an earlier one
+            for baseline_date in [
+                timestamp(2019, 8, 1),
+                timestamp(2018, 1, 1),
+                timestamp(2018, 8, 9),
+                timestamp(2019, 8, 9),
+                timestamp(2020, 1, 1),
+                timestamp(2018, 8, 9),
+            ] {
+                loaded_accounts
+                    .push(load(&account_ports, AccountId(account_id), baseline_date).await);
+            }
+        }
+        loaded_accounts
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_loads_updated_accounts_identically() {
+    let loaded_accounts = assert_identical(|account_ports| async move {
+        // Given
+        let mut source_account = account_ports
+            .load_account_port
+            .load_account(AccountId(1), timestamp(2019, 8, 1))
+            .await;
+        let mut target_account = account_ports
+            .load_account_port
+            .load_account(AccountId(2), timestamp(2019, 8, 1))
+            .await;
+
+        // When money is sent in one unit of work
+        source_account.withdraw(Money::of(300), AccountId(2));
+        target_account.deposit(Money::of(300), AccountId(1));
+        account_ports
+            .update_account_state_port
+            .update_accounts(vec![source_account, target_account])
+            .await;
+
+        // And a single activity is added
+        let mut target_account = account_ports
+            .load_account_port
+            .load_account(AccountId(2), timestamp(2019, 8, 1))
+            .await;
+        target_account.deposit(Money::of(50), AccountId(1));
+        account_ports
+            .update_account_state_port
+            .update_activities(target_account)
+            .await;
+
+        // Then
+        vec![
+            load(&account_ports, AccountId(1), timestamp(2019, 8, 1)).await,
+            load(&account_ports, AccountId(2), timestamp(2019, 8, 1)).await,
+        ]
+    })
+    .await;
+
+    assert_eq!(Money::of(200), loaded_accounts[0].balance);
+    assert_eq!(Money::of(-150), loaded_accounts[1].balance);
+}
+
+#[tokio::test]
+async fn test_loads_accounts_updated_with_events_identically() {
+    assert_identical(|account_ports| async move {
+        // Given
+        let mut account = account_ports
+            .load_account_port
+            .load_account(AccountId(1), timestamp(2019, 8, 1))
+            .await;
+
+        // When
+        account.withdraw(Money::of(100), AccountId(100));
+        let domain_events: Vec<DomainEvent> = account.take_domain_events();
+        account_ports
+            .update_account_state_port
+            .update_accounts_and_record_events(vec![account], domain_events)
+            .await;
+
+        // Then
+        load(&account_ports, AccountId(1), timestamp(2019, 8, 1)).await
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_loads_changed_overdraft_limit_identically() {
+    let loaded_account = assert_identical(|account_ports| async move {
+        // When
+        account_ports
+            .update_overdraft_limit_port
+            .update_overdraft_limit(OverdraftLimitChange::new(
+                AccountId(1),
+                Money::of(0),
+                Money::of(250),
+                "operator".to_string(),
+                timestamp(2019, 9, 1),
+            ))
+            .await;
+
+        // Then
+        load(&account_ports, AccountId(1), timestamp(2019, 8, 1)).await
+    })
+    .await;
+
+    assert_eq!(Money::of(250), loaded_account.overdraft_limit);
+}
+
+#[tokio::test]
+async fn test_fees_do_not_count_as_outgoing_transfers() {
+    let since = Local::now().naive_local() - Duration::minutes(1);
+    let outgoing_transfer_volumes = assert_identical(|account_ports| async move {
+        // Given
+        let mut account = account_ports
+            .load_account_port
+            .load_account(AccountId(1), timestamp(2019, 8, 1))
+            .await;
+
+        // When 300 are sent for a fee of 5 withdrawn to the fee collection account
+        assert!(account.withdraw(Money::of(300), AccountId(2)));
+        assert!(account.withdraw(Money::of(5), AccountId(100)));
+        account_ports
+            .update_account_state_port
+            .update_activities(account)
+            .await;
+
+        // And the volume is loaded with and without the fee collection account
+        let mut outgoing_transfer_volumes = Vec::new();
+        for fee_collection_account_id in [Some(AccountId(100)), None] {
+            outgoing_transfer_volumes.push(
+                account_ports
+                    .load_transfer_limits_port
+                    .load_outgoing_transfer_volume(AccountId(1), since, fee_collection_account_id)
+                    .await,
+            );
+        }
+        outgoing_transfer_volumes
+    })
+    .await;
+
+    // Then the transfer counts once and without its fee, unless no fees are collected
+    assert_eq!(
+        vec![
+            TransferVolume::new(Money::of(300), 1),
+            TransferVolume::new(Money::of(305), 2),
+        ],
+        outgoing_transfer_volumes
+    );
+}
+
+#[tokio::test]
+async fn test_loads_accounts_identically_from_snapshots() {
+    assert_identical(|account_ports| async move {
+        // Given a snapshot is taken when the account is loaded
+        load(&account_ports, AccountId(1), timestamp(2019, 8, 1)).await;
+
+        // When money is withdrawn after it
+        let mut account = account_ports
+            .load_account_port
+            .load_account(AccountId(1), timestamp(2019, 8, 1))
+            .await;
+        account.withdraw(Money::of(100), AccountId(2));
+        account_ports
+            .update_account_state_port
+            .update_activities(account)
+            .await;
+
+        // Then
+        vec![
+            load(&account_ports, AccountId(1), timestamp(2019, 8, 1)).await,
+            load(&account_ports, AccountId(1), timestamp(2020, 1, 1)).await,
+        ]
+    })
+    .await;
+}
+
+/**
+ * Runs the scenario against each account persistence with its own database.
+ * @return the result of the scenario, which is the same for all of them
+ */
+async fn assert_identical<T, F, Fut>(scenario: F) -> T
+where
+    T: PartialEq + std::fmt::Debug,
+    F: Fn(AccountPorts) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let mut results = Vec::new();
+    for account_persistence in ACCOUNT_PERSISTENCES {
+        let db_pool = create_db_pool().await;
+        migrate_database(db_pool.clone()).await;
+        results.push(scenario(wire_dependencies(db_pool, account_persistence)).await);
+    }
+    let event_sourced = results.pop().unwrap();
+    let crud = results.pop().unwrap();
+    assert_eq!(crud, event_sourced);
+    crud
+}
+
+async fn load(
+    account_ports: &AccountPorts,
+    account_id: AccountId,
+    baseline_date: NaiveDateTime,
+) -> LoadedAccount {
+    let account: Account = account_ports
+        .load_account_port
+        .load_account(account_id, baseline_date)
+        .await;
+    LoadedAccount {
+        balance: account.calculate_balance(),
+        currency: account.get_currency(),
+        overdraft_limit: account.get_overdraft_limit(),
+        activities: account
+            .activity_window
+            .activities
+            .into_iter()
+            .map(|activity| {
+                (
+                    activity.id.map(|id| id.0),
+                    activity.source_account_id.0,
+                    activity.target_account_id.0,
+                    activity.money,
+                )
+            })
+            .collect(),
+    }
+}
+
+async fn create_db_pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+async fn migrate_database(db_pool: SqlitePool) {
+    migrate!("./migrations").run(&db_pool).await.unwrap();
+}
+
+fn wire_dependencies(db_pool: SqlitePool, account_persistence: AccountPersistence) -> AccountPorts {
+    let account_persistence_adapter = Arc::new(AccountPersistenceAdapter::new(
+        Box::new(AccountRepositoryImpl::new(db_pool.clone())),
+        Box::new(ActivityRepositoryImpl::new(db_pool.clone())),
+    ));
+    match account_persistence {
+        AccountPersistence::Crud => AccountPorts {
+            load_account_port: account_persistence_adapter.clone(),
+            update_account_state_port: account_persistence_adapter.clone(),
+            update_overdraft_limit_port: account_persistence_adapter.clone(),
+            load_transfer_limits_port: account_persistence_adapter,
+        },
+        AccountPersistence::EventSourced => {
+            // every load with more than a single event before the baseline date takes a snapshot
+            let event_sourced_account_persistence_adapter =
+                Arc::new(EventSourcedAccountPersistenceAdapter::new(
+                    Box::new(AccountEventRepositoryImpl::new(db_pool.clone())),
+                    Box::new(ActivityRepositoryImpl::new(db_pool)),
+                    2,
+                ));
+            AccountPorts {
+                load_account_port: event_sourced_account_persistence_adapter.clone(),
+                update_account_state_port: event_sourced_account_persistence_adapter,
+                update_overdraft_limit_port: account_persistence_adapter.clone(),
+                // the transfer limits are checked against the activities in either case
+                load_transfer_limits_port: account_persistence_adapter,
+            }
+        }
+    }
+}
+
+fn timestamp(year: i32, month: u32, day: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
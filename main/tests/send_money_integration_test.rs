@@ -1,14 +1,13 @@
 #[cfg(test)]
 mod tests {
     use application::{
+        in_process_domain_event_publisher::InProcessDomainEventPublisher,
         no_op_account_lock::NoOpAccountLock,
         outbound_ports::LoadAccountPort,
         send_money_use_case::{MoneyTransferProperties, SendMoneyUseCaseImpl},
     };
     use chrono::Local;
     use domain::{ar::account::AccountId, vo::money::Money};
-    use env_logger::WriteStyle;
-    use log::LevelFilter;
     use persistence::{
         account_persistence_adapter::AccountPersistenceAdapter,
         account_repository::AccountRepositoryImpl, activity_repository::ActivityRepositoryImpl,
@@ -26,13 +25,10 @@ mod tests {
     //     let db_pool = create_db_pool().await;
     //     migrate_database(db_pool.clone()).await;
 
-    //     env_logger::Builder::new()
-    //         .filter(
-    //             Some("adapters_outbound_persistence::account_persistence_adapter"),
-    //             LevelFilter::Debug,
-    //         )
-    //         .write_style(WriteStyle::Never)
-    //         // .is_test(true)
+    //     tracing_subscriber::fmt()
+    //         .with_env_filter("adapters_outbound_persistence::account_persistence_adapter=debug")
+    //         .with_ansi(false)
+    //         // .with_test_writer()
     //         .init();
 
     //     let load_account_port = wire_dependencies(db_pool);
@@ -117,12 +113,15 @@ mod tests {
 
         let account_lock = Box::new(NoOpAccountLock {});
 
-        let money_transfer_properties = MoneyTransferProperties::new(Some(Money::of(1_000)));
+        let money_transfer_properties =
+            MoneyTransferProperties::new(Some(Money::of(1_000)), None, None);
 
-        let send_money_use_case = Box::new(SendMoneyUseCaseImpl::new(
+        let send_money_use_case = Arc::new(SendMoneyUseCaseImpl::new(
             account_persistence_adapter.clone(),
             account_lock,
             account_persistence_adapter.clone(),
+            account_persistence_adapter.clone(),
+            Arc::new(InProcessDomainEventPublisher::new()),
             money_transfer_properties,
         ));
         set_dependencies(send_money_use_case);